@@ -0,0 +1,20 @@
+-- 软删除：删除时记录删除时间，级联删除的子数据使用同一时间，恢复时按时间一起恢复
+ALTER TABLE textbooks ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE units ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE word_unit_mappings ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE words ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- 已删除的单元单词不占用唯一约束，单词可以重新加入单元
+ALTER TABLE word_unit_mappings DROP CONSTRAINT IF EXISTS word_unit_mappings_word_id_unit_id_key;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_word_unit_mappings_live
+    ON word_unit_mappings (word_id, unit_id) WHERE deleted_at IS NULL;
+
+-- 已删除的课本不占用版本、年级、学期的唯一约束，可以重新创建或复制到同一位置
+ALTER TABLE textbooks DROP CONSTRAINT IF EXISTS textbooks_version_id_grade_id_semester_id_key;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_textbooks_live
+    ON textbooks (version_id, grade_id, semester_id) WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_textbooks_deleted_at ON textbooks (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_units_deleted_at ON units (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_word_unit_mappings_deleted_at ON word_unit_mappings (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_words_deleted_at ON words (deleted_at) WHERE deleted_at IS NOT NULL;
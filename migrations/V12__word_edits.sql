@@ -0,0 +1,15 @@
+-- 编辑手动修正后锁定的字段（phonetic_us / phonetic_uk / meaning / example），补全任务不再覆盖
+ALTER TABLE words ADD COLUMN IF NOT EXISTS locked_fields TEXT[] NOT NULL DEFAULT '{}';
+
+-- 单词的人工修改记录，每个字段的一次修改一行
+CREATE TABLE IF NOT EXISTS word_edits (
+    id SERIAL PRIMARY KEY,
+    word_id INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    field VARCHAR(32) NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    editor VARCHAR(100),                                 -- 修改人，未填写时为空
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_word_edits_word_id ON word_edits (word_id, id);
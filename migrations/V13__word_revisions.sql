@@ -0,0 +1,27 @@
+-- 单词的修订版本：每次修改保存修改后的完整内容、来源和与上一版本的差异，可比较和回滚
+CREATE TABLE IF NOT EXISTS word_revisions (
+    id SERIAL PRIMARY KEY,
+    word_id INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    source VARCHAR(20) NOT NULL,                         -- llm / dictionary / editor / rollback / system
+    editor VARCHAR(100),
+    content JSONB NOT NULL,                              -- 修改后的音标、发音、释义、例句及提示词版本
+    changes JSONB NOT NULL DEFAULT '{}',                 -- {字段: {"old": 旧值, "new": 新值}}
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_word_revisions_word_id ON word_revisions (word_id, id);
+
+-- 为已有单词记录初始版本，之后的修改都可以回滚到这里
+INSERT INTO word_revisions (word_id, source, content)
+SELECT w.word_id, 'system', jsonb_build_object(
+    'phonetic_us', w.phonetic_us,
+    'phonetic_uk', w.phonetic_uk,
+    'pronunciation_us', w.pronunciation_us,
+    'pronunciation_uk', w.pronunciation_uk,
+    'meaning', w.meaning,
+    'example', w.example,
+    'meaning_prompt_version', w.meaning_prompt_version,
+    'example_prompt_version', w.example_prompt_version
+)
+FROM words w
+WHERE NOT EXISTS (SELECT 1 FROM word_revisions r WHERE r.word_id = w.word_id);
@@ -0,0 +1,107 @@
+-- 单词释义拆分为独立的表：每条释义一行，按 sequence 排序
+CREATE TABLE IF NOT EXISTS word_meanings (
+    id SERIAL PRIMARY KEY,
+    word_id INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    sequence INTEGER NOT NULL,                           -- 释义顺序，从 1 开始
+    pos VARCHAR(20) NOT NULL DEFAULT '',                 -- 词性，如 n. / v.，可以为空
+    definition TEXT NOT NULL,                            -- 中文释义
+    gloss TEXT,                                          -- 可选的英文释义
+    source VARCHAR(20),                                  -- llm / dictionary / editor / rollback / system
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (word_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_word_meanings_definition ON word_meanings (definition);
+
+-- 迁移时无法解析的释义，保留原文供人工处理
+CREATE TABLE IF NOT EXISTS word_meaning_parse_failures (
+    word_id INTEGER PRIMARY KEY REFERENCES words(word_id) ON DELETE CASCADE,
+    meaning TEXT NOT NULL,
+    error TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 解析 words.meaning 中的 WordMeaning JSON 数组，逐行处理，单行失败只记录不中断迁移
+DO $$
+DECLARE
+    row RECORD;
+    parsed JSONB;
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'words' AND column_name = 'meaning'
+    ) THEN
+        RETURN;
+    END IF;
+
+    FOR row IN
+        EXECUTE 'SELECT word_id, meaning, meaning_prompt_version FROM words
+                 WHERE meaning IS NOT NULL AND btrim(meaning) <> '''''
+    LOOP
+        BEGIN
+            parsed := row.meaning::jsonb;
+            IF jsonb_typeof(parsed) <> 'array' THEN
+                RAISE EXCEPTION 'meaning is not a JSON array';
+            END IF;
+            IF EXISTS (
+                SELECT 1 FROM jsonb_array_elements(parsed) AS m(value)
+                WHERE jsonb_typeof(m.value) <> 'object'
+                   OR btrim(coalesce(m.value->>'definition', '')) = ''
+            ) THEN
+                RAISE EXCEPTION 'meaning item without definition';
+            END IF;
+
+            INSERT INTO word_meanings (word_id, sequence, pos, definition, gloss, source)
+            SELECT row.word_id, m.ordinality,
+                   coalesce(m.value->>'pos', ''), btrim(m.value->>'definition'), m.value->>'gloss',
+                   CASE WHEN row.meaning_prompt_version IS NULL THEN 'dictionary' ELSE 'llm' END
+            FROM jsonb_array_elements(parsed) WITH ORDINALITY AS m(value, ordinality)
+            ON CONFLICT (word_id, sequence) DO NOTHING;
+        EXCEPTION WHEN OTHERS THEN
+            INSERT INTO word_meaning_parse_failures (word_id, meaning, error)
+            VALUES (row.word_id, row.meaning, SQLERRM)
+            ON CONFLICT (word_id) DO NOTHING;
+        END;
+    END LOOP;
+END $$;
+
+-- 修订内容中的释义同样改为数组；无法解析的记为空数组，原文保留在 meaning_raw 中，这样的版本不能回滚
+DO $$
+DECLARE
+    revision RECORD;
+    meanings JSONB;
+    raw JSONB;
+BEGIN
+    FOR revision IN SELECT id, content FROM word_revisions WHERE content ? 'meaning' LOOP
+        raw := '{}'::jsonb;
+        BEGIN
+            meanings := coalesce((revision.content->>'meaning')::jsonb, '[]'::jsonb);
+            IF jsonb_typeof(meanings) <> 'array' THEN
+                RAISE EXCEPTION 'meaning is not a JSON array';
+            END IF;
+        EXCEPTION WHEN OTHERS THEN
+            meanings := '[]'::jsonb;
+            raw := jsonb_build_object('meaning_raw', revision.content->>'meaning');
+        END;
+        UPDATE word_revisions
+        SET content = (revision.content - 'meaning') || jsonb_build_object('meanings', meanings) || raw
+        WHERE id = revision.id;
+    END LOOP;
+END $$;
+
+ALTER TABLE words DROP COLUMN IF EXISTS meaning;
+
+-- 单词及按顺序聚合为 JSON 数组的释义，供查询 Word 使用
+CREATE OR REPLACE VIEW word_details AS
+SELECT w.word_id, w.word, w.phonetic_us, w.pronunciation_us,
+       w.phonetic_uk, w.pronunciation_uk, w.example,
+       w.created_at, w.updated_at, w.meaning_prompt_version, w.example_prompt_version,
+       w.deleted_at,
+       COALESCE((
+           SELECT jsonb_agg(jsonb_strip_nulls(jsonb_build_object(
+               'pos', m.pos, 'definition', m.definition, 'gloss', m.gloss, 'source', m.source
+           )) ORDER BY m.sequence)
+           FROM word_meanings m
+           WHERE m.word_id = w.word_id
+       ), '[]') AS meanings
+FROM words w;
@@ -0,0 +1,42 @@
+-- 教师给学生布置的单元，学生只复习布置给自己的单元中的单词
+CREATE TABLE IF NOT EXISTS unit_assignments (
+    id SERIAL PRIMARY KEY,
+    unit_id INTEGER NOT NULL REFERENCES units(id) ON DELETE CASCADE,
+    student_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    assigned_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (unit_id, student_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_unit_assignments_student_id ON unit_assignments (student_id);
+
+-- 学生对每个单词的复习状态（SM-2），同一单词出现在多个单元时共用一条
+CREATE TABLE IF NOT EXISTS review_states (
+    id SERIAL PRIMARY KEY,
+    student_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    word_id INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    ease DOUBLE PRECISION NOT NULL DEFAULT 2.5,          -- 难度系数，最低 1.3
+    interval_days INTEGER NOT NULL DEFAULT 0,            -- 当前复习间隔（天）
+    repetitions INTEGER NOT NULL DEFAULT 0,              -- 连续答对次数
+    lapses INTEGER NOT NULL DEFAULT 0,                   -- 记住后又遗忘的次数
+    due_at TIMESTAMPTZ NOT NULL,
+    last_reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (student_id, word_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_states_due ON review_states (student_id, due_at);
+
+-- 每次作答的记录
+CREATE TABLE IF NOT EXISTS review_logs (
+    id SERIAL PRIMARY KEY,
+    student_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    word_id INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    quality SMALLINT NOT NULL CHECK (quality BETWEEN 0 AND 5),
+    ease DOUBLE PRECISION NOT NULL,                      -- 作答后的难度系数和间隔
+    interval_days INTEGER NOT NULL,
+    reviewed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_logs_student_id ON review_logs (student_id, reviewed_at);
@@ -0,0 +1,22 @@
+-- 根据单元单词生成的测验，保存生成时的题目和答案，交卷时按保存的答案判分
+CREATE TABLE IF NOT EXISTS quizzes (
+    id SERIAL PRIMARY KEY,
+    unit_ids INTEGER[] NOT NULL,
+    seed BIGINT NOT NULL,                                -- 相同单元、题型、数量和种子生成相同的题目
+    questions JSONB NOT NULL,                            -- [{index, kind, word_id, unit_id, prompt, options, answer}]
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 交卷记录和每题的判分结果
+CREATE TABLE IF NOT EXISTS quiz_attempts (
+    id SERIAL PRIMARY KEY,
+    quiz_id INTEGER NOT NULL REFERENCES quizzes(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    results JSONB NOT NULL,                              -- [{index, kind, word_id, correct, answer, expected}]
+    correct INTEGER NOT NULL,
+    total INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_attempts_user_id ON quiz_attempts (user_id, created_at);
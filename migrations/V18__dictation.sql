@@ -0,0 +1,38 @@
+-- 单元听写，保存开始时的单词顺序和正确拼写，交卷时按保存的拼写判分
+CREATE TABLE IF NOT EXISTS dictation_sessions (
+    id SERIAL PRIMARY KEY,
+    unit_id INTEGER NOT NULL REFERENCES units(id) ON DELETE CASCADE,
+    seed BIGINT,                                         -- 打乱顺序的种子，为空表示按单元顺序
+    repeat_count INTEGER NOT NULL,                       -- 每个单词播放的次数
+    pause_ms INTEGER NOT NULL,                           -- 每次播放后的停顿
+    entries JSONB NOT NULL,                              -- [{index, word_id, unit_id, word, pronunciation_us, pronunciation_uk}]
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 听写交卷记录和每个单词的判分结果
+CREATE TABLE IF NOT EXISTS dictation_attempts (
+    id SERIAL PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES dictation_sessions(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    results JSONB NOT NULL,                              -- [{index, word_id, correct, answer, expected, diff}]
+    correct INTEGER NOT NULL,
+    total INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_dictation_attempts_user_id ON dictation_attempts (user_id, created_at);
+
+-- 学生的错题本，同一单词的同一类错误只记一条
+CREATE TABLE IF NOT EXISTS mistakes (
+    id SERIAL PRIMARY KEY,
+    student_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    word_id INTEGER NOT NULL REFERENCES words(word_id) ON DELETE CASCADE,
+    unit_id INTEGER REFERENCES units(id) ON DELETE SET NULL,   -- 最近一次出错时所在的单元
+    error_type VARCHAR(20) NOT NULL,                     -- spelling, meaning, pronunciation
+    wrong_count INTEGER NOT NULL DEFAULT 1,
+    last_wrong_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (student_id, word_id, error_type)
+);
@@ -0,0 +1,4 @@
+-- 错题本：连续答对的次数，达到设定次数后移出错题本
+ALTER TABLE mistakes ADD COLUMN IF NOT EXISTS correct_streak INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_mistakes_student_id ON mistakes (student_id, last_wrong_at);
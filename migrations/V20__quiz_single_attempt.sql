@@ -0,0 +1,6 @@
+-- 每人每份测验只能交卷一次，保留最早的交卷记录
+DELETE FROM quiz_attempts a
+USING quiz_attempts b
+WHERE a.quiz_id = b.quiz_id AND a.user_id = b.user_id AND a.id > b.id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_quiz_attempts_quiz_user ON quiz_attempts (quiz_id, user_id);
@@ -0,0 +1,6 @@
+-- 每人每次听写只能交卷一次，保留最早的交卷记录
+DELETE FROM dictation_attempts a
+USING dictation_attempts b
+WHERE a.session_id = b.session_id AND a.user_id = b.user_id AND a.id > b.id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_dictation_attempts_session_user ON dictation_attempts (session_id, user_id);
@@ -0,0 +1,19 @@
+-- 同一课本中未删除的单元序号不能重复；约束在每条语句结束时检查，整体后移或重排序号的单条 UPDATE 不会中途冲突
+WITH duplicated AS (
+    SELECT textbook_id FROM units
+    WHERE deleted_at IS NULL AND textbook_id IS NOT NULL AND sequence_number IS NOT NULL
+    GROUP BY textbook_id, sequence_number
+    HAVING COUNT(*) > 1
+), renumbered AS (
+    SELECT u.id, ROW_NUMBER() OVER (PARTITION BY u.textbook_id ORDER BY u.sequence_number NULLS LAST, u.id) AS position
+    FROM units u
+    WHERE u.deleted_at IS NULL AND u.textbook_id IN (SELECT textbook_id FROM duplicated)
+)
+UPDATE units u SET sequence_number = r.position
+FROM renumbered r
+WHERE u.id = r.id;
+
+ALTER TABLE units DROP CONSTRAINT IF EXISTS units_textbook_sequence_live;
+ALTER TABLE units ADD CONSTRAINT units_textbook_sequence_live
+    EXCLUDE USING btree (textbook_id WITH =, sequence_number WITH =) WHERE (deleted_at IS NULL)
+    DEFERRABLE INITIALLY IMMEDIATE;
@@ -0,0 +1,12 @@
+-- 结构化输出的每次尝试及校验结果，重启后仍可在健康报告中查看
+CREATE TABLE IF NOT EXISTS llm_structured_attempts (
+    id SERIAL PRIMARY KEY,
+    task VARCHAR(50) NOT NULL,                           -- 任务名称，如 word_info / example_sentences
+    subject TEXT NOT NULL,                               -- 任务对象，通常是单词
+    attempt INTEGER NOT NULL,                            -- 第几次尝试，从 1 开始
+    success BOOLEAN NOT NULL,
+    error TEXT,                                          -- 校验失败的原因
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_structured_attempts_occurred_at ON llm_structured_attempts (occurred_at);
@@ -0,0 +1,4 @@
+-- 执行中的任务持有租约，worker 定期续租；只有租约过期的任务（实例崩溃或退出后遗留）才会重新排队
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS locked_until TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_jobs_running ON jobs (locked_until) WHERE status = 'running';
@@ -0,0 +1,3 @@
+-- 按模型配置的每 1K token 价格，如 {"deepseek-reasoner": {"prompt_per_1k": 0.004, "completion_per_1k": 0.016}}；
+-- 用量按模型统计，没有单独配置价格的模型使用服务的 prompt_price_per_1k / completion_price_per_1k
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS model_prices JSONB;
@@ -0,0 +1,11 @@
+-- 教师把测验布置给学生，被布置的学生可以查看和作答他人创建的测验
+CREATE TABLE IF NOT EXISTS quiz_assignments (
+    id SERIAL PRIMARY KEY,
+    quiz_id INTEGER NOT NULL REFERENCES quizzes(id) ON DELETE CASCADE,
+    student_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    assigned_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (quiz_id, student_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_assignments_student_id ON quiz_assignments (student_id);
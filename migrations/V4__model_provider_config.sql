@@ -0,0 +1,10 @@
+-- 模型服务配置入库，替代 LLM_{NAME}_* 环境变量
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS provider_type VARCHAR(50);       -- yi / deepseek / openai-compatible
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS model_name VARCHAR(100);
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS api_key_encrypted TEXT;          -- AES-256-GCM 加密后的 API key
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS timeout_secs INTEGER DEFAULT 30;
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS temperature REAL;
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS top_p REAL;
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS max_tokens INTEGER;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_model_providers_name ON model_providers (provider_name);
@@ -0,0 +1,17 @@
+-- 大模型调用用量，每次成功的 chat 调用一条记录
+CREATE TABLE IF NOT EXISTS llm_usage (
+    id SERIAL PRIMARY KEY,
+    provider VARCHAR(50) NOT NULL,                       -- 模型服务名称，如 deepseek / qwen
+    model VARCHAR(100) NOT NULL,
+    operation VARCHAR(100) NOT NULL,                     -- 调用方操作，如 get_word_info
+    prompt_tokens INTEGER NOT NULL DEFAULT 0,
+    completion_tokens INTEGER NOT NULL DEFAULT 0,
+    latency_ms BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_usage_created_at ON llm_usage (created_at);
+
+-- 每 1K token 的价格，用于估算费用
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS prompt_price_per_1k DOUBLE PRECISION;
+ALTER TABLE model_providers ADD COLUMN IF NOT EXISTS completion_price_per_1k DOUBLE PRECISION;
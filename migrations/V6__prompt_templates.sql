@@ -0,0 +1,20 @@
+-- 版本化的提示词模板，替代代码中硬编码的提示词
+CREATE TABLE IF NOT EXISTS prompt_templates (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL,                           -- phonetics / example_sentences / word_info / word_explain
+    version INTEGER NOT NULL,                            -- 0 保留给代码内置模板
+    system_prompt TEXT NOT NULL,
+    user_prompt TEXT NOT NULL,                           -- 支持 {word}、{grade}、{count} 等变量
+    description TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (name, version)
+);
+
+-- 每个模板同时只能有一个启用的版本
+CREATE UNIQUE INDEX IF NOT EXISTS idx_prompt_templates_active ON prompt_templates (name) WHERE is_active;
+
+-- 记录生成释义和例句时使用的提示词版本，第三方词典的释义为空
+ALTER TABLE words ADD COLUMN IF NOT EXISTS meaning_prompt_version INTEGER;
+ALTER TABLE words ADD COLUMN IF NOT EXISTS example_prompt_version INTEGER;
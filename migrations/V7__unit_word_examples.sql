@@ -0,0 +1,3 @@
+-- 单元内的例句按教材年级生成，为空时使用 words.example
+ALTER TABLE word_unit_mappings ADD COLUMN IF NOT EXISTS example TEXT;
+ALTER TABLE word_unit_mappings ADD COLUMN IF NOT EXISTS example_prompt_version INTEGER;
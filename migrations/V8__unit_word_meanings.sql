@@ -0,0 +1,3 @@
+-- 单词在单元中的具体含义和词性，为空时使用 words.meaning
+ALTER TABLE word_unit_mappings ADD COLUMN IF NOT EXISTS meaning TEXT;
+ALTER TABLE word_unit_mappings ADD COLUMN IF NOT EXISTS pos VARCHAR(20);
@@ -0,0 +1,2 @@
+-- 单词在单元中的顺序，批量导入时可指定
+ALTER TABLE word_unit_mappings ADD COLUMN IF NOT EXISTS sequence INTEGER;
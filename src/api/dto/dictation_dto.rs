@@ -0,0 +1,124 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::dictation::{DictationAttempt, DictationResult, DictationSession};
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn format_time(value: Option<OffsetDateTime>) -> Result<Option<String>, ConversionError> {
+    match value {
+        Some(dt) => Ok(Some(dt.format(&Rfc3339)?)),
+        None => Ok(None),
+    }
+}
+
+/// 开始单元听写：shuffle 为 true 或指定 seed 时打乱顺序，seed 相同时顺序相同；
+/// repeat_count 为每个单词播放的次数，pause_ms 为每次播放后的停顿毫秒数
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct StartDictationDTO {
+    pub unit_id: i32,
+    pub shuffle: Option<bool>,
+    pub seed: Option<i64>,
+    pub repeat_count: Option<i32>,
+    pub pause_ms: Option<i32>,
+}
+
+/// 按顺序播放的单词，不含拼写
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DictationEntryDTO {
+    pub index: usize,
+    pub word_id: i32,
+    pub pronunciation_us: Option<String>,
+    pub pronunciation_uk: Option<String>,
+    pub repeat_count: i32,
+    pub pause_ms: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DictationSessionDTO {
+    pub id: Option<i32>,
+    pub unit_id: i32,
+    pub seed: Option<i64>,
+    pub repeat_count: i32,
+    pub pause_ms: i32,
+    pub entries: Vec<DictationEntryDTO>,
+    pub created_at: Option<String>,
+}
+
+impl TryFrom<DictationSession> for DictationSessionDTO {
+    type Error = ConversionError;
+
+    fn try_from(session: DictationSession) -> Result<Self, Self::Error> {
+        let entries = session
+            .entries
+            .into_iter()
+            .map(|entry| DictationEntryDTO {
+                index: entry.index,
+                word_id: entry.word_id,
+                pronunciation_us: entry.pronunciation_us,
+                pronunciation_uk: entry.pronunciation_uk,
+                repeat_count: session.repeat_count,
+                pause_ms: session.pause_ms,
+            })
+            .collect();
+        Ok(Self {
+            id: session.id,
+            unit_id: session.unit_id,
+            seed: session.seed,
+            repeat_count: session.repeat_count,
+            pause_ms: session.pause_ms,
+            entries,
+            created_at: format_time(session.created_at)?,
+        })
+    }
+}
+
+/// 按听写ID查询
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DictationIdDTO {
+    pub session_id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DictationAnswerDTO {
+    pub index: usize,
+    pub answer: String,
+}
+
+/// 交卷，没有作答的单词判为错误
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmitDictationDTO {
+    pub session_id: i32,
+    pub answers: Vec<DictationAnswerDTO>,
+}
+
+/// 判分结果，score 为答对的百分比，mistakes 为记入错题本的单词数
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DictationResultDTO {
+    pub attempt_id: Option<i32>,
+    pub session_id: i32,
+    pub correct: i32,
+    pub total: i32,
+    pub score: f64,
+    pub results: Vec<DictationResult>,
+    pub mistakes: usize,
+    pub submitted_at: Option<String>,
+}
+
+impl DictationResultDTO {
+    pub fn new(attempt: DictationAttempt, mistakes: usize) -> Result<Self, ConversionError> {
+        let score = match attempt.total {
+            0 => 0.0,
+            total => (attempt.correct as f64 * 1000.0 / total as f64).round() / 10.0,
+        };
+        Ok(Self {
+            attempt_id: attempt.id,
+            session_id: attempt.session_id,
+            correct: attempt.correct,
+            total: attempt.total,
+            score,
+            results: attempt.results,
+            mistakes,
+            submitted_at: format_time(attempt.created_at)?,
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// 可打印的讲义，在浏览器中打印或另存为 PDF
+    #[default]
+    Html,
+    Csv,
+    /// Anki 牌组（.apkg）
+    #[serde(alias = "apkg")]
+    Anki,
+}
+
+/// 导出请求，textbook_id 和 unit_id 二选一
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ExportQueryDTO {
+    pub textbook_id: Option<i32>,
+    pub unit_id: Option<i32>,
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// 默写版讲义：隐藏释义和例句，只对 HTML 生效
+    #[serde(default)]
+    pub dictation: bool,
+}
@@ -0,0 +1,69 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::job::Job;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// 后台任务，入队时只需要 kind / payload / max_attempts，查询和取消时只需要 id
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JobDTO {
+    pub id: Option<i32>,
+    pub kind: Option<String>,
+    pub payload: Option<Value>,
+    pub status: Option<String>,
+    pub attempts: Option<i32>,
+    pub max_attempts: Option<i32>,
+    pub progress_done: Option<i32>,
+    pub progress_total: Option<i32>,
+    /// 任务记录的断点，重试或重启后从断点继续
+    pub checkpoint: Option<Value>,
+    pub result: Option<Value>,
+    pub last_error: Option<String>,
+    /// 下次执行时间，失败重试时会按退避时间推后
+    pub run_at: Option<String>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+fn format_time(value: Option<OffsetDateTime>) -> Result<Option<String>, ConversionError> {
+    match value {
+        Some(dt) => Ok(Some(dt.format(&Rfc3339)?)),
+        None => Ok(None),
+    }
+}
+
+impl TryFrom<Job> for JobDTO {
+    type Error = ConversionError;
+
+    fn try_from(job: Job) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: job.id,
+            kind: Some(job.kind),
+            payload: Some(job.payload),
+            status: Some(job.status),
+            attempts: Some(job.attempts),
+            max_attempts: Some(job.max_attempts),
+            progress_done: Some(job.progress_done),
+            progress_total: Some(job.progress_total),
+            checkpoint: job.checkpoint,
+            result: job.result,
+            last_error: job.last_error,
+            run_at: format_time(Some(job.run_at))?,
+            started_at: format_time(job.started_at)?,
+            finished_at: format_time(job.finished_at)?,
+            created_at: format_time(job.created_at)?,
+            updated_at: format_time(job.updated_at)?,
+        })
+    }
+}
+
+/// 任务列表查询条件，默认返回最近 50 个任务
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JobQueryDTO {
+    pub status: Option<String>,
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+}
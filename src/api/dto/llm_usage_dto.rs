@@ -0,0 +1,71 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::llm_usage::LLMUsageSummary;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Iso8601;
+use time::Date;
+
+/// 用量报表查询条件，日期格式为 YYYY-MM-DD，均为 UTC 日期
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LLMUsageQueryDTO {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl LLMUsageQueryDTO {
+    pub fn parse_date(value: &str) -> Result<Date, ConversionError> {
+        Ok(Date::parse(value.trim(), &Iso8601::DATE)?)
+    }
+
+    pub fn format_date(date: Date) -> Result<String, ConversionError> {
+        Ok(date.format(&Iso8601::DATE)?)
+    }
+}
+
+/// 按 日期 + 模型服务 + 模型 + 操作 汇总的一行用量
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LLMUsageRowDTO {
+    pub day: String,
+    pub provider: String,
+    pub model: String,
+    pub operation: String,
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    pub avg_latency_ms: f64,
+    /// 未配置价格时为空
+    pub estimated_cost: Option<f64>,
+}
+
+impl TryFrom<LLMUsageSummary> for LLMUsageRowDTO {
+    type Error = ConversionError;
+
+    fn try_from(summary: LLMUsageSummary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            day: LLMUsageQueryDTO::format_date(summary.day)?,
+            provider: summary.provider,
+            model: summary.model,
+            operation: summary.operation,
+            calls: summary.calls,
+            prompt_tokens: summary.prompt_tokens,
+            completion_tokens: summary.completion_tokens,
+            total_tokens: summary.prompt_tokens + summary.completion_tokens,
+            avg_latency_ms: summary.avg_latency_ms,
+            estimated_cost: None,
+        })
+    }
+}
+
+/// 用量报表
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LLMUsageReportDTO {
+    pub from: String,
+    pub to: String,
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub total_tokens: i64,
+    /// 已配置价格部分的估算费用合计
+    pub estimated_cost: f64,
+    pub rows: Vec<LLMUsageRowDTO>,
+}
@@ -0,0 +1,72 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::mistake::{Mistake, MistakeType};
+use crate::infrastructure::dto::WordMeaning;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn format_time(value: Option<OffsetDateTime>) -> Result<Option<String>, ConversionError> {
+    match value {
+        Some(dt) => Ok(Some(dt.format(&Rfc3339)?)),
+        None => Ok(None),
+    }
+}
+
+/// 查询错题本，student_id 为空时查询自己，error_type 为空时返回全部类型
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MistakeQueryDTO {
+    pub student_id: Option<i32>,
+    pub error_type: Option<MistakeType>,
+}
+
+/// 针对性复习的条件，limit 为最多返回的错题数
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MistakeReviewQueryDTO {
+    pub limit: Option<usize>,
+}
+
+/// 错题及其单词和单元，weight 为复习权重
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MistakeDTO {
+    pub id: i32,
+    pub word_id: i32,
+    pub word: String,
+    pub phonetic_us: Option<String>,
+    pub phonetic_uk: Option<String>,
+    pub pronunciation_us: Option<String>,
+    pub pronunciation_uk: Option<String>,
+    pub meanings: Vec<WordMeaning>,
+    pub unit_id: Option<i32>,
+    pub unit_name: Option<String>,
+    pub textbook_id: Option<i32>,
+    pub error_type: String,
+    pub wrong_count: i32,
+    pub correct_streak: i32,
+    pub weight: f64,
+    pub last_wrong_at: Option<String>,
+    pub created_at: Option<String>,
+}
+
+impl MistakeDTO {
+    pub fn new(mistake: Mistake, now: OffsetDateTime) -> Result<Self, ConversionError> {
+        Ok(Self {
+            weight: (mistake.weight(now) * 1000.0).round() / 1000.0,
+            id: mistake.id,
+            word_id: mistake.word_id,
+            word: mistake.word,
+            phonetic_us: mistake.phonetic_us,
+            phonetic_uk: mistake.phonetic_uk,
+            pronunciation_us: mistake.pronunciation_us,
+            pronunciation_uk: mistake.pronunciation_uk,
+            meanings: mistake.meanings,
+            unit_id: mistake.unit_id,
+            unit_name: mistake.unit_name,
+            textbook_id: mistake.textbook_id,
+            error_type: mistake.error_type,
+            wrong_count: mistake.wrong_count,
+            correct_streak: mistake.correct_streak,
+            last_wrong_at: format_time(Some(mistake.last_wrong_at))?,
+            created_at: format_time(mistake.created_at)?,
+        })
+    }
+}
@@ -1,5 +1,20 @@
+pub mod dictation_dto;
+pub mod export_dto;
+pub mod job_dto;
+pub mod llm_usage_dto;
+pub mod mistake_dto;
 pub mod model_dto;
+pub mod model_provider_dto;
+pub mod prompt_template_dto;
+pub mod quiz_dto;
 pub mod response;
+pub mod sequence_dto;
+pub mod study_dto;
 pub mod textbook_dto;
+pub mod trash_dto;
 pub mod unit_dto;
 pub mod unit_word_dto;
+pub mod user_dto;
+pub mod word_edit_dto;
+pub mod word_meaning_dto;
+pub mod word_revision_dto;
@@ -0,0 +1,97 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::model_provider::ModelProvider;
+use crate::infrastructure::llm::usage::ModelPricing;
+use anyhow::Result;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use time::format_description;
+
+/// 修改时区分缺省和 null：字段缺省为 None，表示不修改；传 null 为 Some(None)，表示清除
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 可选配置字段为 `Option<Option<T>>`，更新时缺省的字段保持不变，传 null 的字段被清除
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModelProviderDTO {
+    pub provider_id: Option<i32>,
+    pub provider_name: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub provider_type: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub api_base_url: Option<Option<String>>,
+    /// 明文 API key，仅用于创建或更新，不会返回给前端
+    #[serde(skip_serializing)]
+    pub api_key: Option<String>,
+    /// 脱敏后的 API key，仅用于展示
+    pub api_key_masked: Option<String>,
+    pub api_key_required: Option<bool>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub model_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub model_types: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub timeout_secs: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub temperature: Option<Option<f32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub top_p: Option<Option<f32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub max_tokens: Option<Option<i32>>,
+    /// 每 1K 输入 / 输出 token 的价格，用于估算费用
+    #[serde(default, deserialize_with = "nullable")]
+    pub prompt_price_per_1k: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub completion_price_per_1k: Option<Option<f64>>,
+    /// 按模型名配置的价格，如 `{"deepseek-reasoner": {"prompt_per_1k": 0.004, "completion_per_1k": 0.016}}`，
+    /// 没有单独配置的模型使用服务的价格
+    #[serde(default, deserialize_with = "nullable")]
+    pub model_prices: Option<Option<HashMap<String, ModelPricing>>>,
+    pub is_active: Option<bool>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl TryFrom<ModelProvider> for ModelProviderDTO {
+    type Error = ConversionError;
+
+    fn try_from(provider: ModelProvider) -> Result<Self, Self::Error> {
+        let format = format_description::well_known::Rfc3339;
+
+        let created_at = match provider.created_at {
+            Some(dt) => Some(dt.format(&format)?),
+            None => None,
+        };
+
+        let updated_at = match provider.updated_at {
+            Some(dt) => Some(dt.format(&format)?),
+            None => None,
+        };
+
+        Ok(Self {
+            provider_id: provider.provider_id,
+            provider_name: provider.provider_name,
+            provider_type: Some(provider.provider_type),
+            api_base_url: Some(provider.api_base_url),
+            api_key: None,
+            api_key_masked: None,
+            api_key_required: provider.api_key_required,
+            model_name: Some(provider.model_name),
+            model_types: Some(provider.model_types),
+            timeout_secs: Some(provider.timeout_secs),
+            temperature: Some(provider.temperature),
+            top_p: Some(provider.top_p),
+            max_tokens: Some(provider.max_tokens),
+            prompt_price_per_1k: Some(provider.prompt_price_per_1k),
+            completion_price_per_1k: Some(provider.completion_price_per_1k),
+            model_prices: Some(provider.model_prices.map(|prices| prices.0)),
+            is_active: provider.is_active,
+            created_at,
+            updated_at,
+        })
+    }
+}
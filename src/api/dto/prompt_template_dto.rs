@@ -0,0 +1,73 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::prompt_template::PromptTemplate;
+use crate::infrastructure::llm::prompts::registry::template_variables;
+use crate::infrastructure::llm::prompts::BUILTIN_VERSION;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use time::format_description;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PromptTemplateDTO {
+    pub id: Option<i32>,
+    pub name: Option<String>,
+    pub version: Option<i32>,
+    pub system_prompt: Option<String>,
+    pub user_prompt: Option<String>,
+    pub description: Option<String>,
+    /// 模板中用到的变量，如 word / grade / count
+    pub variables: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+    /// 是否为代码内置的模板
+    pub builtin: Option<bool>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl TryFrom<PromptTemplate> for PromptTemplateDTO {
+    type Error = ConversionError;
+
+    fn try_from(template: PromptTemplate) -> Result<Self, Self::Error> {
+        let format = format_description::well_known::Rfc3339;
+
+        let created_at = match template.created_at {
+            Some(dt) => Some(dt.format(&format)?),
+            None => None,
+        };
+
+        let updated_at = match template.updated_at {
+            Some(dt) => Some(dt.format(&format)?),
+            None => None,
+        };
+
+        let mut variables = template_variables(&template.system_prompt);
+        for name in template_variables(&template.user_prompt) {
+            if !variables.contains(&name) {
+                variables.push(name);
+            }
+        }
+
+        Ok(Self {
+            id: template.id,
+            name: Some(template.name),
+            version: Some(template.version),
+            system_prompt: Some(template.system_prompt),
+            user_prompt: Some(template.user_prompt),
+            description: template.description,
+            variables: Some(variables),
+            is_active: Some(template.is_active),
+            builtin: Some(template.version == BUILTIN_VERSION),
+            created_at,
+            updated_at,
+        })
+    }
+}
+
+/// 预览请求，未指定版本时使用当前启用的版本
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PromptPreviewDTO {
+    pub name: String,
+    pub version: Option<i32>,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
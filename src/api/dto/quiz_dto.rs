@@ -0,0 +1,134 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::quiz::{QuestionKind, QuestionResult, Quiz, QuizAttempt, QuizQuestion};
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn format_time(value: Option<OffsetDateTime>) -> Result<Option<String>, ConversionError> {
+    match value {
+        Some(dt) => Ok(Some(dt.format(&Rfc3339)?)),
+        None => Ok(None),
+    }
+}
+
+/// 生成测验：指定 unit_id 时只用该单元，否则使用 textbook_id 教材中序号在 from_unit 到 to_unit 之间的单元，
+/// 序号为空表示不限；kinds 为空时使用全部题型，seed 相同时生成的题目相同
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GenerateQuizDTO {
+    pub unit_id: Option<i32>,
+    pub textbook_id: Option<i32>,
+    pub from_unit: Option<i32>,
+    pub to_unit: Option<i32>,
+    pub kinds: Option<Vec<QuestionKind>>,
+    pub count: Option<usize>,
+    pub seed: Option<i64>,
+}
+
+/// 发给学生的题目，不含答案
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizQuestionDTO {
+    pub index: usize,
+    pub kind: QuestionKind,
+    pub word_id: i32,
+    pub unit_id: Option<i32>,
+    pub prompt: String,
+    pub options: Vec<String>,
+}
+
+impl From<QuizQuestion> for QuizQuestionDTO {
+    fn from(question: QuizQuestion) -> Self {
+        Self {
+            index: question.index,
+            kind: question.kind,
+            word_id: question.word_id,
+            unit_id: question.unit_id,
+            prompt: question.prompt,
+            options: question.options,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizDTO {
+    pub id: Option<i32>,
+    pub unit_ids: Vec<i32>,
+    pub seed: i64,
+    pub questions: Vec<QuizQuestionDTO>,
+    pub created_at: Option<String>,
+}
+
+impl TryFrom<Quiz> for QuizDTO {
+    type Error = ConversionError;
+
+    fn try_from(quiz: Quiz) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: quiz.id,
+            unit_ids: quiz.unit_ids,
+            seed: quiz.seed,
+            questions: quiz
+                .questions
+                .into_iter()
+                .map(QuizQuestionDTO::from)
+                .collect(),
+            created_at: format_time(quiz.created_at)?,
+        })
+    }
+}
+
+/// 按测验ID查询
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizIdDTO {
+    pub quiz_id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizAnswerDTO {
+    pub index: usize,
+    pub answer: String,
+}
+
+/// 把测验布置给学生
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssignQuizDTO {
+    pub quiz_id: i32,
+    pub student_ids: Vec<i32>,
+}
+
+/// 交卷，没有作答的题目判为错误
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmitQuizDTO {
+    pub quiz_id: i32,
+    pub answers: Vec<QuizAnswerDTO>,
+}
+
+/// 判分结果，score 为答对的百分比
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuizResultDTO {
+    pub attempt_id: Option<i32>,
+    pub quiz_id: i32,
+    pub correct: i32,
+    pub total: i32,
+    pub score: f64,
+    pub results: Vec<QuestionResult>,
+    pub submitted_at: Option<String>,
+}
+
+impl TryFrom<QuizAttempt> for QuizResultDTO {
+    type Error = ConversionError;
+
+    fn try_from(attempt: QuizAttempt) -> Result<Self, Self::Error> {
+        let score = match attempt.total {
+            0 => 0.0,
+            total => (attempt.correct as f64 * 1000.0 / total as f64).round() / 10.0,
+        };
+        Ok(Self {
+            attempt_id: attempt.id,
+            quiz_id: attempt.quiz_id,
+            correct: attempt.correct,
+            total: attempt.total,
+            score,
+            results: attempt.results,
+            submitted_at: format_time(attempt.created_at)?,
+        })
+    }
+}
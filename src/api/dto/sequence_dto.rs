@@ -0,0 +1,34 @@
+use crate::common::utils::sequence::Reorder;
+use serde::{Deserialize, Serialize};
+
+/// 把单元（或单元中的单词）移动到 position，position 从 1 开始，超出范围时放到最后
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MoveDTO {
+    pub id: i32,
+    pub position: i32,
+}
+
+impl From<&MoveDTO> for Reorder {
+    fn from(dto: &MoveDTO) -> Self {
+        Reorder::Move {
+            id: dto.id,
+            position: dto.position,
+        }
+    }
+}
+
+/// 交换两个单元（或同一单元中两个单词）的位置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SwapDTO {
+    pub id: i32,
+    pub other_id: i32,
+}
+
+impl From<&SwapDTO> for Reorder {
+    fn from(dto: &SwapDTO) -> Self {
+        Reorder::Swap {
+            first: dto.id,
+            second: dto.other_id,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::review::{ReviewItem, ReviewState, UnitAssignment, UnitMastery};
+use crate::infrastructure::dto::WordMeaning;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn format_time(value: Option<OffsetDateTime>) -> Result<Option<String>, ConversionError> {
+    match value {
+        Some(dt) => Ok(Some(dt.format(&Rfc3339)?)),
+        None => Ok(None),
+    }
+}
+
+/// 教师把单元布置给学生
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssignUnitDTO {
+    pub unit_id: i32,
+    pub student_ids: Vec<i32>,
+}
+
+/// 取消布置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnassignUnitDTO {
+    pub unit_id: i32,
+    pub student_id: i32,
+}
+
+/// 布置结果，assigned 为新布置的学生数，已经布置过的不重复计算
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssignResultDTO {
+    pub assigned: u64,
+}
+
+/// 查询某个学生的数据，为空时查询自己；只有教师及以上角色可以查询其他学生
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct StudentQueryDTO {
+    pub student_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnitAssignmentDTO {
+    pub unit_id: i32,
+    pub unit_name: Option<String>,
+    pub textbook_id: Option<i32>,
+    pub student_id: i32,
+    pub assigned_by: Option<i32>,
+    pub assigned_at: Option<String>,
+}
+
+impl TryFrom<UnitAssignment> for UnitAssignmentDTO {
+    type Error = ConversionError;
+
+    fn try_from(assignment: UnitAssignment) -> Result<Self, Self::Error> {
+        Ok(Self {
+            unit_id: assignment.unit_id,
+            unit_name: assignment.unit_name,
+            textbook_id: assignment.textbook_id,
+            student_id: assignment.student_id,
+            assigned_by: assignment.assigned_by,
+            assigned_at: format_time(assignment.created_at)?,
+        })
+    }
+}
+
+/// 今日复习队列的条件：limit 为最多返回的单词数，new_limit 为每天最多学习的新词数，
+/// utc_offset_minutes 为客户端所在时区与 UTC 相差的分钟数，用于划分“今天”，不传时使用服务端配置
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ReviewQueueQueryDTO {
+    pub limit: Option<i64>,
+    pub new_limit: Option<i64>,
+    pub utc_offset_minutes: Option<i32>,
+}
+
+/// 复习队列中的单词，先返回到期的复习，再返回新词
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewItemDTO {
+    pub word_id: i32,
+    pub word: String,
+    pub phonetic_us: Option<String>,
+    pub phonetic_uk: Option<String>,
+    pub meanings: Vec<WordMeaning>,
+    pub unit_id: i32,
+    pub unit_name: Option<String>,
+    pub is_new: bool,
+    pub due_at: Option<String>,
+    pub repetitions: i32,
+    pub lapses: i32,
+}
+
+impl TryFrom<ReviewItem> for ReviewItemDTO {
+    type Error = ConversionError;
+
+    fn try_from(item: ReviewItem) -> Result<Self, Self::Error> {
+        Ok(Self {
+            word_id: item.word_id,
+            word: item.word,
+            phonetic_us: item.phonetic_us,
+            phonetic_uk: item.phonetic_uk,
+            meanings: item.meanings,
+            unit_id: item.unit_id,
+            unit_name: item.unit_name,
+            is_new: item.due_at.is_none(),
+            due_at: format_time(item.due_at)?,
+            repetitions: item.repetitions,
+            lapses: item.lapses,
+        })
+    }
+}
+
+/// 提交一次作答，quality 为 0-5 的自评或判分结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewAnswerDTO {
+    pub word_id: i32,
+    pub quality: u8,
+}
+
+/// 作答后的复习状态
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewStateDTO {
+    pub word_id: i32,
+    pub ease: f64,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub lapses: i32,
+    pub due_at: Option<String>,
+    pub last_reviewed_at: Option<String>,
+}
+
+impl TryFrom<ReviewState> for ReviewStateDTO {
+    type Error = ConversionError;
+
+    fn try_from(state: ReviewState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            word_id: state.word_id,
+            ease: state.ease,
+            interval_days: state.interval_days,
+            repetitions: state.repetitions,
+            lapses: state.lapses,
+            due_at: format_time(Some(state.due_at))?,
+            last_reviewed_at: format_time(state.last_reviewed_at)?,
+        })
+    }
+}
+
+/// 单元掌握情况，mastery 为已掌握单词的百分比
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnitMasteryDTO {
+    pub unit_id: i32,
+    pub unit_name: Option<String>,
+    pub textbook_id: Option<i32>,
+    pub total: i64,
+    pub learned: i64,
+    pub mastered: i64,
+    pub mastery: f64,
+}
+
+impl From<UnitMastery> for UnitMasteryDTO {
+    fn from(mastery: UnitMastery) -> Self {
+        let percent = match mastery.total {
+            0 => 0.0,
+            total => (mastery.mastered as f64 * 1000.0 / total as f64).round() / 10.0,
+        };
+        Self {
+            unit_id: mastery.unit_id,
+            unit_name: mastery.unit_name,
+            textbook_id: mastery.textbook_id,
+            total: mastery.total,
+            learned: mastery.learned,
+            mastered: mastery.mastered,
+            mastery: percent,
+        }
+    }
+}
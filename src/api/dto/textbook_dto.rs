@@ -92,3 +92,103 @@ impl TryFrom<TextbookDTO> for Textbook {
         })
     }
 }
+
+/// 复制教材请求，未指定的版本、年级、学期沿用源教材；名称为空时按版本、年级、学期生成
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TextbookCloneDTO {
+    pub source_id: i32,
+    pub version_id: Option<i32>,
+    pub grade_id: Option<i32>,
+    pub semester_id: Option<i32>,
+    pub name: Option<String>,
+}
+
+/// 对比两本教材，source 为旧版，target 为新版
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TextbookDiffQueryDTO {
+    pub source_id: i32,
+    pub target_id: i32,
+}
+
+/// 从其他单元移入的单词
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MovedWordDTO {
+    pub word: String,
+    /// 单词在旧版中所在的单元
+    pub from_unit: String,
+}
+
+/// 按单元序号对应的一组单元的差异，单元只存在于一侧时另一侧为空
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnitDiffDTO {
+    pub sequence_number: i32,
+    pub source_unit_id: Option<i32>,
+    pub source_unit: Option<String>,
+    pub target_unit_id: Option<i32>,
+    pub target_unit: Option<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub moved: Vec<MovedWordDTO>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TextbookDiffDTO {
+    pub source_id: i32,
+    pub target_id: i32,
+    pub added: usize,
+    pub removed: usize,
+    pub moved: usize,
+    pub units: Vec<UnitDiffDTO>,
+}
+
+impl TextbookDiffDTO {
+    pub fn new(source_id: i32, target_id: i32, units: Vec<UnitDiffDTO>) -> Self {
+        Self {
+            source_id,
+            target_id,
+            added: units.iter().map(|unit| unit.added.len()).sum(),
+            removed: units.iter().map(|unit| unit.removed.len()).sum(),
+            moved: units.iter().map(|unit| unit.moved.len()).sum(),
+            units,
+        }
+    }
+}
+
+/// 计数字段与实际数据不一致的一条记录，entity 为 unit 或 textbook
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CounterDiscrepancyDTO {
+    pub entity: String,
+    pub id: i32,
+    pub field: String,
+    pub stored: Option<i32>,
+    pub actual: i32,
+}
+
+impl CounterDiscrepancyDTO {
+    pub fn new(entity: &str, id: i32, field: &str, stored: Option<i32>, actual: i32) -> Self {
+        Self {
+            entity: entity.to_string(),
+            id,
+            field: field.to_string(),
+            stored,
+            actual,
+        }
+    }
+}
+
+/// 重新统计单元、课本计数的结果，discrepancies 为已修正的记录
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecountReportDTO {
+    pub fixed: usize,
+    pub discrepancies: Vec<CounterDiscrepancyDTO>,
+}
+
+impl From<Vec<CounterDiscrepancyDTO>> for RecountReportDTO {
+    fn from(discrepancies: Vec<CounterDiscrepancyDTO>) -> Self {
+        Self {
+            fixed: discrepancies.len(),
+            discrepancies,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::trash::{TrashEntity, TrashItem};
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+/// 删除预览或恢复的目标，entity 为 textbook / unit / unit_word / word
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashTargetDTO {
+    pub entity: TrashEntity,
+    pub id: i32,
+}
+
+/// 回收站查询条件，entity 为空时返回全部类型
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TrashQueryDTO {
+    pub entity: Option<TrashEntity>,
+}
+
+/// 删除预览、回收站中的一条数据或恢复结果，units / unit_words 为连带删除或恢复的数量
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashItemDTO {
+    pub entity: TrashEntity,
+    pub id: i32,
+    pub name: String,
+    pub deleted_at: Option<String>,
+    /// 超过该时间后不能再恢复，会被永久删除
+    pub expires_at: Option<String>,
+    pub units: i64,
+    pub unit_words: i64,
+}
+
+fn format_time(value: Option<OffsetDateTime>) -> Result<Option<String>, ConversionError> {
+    match value {
+        Some(dt) => Ok(Some(dt.format(&Rfc3339)?)),
+        None => Ok(None),
+    }
+}
+
+impl TrashItemDTO {
+    pub fn new(item: TrashItem, retention: Duration) -> Result<Self, ConversionError> {
+        Ok(Self {
+            entity: item.entity,
+            id: item.id,
+            name: item.name,
+            deleted_at: format_time(item.deleted_at)?,
+            expires_at: format_time(item.deleted_at.map(|at| at + retention))?,
+            units: item.units,
+            unit_words: item.unit_words,
+        })
+    }
+}
+
+/// 永久删除过期数据的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashPurgeDTO {
+    pub purged: u64,
+}
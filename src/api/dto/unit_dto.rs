@@ -97,6 +97,7 @@ impl TryFrom<UnitDTO> for Unit {
             created_at: None,
             word_count: dto.word_count,
             updated_at: None,
+            deleted_at: None,
         })
     }
 }
@@ -113,6 +114,7 @@ impl TryFrom<&UnitDTO> for Unit {
             created_at: None,
             word_count: dto.word_count,
             updated_at: None,
+            deleted_at: None,
         })
     }
 }
@@ -1,14 +1,23 @@
+use crate::common::utils::word_list::ImportFormat;
 use crate::domain::models::word::Word;
 use crate::domain::models::word_unit_mapping::WordUnitMapping;
+use crate::infrastructure::dto::WordMeaning;
 use serde::{Deserialize, Serialize};
 use time::format_description;
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, sqlx::FromRow)]
 pub struct WordDTO {
     pub id: Option<i32>,
     pub word_id: Option<i32>,
     pub word: Option<String>,
-    pub meaning: Option<String>,
+    /// 单元含义，未设置时为单词的全部释义
+    #[serde(default)]
+    #[sqlx(json)]
+    pub meanings: Vec<WordMeaning>,
+    /// 单词在该单元中的词性，未设置单元含义时为空
+    pub pos: Option<String>,
+    /// 单词在单元中的顺序
+    pub sequence: Option<i32>,
     pub example: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
@@ -27,8 +36,12 @@ impl WordDTO {
             id: unit_word.id,
             word_id: unit_word.word_id,
             word: Some(word.word.clone()),
-            meaning: word.meaning.clone(),
-            example: word.example.clone(),
+            meanings: unit_word
+                .unit_meaning()
+                .map_or_else(|| word.meanings.clone(), |meaning| vec![meaning]),
+            pos: unit_word.pos.clone(),
+            sequence: unit_word.sequence,
+            example: unit_word.example.clone().or_else(|| word.example.clone()),
             created_at: Option::from(unit_word.created_at.unwrap().format(&format).unwrap()),
             updated_at: Option::from(unit_word.updated_at.unwrap().format(&format).unwrap()),
             phonetic_us: word.phonetic_us.clone(),
@@ -40,6 +53,90 @@ impl WordDTO {
     }
 }
 
+/// 单词在单元中的含义，meaning 为空时恢复使用单词的通用释义
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct UnitMeaningDTO {
+    /// word_unit_mappings.id
+    pub id: Option<i32>,
+    pub pos: Option<String>,
+    pub meaning: Option<String>,
+}
+
+/// 批量导入单元单词，content 为 CSV / TSV 文本或每行一个单词的粘贴内容，
+/// 可选列：pos（词性）、meaning（单元含义）、sequence（顺序）
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UnitWordImportDTO {
+    pub unit_id: i32,
+    /// csv / tsv / text，为空时根据内容推断
+    pub format: Option<ImportFormat>,
+    pub content: String,
+}
+
+/// 导入结果中每一行的状态
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Created,
+    AlreadyPresent,
+    EnrichmentFailed,
+    Invalid,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct UnitWordImportRowDTO {
+    /// 导入内容中的行号，从 1 开始
+    pub line: usize,
+    pub word: Option<String>,
+    pub status: ImportRowStatus,
+    /// 新建的 word_unit_mappings.id
+    pub id: Option<i32>,
+    pub message: Option<String>,
+}
+
+impl UnitWordImportRowDTO {
+    pub fn new(line: usize, word: Option<String>, status: ImportRowStatus) -> Self {
+        Self {
+            line,
+            word,
+            status,
+            id: None,
+            message: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct UnitWordImportReportDTO {
+    pub unit_id: i32,
+    pub total: usize,
+    pub created: usize,
+    pub already_present: usize,
+    pub enrichment_failed: usize,
+    pub invalid: usize,
+    pub rows: Vec<UnitWordImportRowDTO>,
+}
+
+impl UnitWordImportReportDTO {
+    pub fn new(unit_id: i32, mut rows: Vec<UnitWordImportRowDTO>) -> Self {
+        rows.sort_by_key(|row| row.line);
+        let count = |status| rows.iter().filter(|row| row.status == status).count();
+        Self {
+            unit_id,
+            total: rows.len(),
+            created: count(ImportRowStatus::Created),
+            already_present: count(ImportRowStatus::AlreadyPresent),
+            enrichment_failed: count(ImportRowStatus::EnrichmentFailed),
+            invalid: count(ImportRowStatus::Invalid),
+            rows,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct WordPageRequestDTO {
     pub word_id: Option<i32>,
@@ -0,0 +1,82 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::user::{Role, User};
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+
+/// 用户名和密码登录
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoginDTO {
+    pub username: String,
+    pub password: String,
+}
+
+/// 登录结果，之后的请求在 `Authorization: Bearer <token>` 中携带令牌
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoginResultDTO {
+    pub token: String,
+    pub expires_at: String,
+    pub user: UserDTO,
+}
+
+/// 短期有效的链接令牌，放在下载链接或事件流地址的 `access_token` 查询参数中
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinkTokenDTO {
+    pub token: String,
+    pub expires_at: String,
+}
+
+/// 用户信息，不包含密码哈希
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserDTO {
+    pub id: Option<i32>,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub role: String,
+    pub disabled: bool,
+    pub created_at: Option<String>,
+}
+
+impl TryFrom<User> for UserDTO {
+    type Error = ConversionError;
+
+    fn try_from(user: User) -> Result<Self, Self::Error> {
+        let created_at = match user.created_at {
+            Some(dt) => Some(dt.format(&Rfc3339)?),
+            None => None,
+        };
+        Ok(Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            role: user.role,
+            disabled: user.disabled,
+            created_at,
+        })
+    }
+}
+
+/// 管理员创建用户
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateUserDTO {
+    pub username: String,
+    pub password: String,
+    pub display_name: Option<String>,
+    pub role: Role,
+}
+
+/// 管理员修改用户，只修改传入的字段；禁用或重置密码后原有的登录全部失效
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateUserDTO {
+    pub id: i32,
+    pub display_name: Option<String>,
+    pub role: Option<Role>,
+    pub disabled: Option<bool>,
+    pub password: Option<String>,
+}
+
+/// 修改自己的密码
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangePasswordDTO {
+    pub old_password: String,
+    pub new_password: String,
+}
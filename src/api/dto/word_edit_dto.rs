@@ -0,0 +1,112 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::word::Word;
+use crate::domain::models::word_edit::{WordEdit, WordField, WordPatch};
+use crate::infrastructure::dto::WordMeaning;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+
+/// 人工修改单词，只修改传入的字段，传入空字符串或空数组表示清空
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WordPatchDTO {
+    pub word_id: i32,
+    pub phonetic_us: Option<String>,
+    pub phonetic_uk: Option<String>,
+    pub meanings: Option<Vec<WordMeaning>>,
+    pub example: Option<String>,
+    /// 是否锁定本次修改的字段，默认锁定，锁定后补全任务不会覆盖
+    pub lock: Option<bool>,
+    /// 解除锁定的字段
+    #[serde(default)]
+    pub unlock: Vec<WordField>,
+    pub editor: Option<String>,
+}
+
+impl WordPatchDTO {
+    pub fn to_patch(&self) -> Result<WordPatch> {
+        let text = |value: &String| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        let mut changes = Vec::new();
+        if let Some(phonetic) = &self.phonetic_us {
+            changes.push((WordField::PhoneticUs, text(phonetic)));
+        }
+        if let Some(phonetic) = &self.phonetic_uk {
+            changes.push((WordField::PhoneticUk, text(phonetic)));
+        }
+        if let Some(meanings) = &self.meanings {
+            if meanings.iter().any(|m| m.definition.trim().is_empty()) {
+                return Err(anyhow!("Meaning definition must not be empty"));
+            }
+            let meaning = if meanings.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(meanings)?)
+            };
+            changes.push((WordField::Meaning, meaning));
+        }
+        if let Some(example) = &self.example {
+            changes.push((WordField::Example, text(example)));
+        }
+        if changes.is_empty() && self.unlock.is_empty() {
+            return Err(anyhow!("Nothing to update"));
+        }
+
+        Ok(WordPatch {
+            changes,
+            lock: self.lock.unwrap_or(true),
+            unlock: self.unlock.clone(),
+            editor: self.editor.clone(),
+        })
+    }
+}
+
+/// 单词及其被编辑锁定的字段
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordDetailDTO {
+    #[serde(flatten)]
+    pub word: Word,
+    pub locked_fields: Vec<WordField>,
+}
+
+/// 单词某个字段的一次人工修改
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordEditDTO {
+    pub id: Option<i32>,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub editor: Option<String>,
+    pub created_at: Option<String>,
+}
+
+impl TryFrom<WordEdit> for WordEditDTO {
+    type Error = ConversionError;
+
+    fn try_from(edit: WordEdit) -> Result<Self, Self::Error> {
+        let created_at = match edit.created_at {
+            Some(dt) => Some(dt.format(&Rfc3339)?),
+            None => None,
+        };
+        Ok(Self {
+            id: edit.id,
+            field: edit.field,
+            old_value: edit.old_value,
+            new_value: edit.new_value,
+            editor: edit.editor,
+            created_at,
+        })
+    }
+}
+
+/// 按单词ID查询
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordIdDTO {
+    pub word_id: i32,
+}
+
+/// 单词当前锁定的字段和修改历史，最新的修改在前
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordHistoryDTO {
+    pub word_id: i32,
+    pub locked_fields: Vec<WordField>,
+    pub edits: Vec<WordEditDTO>,
+}
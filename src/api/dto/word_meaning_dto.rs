@@ -0,0 +1,31 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::word::MeaningParseFailure;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+
+/// 迁移时无法解析的释义，resolved 为 true 表示单词之后已补全或人工填写了释义
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MeaningParseFailureDTO {
+    pub word_id: i32,
+    pub word: String,
+    /// 原 words.meaning 中的文本
+    pub meaning: String,
+    pub error: String,
+    pub resolved: bool,
+    pub created_at: String,
+}
+
+impl TryFrom<MeaningParseFailure> for MeaningParseFailureDTO {
+    type Error = ConversionError;
+
+    fn try_from(failure: MeaningParseFailure) -> Result<Self, Self::Error> {
+        Ok(Self {
+            word_id: failure.word_id,
+            word: failure.word,
+            meaning: failure.meaning,
+            error: failure.error,
+            resolved: failure.resolved,
+            created_at: failure.created_at.format(&Rfc3339)?,
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use crate::common::errors::ConversionError;
+use crate::domain::models::word_revision::WordRevision;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+
+/// 单词的一个修订版本，source 为 llm / dictionary / editor / rollback / system
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordRevisionDTO {
+    pub id: Option<i32>,
+    pub word_id: i32,
+    pub source: String,
+    pub editor: Option<String>,
+    /// 修改后的完整内容
+    pub content: Value,
+    /// 与上一版本的差异：`{字段: {"old": 旧值, "new": 新值}}`
+    pub changes: Value,
+    pub created_at: Option<String>,
+}
+
+impl TryFrom<WordRevision> for WordRevisionDTO {
+    type Error = ConversionError;
+
+    fn try_from(revision: WordRevision) -> Result<Self, Self::Error> {
+        let created_at = match revision.created_at {
+            Some(dt) => Some(dt.format(&Rfc3339)?),
+            None => None,
+        };
+        Ok(Self {
+            id: revision.id,
+            word_id: revision.word_id,
+            source: revision.source,
+            editor: revision.editor,
+            content: revision.content,
+            changes: revision.changes,
+            created_at,
+        })
+    }
+}
+
+/// 比较同一单词的两个版本，to 为空时与单词当前内容比较
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordRevisionCompareDTO {
+    pub word_id: i32,
+    pub from: i32,
+    pub to: Option<i32>,
+}
+
+/// 两个版本之间的差异，只包含有变化的字段
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordRevisionDiffDTO {
+    pub word_id: i32,
+    pub from: i32,
+    pub to: Option<i32>,
+    pub changes: Value,
+}
+
+/// 把单词回滚到指定版本
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordRollbackDTO {
+    pub word_id: i32,
+    pub revision_id: i32,
+    pub editor: Option<String>,
+}
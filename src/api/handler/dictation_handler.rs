@@ -0,0 +1,55 @@
+use crate::api::dto::dictation_dto::{DictationIdDTO, StartDictationDTO, SubmitDictationDTO};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::{AuthUser, SIGNED_IN};
+use crate::domain::services::DictationService;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct DictationHandler {
+    service: Arc<dyn DictationService>,
+}
+
+impl DictationHandler {
+    pub fn new(service: Arc<dyn DictationService>) -> Self {
+        Self { service }
+    }
+}
+
+async fn start(
+    data: web::Data<DictationHandler>,
+    user: AuthUser,
+    dto: web::Json<StartDictationDTO>,
+) -> impl Responder {
+    let result = data.service.start(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_session(
+    data: web::Data<DictationHandler>,
+    user: AuthUser,
+    dto: web::Json<DictationIdDTO>,
+) -> impl Responder {
+    let result = data.service.get_session(&user, dto.session_id).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 交卷并返回每个单词的拼写差异
+async fn submit(
+    data: web::Data<DictationHandler>,
+    user: AuthUser,
+    dto: web::Json<SubmitDictationDTO>,
+) -> impl Responder {
+    let result = data.service.submit(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    DictationHandler,
+    post "/start" => start : SIGNED_IN,
+    post "/get" => get_session : SIGNED_IN,
+    post "/submit" => submit : SIGNED_IN,
+);
@@ -0,0 +1,125 @@
+use crate::api::dto::export_dto::{ExportFormat, ExportQueryDTO};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::STAFF;
+use crate::domain::services::ExportService;
+use actix_web::http::header::{
+    Charset, ContentDisposition, DispositionParam, DispositionType, ExtendedValue,
+};
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct ExportHandler {
+    service: Arc<dyn ExportService>,
+}
+
+impl ExportHandler {
+    pub fn new(service: Arc<dyn ExportService>) -> Self {
+        Self { service }
+    }
+}
+
+/// 下载单词表；HTML 讲义直接在浏览器中打开以便打印，其他格式作为附件下载。
+/// 浏览器直接打开时无法设置请求头，用 `access_token` 查询参数携带链接令牌
+async fn export_words(
+    data: web::Data<ExportHandler>,
+    query: web::Query<ExportQueryDTO>,
+) -> impl Responder {
+    match data.service.export_words(&query).await {
+        Ok(file) => {
+            let disposition = ContentDisposition {
+                disposition: match query.format {
+                    ExportFormat::Html => DispositionType::Inline,
+                    _ => DispositionType::Attachment,
+                },
+                parameters: vec![
+                    // 旧浏览器不支持 filename*，提供一个 ASCII 文件名兜底
+                    DispositionParam::Filename(ascii_file_name(&file.file_name)),
+                    DispositionParam::FilenameExt(ExtendedValue {
+                        charset: Charset::Ext("UTF-8".to_string()),
+                        language_tag: None,
+                        value: file.file_name.into_bytes(),
+                    }),
+                ],
+            };
+            HttpResponse::Ok()
+                .content_type(file.content_type)
+                .insert_header(disposition)
+                .body(file.content)
+        }
+        Err(e) => HttpResponse::Ok().json(to_api_response::<()>(Err(e))),
+    }
+}
+
+fn ascii_file_name(file_name: &str) -> String {
+    let extension = file_name.rsplit('.').next().unwrap_or_default();
+    format!("words.{}", extension)
+}
+
+define_routes!(
+    ExportHandler,
+    get "/words" => export_words : STAFF,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handler::Handler;
+    use crate::app::testing::TestAuth;
+    use crate::domain::models::user::Role;
+    use crate::infrastructure::export::ExportFile;
+    use actix_web::{test, App};
+    use async_trait::async_trait;
+
+    struct StubExportService;
+
+    #[async_trait]
+    impl ExportService for StubExportService {
+        async fn export_words(&self, query: &ExportQueryDTO) -> anyhow::Result<ExportFile> {
+            if query.textbook_id.is_none() {
+                anyhow::bail!("Exactly one of textbook_id and unit_id is required");
+            }
+            Ok(ExportFile {
+                file_name: "三年级上册.csv".to_string(),
+                content_type: "text/csv; charset=utf-8",
+                content: b"unit,word\r\n".to_vec(),
+            })
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_export_words_download() {
+        let handler = web::Data::new(ExportHandler::new(Arc::new(StubExportService)));
+        let auth = TestAuth::default();
+        let app = test::init_service(
+            App::new()
+                .wrap(auth.middleware())
+                .app_data(handler)
+                .service(web::scope("/api/export").configure(ExportHandler::register)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/api/export/words?textbook_id=1&format=csv")
+            .insert_header(auth.bearer(Role::Teacher).await)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        let disposition = response
+            .headers()
+            .get("content-disposition")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(disposition.starts_with("attachment; filename=\"words.csv\""));
+        assert!(disposition.contains("filename*=UTF-8''%E4%B8%89"));
+        assert_eq!(test::read_body(response).await, "unit,word\r\n");
+
+        let request = test::TestRequest::get()
+            .uri("/api/export/words?format=anki")
+            .insert_header(auth.bearer(Role::Teacher).await)
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["code"], 500);
+    }
+}
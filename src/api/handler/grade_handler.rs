@@ -1,5 +1,6 @@
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
+use crate::domain::models::user::SIGNED_IN;
 use crate::domain::services::interfaces::grade_service::GradeService;
 use actix_web::{web, HttpResponse, Responder};
 use std::sync::Arc;
@@ -22,5 +23,5 @@ async fn get_grades(handler: web::Data<GradeHandler>) -> impl Responder {
 
 define_routes!(
     GradeHandler,
-    get "/list" => get_grades,
+    get "/list" => get_grades : SIGNED_IN,
 );
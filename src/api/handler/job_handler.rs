@@ -0,0 +1,49 @@
+use crate::api::dto::job_dto::{JobDTO, JobQueryDTO};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::EDITORS;
+use crate::domain::services::JobService;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct JobHandler {
+    service: Arc<dyn JobService>,
+}
+
+impl JobHandler {
+    pub fn new(service: Arc<dyn JobService>) -> Self {
+        Self { service }
+    }
+}
+
+async fn enqueue_job(data: web::Data<JobHandler>, dto: web::Json<JobDTO>) -> impl Responder {
+    let result = data.service.enqueue(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_jobs(data: web::Data<JobHandler>, query: web::Query<JobQueryDTO>) -> impl Responder {
+    let result = data.service.get_jobs(&query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_job(data: web::Data<JobHandler>, dto: web::Json<JobDTO>) -> impl Responder {
+    let result = data.service.get_job(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn cancel_job(data: web::Data<JobHandler>, dto: web::Json<JobDTO>) -> impl Responder {
+    let result = data.service.cancel_job(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    JobHandler,
+    post "/enqueue" => enqueue_job : EDITORS,
+    get "/list" => get_jobs : EDITORS,
+    post "/get" => get_job : EDITORS,
+    post "/cancel" => cancel_job : EDITORS,
+);
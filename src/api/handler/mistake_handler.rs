@@ -0,0 +1,44 @@
+use crate::api::dto::mistake_dto::{MistakeQueryDTO, MistakeReviewQueryDTO};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::{AuthUser, SIGNED_IN};
+use crate::domain::services::MistakeService;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct MistakeHandler {
+    service: Arc<dyn MistakeService>,
+}
+
+impl MistakeHandler {
+    pub fn new(service: Arc<dyn MistakeService>) -> Self {
+        Self { service }
+    }
+}
+
+async fn get_mistakes(
+    data: web::Data<MistakeHandler>,
+    user: AuthUser,
+    query: web::Json<MistakeQueryDTO>,
+) -> impl Responder {
+    let result = data.service.get_mistakes(&user, &query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 当前用户按权重排序的错题复习集
+async fn get_review(
+    data: web::Data<MistakeHandler>,
+    user: AuthUser,
+    query: web::Query<MistakeReviewQueryDTO>,
+) -> impl Responder {
+    let result = data.service.get_review(&user, &query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    MistakeHandler,
+    post "/list" => get_mistakes : SIGNED_IN,
+    get "/review" => get_review : SIGNED_IN,
+);
@@ -1,11 +1,20 @@
+pub mod dictation_handler;
+pub mod export_handler;
 pub mod grade_handler;
 pub mod handler_trait;
+pub mod job_handler;
+pub mod mistake_handler;
 pub mod model_provider_handler;
+pub mod prompt_template_handler;
+pub mod quiz_handler;
 pub mod semester_handler;
+pub mod study_handler;
 pub mod system_config_handler;
 pub mod textbook_handler;
 pub mod textbook_version_handler;
+pub mod trash_handler;
 pub mod unit_handler;
+pub mod user_handler;
 pub mod word_handler;
 pub mod word_unit_handler;
 
@@ -1,5 +1,8 @@
+use crate::api::dto::llm_usage_dto::LLMUsageQueryDTO;
+use crate::api::dto::model_provider_dto::ModelProviderDTO;
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
+use crate::domain::models::user::ADMIN;
 use crate::domain::services::ModelProviderService;
 use actix_web::{web, HttpResponse, Responder};
 use std::sync::Arc;
@@ -20,7 +23,64 @@ async fn get_all_providers(data: web::Data<ModelProviderHandler>) -> impl Respon
     HttpResponse::Ok().json(response)
 }
 
+async fn get_provider(
+    data: web::Data<ModelProviderHandler>,
+    dto: web::Json<ModelProviderDTO>,
+) -> impl Responder {
+    let result = data.service.get_provider(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn create_provider(
+    data: web::Data<ModelProviderHandler>,
+    dto: web::Json<ModelProviderDTO>,
+) -> impl Responder {
+    let result = data.service.create_provider(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn update_provider(
+    data: web::Data<ModelProviderHandler>,
+    dto: web::Json<ModelProviderDTO>,
+) -> impl Responder {
+    let result = data.service.update_provider(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn delete_provider(
+    data: web::Data<ModelProviderHandler>,
+    dto: web::Json<ModelProviderDTO>,
+) -> impl Responder {
+    let result = data.service.delete_provider(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_health(data: web::Data<ModelProviderHandler>) -> impl Responder {
+    let result = data.service.get_health().await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_usage_report(
+    data: web::Data<ModelProviderHandler>,
+    query: web::Query<LLMUsageQueryDTO>,
+) -> impl Responder {
+    let result = data.service.get_usage_report(&query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
 define_routes!(
     ModelProviderHandler,
-    get "/list" => get_all_providers,
+    get "/list" => get_all_providers : ADMIN,
+    post "/get" => get_provider : ADMIN,
+    post "/create" => create_provider : ADMIN,
+    post "/update" => update_provider : ADMIN,
+    post "/delete" => delete_provider : ADMIN,
+    get "/health" => get_health : ADMIN,
+    get "/usage" => get_usage_report : ADMIN,
 );
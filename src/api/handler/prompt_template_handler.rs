@@ -0,0 +1,58 @@
+use crate::api::dto::prompt_template_dto::{PromptPreviewDTO, PromptTemplateDTO};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::ADMIN;
+use crate::domain::services::PromptTemplateService;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct PromptTemplateHandler {
+    service: Arc<dyn PromptTemplateService>,
+}
+
+impl PromptTemplateHandler {
+    pub fn new(service: Arc<dyn PromptTemplateService>) -> Self {
+        Self { service }
+    }
+}
+
+async fn get_templates(data: web::Data<PromptTemplateHandler>) -> impl Responder {
+    let result = data.service.get_templates().await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn create_template(
+    data: web::Data<PromptTemplateHandler>,
+    dto: web::Json<PromptTemplateDTO>,
+) -> impl Responder {
+    let result = data.service.create_template(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn preview_template(
+    data: web::Data<PromptTemplateHandler>,
+    dto: web::Json<PromptPreviewDTO>,
+) -> impl Responder {
+    let result = data.service.preview_template(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn activate_template(
+    data: web::Data<PromptTemplateHandler>,
+    dto: web::Json<PromptTemplateDTO>,
+) -> impl Responder {
+    let result = data.service.activate_template(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    PromptTemplateHandler,
+    get "/list" => get_templates : ADMIN,
+    post "/create" => create_template : ADMIN,
+    post "/preview" => preview_template : ADMIN,
+    post "/activate" => activate_template : ADMIN,
+);
@@ -0,0 +1,67 @@
+use crate::api::dto::quiz_dto::{AssignQuizDTO, GenerateQuizDTO, QuizIdDTO, SubmitQuizDTO};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::{AuthUser, SIGNED_IN, STAFF};
+use crate::domain::services::QuizService;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct QuizHandler {
+    service: Arc<dyn QuizService>,
+}
+
+impl QuizHandler {
+    pub fn new(service: Arc<dyn QuizService>) -> Self {
+        Self { service }
+    }
+}
+
+async fn generate(
+    data: web::Data<QuizHandler>,
+    user: AuthUser,
+    dto: web::Json<GenerateQuizDTO>,
+) -> impl Responder {
+    let result = data.service.generate(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_quiz(
+    data: web::Data<QuizHandler>,
+    user: AuthUser,
+    dto: web::Json<QuizIdDTO>,
+) -> impl Responder {
+    let result = data.service.get_quiz(&user, dto.quiz_id).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 把测验布置给学生，返回新布置的数量
+async fn assign(
+    data: web::Data<QuizHandler>,
+    user: AuthUser,
+    dto: web::Json<AssignQuizDTO>,
+) -> impl Responder {
+    let result = data.service.assign(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 交卷并返回每道题的判分结果
+async fn submit(
+    data: web::Data<QuizHandler>,
+    user: AuthUser,
+    dto: web::Json<SubmitQuizDTO>,
+) -> impl Responder {
+    let result = data.service.submit(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    QuizHandler,
+    post "/generate" => generate : SIGNED_IN,
+    post "/get" => get_quiz : SIGNED_IN,
+    post "/submit" => submit : SIGNED_IN,
+    post "/assign" => assign : STAFF,
+);
@@ -1,5 +1,6 @@
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
+use crate::domain::models::user::SIGNED_IN;
 use crate::domain::services::interfaces::semester_service::SemesterService;
 use actix_web::{web, HttpResponse, Responder};
 use std::sync::Arc;
@@ -22,5 +23,5 @@ async fn get_semesters(data: web::Data<SemesterHandler>) -> impl Responder {
 
 define_routes!(
     SemesterHandler,
-    get "/list" => get_semesters,
+    get "/list" => get_semesters : SIGNED_IN,
 );
@@ -0,0 +1,89 @@
+use crate::api::dto::study_dto::{
+    AssignUnitDTO, ReviewAnswerDTO, ReviewQueueQueryDTO, StudentQueryDTO, UnassignUnitDTO,
+};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::{AuthUser, SIGNED_IN, STAFF};
+use crate::domain::services::StudyService;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct StudyHandler {
+    service: Arc<dyn StudyService>,
+}
+
+impl StudyHandler {
+    pub fn new(service: Arc<dyn StudyService>) -> Self {
+        Self { service }
+    }
+}
+
+async fn assign_unit(
+    data: web::Data<StudyHandler>,
+    user: AuthUser,
+    dto: web::Json<AssignUnitDTO>,
+) -> impl Responder {
+    let result = data.service.assign_unit(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn unassign_unit(
+    data: web::Data<StudyHandler>,
+    dto: web::Json<UnassignUnitDTO>,
+) -> impl Responder {
+    let result = data.service.unassign_unit(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_assignments(
+    data: web::Data<StudyHandler>,
+    user: AuthUser,
+    query: web::Json<StudentQueryDTO>,
+) -> impl Responder {
+    let result = data.service.get_assignments(&user, &query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 当前用户今天的复习队列
+async fn get_review_queue(
+    data: web::Data<StudyHandler>,
+    user: AuthUser,
+    query: web::Query<ReviewQueueQueryDTO>,
+) -> impl Responder {
+    let result = data.service.get_review_queue(&user, &query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn answer(
+    data: web::Data<StudyHandler>,
+    user: AuthUser,
+    dto: web::Json<ReviewAnswerDTO>,
+) -> impl Responder {
+    let result = data.service.answer(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_mastery(
+    data: web::Data<StudyHandler>,
+    user: AuthUser,
+    query: web::Json<StudentQueryDTO>,
+) -> impl Responder {
+    let result = data.service.get_mastery(&user, &query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    StudyHandler,
+    post "/assign" => assign_unit : STAFF,
+    post "/unassign" => unassign_unit : STAFF,
+    post "/assignments" => get_assignments : SIGNED_IN,
+    get "/queue" => get_review_queue : SIGNED_IN,
+    post "/answer" => answer : SIGNED_IN,
+    post "/mastery" => get_mastery : SIGNED_IN,
+);
@@ -2,6 +2,8 @@ use crate::api::dto::model_dto::ModelDto;
 use crate::api::dto::response::ApiResponse;
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
+use crate::domain::models::user::ADMIN;
+use crate::domain::services::interfaces::textbook_service::TextbookService;
 use crate::domain::services::interfaces::SystemConfigService;
 use actix_web::{web, HttpResponse, Responder};
 use std::sync::Arc;
@@ -9,11 +11,18 @@ use tracing::{debug, error, info, instrument};
 
 pub struct SystemConfigHandler {
     service: Arc<dyn SystemConfigService>,
+    textbook_service: Arc<dyn TextbookService>,
 }
 
 impl SystemConfigHandler {
-    pub fn new(service: Arc<dyn SystemConfigService>) -> Self {
-        Self { service }
+    pub fn new(
+        service: Arc<dyn SystemConfigService>,
+        textbook_service: Arc<dyn TextbookService>,
+    ) -> Self {
+        Self {
+            service,
+            textbook_service,
+        }
     }
 }
 
@@ -42,7 +51,19 @@ async fn set_use_model(
     HttpResponse::Ok().json(response)
 }
 
+/// 重新统计单元和课本的单词数、单元数，返回修正的不一致记录
+#[instrument(skip(data))]
+async fn recount(data: web::Data<SystemConfigHandler>) -> impl Responder {
+    let result = data.textbook_service.recount_counters().await;
+    if let Ok(report) = &result {
+        info!("Recount fixed {} counters", report.fixed);
+    }
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
 define_routes!(
     SystemConfigHandler,
-    post "/model" => set_use_model,
+    post "/model" => set_use_model : ADMIN,
+    post "/recount" => recount : ADMIN,
 );
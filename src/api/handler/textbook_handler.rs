@@ -1,7 +1,8 @@
-use crate::api::dto::textbook_dto::TextbookDTO;
+use crate::api::dto::textbook_dto::{TextbookCloneDTO, TextbookDTO, TextbookDiffQueryDTO};
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
 use crate::domain::models::textbook::Textbook;
+use crate::domain::models::user::{EDITORS, SIGNED_IN};
 use crate::domain::services::interfaces::textbook_service::TextbookService;
 use actix_web::{web, HttpResponse, Responder};
 use std::sync::Arc;
@@ -49,10 +50,30 @@ async fn get_unit_by_textbook(
     HttpResponse::Ok().json(response)
 }
 
+async fn clone_textbook(
+    data: web::Data<TextbookHandler>,
+    dto: web::Json<TextbookCloneDTO>,
+) -> impl Responder {
+    let result = data.service.clone_textbook(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn diff_textbooks(
+    data: web::Data<TextbookHandler>,
+    dto: web::Json<TextbookDiffQueryDTO>,
+) -> impl Responder {
+    let result = data.service.diff_textbooks(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
 define_routes!(
     TextbookHandler,
-    get "/list" => get_textbooks,
-    post "/create" => create_textbook,
-    post "/delete" => delete_textbook,
-    post "/units" => get_unit_by_textbook,
+    get "/list" => get_textbooks : SIGNED_IN,
+    post "/create" => create_textbook : EDITORS,
+    post "/delete" => delete_textbook : EDITORS,
+    post "/units" => get_unit_by_textbook : SIGNED_IN,
+    post "/clone" => clone_textbook : EDITORS,
+    post "/diff" => diff_textbooks : SIGNED_IN,
 );
@@ -1,6 +1,7 @@
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
 use crate::domain::models::textbook_version::TextbookVersion;
+use crate::domain::models::user::{EDITORS, SIGNED_IN};
 use crate::domain::services::interfaces::textbook_version_service::TextbookVersionService;
 use actix_web::{web, HttpResponse, Responder};
 use std::sync::Arc;
@@ -59,8 +60,8 @@ async fn delete_textbook_version(
 
 define_routes!(
     TextbookVersionHandler,
-    get "/list" => get_textbook_versions,
-    post "/create" => create_textbook_version,
-    post "/update" => update_textbook_version,
-    post "/delete" => delete_textbook_version,
+    get "/list" => get_textbook_versions : SIGNED_IN,
+    post "/create" => create_textbook_version : EDITORS,
+    post "/update" => update_textbook_version : EDITORS,
+    post "/delete" => delete_textbook_version : EDITORS,
 );
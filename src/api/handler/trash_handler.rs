@@ -0,0 +1,56 @@
+use crate::api::dto::trash_dto::{TrashQueryDTO, TrashTargetDTO};
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::{ADMIN, EDITORS};
+use crate::domain::services::TrashService;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct TrashHandler {
+    service: Arc<dyn TrashService>,
+}
+
+impl TrashHandler {
+    pub fn new(service: Arc<dyn TrashService>) -> Self {
+        Self { service }
+    }
+}
+
+/// 删除前预览会连带删除的单元和单元单词
+async fn preview_delete(
+    data: web::Data<TrashHandler>,
+    dto: web::Json<TrashTargetDTO>,
+) -> impl Responder {
+    let result = data.service.preview_delete(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_trash(
+    data: web::Data<TrashHandler>,
+    query: web::Query<TrashQueryDTO>,
+) -> impl Responder {
+    let result = data.service.get_trash(&query).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn restore(data: web::Data<TrashHandler>, dto: web::Json<TrashTargetDTO>) -> impl Responder {
+    let result = data.service.restore(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn purge_expired(data: web::Data<TrashHandler>) -> impl Responder {
+    let result = data.service.purge_expired().await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    TrashHandler,
+    post "/preview" => preview_delete : EDITORS,
+    get "/list" => get_trash : EDITORS,
+    post "/restore" => restore : EDITORS,
+    post "/purge" => purge_expired : ADMIN,
+);
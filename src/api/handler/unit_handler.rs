@@ -1,6 +1,8 @@
+use crate::api::dto::sequence_dto::{MoveDTO, SwapDTO};
 use crate::api::dto::unit_dto::UnitDTO;
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
+use crate::domain::models::user::{EDITORS, SIGNED_IN};
 use crate::domain::services::interfaces::unit_service::UnitService;
 use actix_web::{web, HttpResponse, Responder};
 use std::sync::Arc;
@@ -33,9 +35,36 @@ async fn delete_unit(data: web::Data<UnitHandler>, unit_dto: web::Json<UnitDTO>)
     HttpResponse::Ok().json(response)
 }
 
+async fn move_unit(data: web::Data<UnitHandler>, dto: web::Json<MoveDTO>) -> impl Responder {
+    let result = data.service.move_unit(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn swap_units(data: web::Data<UnitHandler>, dto: web::Json<SwapDTO>) -> impl Responder {
+    let result = data.service.swap_units(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn renumber_units(
+    data: web::Data<UnitHandler>,
+    unit_dto: web::Json<UnitDTO>,
+) -> impl Responder {
+    let result = match unit_dto.textbook_id {
+        Some(textbook_id) => data.service.renumber_units(textbook_id).await,
+        None => Err(anyhow::anyhow!("textbook_id is required")),
+    };
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
 define_routes!(
     UnitHandler,
-    post "/create" => create_unit,
-    post "/list" => get_units,
-    post "/delete" => delete_unit,
+    post "/create" => create_unit : EDITORS,
+    post "/list" => get_units : SIGNED_IN,
+    post "/delete" => delete_unit : EDITORS,
+    post "/move" => move_unit : EDITORS,
+    post "/swap" => swap_units : EDITORS,
+    post "/renumber" => renumber_units : EDITORS,
 );
@@ -0,0 +1,91 @@
+use crate::api::dto::user_dto::{ChangePasswordDTO, CreateUserDTO, LoginDTO, UpdateUserDTO};
+use crate::app::bearer_token;
+use crate::common::utils::response::to_api_response;
+use crate::define_routes;
+use crate::domain::models::user::{AuthUser, ADMIN, PUBLIC, SIGNED_IN};
+use crate::domain::services::UserService;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct UserHandler {
+    service: Arc<dyn UserService>,
+}
+
+impl UserHandler {
+    pub fn new(service: Arc<dyn UserService>) -> Self {
+        Self { service }
+    }
+}
+
+async fn login(data: web::Data<UserHandler>, dto: web::Json<LoginDTO>) -> impl Responder {
+    let result = data.service.login(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn logout(data: web::Data<UserHandler>, req: HttpRequest) -> impl Responder {
+    let result = match bearer_token(&req) {
+        Some(token) => data.service.logout(token).await,
+        None => Ok(()),
+    };
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_current_user(data: web::Data<UserHandler>, user: AuthUser) -> impl Responder {
+    let result = data.service.get_user(user.id).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn issue_link_token(data: web::Data<UserHandler>, user: AuthUser) -> impl Responder {
+    let result = data.service.issue_link_token(&user).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn change_password(
+    data: web::Data<UserHandler>,
+    user: AuthUser,
+    dto: web::Json<ChangePasswordDTO>,
+) -> impl Responder {
+    let result = data.service.change_password(&user, &dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn get_users(data: web::Data<UserHandler>) -> impl Responder {
+    let result = data.service.get_users().await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn create_user(
+    data: web::Data<UserHandler>,
+    dto: web::Json<CreateUserDTO>,
+) -> impl Responder {
+    let result = data.service.create_user(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn update_user(
+    data: web::Data<UserHandler>,
+    dto: web::Json<UpdateUserDTO>,
+) -> impl Responder {
+    let result = data.service.update_user(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+define_routes!(
+    UserHandler,
+    post "/login" => login : PUBLIC,
+    post "/logout" => logout : SIGNED_IN,
+    get "/me" => get_current_user : SIGNED_IN,
+    post "/link-token" => issue_link_token : SIGNED_IN,
+    post "/password" => change_password : SIGNED_IN,
+    get "/list" => get_users : ADMIN,
+    post "/create" => create_user : ADMIN,
+    post "/update" => update_user : ADMIN,
+);
@@ -1,43 +1,330 @@
+use crate::api::dto::job_dto::JobDTO;
 use crate::api::dto::unit_word_dto::WordPageRequestDTO;
+use crate::api::dto::word_edit_dto::{WordIdDTO, WordPatchDTO};
+use crate::api::dto::word_revision_dto::{WordRevisionCompareDTO, WordRollbackDTO};
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
+use crate::domain::models::job::{CREATE_WORD_JOB, ENRICH_WORDS_JOB};
+use crate::domain::models::user::{AuthUser, EDITORS, SIGNED_IN};
 use crate::domain::models::word::Word;
 use crate::domain::services::interfaces::word_service::WordService;
+use crate::domain::services::JobService;
 use actix_web::{web, HttpResponse, Responder};
+use futures::{future, stream, StreamExt};
+use serde_json::json;
 use std::sync::Arc;
 use tracing::instrument;
 
 pub struct WordHandler {
     service: Arc<dyn WordService>,
+    job_service: Arc<dyn JobService>,
 }
 
 impl WordHandler {
-    pub fn new(service: Arc<dyn WordService>) -> Self {
-        Self { service }
+    pub fn new(service: Arc<dyn WordService>, job_service: Arc<dyn JobService>) -> Self {
+        Self {
+            service,
+            job_service,
+        }
     }
 }
 
-async fn create_word(data: web::Data<WordHandler>, word: web::Json<Word>) -> impl Responder {
-    let result = data.service.create_word(&word.word).await;
+async fn get_word(data: web::Data<WordHandler>, word: web::Json<Word>) -> impl Responder {
+    let result = data.service.get_word(&word.word).await;
     let response = to_api_response(result);
     HttpResponse::Ok().json(response)
 }
 
-async fn get_word(data: web::Data<WordHandler>, word: web::Json<Word>) -> impl Responder {
-    let result = data.service.get_word(&word.word).await;
+/// 软删除单词，同时从所有单元中移除
+async fn delete_word(data: web::Data<WordHandler>, word: web::Json<Word>) -> impl Responder {
+    let result = data.service.delete_word(&word.word).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 人工修改单词的音标、释义和例句，只修改传入的字段，修改人为当前登录用户
+async fn update_word(
+    data: web::Data<WordHandler>,
+    user: AuthUser,
+    patch: web::Json<WordPatchDTO>,
+) -> impl Responder {
+    let mut patch = patch.into_inner();
+    patch.editor = Some(user.username);
+    let result = data.service.update_word(&patch).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 单词锁定的字段和人工修改历史
+async fn get_word_history(
+    data: web::Data<WordHandler>,
+    dto: web::Json<WordIdDTO>,
+) -> impl Responder {
+    let result = data.service.get_word_history(dto.word_id).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 单词的修订版本，包括大模型、词典、编辑和回滚产生的每一次修改
+async fn get_word_revisions(
+    data: web::Data<WordHandler>,
+    dto: web::Json<WordIdDTO>,
+) -> impl Responder {
+    let result = data.service.get_word_revisions(dto.word_id).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn compare_word_revisions(
+    data: web::Data<WordHandler>,
+    dto: web::Json<WordRevisionCompareDTO>,
+) -> impl Responder {
+    let result = data.service.compare_word_revisions(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn rollback_word(
+    data: web::Data<WordHandler>,
+    user: AuthUser,
+    dto: web::Json<WordRollbackDTO>,
+) -> impl Responder {
+    let mut dto = dto.into_inner();
+    dto.editor = Some(user.username);
+    let result = data.service.rollback_word(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 迁移时无法解析的释义报告
+async fn get_meaning_parse_failures(data: web::Data<WordHandler>) -> impl Responder {
+    let result = data.service.get_meaning_parse_failures().await;
     let response = to_api_response(result);
     HttpResponse::Ok().json(response)
 }
 
+/// 在后台任务中新建单词，返回 JobDTO 而不是单词本身，
+/// 可通过 /api/job/get 查询进度，完成后 result 为 {word_id, word}
+async fn create_word(data: web::Data<WordHandler>, word: web::Json<Word>) -> impl Responder {
+    let job = JobDTO {
+        kind: Some(CREATE_WORD_JOB.to_string()),
+        payload: Some(json!({ "word": word.word })),
+        ..Default::default()
+    };
+    let result = data.job_service.enqueue(&job).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 在后台任务中补全所有单词的信息，返回任务信息
 async fn update_batch_words(data: web::Data<WordHandler>) -> impl Responder {
-    let result = data.service.update_batch_words().await;
+    let job = JobDTO {
+        kind: Some(ENRICH_WORDS_JOB.to_string()),
+        ..Default::default()
+    };
+    let result = data.job_service.enqueue(&job).await;
     let response = to_api_response(result);
     HttpResponse::Ok().json(response)
 }
 
+/// EventSource 无法设置请求头，用 `access_token` 查询参数携带链接令牌
+async fn explain_word_stream(
+    data: web::Data<WordHandler>,
+    word: web::Query<Word>,
+) -> impl Responder {
+    match data.service.explain_word_stream(&word.word).await {
+        Ok(deltas) => {
+            let events = deltas
+                .map(|delta| {
+                    let event = match delta {
+                        Ok(text) => to_sse_event(None, &text),
+                        Err(e) => to_sse_event(Some("error"), &e.to_string()),
+                    };
+                    Ok::<_, actix_web::Error>(web::Bytes::from(event))
+                })
+                .chain(stream::once(future::ready(Ok(web::Bytes::from(
+                    to_sse_event(Some("done"), "[DONE]"),
+                )))));
+
+            HttpResponse::Ok()
+                .content_type("text/event-stream")
+                .insert_header(("Cache-Control", "no-cache"))
+                .streaming(events)
+        }
+        Err(e) => HttpResponse::Ok().json(to_api_response::<()>(Err(e))),
+    }
+}
+
+/// 构造一条 SSE 消息，数据使用 JSON 字符串编码以保留换行
+fn to_sse_event(event: Option<&str>, data: &str) -> String {
+    let data = serde_json::to_string(data).unwrap_or_default();
+    match event {
+        Some(event) => format!("event: {}\ndata: {}\n\n", event, data),
+        None => format!("data: {}\n\n", data),
+    }
+}
+
 define_routes!(
     WordHandler,
-    post "/create" => create_word,
-    post "/get" => get_word,
-    post "/update-batch" => update_batch_words,
+    post "/create" => create_word : EDITORS,
+    post "/get" => get_word : SIGNED_IN,
+    post "/delete" => delete_word : EDITORS,
+    post "/update" => update_word : EDITORS,
+    post "/history" => get_word_history : EDITORS,
+    post "/revisions" => get_word_revisions : EDITORS,
+    post "/revisions/compare" => compare_word_revisions : EDITORS,
+    post "/revisions/rollback" => rollback_word : EDITORS,
+    post "/meanings/failures" => get_meaning_parse_failures : EDITORS,
+    post "/update-batch" => update_batch_words : EDITORS,
+    get "/explain/stream" => explain_word_stream : SIGNED_IN,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handler::Handler;
+    use crate::app::testing::{
+        self, FixedModelConfig, InMemoryJobRepository, InMemoryWordRepository, OfflineThirdParty,
+        TestAuth,
+    };
+    use crate::domain::models::user::Role;
+    use crate::domain::services::impls::word_jobs::{CreateWordJob, EnrichWordsJob};
+    use crate::domain::services::impls::word_service_impl::WordServiceImpl;
+    use crate::domain::services::JobServiceImpl;
+    use actix_web::{test, App};
+    use serde_json::{json, Value};
+
+    fn handler() -> web::Data<WordHandler> {
+        testing::init_mock_llm("mock");
+        let words = Arc::new(InMemoryWordRepository::default());
+        let service = Arc::new(WordServiceImpl::new(
+            words.clone(),
+            FixedModelConfig::new("mock"),
+            Arc::new(OfflineThirdParty),
+        ));
+        let job_service = JobServiceImpl::new(
+            Arc::new(InMemoryJobRepository::default()),
+            vec![
+                Arc::new(CreateWordJob::new(service.clone())),
+                Arc::new(EnrichWordsJob::new(words, service.clone())),
+            ],
+            std::time::Duration::from_secs(60),
+        );
+        web::Data::new(WordHandler::new(service, Arc::new(job_service)))
+    }
+
+    #[actix_web::test]
+    async fn test_create_and_get_word() {
+        let auth = TestAuth::default();
+        let data = handler();
+        let app = test::init_service(
+            App::new()
+                .wrap(auth.middleware())
+                .app_data(data.clone())
+                .service(web::scope("/api/word").configure(WordHandler::register)),
+        )
+        .await;
+
+        // 新建单词在后台任务中执行
+        let request = test::TestRequest::post()
+            .uri("/api/word/create")
+            .insert_header(auth.bearer(Role::Editor).await)
+            .set_json(json!({"word": "hello"}))
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["code"], 200);
+        assert_eq!(body["data"]["kind"], "create_word");
+        assert!(data.job_service.run_next().await.unwrap());
+        let request = test::TestRequest::post()
+            .uri("/api/word/get")
+            .insert_header(auth.bearer(Role::Student).await)
+            .set_json(json!({"word": "hello"}))
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["data"]["phonetic_us"], "/həˈloʊ/");
+
+        // 未登录和学生都不能创建单词
+        let request = test::TestRequest::post()
+            .uri("/api/word/create")
+            .set_json(json!({"word": "world"}))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 401);
+        let request = test::TestRequest::post()
+            .uri("/api/word/create")
+            .insert_header(auth.bearer(Role::Student).await)
+            .set_json(json!({"word": "world"}))
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["code"], 403);
+
+        let request = test::TestRequest::post()
+            .uri("/api/word/get")
+            .insert_header(auth.bearer(Role::Student).await)
+            .set_json(json!({"word": "missing"}))
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["code"], 500);
+        assert_eq!(body["message"], "Word not found: missing");
+    }
+
+    #[actix_web::test]
+    async fn test_long_operations_are_enqueued() {
+        let auth = TestAuth::default();
+        let app = test::init_service(
+            App::new()
+                .wrap(auth.middleware())
+                .app_data(handler())
+                .service(web::scope("/api/word").configure(WordHandler::register)),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/api/word/create")
+            .insert_header(auth.bearer(Role::Editor).await)
+            .set_json(json!({"word": "hello"}))
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["code"], 200);
+        assert_eq!(body["data"]["kind"], "create_word");
+        assert_eq!(body["data"]["status"], "pending");
+        assert_eq!(body["data"]["payload"]["word"], "hello");
+
+        let request = test::TestRequest::post()
+            .uri("/api/word/update-batch")
+            .insert_header(auth.bearer(Role::Editor).await)
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["data"]["kind"], "enrich_words");
+    }
+
+    #[actix_web::test]
+    async fn test_explain_word_stream() {
+        let auth = TestAuth::default();
+        let app = test::init_service(
+            App::new()
+                .wrap(auth.middleware())
+                .app_data(handler())
+                .service(web::scope("/api/word").configure(WordHandler::register)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/api/word/explain/stream?word=hello")
+            .insert_header(auth.bearer(Role::Student).await)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "text/event-stream"
+        );
+        let body = test::read_body(response).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("hello"));
+        assert!(
+            body.ends_with("event: done\ndata: \"[DONE]\"\n\n"),
+            "{}",
+            body
+        );
+    }
+}
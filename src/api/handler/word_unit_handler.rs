@@ -1,17 +1,37 @@
-use crate::api::dto::unit_word_dto::WordDTO;
+use crate::api::dto::job_dto::JobDTO;
+use crate::api::dto::sequence_dto::{MoveDTO, SwapDTO};
+use crate::api::dto::unit_word_dto::{UnitMeaningDTO, UnitWordImportDTO, WordDTO};
 use crate::common::utils::response::to_api_response;
 use crate::define_routes;
+use crate::domain::models::job::{CREATE_UNIT_WORD_JOB, IMPORT_UNIT_WORDS_JOB};
+use crate::domain::models::user::{EDITORS, SIGNED_IN};
 use crate::domain::services::interfaces::word_unit_service::WordUnitService;
+use crate::domain::services::JobService;
 use actix_web::{web, HttpResponse, Responder};
+use serde::Serialize;
 use std::sync::Arc;
 
 pub struct WordUnitHandler {
     service: Arc<dyn WordUnitService>,
+    job_service: Arc<dyn JobService>,
 }
 
 impl WordUnitHandler {
-    pub fn new(service: Arc<dyn WordUnitService>) -> Self {
-        Self { service }
+    pub fn new(service: Arc<dyn WordUnitService>, job_service: Arc<dyn JobService>) -> Self {
+        Self {
+            service,
+            job_service,
+        }
+    }
+
+    /// 新建 kind 类型的后台任务，payload 为请求内容
+    async fn enqueue(&self, kind: &str, payload: &impl Serialize) -> anyhow::Result<JobDTO> {
+        let job = JobDTO {
+            kind: Some(kind.to_string()),
+            payload: Some(serde_json::to_value(payload)?),
+            ..Default::default()
+        };
+        self.job_service.enqueue(&job).await
     }
 }
 
@@ -27,11 +47,43 @@ async fn get_unit_words(
     HttpResponse::Ok().json(response)
 }
 
+/// 在后台任务中新建单词并绑定到单元，返回 JobDTO，完成后 result 为新建的单元单词 WordDTO
 async fn create_word_unit_mapping(
     data: web::Data<WordUnitHandler>,
     unit_word: web::Json<WordDTO>,
 ) -> impl Responder {
-    let result = data.service.create_word_unit_mapping(&unit_word).await;
+    let result = data.enqueue(CREATE_UNIT_WORD_JOB, &*unit_word).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+/// 在后台任务中批量导入单元单词，返回 JobDTO，完成后 result 为 UnitWordImportReportDTO
+async fn import_unit_words(
+    data: web::Data<WordUnitHandler>,
+    import: web::Json<UnitWordImportDTO>,
+) -> impl Responder {
+    let result = data.enqueue(IMPORT_UNIT_WORDS_JOB, &*import).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn suggest_unit_meaning(
+    data: web::Data<WordUnitHandler>,
+    unit_meaning: web::Json<UnitMeaningDTO>,
+) -> impl Responder {
+    let result = match unit_meaning.id {
+        Some(id) => data.service.suggest_unit_meaning(id).await,
+        None => Err(anyhow::anyhow!("id is required")),
+    };
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn update_unit_meaning(
+    data: web::Data<WordUnitHandler>,
+    unit_meaning: web::Json<UnitMeaningDTO>,
+) -> impl Responder {
+    let result = data.service.update_unit_meaning(&unit_meaning).await;
     let response = to_api_response(result);
     HttpResponse::Ok().json(response)
 }
@@ -45,9 +97,45 @@ async fn delete_unit_word(
     HttpResponse::Ok().json(response)
 }
 
+async fn move_unit_word(
+    data: web::Data<WordUnitHandler>,
+    dto: web::Json<MoveDTO>,
+) -> impl Responder {
+    let result = data.service.move_unit_word(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn swap_unit_words(
+    data: web::Data<WordUnitHandler>,
+    dto: web::Json<SwapDTO>,
+) -> impl Responder {
+    let result = data.service.swap_unit_words(&dto).await;
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
+async fn renumber_unit_words(
+    data: web::Data<WordUnitHandler>,
+    unit_word: web::Json<WordDTO>,
+) -> impl Responder {
+    let result = match unit_word.unit_id {
+        Some(unit_id) => data.service.renumber_unit_words(unit_id).await,
+        None => Err(anyhow::anyhow!("unit_id is required")),
+    };
+    let response = to_api_response(result);
+    HttpResponse::Ok().json(response)
+}
+
 define_routes!(
     WordUnitHandler,
-    post "/words" => get_unit_words,
-    post "/create" => create_word_unit_mapping,
-    post "/delete" => delete_unit_word,
+    post "/words" => get_unit_words : SIGNED_IN,
+    post "/create" => create_word_unit_mapping : EDITORS,
+    post "/delete" => delete_unit_word : EDITORS,
+    post "/import" => import_unit_words : EDITORS,
+    post "/meaning/suggest" => suggest_unit_meaning : EDITORS,
+    post "/meaning/update" => update_unit_meaning : EDITORS,
+    post "/move" => move_unit_word : EDITORS,
+    post "/swap" => swap_unit_words : EDITORS,
+    post "/renumber" => renumber_unit_words : EDITORS,
 );
@@ -1,6 +1,15 @@
 pub mod route_macros;
 
+use crate::api::handler::dictation_handler::DictationHandler;
+use crate::api::handler::export_handler::ExportHandler;
+use crate::api::handler::job_handler::JobHandler;
+use crate::api::handler::mistake_handler::MistakeHandler;
 use crate::api::handler::model_provider_handler::ModelProviderHandler;
+use crate::api::handler::prompt_template_handler::PromptTemplateHandler;
+use crate::api::handler::quiz_handler::QuizHandler;
+use crate::api::handler::study_handler::StudyHandler;
+use crate::api::handler::trash_handler::TrashHandler;
+use crate::api::handler::user_handler::UserHandler;
 use crate::api::handler::{
     grade_handler::GradeHandler, semester_handler::SemesterHandler,
     system_config_handler::SystemConfigHandler, textbook_handler::TextbookHandler,
@@ -23,6 +32,15 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig, handler_factory: HandlerFa
     let word_unit_handler = web::Data::new(handler_factory.create_word_unit_handler());
     let system_config_handler = web::Data::new(handler_factory.create_system_config_handler());
     let model_provider = web::Data::new(handler_factory.create_model_provider_handler());
+    let prompt_template = web::Data::new(handler_factory.create_prompt_template_handler());
+    let job_handler = web::Data::new(handler_factory.create_job_handler());
+    let export_handler = web::Data::new(handler_factory.create_export_handler());
+    let trash_handler = web::Data::new(handler_factory.create_trash_handler());
+    let user_handler = web::Data::new(handler_factory.create_user_handler());
+    let study_handler = web::Data::new(handler_factory.create_study_handler());
+    let quiz_handler = web::Data::new(handler_factory.create_quiz_handler());
+    let dictation_handler = web::Data::new(handler_factory.create_dictation_handler());
+    let mistake_handler = web::Data::new(handler_factory.create_mistake_handler());
 
     cfg.service(
         web::scope("/api")
@@ -35,6 +53,15 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig, handler_factory: HandlerFa
             .app_data(word_unit_handler.clone())
             .app_data(system_config_handler.clone())
             .app_data(model_provider.clone())
+            .app_data(prompt_template.clone())
+            .app_data(job_handler.clone())
+            .app_data(export_handler.clone())
+            .app_data(trash_handler.clone())
+            .app_data(user_handler.clone())
+            .app_data(study_handler.clone())
+            .app_data(quiz_handler.clone())
+            .app_data(dictation_handler.clone())
+            .app_data(mistake_handler.clone())
             .service(web::scope("/grade").configure(GradeHandler::register))
             .service(web::scope("/semester").configure(SemesterHandler::register))
             .service(web::scope("/textbook").configure(TextbookHandler::register))
@@ -43,6 +70,15 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig, handler_factory: HandlerFa
             .service(web::scope("/word").configure(WordHandler::register))
             .service(web::scope("/unit-word").configure(WordUnitHandler::register))
             .service(web::scope("/system").configure(SystemConfigHandler::register))
-            .service(web::scope("/model").configure(ModelProviderHandler::register)),
+            .service(web::scope("/model").configure(ModelProviderHandler::register))
+            .service(web::scope("/prompt").configure(PromptTemplateHandler::register))
+            .service(web::scope("/job").configure(JobHandler::register))
+            .service(web::scope("/export").configure(ExportHandler::register))
+            .service(web::scope("/trash").configure(TrashHandler::register))
+            .service(web::scope("/user").configure(UserHandler::register))
+            .service(web::scope("/study").configure(StudyHandler::register))
+            .service(web::scope("/quiz").configure(QuizHandler::register))
+            .service(web::scope("/dictation").configure(DictationHandler::register))
+            .service(web::scope("/mistake").configure(MistakeHandler::register)),
     );
 }
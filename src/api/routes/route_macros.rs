@@ -1,6 +1,7 @@
+/// 注册处理器的路由，每个路由声明允许访问的角色，例如 `post "/create" => create_word : EDITORS`
 #[macro_export]
 macro_rules! define_routes {
-    ($handler:ty, $($method:ident $path:expr => $handler_fn:path),* $(,)?) => {
+    ($handler:ty, $($method:ident $path:expr => $handler_fn:path : $access:expr),* $(,)?) => {
         impl crate::api::handler::Handler for $handler {
             fn configure(cfg: &mut actix_web::web::ServiceConfig) {
                 use actix_web::web;
@@ -8,6 +9,7 @@ macro_rules! define_routes {
                     cfg.service(
                         web::resource($path)
                             .$method($handler_fn)
+                            .wrap(crate::app::RequireRole::new($access))
                     );
                 )*
             }
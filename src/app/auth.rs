@@ -0,0 +1,200 @@
+use crate::api::dto::response::ApiResponse;
+use crate::domain::models::user::{Access, AuthUser};
+use crate::domain::services::UserService;
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{header, Method};
+use actix_web::{web, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use tracing::warn;
+
+/// 读取 `Authorization: Bearer <token>` 中的令牌
+pub fn bearer_token(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// 读取查询参数 `access_token` 中的链接令牌，只用于 GET 请求
+fn link_token(req: &HttpRequest) -> Option<String> {
+    if req.method() != Method::GET {
+        return None;
+    }
+    web::Query::<LinkTokenQuery>::from_query(req.query_string())
+        .ok()?
+        .into_inner()
+        .access_token
+        .filter(|token| !token.is_empty())
+}
+
+#[derive(Deserialize)]
+struct LinkTokenQuery {
+    access_token: Option<String>,
+}
+
+/// 识别登录用户：请求头中的登录令牌或 GET 请求查询参数中的链接令牌有效时把 `AuthUser` 放入请求扩展，是否允许访问由路由上的 `RequireRole` 判断
+#[derive(Clone)]
+pub struct Authentication {
+    service: Arc<dyn UserService>,
+}
+
+impl Authentication {
+    pub fn new(service: Arc<dyn UserService>) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Authentication
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = AuthenticationMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthenticationMiddleware {
+            service: Rc::new(service),
+            user_service: self.service.clone(),
+        }))
+    }
+}
+
+pub struct AuthenticationMiddleware<S> {
+    service: Rc<S>,
+    user_service: Arc<dyn UserService>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let user_service = self.user_service.clone();
+
+        Box::pin(async move {
+            let user = match bearer_token(req.request()).map(str::to_string) {
+                Some(token) => user_service.authenticate(&token).await,
+                None => match link_token(req.request()) {
+                    Some(token) => user_service.authenticate_link(&token).await,
+                    None => Ok(None),
+                },
+            };
+            match user {
+                Ok(Some(user)) => {
+                    req.extensions_mut().insert(user);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to authenticate request: {}", e),
+            }
+            service.call(req).await
+        })
+    }
+}
+
+/// 路由级的权限检查：未登录返回 401，角色不允许返回 403
+#[derive(Clone, Copy)]
+pub struct RequireRole {
+    access: Access,
+}
+
+impl RequireRole {
+    pub fn new(access: Access) -> Self {
+        Self { access }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequireRole
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RequireRoleMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequireRoleMiddleware {
+            service,
+            access: self.access,
+        }))
+    }
+}
+
+pub struct RequireRoleMiddleware<S> {
+    service: S,
+    access: Access,
+}
+
+impl<S, B> Service<ServiceRequest> for RequireRoleMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let denied = match (self.access, req.extensions().get::<AuthUser>()) {
+            (Access::Public, _) => None,
+            (_, None) => Some(HttpResponse::Unauthorized().json(ApiResponse::<()>::error(
+                401,
+                "Sign in required".to_string(),
+            ))),
+            (access, Some(user)) if !access.allows(user.role) => {
+                Some(HttpResponse::Forbidden().json(ApiResponse::<()>::error(
+                    403,
+                    format!("Role {} is not allowed to access this resource", user.role),
+                )))
+            }
+            _ => None,
+        };
+        if let Some(response) = denied {
+            return Box::pin(ready(Ok(req.into_response(response).map_into_right_body())));
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move { Ok(fut.await?.map_into_left_body()) })
+    }
+}
+
+/// 在处理函数中获取当前登录用户，路由允许匿名访问且未登录时返回 401
+impl FromRequest for AuthUser {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<AuthUser>()
+                .cloned()
+                .ok_or_else(|| actix_web::error::ErrorUnauthorized("Sign in required")),
+        )
+    }
+}
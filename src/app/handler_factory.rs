@@ -1,10 +1,19 @@
+use crate::api::handler::dictation_handler::DictationHandler;
+use crate::api::handler::export_handler::ExportHandler;
 use crate::api::handler::grade_handler::GradeHandler;
+use crate::api::handler::job_handler::JobHandler;
+use crate::api::handler::mistake_handler::MistakeHandler;
 use crate::api::handler::model_provider_handler::ModelProviderHandler;
+use crate::api::handler::prompt_template_handler::PromptTemplateHandler;
+use crate::api::handler::quiz_handler::QuizHandler;
 use crate::api::handler::semester_handler::SemesterHandler;
+use crate::api::handler::study_handler::StudyHandler;
 use crate::api::handler::system_config_handler::SystemConfigHandler;
 use crate::api::handler::textbook_handler::TextbookHandler;
 use crate::api::handler::textbook_version_handler::TextbookVersionHandler;
+use crate::api::handler::trash_handler::TrashHandler;
 use crate::api::handler::unit_handler::UnitHandler;
+use crate::api::handler::user_handler::UserHandler;
 use crate::api::handler::word_handler::WordHandler;
 use crate::api::handler::word_unit_handler::WordUnitHandler;
 use crate::domain::services::interfaces::grade_service::GradeService;
@@ -14,7 +23,10 @@ use crate::domain::services::interfaces::unit_service::UnitService;
 use crate::domain::services::interfaces::word_service::WordService;
 use crate::domain::services::interfaces::word_unit_service::WordUnitService;
 use crate::domain::services::interfaces::{SystemConfigService, TextbookVersionService};
-use crate::domain::services::ModelProviderService;
+use crate::domain::services::{
+    DictationService, ExportService, JobService, MistakeService, ModelProviderService,
+    PromptTemplateService, QuizService, StudyService, TrashService, UserService,
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -28,6 +40,15 @@ pub struct HandlerFactory {
     word_service: Arc<dyn WordService>,
     word_unit_service: Arc<dyn WordUnitService>,
     model_provider_service: Arc<dyn ModelProviderService>,
+    prompt_template_service: Arc<dyn PromptTemplateService>,
+    job_service: Arc<dyn JobService>,
+    export_service: Arc<dyn ExportService>,
+    trash_service: Arc<dyn TrashService>,
+    user_service: Arc<dyn UserService>,
+    study_service: Arc<dyn StudyService>,
+    quiz_service: Arc<dyn QuizService>,
+    dictation_service: Arc<dyn DictationService>,
+    mistake_service: Arc<dyn MistakeService>,
 }
 
 impl HandlerFactory {
@@ -41,6 +62,15 @@ impl HandlerFactory {
         word_service: Arc<dyn WordService>,
         word_unit_service: Arc<dyn WordUnitService>,
         model_provider_service: Arc<dyn ModelProviderService>,
+        prompt_template_service: Arc<dyn PromptTemplateService>,
+        job_service: Arc<dyn JobService>,
+        export_service: Arc<dyn ExportService>,
+        trash_service: Arc<dyn TrashService>,
+        user_service: Arc<dyn UserService>,
+        study_service: Arc<dyn StudyService>,
+        quiz_service: Arc<dyn QuizService>,
+        dictation_service: Arc<dyn DictationService>,
+        mistake_service: Arc<dyn MistakeService>,
     ) -> Self {
         Self {
             grade_service,
@@ -52,6 +82,15 @@ impl HandlerFactory {
             word_service,
             word_unit_service,
             model_provider_service,
+            prompt_template_service,
+            job_service,
+            export_service,
+            trash_service,
+            user_service,
+            study_service,
+            quiz_service,
+            dictation_service,
+            mistake_service,
         }
     }
 
@@ -64,7 +103,10 @@ impl HandlerFactory {
     }
 
     pub fn create_system_config_handler(&self) -> SystemConfigHandler {
-        SystemConfigHandler::new(self.system_config_service.clone())
+        SystemConfigHandler::new(
+            self.system_config_service.clone(),
+            self.textbook_service.clone(),
+        )
     }
 
     pub fn create_textbook_handler(&self) -> TextbookHandler {
@@ -80,14 +122,50 @@ impl HandlerFactory {
     }
 
     pub fn create_word_handler(&self) -> WordHandler {
-        WordHandler::new(self.word_service.clone())
+        WordHandler::new(self.word_service.clone(), self.job_service.clone())
     }
 
     pub fn create_word_unit_handler(&self) -> WordUnitHandler {
-        WordUnitHandler::new(self.word_unit_service.clone())
+        WordUnitHandler::new(self.word_unit_service.clone(), self.job_service.clone())
     }
 
     pub fn create_model_provider_handler(&self) -> ModelProviderHandler {
         ModelProviderHandler::new(self.model_provider_service.clone())
     }
+
+    pub fn create_prompt_template_handler(&self) -> PromptTemplateHandler {
+        PromptTemplateHandler::new(self.prompt_template_service.clone())
+    }
+
+    pub fn create_job_handler(&self) -> JobHandler {
+        JobHandler::new(self.job_service.clone())
+    }
+
+    pub fn create_export_handler(&self) -> ExportHandler {
+        ExportHandler::new(self.export_service.clone())
+    }
+
+    pub fn create_trash_handler(&self) -> TrashHandler {
+        TrashHandler::new(self.trash_service.clone())
+    }
+
+    pub fn create_user_handler(&self) -> UserHandler {
+        UserHandler::new(self.user_service.clone())
+    }
+
+    pub fn create_study_handler(&self) -> StudyHandler {
+        StudyHandler::new(self.study_service.clone())
+    }
+
+    pub fn create_quiz_handler(&self) -> QuizHandler {
+        QuizHandler::new(self.quiz_service.clone())
+    }
+
+    pub fn create_dictation_handler(&self) -> DictationHandler {
+        DictationHandler::new(self.dictation_service.clone())
+    }
+
+    pub fn create_mistake_handler(&self) -> MistakeHandler {
+        MistakeHandler::new(self.mistake_service.clone())
+    }
 }
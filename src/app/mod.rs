@@ -10,12 +10,16 @@
 //! The `app` module maintains the runtime state of the application and ensures
 //! proper initialization and lifecycle management of all services.
 
+mod auth;
 mod handler_factory;
 mod redis_factory;
 mod repository_factory;
 mod request_logger;
 mod service_container;
+#[cfg(test)]
+pub mod testing;
 
+pub use auth::{bearer_token, Authentication, RequireRole};
 pub use handler_factory::HandlerFactory;
 pub use request_logger::RequestLogger;
 pub use service_container::ServiceContainer;
@@ -1,8 +1,13 @@
 use crate::infrastructure::database::repositories::{
-    GradeRepository, GradeRepositoryImpl, ModelProviderRepository, ModelProviderRepositoryImpl,
-    SemesterRepository, SemesterRepositoryImpl, TextbookRepository, TextbookRepositoryImpl,
-    TextbookVersionRepository, TextbookVersionRepositoryImpl, UnitRepository, UnitRepositoryImpl,
-    WordRepository, WordRepositoryImpl, WordUnitMappingRepository, WordUnitMappingRepositoryImpl,
+    DictationRepository, DictationRepositoryImpl, GradeRepository, GradeRepositoryImpl,
+    JobRepository, JobRepositoryImpl, LLMUsageRepository, LLMUsageRepositoryImpl,
+    MistakeRepository, MistakeRepositoryImpl, ModelProviderRepository, ModelProviderRepositoryImpl,
+    PromptTemplateRepository, PromptTemplateRepositoryImpl, QuizRepository, QuizRepositoryImpl,
+    SemesterRepository, SemesterRepositoryImpl, StudyRepository, StudyRepositoryImpl,
+    TextbookRepository, TextbookRepositoryImpl, TextbookVersionRepository,
+    TextbookVersionRepositoryImpl, TrashRepository, TrashRepositoryImpl, UnitRepository,
+    UnitRepositoryImpl, UserRepository, UserRepositoryImpl, WordRepository, WordRepositoryImpl,
+    WordUnitMappingRepository, WordUnitMappingRepositoryImpl,
 };
 use once_cell::sync::OnceCell;
 use sqlx::PgPool;
@@ -18,6 +23,15 @@ pub struct RepositoryFactory {
     word_repository: OnceCell<Arc<dyn WordRepository>>,
     word_unit_mapping_repository: OnceCell<Arc<dyn WordUnitMappingRepository>>,
     model_provider_repository: OnceCell<Arc<dyn ModelProviderRepository>>,
+    llm_usage_repository: OnceCell<Arc<dyn LLMUsageRepository>>,
+    prompt_template_repository: OnceCell<Arc<dyn PromptTemplateRepository>>,
+    job_repository: OnceCell<Arc<dyn JobRepository>>,
+    trash_repository: OnceCell<Arc<dyn TrashRepository>>,
+    user_repository: OnceCell<Arc<dyn UserRepository>>,
+    study_repository: OnceCell<Arc<dyn StudyRepository>>,
+    quiz_repository: OnceCell<Arc<dyn QuizRepository>>,
+    dictation_repository: OnceCell<Arc<dyn DictationRepository>>,
+    mistake_repository: OnceCell<Arc<dyn MistakeRepository>>,
 }
 
 impl RepositoryFactory {
@@ -32,6 +46,15 @@ impl RepositoryFactory {
             word_repository: OnceCell::new(),
             word_unit_mapping_repository: OnceCell::new(),
             model_provider_repository: OnceCell::new(),
+            llm_usage_repository: OnceCell::new(),
+            prompt_template_repository: OnceCell::new(),
+            job_repository: OnceCell::new(),
+            trash_repository: OnceCell::new(),
+            user_repository: OnceCell::new(),
+            study_repository: OnceCell::new(),
+            quiz_repository: OnceCell::new(),
+            dictation_repository: OnceCell::new(),
+            mistake_repository: OnceCell::new(),
         }
     }
 
@@ -82,4 +105,58 @@ impl RepositoryFactory {
             .get_or_init(|| Arc::new(ModelProviderRepositoryImpl::new(self.db_pool.clone())))
             .clone()
     }
+
+    pub fn create_llm_usage_repository(&self) -> Arc<dyn LLMUsageRepository> {
+        self.llm_usage_repository
+            .get_or_init(|| Arc::new(LLMUsageRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_prompt_template_repository(&self) -> Arc<dyn PromptTemplateRepository> {
+        self.prompt_template_repository
+            .get_or_init(|| Arc::new(PromptTemplateRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_job_repository(&self) -> Arc<dyn JobRepository> {
+        self.job_repository
+            .get_or_init(|| Arc::new(JobRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_trash_repository(&self) -> Arc<dyn TrashRepository> {
+        self.trash_repository
+            .get_or_init(|| Arc::new(TrashRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_user_repository(&self) -> Arc<dyn UserRepository> {
+        self.user_repository
+            .get_or_init(|| Arc::new(UserRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_study_repository(&self) -> Arc<dyn StudyRepository> {
+        self.study_repository
+            .get_or_init(|| Arc::new(StudyRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_quiz_repository(&self) -> Arc<dyn QuizRepository> {
+        self.quiz_repository
+            .get_or_init(|| Arc::new(QuizRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_dictation_repository(&self) -> Arc<dyn DictationRepository> {
+        self.dictation_repository
+            .get_or_init(|| Arc::new(DictationRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
+
+    pub fn create_mistake_repository(&self) -> Arc<dyn MistakeRepository> {
+        self.mistake_repository
+            .get_or_init(|| Arc::new(MistakeRepositoryImpl::new(self.db_pool.clone())))
+            .clone()
+    }
 }
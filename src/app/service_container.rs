@@ -1,5 +1,8 @@
 use super::repository_factory::RepositoryFactory;
 use crate::app::redis_factory::RedisFactory;
+use crate::domain::services::impls::word_jobs::{
+    CreateUnitWordJob, CreateWordJob, EnrichWordsJob, ImportUnitWordsJob,
+};
 use crate::domain::services::impls::{
     grade_service_impl::GradeServiceImpl, semester_service_impl::SemesterServiceImpl,
     system_config_service_impl::SystemConfigServiceImpl,
@@ -14,7 +17,14 @@ use crate::domain::services::interfaces::unit_service::UnitService;
 use crate::domain::services::interfaces::word_service::WordService;
 use crate::domain::services::interfaces::word_unit_service::WordUnitService;
 use crate::domain::services::interfaces::{SystemConfigService, TextbookVersionService};
-use crate::domain::services::{ModelProviderService, ModelProviderServiceImpl};
+use crate::domain::services::{
+    clear_streak_from_env, job_lease_from_env, retention_from_env, token_ttl_from_env,
+    DictationService, DictationServiceImpl, ExportService, ExportServiceImpl, JobService,
+    JobServiceImpl, MistakeService, MistakeServiceImpl, ModelProviderService,
+    ModelProviderServiceImpl, PromptTemplateService, PromptTemplateServiceImpl, QuizService,
+    QuizServiceImpl, StudyService, StudyServiceImpl, TrashService, TrashServiceImpl, UserService,
+    UserServiceImpl,
+};
 use crate::infrastructure::third_party::implementations::HongliangServiceImpl;
 use crate::infrastructure::third_party::interface::ThirdPartyService;
 use once_cell::sync::OnceCell;
@@ -33,6 +43,15 @@ pub struct ServiceContainer {
     word_unit_service: OnceCell<Arc<dyn WordUnitService>>,
     third_party_service: OnceCell<Arc<dyn ThirdPartyService>>,
     model_provider_service: OnceCell<Arc<dyn ModelProviderService>>,
+    prompt_template_service: OnceCell<Arc<dyn PromptTemplateService>>,
+    job_service: OnceCell<Arc<dyn JobService>>,
+    export_service: OnceCell<Arc<dyn ExportService>>,
+    trash_service: OnceCell<Arc<dyn TrashService>>,
+    user_service: OnceCell<Arc<dyn UserService>>,
+    study_service: OnceCell<Arc<dyn StudyService>>,
+    quiz_service: OnceCell<Arc<dyn QuizService>>,
+    dictation_service: OnceCell<Arc<dyn DictationService>>,
+    mistake_service: OnceCell<Arc<dyn MistakeService>>,
 }
 
 impl ServiceContainer {
@@ -49,6 +68,15 @@ impl ServiceContainer {
             word_unit_service: OnceCell::new(),
             third_party_service: OnceCell::new(),
             model_provider_service: OnceCell::new(),
+            prompt_template_service: OnceCell::new(),
+            job_service: OnceCell::new(),
+            export_service: OnceCell::new(),
+            trash_service: OnceCell::new(),
+            user_service: OnceCell::new(),
+            study_service: OnceCell::new(),
+            quiz_service: OnceCell::new(),
+            dictation_service: OnceCell::new(),
+            mistake_service: OnceCell::new(),
         }
     }
 
@@ -91,6 +119,8 @@ impl ServiceContainer {
                     self.repository_factory.create_grade_repository(),
                     self.repository_factory.create_semester_repository(),
                     self.repository_factory.create_unit_repository(),
+                    self.repository_factory
+                        .create_word_unit_mapping_repository(),
                 ))
             })
             .clone()
@@ -154,6 +184,124 @@ impl ServiceContainer {
             .get_or_init(|| {
                 Arc::new(ModelProviderServiceImpl::new(
                     self.repository_factory.create_model_provider_repository(),
+                    self.repository_factory.create_llm_usage_repository(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_prompt_template_service(&self) -> Arc<dyn PromptTemplateService> {
+        self.prompt_template_service
+            .get_or_init(|| {
+                Arc::new(PromptTemplateServiceImpl::new(
+                    self.repository_factory.create_prompt_template_repository(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_job_service(&self) -> Arc<dyn JobService> {
+        self.job_service
+            .get_or_init(|| {
+                Arc::new(JobServiceImpl::new(
+                    self.repository_factory.create_job_repository(),
+                    vec![
+                        Arc::new(CreateWordJob::new(self.get_word_service())),
+                        Arc::new(EnrichWordsJob::new(
+                            self.repository_factory.create_word_repository(),
+                            self.get_word_service(),
+                        )),
+                        Arc::new(CreateUnitWordJob::new(self.get_word_unit_service())),
+                        Arc::new(ImportUnitWordsJob::new(self.get_word_unit_service())),
+                    ],
+                    job_lease_from_env(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_export_service(&self) -> Arc<dyn ExportService> {
+        self.export_service
+            .get_or_init(|| {
+                Arc::new(ExportServiceImpl::new(
+                    self.repository_factory.create_textbook_repository(),
+                    self.repository_factory.create_unit_repository(),
+                    self.repository_factory
+                        .create_word_unit_mapping_repository(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_trash_service(&self) -> Arc<dyn TrashService> {
+        self.trash_service
+            .get_or_init(|| {
+                Arc::new(TrashServiceImpl::new(
+                    self.repository_factory.create_trash_repository(),
+                    retention_from_env(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_user_service(&self) -> Arc<dyn UserService> {
+        self.user_service
+            .get_or_init(|| {
+                Arc::new(UserServiceImpl::new(
+                    self.repository_factory.create_user_repository(),
+                    token_ttl_from_env(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_study_service(&self) -> Arc<dyn StudyService> {
+        self.study_service
+            .get_or_init(|| {
+                Arc::new(StudyServiceImpl::new(
+                    self.repository_factory.create_study_repository(),
+                    self.repository_factory.create_user_repository(),
+                    self.repository_factory.create_unit_repository(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_quiz_service(&self) -> Arc<dyn QuizService> {
+        self.quiz_service
+            .get_or_init(|| {
+                Arc::new(QuizServiceImpl::new(
+                    self.repository_factory.create_quiz_repository(),
+                    self.repository_factory
+                        .create_word_unit_mapping_repository(),
+                    self.repository_factory.create_unit_repository(),
+                    self.repository_factory.create_user_repository(),
+                    self.get_mistake_service(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_dictation_service(&self) -> Arc<dyn DictationService> {
+        self.dictation_service
+            .get_or_init(|| {
+                Arc::new(DictationServiceImpl::new(
+                    self.repository_factory.create_dictation_repository(),
+                    self.get_mistake_service(),
+                    self.repository_factory
+                        .create_word_unit_mapping_repository(),
+                    self.repository_factory.create_unit_repository(),
+                ))
+            })
+            .clone()
+    }
+
+    pub fn get_mistake_service(&self) -> Arc<dyn MistakeService> {
+        self.mistake_service
+            .get_or_init(|| {
+                Arc::new(MistakeServiceImpl::new(
+                    self.repository_factory.create_mistake_repository(),
+                    clear_streak_from_env(),
                 ))
             })
             .clone()
@@ -0,0 +1,1272 @@
+//! 测试辅助：内存仓储与模拟大模型，用于在没有数据库和网络的环境下测试服务与接口
+
+use crate::api::dto::textbook_dto::{CounterDiscrepancyDTO, TextbookDTO};
+use crate::api::dto::unit_dto::UnitDTO;
+use crate::api::dto::unit_word_dto::WordDTO;
+use crate::app::Authentication;
+use crate::common::utils::crypto;
+use crate::common::utils::sequence::Reorder;
+use crate::domain::models::grade::Grade;
+use crate::domain::models::job::{Job, JobStatus};
+use crate::domain::models::prompt_template::PromptTemplate;
+use crate::domain::models::review::{ReviewItem, ReviewState, UnitAssignment, UnitMastery};
+use crate::domain::models::semester::Semester;
+use crate::domain::models::textbook::Textbook;
+use crate::domain::models::textbook_version::TextbookVersion;
+use crate::domain::models::unit::Unit;
+use crate::domain::models::user::{Role, User};
+use crate::domain::models::word::{MeaningParseFailure, Word};
+use crate::domain::models::word_edit::{WordEdit, WordField, WordPatch};
+use crate::domain::models::word_revision::{RevisionSource, WordContent, WordRevision};
+use crate::domain::models::word_unit_mapping::WordUnitMapping;
+use crate::domain::services::interfaces::SystemConfigService;
+use crate::domain::services::UserServiceImpl;
+use crate::infrastructure::database::repositories::{
+    GradeRepository, JobRepository, PromptTemplateRepository, Repository, SemesterRepository,
+    StudyRepository, TextbookRepository, TextbookVersionRepository, UnitRepository, UserRepository,
+    WordRepository, WordUnitMappingRepository,
+};
+use crate::infrastructure::dto::WordInfo;
+use crate::infrastructure::llm;
+use crate::infrastructure::llm::factory::LLMServiceFactory;
+use crate::infrastructure::llm::provider::{LLMConfig, LLMProvider};
+use crate::infrastructure::third_party::ThirdPartyService;
+use actix_web::http::header;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime};
+
+/// 初始化全局模型管理器，并以 `name` 注册一个读取默认 fixture 的模拟服务
+pub fn init_mock_llm(name: &str) {
+    // 多个测试共享全局管理器，重复初始化的错误可以忽略
+    let _ = llm::init_llm_manager();
+    LLMServiceFactory::register_config(name, LLMConfig::new(LLMProvider::Mock));
+}
+
+/// 固定返回指定模型名称的系统配置
+pub struct FixedModelConfig {
+    model: Mutex<String>,
+}
+
+impl FixedModelConfig {
+    pub fn new(model: &str) -> Arc<Self> {
+        Arc::new(Self {
+            model: Mutex::new(model.to_string()),
+        })
+    }
+}
+
+#[async_trait]
+impl SystemConfigService for FixedModelConfig {
+    async fn set_use_model(&self, model_name: &str) -> Result<()> {
+        *self.model.lock().unwrap() = model_name.to_string();
+        Ok(())
+    }
+
+    async fn get_use_model(&self) -> Result<String> {
+        Ok(self.model.lock().unwrap().clone())
+    }
+}
+
+/// 始终失败的第三方词典，使单词信息走大模型
+pub struct OfflineThirdParty;
+
+#[async_trait]
+impl ThirdPartyService for OfflineThirdParty {
+    async fn fetch_word_info(&self, _word: &str) -> Result<WordInfo> {
+        Err(anyhow!("third party service is offline in tests"))
+    }
+}
+
+/// 带自增主键的内存表
+pub trait Row: Clone + Send {
+    fn id(&self) -> Option<i32>;
+    fn assign_id(&mut self, id: i32);
+}
+
+impl Row for Word {
+    fn id(&self) -> Option<i32> {
+        self.word_id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.word_id = Some(id);
+    }
+}
+
+impl Row for Unit {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+impl Row for Textbook {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+impl Row for PromptTemplate {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+impl Row for WordRevision {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+impl Row for WordEdit {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+impl Row for WordUnitMapping {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+/// 只有 id 和名称的字典表：年级、学期、教材版本
+macro_rules! impl_dictionary_repository {
+    ($row:ty, $name:ident) => {
+        impl Row for $row {
+            fn id(&self) -> Option<i32> {
+                self.id
+            }
+
+            fn assign_id(&mut self, id: i32) {
+                self.id = Some(id);
+            }
+        }
+
+        #[derive(Default)]
+        pub struct $name {
+            pub table: Table<$row>,
+        }
+
+        #[async_trait]
+        impl Repository<$row, i32> for $name {
+            async fn find_by_id(&self, id: i32) -> Result<Option<$row>> {
+                Ok(self.table.find(id))
+            }
+
+            async fn find_all(&self) -> Result<Vec<$row>> {
+                Ok(self.table.all())
+            }
+
+            async fn save(&self, entity: &$row) -> Result<$row> {
+                Ok(self.table.upsert(entity))
+            }
+
+            async fn delete(&self, id: i32) -> Result<()> {
+                self.table.remove(id)
+            }
+        }
+    };
+}
+
+impl_dictionary_repository!(Grade, InMemoryGradeRepository);
+impl_dictionary_repository!(Semester, InMemorySemesterRepository);
+impl_dictionary_repository!(TextbookVersion, InMemoryTextbookVersionRepository);
+
+impl GradeRepository for InMemoryGradeRepository {}
+
+impl SemesterRepository for InMemorySemesterRepository {}
+
+#[async_trait]
+impl TextbookVersionRepository for InMemoryTextbookVersionRepository {
+    async fn find_by_name(&self, name: &str) -> Result<Option<TextbookVersion>> {
+        Ok(self
+            .table
+            .filter(|row| row.name.as_deref() == Some(name))
+            .pop())
+    }
+}
+
+pub struct Table<T: Row> {
+    rows: Mutex<Vec<T>>,
+    next_id: AtomicI32,
+}
+
+impl<T: Row> Default for Table<T> {
+    fn default() -> Self {
+        Self {
+            rows: Mutex::new(Vec::new()),
+            next_id: AtomicI32::new(1),
+        }
+    }
+}
+
+impl<T: Row> Table<T> {
+    pub fn find(&self, id: i32) -> Option<T> {
+        self.filter(|row| row.id() == Some(id)).pop()
+    }
+
+    pub fn filter(&self, predicate: impl Fn(&T) -> bool) -> Vec<T> {
+        self.rows
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|row| predicate(row))
+            .cloned()
+            .collect()
+    }
+
+    pub fn all(&self) -> Vec<T> {
+        self.filter(|_| true)
+    }
+
+    /// 没有主键时插入，否则按主键更新
+    pub fn upsert(&self, entity: &T) -> T {
+        let mut entity = entity.clone();
+        let mut rows = self.rows.lock().unwrap();
+        match entity.id() {
+            Some(id) => match rows.iter_mut().find(|row| row.id() == Some(id)) {
+                Some(row) => *row = entity.clone(),
+                None => rows.push(entity.clone()),
+            },
+            None => {
+                entity.assign_id(self.next_id.fetch_add(1, Ordering::SeqCst));
+                rows.push(entity.clone());
+            }
+        }
+        entity
+    }
+
+    pub fn remove(&self, id: i32) -> Result<()> {
+        let mut rows = self.rows.lock().unwrap();
+        let before = rows.len();
+        rows.retain(|row| row.id() != Some(id));
+        if rows.len() == before {
+            return Err(anyhow!("No row found with id: {}", id));
+        }
+        Ok(())
+    }
+}
+
+fn now_primitive() -> PrimitiveDateTime {
+    let now = OffsetDateTime::now_utc();
+    PrimitiveDateTime::new(now.date(), now.time())
+}
+
+#[derive(Default)]
+pub struct InMemoryWordRepository {
+    pub table: Table<Word>,
+    pub edits: Table<WordEdit>,
+    pub revisions: Table<WordRevision>,
+    locks: Mutex<HashMap<i32, Vec<WordField>>>,
+}
+
+impl InMemoryWordRepository {
+    /// 与数据库实现一致：未指明来源的释义记为本次保存的来源
+    fn stamp_sources(word: &mut Word, source: RevisionSource) {
+        for meaning in &mut word.meanings {
+            meaning.source.get_or_insert_with(|| source.to_string());
+        }
+    }
+
+    fn record_revision(
+        &self,
+        word: &Word,
+        previous: Option<&Word>,
+        source: RevisionSource,
+        editor: Option<&str>,
+    ) {
+        let content = WordContent::from(word);
+        let changes = previous
+            .map(WordContent::from)
+            .unwrap_or_default()
+            .diff(&content);
+        if changes.is_empty() && previous.is_some() {
+            return;
+        }
+        self.revisions.upsert(&WordRevision {
+            id: None,
+            word_id: word.word_id.unwrap(),
+            source: source.to_string(),
+            editor: editor.map(str::to_string),
+            content: serde_json::to_value(&content).unwrap(),
+            changes: Value::Object(changes),
+            created_at: Some(OffsetDateTime::now_utc()),
+        });
+    }
+}
+
+#[async_trait]
+impl Repository<Word, i32> for InMemoryWordRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<Word>> {
+        Ok(self.table.find(id))
+    }
+
+    async fn find_all(&self) -> Result<Vec<Word>> {
+        Ok(self.table.all())
+    }
+
+    async fn save(&self, entity: &Word) -> Result<Word> {
+        self.save_as(entity, RevisionSource::System).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        self.table.remove(id)
+    }
+}
+
+#[async_trait]
+impl WordRepository for InMemoryWordRepository {
+    async fn find_by_word(&self, word: &str) -> Result<Option<Word>> {
+        Ok(self.table.filter(|row| row.word == word).pop())
+    }
+
+    async fn find_by_unit_id(&self, _unit_id: i32) -> Result<Vec<Word>> {
+        Err(anyhow!("use InMemoryWordUnitMappingRepository instead"))
+    }
+
+    async fn search_words(&self, keyword: &str) -> Result<Vec<Word>> {
+        Ok(self.table.filter(|row| {
+            row.word.contains(keyword)
+                || row.meanings.iter().any(|meaning| {
+                    meaning.definition.contains(keyword)
+                        || meaning.gloss.as_ref().is_some_and(|g| g.contains(keyword))
+                })
+        }))
+    }
+
+    async fn count(&self) -> Result<u32> {
+        Ok(self.table.all().len() as u32)
+    }
+
+    async fn find_locked_fields(&self, word_id: i32) -> Result<Vec<WordField>> {
+        let locks = self.locks.lock().unwrap();
+        Ok(locks.get(&word_id).cloned().unwrap_or_default())
+    }
+
+    async fn patch(&self, word_id: i32, patch: &WordPatch) -> Result<Word> {
+        let mut word = self
+            .table
+            .find(word_id)
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        for (field, value) in &patch.changes {
+            let old_value = word.field(*field);
+            if old_value == *value {
+                continue;
+            }
+            word.set_field(*field, value.clone())?;
+            self.edits.upsert(&WordEdit {
+                id: None,
+                word_id,
+                field: field.to_string(),
+                old_value,
+                new_value: value.clone(),
+                editor: patch.editor.clone(),
+                created_at: Some(OffsetDateTime::now_utc()),
+            });
+        }
+
+        let mut locks = self.locks.lock().unwrap();
+        let locked = locks.entry(word_id).or_default();
+        if patch.lock {
+            locked.extend(patch.changes.iter().map(|(field, _)| *field));
+        }
+        locked.retain(|field| !patch.unlock.contains(field));
+        locked.sort_by_key(|field| field.as_str());
+        locked.dedup();
+        Self::stamp_sources(&mut word, RevisionSource::Editor);
+        let previous = self.table.find(word_id);
+        let word = self.table.upsert(&word);
+        self.record_revision(
+            &word,
+            previous.as_ref(),
+            RevisionSource::Editor,
+            patch.editor.as_deref(),
+        );
+        Ok(word)
+    }
+
+    async fn find_edits(&self, word_id: i32) -> Result<Vec<WordEdit>> {
+        let mut edits = self.edits.filter(|row| row.word_id == word_id);
+        edits.reverse();
+        Ok(edits)
+    }
+
+    /// 与数据库实现一致：没有主键但单词已存在时更新原记录，锁定的字段保持原值
+    async fn save_as(&self, entity: &Word, source: RevisionSource) -> Result<Word> {
+        let mut word = entity.clone();
+        if word.word_id.is_none() {
+            word.word_id = self
+                .table
+                .filter(|row| row.word == word.word)
+                .pop()
+                .and_then(|row| row.word_id);
+        }
+        let stored = word.word_id.and_then(|id| self.table.find(id));
+        if let Some(stored) = &stored {
+            let locks = self.locks.lock().unwrap();
+            for field in locks.get(&stored.word_id.unwrap()).into_iter().flatten() {
+                word.set_field(*field, stored.field(*field))?;
+                match field {
+                    WordField::Meaning => {
+                        word.meaning_prompt_version = stored.meaning_prompt_version
+                    }
+                    WordField::Example => {
+                        word.example_prompt_version = stored.example_prompt_version
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Self::stamp_sources(&mut word, source);
+        let now = now_primitive();
+        word.created_at.get_or_insert(now);
+        word.updated_at = Some(now);
+        let word = self.table.upsert(&word);
+        self.record_revision(&word, stored.as_ref(), source, None);
+        Ok(word)
+    }
+
+    async fn find_revisions(&self, word_id: i32) -> Result<Vec<WordRevision>> {
+        let mut revisions = self.revisions.filter(|row| row.word_id == word_id);
+        revisions.reverse();
+        Ok(revisions)
+    }
+
+    async fn rollback(&self, word_id: i32, revision_id: i32, editor: Option<&str>) -> Result<Word> {
+        let stored = self
+            .table
+            .find(word_id)
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        let revision = self
+            .revisions
+            .find(revision_id)
+            .filter(|revision| revision.word_id == word_id)
+            .ok_or_else(|| anyhow!("Revision {} of word {} not found", revision_id, word_id))?;
+        let content = revision.content()?;
+        let mut word = Word {
+            phonetic_us: content.phonetic_us,
+            phonetic_uk: content.phonetic_uk,
+            pronunciation_us: content.pronunciation_us,
+            pronunciation_uk: content.pronunciation_uk,
+            meanings: content.meanings,
+            example: content.example,
+            meaning_prompt_version: content.meaning_prompt_version,
+            example_prompt_version: content.example_prompt_version,
+            ..stored.clone()
+        };
+        Self::stamp_sources(&mut word, RevisionSource::Rollback);
+        let word = self.table.upsert(&word);
+        self.record_revision(&word, Some(&stored), RevisionSource::Rollback, editor);
+        Ok(word)
+    }
+
+    async fn find_meaning_parse_failures(&self) -> Result<Vec<MeaningParseFailure>> {
+        Ok(Vec::new())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryUnitRepository {
+    pub table: Table<Unit>,
+}
+
+#[async_trait]
+impl Repository<Unit, i32> for InMemoryUnitRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<Unit>> {
+        Ok(self.table.find(id))
+    }
+
+    async fn find_all(&self) -> Result<Vec<Unit>> {
+        Ok(self.table.all())
+    }
+
+    /// 与数据库实现一致：word_count 只随单词映射的增删变化
+    async fn save(&self, entity: &Unit) -> Result<Unit> {
+        let mut unit = entity.clone();
+        unit.word_count = match unit.id.and_then(|id| self.table.find(id)) {
+            Some(stored) => stored.word_count,
+            None => Some(0),
+        };
+        Ok(self.table.upsert(&unit))
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        self.table.remove(id)
+    }
+}
+
+#[async_trait]
+impl UnitRepository for InMemoryUnitRepository {
+    async fn find_by_dto(&self, dto: &UnitDTO) -> Result<Vec<Unit>> {
+        Ok(self.table.filter(|row| {
+            dto.textbook_id.is_none_or(|id| row.textbook_id == Some(id))
+                && dto
+                    .name
+                    .as_ref()
+                    .is_none_or(|name| row.name.as_ref() == Some(name))
+        }))
+    }
+
+    async fn find_by_textbook_id(&self, textbook_id: Option<i32>) -> Result<Vec<Unit>> {
+        let mut units = self.table.filter(|row| row.textbook_id == textbook_id);
+        units.sort_by_key(|row| (row.sequence_number.is_none(), row.sequence_number, row.id));
+        Ok(units)
+    }
+
+    async fn reorder(&self, textbook_id: i32, op: Reorder) -> Result<()> {
+        let units = self.find_by_textbook_id(Some(textbook_id)).await?;
+        let mut ids: Vec<i32> = units.iter().filter_map(|row| row.id).collect();
+        op.apply(&mut ids)?;
+        for (position, id) in (1..).zip(ids) {
+            if let Some(mut unit) = self.table.find(id) {
+                unit.sequence_number = Some(position);
+                self.table.upsert(&unit);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryTextbookRepository {
+    pub table: Table<Textbook>,
+}
+
+#[async_trait]
+impl TextbookRepository for InMemoryTextbookRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<Textbook>> {
+        Ok(self.table.find(id))
+    }
+
+    async fn find_by_dto(&self, dto: &TextbookDTO) -> Result<Vec<Textbook>> {
+        Ok(self.table.filter(|row| {
+            dto.id.is_none_or(|id| row.id == Some(id))
+                && dto.version_id.is_none_or(|id| row.version_id == Some(id))
+                && dto.grade_id.is_none_or(|id| row.grade_id == Some(id))
+                && dto.semester_id.is_none_or(|id| row.semester_id == Some(id))
+        }))
+    }
+
+    async fn save(&self, textbook: &Textbook) -> Result<Textbook> {
+        Ok(self.table.upsert(textbook))
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        self.table.remove(id)
+    }
+
+    async fn find_all(&self) -> Result<Vec<Textbook>> {
+        Ok(self.table.all())
+    }
+
+    async fn clone_textbook(&self, _source_id: i32, _target: &Textbook) -> Result<Textbook> {
+        Err(anyhow!("clone_textbook needs a database transaction"))
+    }
+
+    async fn recount_counters(&self) -> Result<Vec<CounterDiscrepancyDTO>> {
+        Err(anyhow!("recount_counters needs a database transaction"))
+    }
+}
+
+pub struct InMemoryWordUnitMappingRepository {
+    pub table: Table<WordUnitMapping>,
+    words: Arc<InMemoryWordRepository>,
+    counters: Option<(Arc<InMemoryUnitRepository>, Arc<InMemoryTextbookRepository>)>,
+}
+
+impl InMemoryWordUnitMappingRepository {
+    pub fn new(words: Arc<InMemoryWordRepository>) -> Self {
+        Self {
+            table: Table::default(),
+            words,
+            counters: None,
+        }
+    }
+
+    /// 像数据库实现一样随映射的增删维护单元和课本的单词数
+    pub fn with_counters(
+        mut self,
+        units: Arc<InMemoryUnitRepository>,
+        textbooks: Arc<InMemoryTextbookRepository>,
+    ) -> Self {
+        self.counters = Some((units, textbooks));
+        self
+    }
+
+    fn adjust_word_count(&self, unit_id: Option<i32>, delta: i32) {
+        let Some((units, textbooks)) = &self.counters else {
+            return;
+        };
+        let Some(mut unit) = unit_id.and_then(|id| units.table.find(id)) else {
+            return;
+        };
+        unit.word_count = Some(unit.word_count.unwrap_or_default() + delta);
+        units.table.upsert(&unit);
+        if let Some(mut textbook) = unit.textbook_id.and_then(|id| textbooks.table.find(id)) {
+            textbook.word_count = Some(textbook.word_count.unwrap_or_default() + delta);
+            textbooks.table.upsert(&textbook);
+        }
+    }
+}
+
+#[async_trait]
+impl Repository<WordUnitMapping, i32> for InMemoryWordUnitMappingRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<WordUnitMapping>> {
+        Ok(self.table.find(id))
+    }
+
+    async fn find_all(&self) -> Result<Vec<WordUnitMapping>> {
+        Ok(self.table.all())
+    }
+
+    async fn save(&self, entity: &WordUnitMapping) -> Result<WordUnitMapping> {
+        let mut mapping = entity.clone();
+        let now = OffsetDateTime::now_utc();
+        mapping.created_at.get_or_insert(now);
+        mapping.updated_at = Some(now);
+        let previous_unit_id = match mapping.id {
+            Some(id) => self.table.find(id).map(|row| row.unit_id),
+            None => None,
+        };
+        let mapping = self.table.upsert(&mapping);
+        match previous_unit_id {
+            Some(unit_id) if unit_id == mapping.unit_id => {}
+            Some(unit_id) => {
+                self.adjust_word_count(unit_id, -1);
+                self.adjust_word_count(mapping.unit_id, 1);
+            }
+            None => self.adjust_word_count(mapping.unit_id, 1),
+        }
+        Ok(mapping)
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        let unit_id = self.table.find(id).and_then(|row| row.unit_id);
+        self.table.remove(id)?;
+        self.adjust_word_count(unit_id, -1);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WordUnitMappingRepository for InMemoryWordUnitMappingRepository {
+    async fn find_word_by_unit_id(&self, unit_id: i32) -> Result<Vec<Word>> {
+        Ok(self
+            .table
+            .filter(|row| row.unit_id == Some(unit_id))
+            .iter()
+            .filter_map(|row| self.words.table.find(row.word_id?))
+            .collect())
+    }
+
+    async fn find_word_dto_by_unit_id(&self, unit_id: i32) -> Result<Vec<WordDTO>> {
+        let mut rows = self.table.filter(|row| row.unit_id == Some(unit_id));
+        rows.sort_by_key(|row| (row.sequence.is_none(), row.sequence, row.id));
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let word = self.words.table.find(row.word_id?)?;
+                Some(WordDTO::new(&word, row))
+            })
+            .collect())
+    }
+
+    async fn find_by_word_id(&self, word_id: i32) -> Result<Vec<WordUnitMapping>> {
+        Ok(self.table.filter(|row| row.word_id == Some(word_id)))
+    }
+
+    async fn batch_save(&self, mappings: &[WordUnitMapping]) -> Result<Vec<WordUnitMapping>> {
+        let mut saved = Vec::with_capacity(mappings.len());
+        for mapping in mappings {
+            saved.push(self.save(mapping).await?);
+        }
+        Ok(saved)
+    }
+
+    async fn reorder(&self, unit_id: i32, op: Reorder) -> Result<()> {
+        let mut rows = self.table.filter(|row| row.unit_id == Some(unit_id));
+        rows.sort_by_key(|row| (row.sequence.is_none(), row.sequence, row.id));
+        let mut ids: Vec<i32> = rows.iter().filter_map(|row| row.id).collect();
+        op.apply(&mut ids)?;
+        for (position, id) in (1..).zip(ids) {
+            if let Some(mut mapping) = self.table.find(id) {
+                mapping.sequence = Some(position);
+                self.table.upsert(&mapping);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryPromptTemplateRepository {
+    pub table: Table<PromptTemplate>,
+}
+
+#[async_trait]
+impl PromptTemplateRepository for InMemoryPromptTemplateRepository {
+    async fn find_all(&self) -> Result<Vec<PromptTemplate>> {
+        Ok(self.table.all())
+    }
+
+    async fn find_by_name_version(
+        &self,
+        name: &str,
+        version: i32,
+    ) -> Result<Option<PromptTemplate>> {
+        Ok(self
+            .table
+            .filter(|row| row.name == name && row.version == version)
+            .pop())
+    }
+
+    async fn max_version(&self, name: &str) -> Result<Option<i32>> {
+        Ok(self
+            .table
+            .filter(|row| row.name == name)
+            .iter()
+            .map(|row| row.version)
+            .max())
+    }
+
+    async fn save(&self, template: &PromptTemplate) -> Result<PromptTemplate> {
+        let mut template = template.clone();
+        template.created_at.get_or_insert(OffsetDateTime::now_utc());
+        Ok(self.table.upsert(&template))
+    }
+
+    async fn activate(&self, name: &str, version: Option<i32>) -> Result<()> {
+        let rows = self.table.filter(|row| row.name == name);
+        if let Some(version) = version {
+            if !rows.iter().any(|row| row.version == version) {
+                return Err(anyhow!(
+                    "Prompt template {} has no version {}",
+                    name,
+                    version
+                ));
+            }
+        }
+        for mut row in rows {
+            row.is_active = Some(row.version) == version;
+            self.table.upsert(&row);
+        }
+        Ok(())
+    }
+}
+
+impl Row for Job {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryJobRepository {
+    pub table: Table<Job>,
+}
+
+impl InMemoryJobRepository {
+    /// 按条件修改执行中的任务，任务不在执行中时返回 false
+    fn update_running(&self, id: i32, update: impl FnOnce(&mut Job)) -> bool {
+        match self.table.find(id) {
+            Some(mut job) if job.status() == JobStatus::Running => {
+                update(&mut job);
+                job.updated_at = Some(OffsetDateTime::now_utc());
+                self.table.upsert(&job);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[async_trait]
+impl JobRepository for InMemoryJobRepository {
+    async fn create(&self, job: &Job) -> Result<Job> {
+        let mut job = job.clone();
+        job.created_at = Some(OffsetDateTime::now_utc());
+        Ok(self.table.upsert(&job))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Job>> {
+        Ok(self.table.find(id))
+    }
+
+    async fn find_recent(
+        &self,
+        status: Option<&str>,
+        kind: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<Job>> {
+        let mut jobs = self.table.filter(|row| {
+            status.is_none_or(|status| row.status == status)
+                && kind.is_none_or(|kind| row.kind == kind)
+        });
+        jobs.reverse();
+        jobs.truncate(limit as usize);
+        Ok(jobs)
+    }
+
+    async fn claim_next(&self, lease: std::time::Duration) -> Result<Option<Job>> {
+        let now = OffsetDateTime::now_utc();
+        let mut pending = self
+            .table
+            .filter(|row| row.status() == JobStatus::Pending && row.run_at <= now);
+        pending.sort_by_key(|row| (row.run_at, row.id));
+        let Some(mut job) = pending.into_iter().next() else {
+            return Ok(None);
+        };
+        job.status = JobStatus::Running.to_string();
+        job.attempts += 1;
+        job.started_at = Some(now);
+        job.locked_until = Some(now + lease);
+        Ok(Some(self.table.upsert(&job)))
+    }
+
+    async fn heartbeat(&self, id: i32, lease: std::time::Duration) -> Result<bool> {
+        Ok(self.update_running(id, |job| {
+            job.locked_until = Some(OffsetDateTime::now_utc() + lease);
+        }))
+    }
+
+    async fn update_progress(
+        &self,
+        id: i32,
+        done: i32,
+        total: i32,
+        checkpoint: Option<&Value>,
+    ) -> Result<bool> {
+        Ok(self.update_running(id, |job| {
+            job.progress_done = done;
+            job.progress_total = total;
+            if let Some(checkpoint) = checkpoint {
+                job.checkpoint = Some(checkpoint.clone());
+            }
+        }))
+    }
+
+    async fn complete(&self, id: i32, result: Option<&Value>) -> Result<()> {
+        self.update_running(id, |job| {
+            job.status = JobStatus::Succeeded.to_string();
+            job.result = result.cloned();
+            job.last_error = None;
+            job.locked_until = None;
+            job.finished_at = Some(OffsetDateTime::now_utc());
+        });
+        Ok(())
+    }
+
+    async fn fail(&self, id: i32, error: &str, retry_at: Option<OffsetDateTime>) -> Result<()> {
+        self.update_running(id, |job| {
+            match retry_at {
+                Some(retry_at) => {
+                    job.status = JobStatus::Pending.to_string();
+                    job.run_at = retry_at;
+                }
+                None => {
+                    job.status = JobStatus::Failed.to_string();
+                    job.finished_at = Some(OffsetDateTime::now_utc());
+                }
+            }
+            job.last_error = Some(error.to_string());
+            job.locked_until = None;
+        });
+        Ok(())
+    }
+
+    async fn cancel(&self, id: i32) -> Result<bool> {
+        match self.table.find(id) {
+            Some(mut job) if !job.status().is_finished() => {
+                job.status = JobStatus::Cancelled.to_string();
+                job.locked_until = None;
+                job.finished_at = Some(OffsetDateTime::now_utc());
+                self.table.upsert(&job);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn requeue_expired(&self) -> Result<u64> {
+        let now = OffsetDateTime::now_utc();
+        let running = self.table.filter(|row| {
+            row.status() == JobStatus::Running && row.locked_until.is_none_or(|until| until < now)
+        });
+        for mut job in running.iter().cloned() {
+            job.status = JobStatus::Pending.to_string();
+            job.locked_until = None;
+            job.attempts = (job.attempts - 1).max(0);
+            job.run_at = OffsetDateTime::now_utc();
+            self.table.upsert(&job);
+        }
+        Ok(running.len() as u64)
+    }
+}
+
+impl Row for User {
+    fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    fn assign_id(&mut self, id: i32) {
+        self.id = Some(id);
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    pub table: Table<User>,
+    /// 令牌摘要 -> (用户 id, 过期时间)
+    sessions: Mutex<HashMap<String, (i32, OffsetDateTime)>>,
+}
+
+#[async_trait]
+impl Repository<User, i32> for InMemoryUserRepository {
+    async fn find_by_id(&self, id: i32) -> Result<Option<User>> {
+        Ok(self.table.find(id))
+    }
+
+    async fn find_all(&self) -> Result<Vec<User>> {
+        Ok(self.table.all())
+    }
+
+    async fn save(&self, entity: &User) -> Result<User> {
+        Ok(self.table.upsert(entity))
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        self.table.remove(id)
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>> {
+        Ok(self.table.filter(|row| row.username == username).pop())
+    }
+
+    async fn count(&self) -> Result<i64> {
+        Ok(self.table.all().len() as i64)
+    }
+
+    async fn create_session(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: OffsetDateTime,
+    ) -> Result<()> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(token_hash.to_string(), (user_id, expires_at));
+        Ok(())
+    }
+
+    async fn find_by_session(&self, token_hash: &str) -> Result<Option<User>> {
+        let session = self.sessions.lock().unwrap().get(token_hash).copied();
+        Ok(match session {
+            Some((user_id, expires_at)) if expires_at > OffsetDateTime::now_utc() => {
+                self.table.find(user_id)
+            }
+            _ => None,
+        })
+    }
+
+    async fn delete_session(&self, token_hash: &str) -> Result<()> {
+        self.sessions.lock().unwrap().remove(token_hash);
+        Ok(())
+    }
+
+    async fn delete_sessions(&self, user_id: i32) -> Result<()> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .retain(|_, (id, _)| *id != user_id);
+        Ok(())
+    }
+}
+
+/// 接口测试用的登录环境：`middleware()` 包装应用，`bearer(role)` 生成对应角色用户的认证请求头
+#[derive(Default)]
+pub struct TestAuth {
+    pub users: Arc<InMemoryUserRepository>,
+}
+
+impl TestAuth {
+    pub fn middleware(&self) -> Authentication {
+        Authentication::new(Arc::new(UserServiceImpl::new(
+            self.users.clone(),
+            Duration::hours(1),
+        )))
+    }
+
+    /// 以角色名作为用户名，用户不存在时创建，跳过密码直接建立会话
+    pub async fn bearer(&self, role: Role) -> (header::HeaderName, String) {
+        let user = match self.users.find_by_username(role.as_str()).await.unwrap() {
+            Some(user) => user,
+            None => self
+                .users
+                .save(&User::new(role.as_str(), String::new(), role))
+                .await
+                .unwrap(),
+        };
+        let token = crypto::generate_token();
+        self.users
+            .create_session(
+                user.id.unwrap(),
+                &crypto::hash_token(&token),
+                OffsetDateTime::now_utc() + Duration::hours(1),
+            )
+            .await
+            .unwrap();
+        (header::AUTHORIZATION, format!("Bearer {}", token))
+    }
+}
+
+/// 内存中的学习记录，单元的单词通过 `add_unit` 指定，复习状态同时记录开始学习的时间
+#[derive(Default)]
+pub struct InMemoryStudyRepository {
+    unit_words: Mutex<HashMap<i32, Vec<i32>>>,
+    assignments: Mutex<Vec<UnitAssignment>>,
+    states: Mutex<Vec<(ReviewState, OffsetDateTime)>>,
+}
+
+impl InMemoryStudyRepository {
+    /// 设置单元中按顺序排列的单词
+    pub fn add_unit(&self, unit_id: i32, word_ids: &[i32]) {
+        self.unit_words
+            .lock()
+            .unwrap()
+            .insert(unit_id, word_ids.to_vec());
+    }
+
+    /// 添加一条在 started_at 开始学习的复习状态
+    pub fn add_state(&self, state: ReviewState, started_at: OffsetDateTime) {
+        self.states.lock().unwrap().push((state, started_at));
+    }
+
+    /// 布置给学生的单词和所在单元，按布置顺序和单元内顺序，同一单词只保留第一次出现
+    fn assigned_words(&self, student_id: i32) -> Vec<(i32, i32)> {
+        let unit_words = self.unit_words.lock().unwrap();
+        let mut words: Vec<(i32, i32)> = Vec::new();
+        for assignment in self.assignments.lock().unwrap().iter() {
+            if assignment.student_id != student_id {
+                continue;
+            }
+            for &word_id in unit_words.get(&assignment.unit_id).into_iter().flatten() {
+                if !words.iter().any(|(id, _)| *id == word_id) {
+                    words.push((word_id, assignment.unit_id));
+                }
+            }
+        }
+        words
+    }
+
+    fn review_item(word_id: i32, unit_id: i32, state: Option<&ReviewState>) -> ReviewItem {
+        ReviewItem {
+            word_id,
+            word: format!("word-{}", word_id),
+            phonetic_us: None,
+            phonetic_uk: None,
+            meanings: Vec::new(),
+            unit_id,
+            unit_name: None,
+            due_at: state.map(|state| state.due_at),
+            repetitions: state.map_or(0, |state| state.repetitions),
+            lapses: state.map_or(0, |state| state.lapses),
+        }
+    }
+}
+
+#[async_trait]
+impl StudyRepository for InMemoryStudyRepository {
+    async fn assign_unit(
+        &self,
+        unit_id: i32,
+        student_ids: &[i32],
+        assigned_by: Option<i32>,
+    ) -> Result<u64> {
+        let mut assignments = self.assignments.lock().unwrap();
+        let mut assigned = 0;
+        for &student_id in student_ids {
+            if assignments
+                .iter()
+                .any(|row| (row.unit_id, row.student_id) == (unit_id, student_id))
+            {
+                continue;
+            }
+            assignments.push(UnitAssignment {
+                unit_id,
+                unit_name: None,
+                textbook_id: None,
+                student_id,
+                assigned_by,
+                created_at: Some(OffsetDateTime::now_utc()),
+            });
+            assigned += 1;
+        }
+        Ok(assigned)
+    }
+
+    async fn unassign_unit(&self, unit_id: i32, student_id: i32) -> Result<bool> {
+        let mut assignments = self.assignments.lock().unwrap();
+        let before = assignments.len();
+        assignments.retain(|row| (row.unit_id, row.student_id) != (unit_id, student_id));
+        Ok(assignments.len() < before)
+    }
+
+    async fn find_assignments(&self, student_id: i32) -> Result<Vec<UnitAssignment>> {
+        Ok(self
+            .assignments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|row| row.student_id == student_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn is_word_assigned(&self, student_id: i32, word_id: i32) -> Result<bool> {
+        Ok(self
+            .assigned_words(student_id)
+            .iter()
+            .any(|(id, _)| *id == word_id))
+    }
+
+    async fn find_review_queue(
+        &self,
+        student_id: i32,
+        due_before: OffsetDateTime,
+        limit: i64,
+        new_limit: i64,
+    ) -> Result<Vec<ReviewItem>> {
+        let states = self.states.lock().unwrap();
+        let state = |word_id: i32| {
+            states
+                .iter()
+                .map(|(state, _)| state)
+                .find(|state| (state.student_id, state.word_id) == (student_id, word_id))
+        };
+        let assigned = self.assigned_words(student_id);
+        let mut due: Vec<ReviewItem> = assigned
+            .iter()
+            .filter_map(|&(word_id, unit_id)| {
+                state(word_id)
+                    .filter(|state| state.due_at < due_before)
+                    .map(|state| Self::review_item(word_id, unit_id, Some(state)))
+            })
+            .collect();
+        due.sort_by_key(|item| item.due_at);
+        let new = assigned
+            .iter()
+            .filter(|(word_id, _)| state(*word_id).is_none())
+            .take(new_limit.max(0) as usize)
+            .map(|&(word_id, unit_id)| Self::review_item(word_id, unit_id, None));
+        Ok(due.into_iter().chain(new).take(limit as usize).collect())
+    }
+
+    async fn count_new_since(&self, student_id: i32, since: OffsetDateTime) -> Result<i64> {
+        Ok(self
+            .states
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(state, started_at)| state.student_id == student_id && *started_at >= since)
+            .count() as i64)
+    }
+
+    async fn find_state(&self, student_id: i32, word_id: i32) -> Result<Option<ReviewState>> {
+        Ok(self
+            .states
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(state, _)| state)
+            .find(|state| (state.student_id, state.word_id) == (student_id, word_id))
+            .cloned())
+    }
+
+    async fn save_review(&self, state: &ReviewState, _quality: u8) -> Result<ReviewState> {
+        let mut states = self.states.lock().unwrap();
+        match states.iter_mut().find(|(stored, _)| {
+            (stored.student_id, stored.word_id) == (state.student_id, state.word_id)
+        }) {
+            Some((stored, _)) => *stored = state.clone(),
+            None => states.push((state.clone(), OffsetDateTime::now_utc())),
+        }
+        Ok(state.clone())
+    }
+
+    async fn find_mastery(
+        &self,
+        student_id: i32,
+        mastered_interval: i32,
+    ) -> Result<Vec<UnitMastery>> {
+        let assignments = self.find_assignments(student_id).await?;
+        let unit_words = self.unit_words.lock().unwrap().clone();
+        let states = self.states.lock().unwrap();
+        Ok(assignments
+            .into_iter()
+            .map(|assignment| {
+                let words = unit_words
+                    .get(&assignment.unit_id)
+                    .cloned()
+                    .unwrap_or_default();
+                let word_states: Vec<&ReviewState> = states
+                    .iter()
+                    .map(|(state, _)| state)
+                    .filter(|state| {
+                        state.student_id == student_id && words.contains(&state.word_id)
+                    })
+                    .collect();
+                UnitMastery {
+                    unit_id: assignment.unit_id,
+                    unit_name: assignment.unit_name,
+                    textbook_id: assignment.textbook_id,
+                    total: words.len() as i64,
+                    learned: word_states
+                        .iter()
+                        .filter(|state| state.repetitions > 0)
+                        .count() as i64,
+                    mastered: word_states
+                        .iter()
+                        .filter(|state| state.interval_days >= mastered_interval)
+                        .count() as i64,
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,217 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::env;
+
+/// 加密密钥来源的环境变量
+const SECRET_ENV: &str = "MODEL_KEY_SECRET";
+const NONCE_LEN: usize = 12;
+/// 密码哈希的算法标识和默认迭代次数
+const PASSWORD_SCHEME: &str = "pbkdf2-sha256";
+const PASSWORD_ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const TOKEN_LEN: usize = 32;
+
+/// 从环境变量读取加密口令
+fn secret_from_env() -> Result<String> {
+    let secret = env::var(SECRET_ENV).map_err(|_| anyhow!("{} not set", SECRET_ENV))?;
+    if secret.is_empty() {
+        return Err(anyhow!("{} must not be empty", SECRET_ENV));
+    }
+    Ok(secret)
+}
+
+fn cipher(secret: &str) -> Aes256Gcm {
+    // 任意长度的口令经 SHA-256 派生为 256 位密钥
+    let digest = Sha256::digest(secret.as_bytes());
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&digest))
+}
+
+/// 使用 AES-256-GCM 加密敏感信息，返回 base64(nonce || ciphertext)
+pub fn encrypt_secret(plain: &str) -> Result<String> {
+    encrypt_with(&secret_from_env()?, plain)
+}
+
+/// 解密 `encrypt_secret` 生成的密文
+pub fn decrypt_secret(encoded: &str) -> Result<String> {
+    decrypt_with(&secret_from_env()?, encoded)
+}
+
+fn encrypt_with(secret: &str, plain: &str) -> Result<String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(secret)
+        .encrypt(&nonce, plain.as_bytes())
+        .map_err(|e| anyhow!("Failed to encrypt secret: {}", e))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(payload))
+}
+
+fn decrypt_with(secret: &str, encoded: &str) -> Result<String> {
+    let payload = STANDARD
+        .decode(encoded)
+        .map_err(|e| anyhow!("Invalid encrypted secret: {}", e))?;
+    if payload.len() <= NONCE_LEN {
+        return Err(anyhow!("Invalid encrypted secret: payload too short"));
+    }
+
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let plain = cipher(secret)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Failed to decrypt secret, check {}", SECRET_ENV))?;
+    String::from_utf8(plain).map_err(Into::into)
+}
+
+/// 脱敏展示，只保留首尾各 4 个字符
+pub fn mask_secret(plain: &str) -> String {
+    let chars: Vec<char> = plain.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    format!(
+        "{}****{}",
+        chars[..4].iter().collect::<String>(),
+        chars[chars.len() - 4..].iter().collect::<String>()
+    )
+}
+
+/// PBKDF2-HMAC-SHA256，输出 32 字节
+fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(password, salt, iterations)
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// 使用随机盐的 PBKDF2-HMAC-SHA256 哈希密码，格式为 `pbkdf2-sha256$迭代次数$盐$哈希`
+pub fn hash_password(password: &str) -> String {
+    hash_password_with(password, PASSWORD_ITERATIONS)
+}
+
+fn hash_password_with(password: &str, iterations: u32) -> String {
+    let salt = random_bytes::<SALT_LEN>();
+    let derived = pbkdf2_sha256(password.as_bytes(), &salt, iterations);
+    format!(
+        "{}${}${}${}",
+        PASSWORD_SCHEME,
+        iterations,
+        STANDARD.encode(salt),
+        STANDARD.encode(derived)
+    )
+}
+
+/// 校验密码，哈希格式无法识别时视为不匹配
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    let parts: Vec<&str> = hash.split('$').collect();
+    let [scheme, iterations, salt, expected] = parts[..] else {
+        return false;
+    };
+    let (Ok(iterations), Ok(salt), Ok(expected)) = (
+        iterations.parse::<u32>(),
+        STANDARD.decode(salt),
+        STANDARD.decode(expected),
+    ) else {
+        return false;
+    };
+    if scheme != PASSWORD_SCHEME || iterations == 0 {
+        return false;
+    }
+    let derived = pbkdf2_sha256(password.as_bytes(), &salt, iterations);
+    // 逐字节比较全部内容，耗时与第一个不同字节的位置无关
+    derived.len() == expected.len()
+        && derived
+            .iter()
+            .zip(expected.iter())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// 用户名不存在时用来校验的哈希，让登录耗时与密码错误时一致，无法据此判断用户名是否存在
+pub fn dummy_password_hash() -> &'static str {
+    static DUMMY_HASH: Lazy<String> = Lazy::new(|| hash_password(&generate_token()));
+    &DUMMY_HASH
+}
+
+/// 生成随机的登录令牌
+pub fn generate_token() -> String {
+    URL_SAFE_NO_PAD.encode(random_bytes::<TOKEN_LEN>())
+}
+
+/// 令牌只保存 SHA-256 摘要，数据库泄露时无法直接使用
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// 链接令牌的摘要加上前缀，与登录令牌互不通用
+pub fn hash_link_token(token: &str) -> String {
+    hash_token(&format!("link:{}", token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let secret = "unit-test-secret";
+
+        let encrypted = encrypt_with(secret, "sk-1234567890abcdef").unwrap();
+        assert!(!encrypted.contains("sk-1234567890abcdef"));
+        assert_ne!(
+            encrypted,
+            encrypt_with(secret, "sk-1234567890abcdef").unwrap()
+        );
+        assert_eq!(
+            decrypt_with(secret, &encrypted).unwrap(),
+            "sk-1234567890abcdef"
+        );
+
+        assert!(decrypt_with("other-secret", &encrypted).is_err());
+        assert!(decrypt_with(secret, "bm90LWEtdmFsaWQtcGF5bG9hZA==").is_err());
+    }
+
+    #[test]
+    fn test_mask_secret() {
+        assert_eq!(mask_secret("sk-1234567890abcdef"), "sk-1****cdef");
+        assert_eq!(mask_secret("short"), "*****");
+    }
+
+    #[test]
+    fn test_pbkdf2_sha256_vectors() {
+        let hex =
+            |bytes: [u8; 32]| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+        assert_eq!(
+            hex(pbkdf2_sha256(b"password", b"salt", 1)),
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+        );
+        assert_eq!(
+            hex(pbkdf2_sha256(b"password", b"salt", 2)),
+            "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"
+        );
+    }
+
+    #[test]
+    fn test_hash_and_verify_password() {
+        let hash = hash_password_with("correct horse", 10);
+        assert!(hash.starts_with("pbkdf2-sha256$10$"));
+        assert_ne!(hash, hash_password_with("correct horse", 10));
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("wrong horse", &hash));
+        assert!(!verify_password("correct horse", "plain-text"));
+        assert!(!verify_password("correct horse", dummy_password_hash()));
+
+        let token = generate_token();
+        assert_ne!(token, generate_token());
+        assert_eq!(hash_token(&token).len(), 64);
+        assert_ne!(hash_link_token(&token), hash_token(&token));
+    }
+}
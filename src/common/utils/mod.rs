@@ -1 +1,5 @@
+pub mod crypto;
 pub mod response;
+pub mod sequence;
+pub mod spelling;
+pub mod word_list;
@@ -0,0 +1,101 @@
+use anyhow::{anyhow, Result};
+
+/// 调整顺序的操作，作用于按当前顺序排列的ID列表
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reorder {
+    /// 把 id 移动到 position（从 1 开始，超出范围时放到最后）
+    Move { id: i32, position: i32 },
+    /// 交换两个ID的位置
+    Swap { first: i32, second: i32 },
+    /// 保持当前顺序，只重新编号为连续的 1..n
+    Renumber,
+}
+
+impl Reorder {
+    /// 按操作重新排列 ids，ID 不在列表中时返回错误
+    pub fn apply(&self, ids: &mut Vec<i32>) -> Result<()> {
+        let index_of = |ids: &[i32], id: i32| {
+            ids.iter()
+                .position(|&current| current == id)
+                .ok_or_else(|| anyhow!("{} is not in the list being reordered", id))
+        };
+
+        match *self {
+            Reorder::Move { id, position } => {
+                if position < 1 {
+                    return Err(anyhow!("Position must start from 1, got {}", position));
+                }
+                let from = index_of(ids, id)?;
+                ids.remove(from);
+                let to = (position as usize - 1).min(ids.len());
+                ids.insert(to, id);
+            }
+            Reorder::Swap { first, second } => {
+                let a = index_of(ids, first)?;
+                let b = index_of(ids, second)?;
+                ids.swap(a, b);
+            }
+            Reorder::Renumber => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(op: Reorder) -> Result<Vec<i32>> {
+        let mut ids = vec![10, 20, 30, 40];
+        op.apply(&mut ids)?;
+        Ok(ids)
+    }
+
+    #[test]
+    fn test_move() {
+        let moved = apply(Reorder::Move {
+            id: 40,
+            position: 1,
+        })
+        .unwrap();
+        assert_eq!(moved, vec![40, 10, 20, 30]);
+        let moved = apply(Reorder::Move {
+            id: 10,
+            position: 3,
+        })
+        .unwrap();
+        assert_eq!(moved, vec![20, 30, 10, 40]);
+        let moved = apply(Reorder::Move {
+            id: 20,
+            position: 99,
+        })
+        .unwrap();
+        assert_eq!(moved, vec![10, 30, 40, 20]);
+        assert!(apply(Reorder::Move {
+            id: 20,
+            position: 0
+        })
+        .is_err());
+        assert!(apply(Reorder::Move {
+            id: 50,
+            position: 1
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_swap_and_renumber() {
+        let swapped = apply(Reorder::Swap {
+            first: 10,
+            second: 30,
+        })
+        .unwrap();
+        assert_eq!(swapped, vec![30, 20, 10, 40]);
+        assert!(apply(Reorder::Swap {
+            first: 10,
+            second: 50
+        })
+        .is_err());
+        assert_eq!(apply(Reorder::Renumber).unwrap(), vec![10, 20, 30, 40]);
+    }
+}
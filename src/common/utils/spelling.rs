@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+/// 全角字符转半角，中文输入法下输入的字母、连字符和空格也能判对
+fn to_half_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '\u{2018}' | '\u{2019}' => '\'',
+        _ => c,
+    }
+}
+
+/// 各种连字符和破折号
+fn is_hyphen(c: char) -> bool {
+    matches!(c, '-' | '\u{2010}'..='\u{2014}')
+}
+
+/// 比较拼写前的规范化：忽略首尾空白和大小写，全角转半角，连字符视为空格，连续空白视为一个空格，
+/// 如 “Ice - Cream” 与 “ice cream” 相同
+pub fn normalize(answer: &str) -> String {
+    answer
+        .chars()
+        .map(to_half_width)
+        .map(|c| if is_hyphen(c) { ' ' } else { c })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// 拼写是否正确
+pub fn spelling_matches(given: &str, expected: &str) -> bool {
+    let expected = normalize(expected);
+    !expected.is_empty() && normalize(given) == expected
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    /// 拼写正确的部分
+    Equal,
+    /// 漏写的字母
+    Missing,
+    /// 多写或写错的字母
+    Extra,
+}
+
+/// 拼写差异中连续的一段
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// 按规范化后的字母逐个比较作答与正确拼写，写错的字母表示为一段 Extra 加一段 Missing
+pub fn spelling_diff(given: &str, expected: &str) -> Vec<DiffSegment> {
+    let given: Vec<char> = normalize(given).chars().collect();
+    let expected: Vec<char> = normalize(expected).chars().collect();
+
+    // lcs[i][j] 为 given[i..] 与 expected[j..] 的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; expected.len() + 1]; given.len() + 1];
+    for i in (0..given.len()).rev() {
+        for j in (0..expected.len()).rev() {
+            lcs[i][j] = if given[i] == expected[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    let mut push = |op: DiffOp, c: char| match segments.last_mut() {
+        Some(last) if last.op == op => last.text.push(c),
+        _ => segments.push(DiffSegment {
+            op,
+            text: c.to_string(),
+        }),
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < given.len() || j < expected.len() {
+        if i < given.len() && j < expected.len() && given[i] == expected[j] {
+            push(DiffOp::Equal, given[i]);
+            i += 1;
+            j += 1;
+        } else if i < given.len() && (j == expected.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            push(DiffOp::Extra, given[i]);
+            i += 1;
+        } else {
+            push(DiffOp::Missing, expected[j]);
+            j += 1;
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spelling_matches() {
+        assert!(spelling_matches("  Apple ", "apple"));
+        assert!(spelling_matches("get   up", "get up"));
+        assert!(!spelling_matches("aple", "apple"));
+        assert!(!spelling_matches("", ""));
+    }
+
+    #[test]
+    fn test_tolerant_spelling() {
+        assert!(spelling_matches("Ice - Cream", "ice-cream"));
+        assert!(spelling_matches("ice cream", "ice-cream"));
+        assert!(spelling_matches("ＡＰＰＬＥ", "apple"));
+        assert!(spelling_matches("don’t", "don't"));
+        assert!(!spelling_matches("icecream", "ice cream"));
+    }
+
+    #[test]
+    fn test_spelling_diff() {
+        let segment = |op, text: &str| DiffSegment {
+            op,
+            text: text.to_string(),
+        };
+        assert_eq!(
+            spelling_diff("Aple", "apple"),
+            vec![
+                segment(DiffOp::Equal, "ap"),
+                segment(DiffOp::Missing, "p"),
+                segment(DiffOp::Equal, "le"),
+            ]
+        );
+        assert_eq!(
+            spelling_diff("frend", "friend"),
+            vec![
+                segment(DiffOp::Equal, "fr"),
+                segment(DiffOp::Missing, "i"),
+                segment(DiffOp::Equal, "end"),
+            ]
+        );
+        assert_eq!(
+            spelling_diff("cat", "cut"),
+            vec![
+                segment(DiffOp::Equal, "c"),
+                segment(DiffOp::Extra, "a"),
+                segment(DiffOp::Missing, "u"),
+                segment(DiffOp::Equal, "t"),
+            ]
+        );
+        assert_eq!(
+            spelling_diff("ice-cream", "Ice Cream"),
+            vec![segment(DiffOp::Equal, "ice cream")]
+        );
+        assert_eq!(
+            spelling_diff("", "ab"),
+            vec![segment(DiffOp::Missing, "ab")]
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use serde::{Deserialize, Serialize};
+
+/// 导入内容的格式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Csv,
+    /// Excel 复制或导出的制表符分隔文本
+    Tsv,
+    /// 直接粘贴的文本，每行一个单词，可在单词后跟中文释义
+    Text,
+}
+
+impl ImportFormat {
+    /// 根据内容推断格式：含制表符为 TSV，含逗号为 CSV，否则为纯文本
+    pub fn detect(content: &str) -> Self {
+        if content.contains('\t') {
+            ImportFormat::Tsv
+        } else if content.contains(',') {
+            ImportFormat::Csv
+        } else {
+            ImportFormat::Text
+        }
+    }
+}
+
+/// 解析后的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordListRow {
+    pub word: String,
+    pub pos: Option<String>,
+    pub meaning: Option<String>,
+    pub sequence: Option<i32>,
+}
+
+/// 导入内容中的一行及其解析结果，line 从 1 开始
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordListLine {
+    pub line: usize,
+    pub row: Result<WordListRow, String>,
+}
+
+/// 单词最大长度
+const MAX_WORD_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy)]
+enum Column {
+    Word,
+    Pos,
+    Meaning,
+    Sequence,
+    Ignored,
+}
+
+impl Column {
+    fn from_header(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "word" | "单词" => Some(Column::Word),
+            "pos" | "词性" => Some(Column::Pos),
+            "meaning" | "释义" | "含义" => Some(Column::Meaning),
+            "sequence" | "seq" | "序号" => Some(Column::Sequence),
+            _ => None,
+        }
+    }
+}
+
+/// 没有表头时的列顺序：单词、释义、序号
+const DEFAULT_COLUMNS: [Column; 3] = [Column::Word, Column::Meaning, Column::Sequence];
+
+/// 解析单元单词列表，空行会被跳过；CSV / TSV 的第一行如果包含 word 或 单词 列则视为表头
+pub fn parse_word_list(content: &str, format: ImportFormat) -> Vec<WordListLine> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut records: Vec<(usize, Vec<String>)> = match format {
+        ImportFormat::Csv => split_csv(content),
+        ImportFormat::Tsv => split_lines(content, |line| {
+            line.split('\t').map(str::to_string).collect()
+        }),
+        ImportFormat::Text => split_lines(content, split_text_line),
+    };
+    records.retain(|(_, cells)| cells.iter().any(|cell| !cell.trim().is_empty()));
+
+    let mut columns = DEFAULT_COLUMNS.to_vec();
+    if format != ImportFormat::Text {
+        if let Some((_, header)) = records.first() {
+            let header: Vec<Option<Column>> = header
+                .iter()
+                .map(|cell| Column::from_header(cell))
+                .collect();
+            if header
+                .iter()
+                .any(|column| matches!(column, Some(Column::Word)))
+            {
+                columns = header
+                    .into_iter()
+                    .map(|column| column.unwrap_or(Column::Ignored))
+                    .collect();
+                records.remove(0);
+            }
+        }
+    }
+
+    records
+        .into_iter()
+        .map(|(line, cells)| WordListLine {
+            line,
+            row: parse_row(&columns, &cells),
+        })
+        .collect()
+}
+
+fn parse_row(columns: &[Column], cells: &[String]) -> Result<WordListRow, String> {
+    let mut row = WordListRow {
+        word: String::new(),
+        pos: None,
+        meaning: None,
+        sequence: None,
+    };
+    for (column, cell) in columns.iter().zip(cells) {
+        let value = cell.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match column {
+            Column::Word => row.word = value.split_whitespace().collect::<Vec<_>>().join(" "),
+            Column::Pos => row.pos = Some(value.to_string()),
+            Column::Meaning => row.meaning = Some(value.to_string()),
+            Column::Sequence => {
+                let sequence = value
+                    .parse::<i32>()
+                    .map_err(|_| format!("invalid sequence: {}", value))?;
+                row.sequence = Some(sequence);
+            }
+            Column::Ignored => {}
+        }
+    }
+    validate_word(&row.word)?;
+    Ok(row)
+}
+
+/// 单词只能包含英文字母、空格、连字符、撇号和点
+fn validate_word(word: &str) -> Result<(), String> {
+    if word.is_empty() {
+        return Err("word is empty".to_string());
+    }
+    if word.chars().count() > MAX_WORD_LEN {
+        return Err(format!("word is longer than {} characters", MAX_WORD_LEN));
+    }
+    if !word.chars().any(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("not an English word: {}", word));
+    }
+    if let Some(c) = word
+        .chars()
+        .find(|c| !(c.is_ascii_alphabetic() || matches!(c, ' ' | '-' | '\'' | '.')))
+    {
+        return Err(format!("invalid character '{}' in word: {}", c, word));
+    }
+    Ok(())
+}
+
+fn split_lines(content: &str, split: impl Fn(&str) -> Vec<String>) -> Vec<(usize, Vec<String>)> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, split(line)))
+        .collect()
+}
+
+/// 纯文本行：第一个非 ASCII 字符之前为单词，之后为释义，如 “apple 苹果”
+fn split_text_line(line: &str) -> Vec<String> {
+    match line.find(|c: char| !c.is_ascii()) {
+        Some(index) => vec![line[..index].to_string(), line[index..].to_string()],
+        None => vec![line.to_string()],
+    }
+}
+
+/// 按 RFC 4180 拆分 CSV，支持双引号包裹的字段、转义的双引号和字段内换行
+fn split_csv(content: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => record.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' => {
+                line += 1;
+                if in_quotes {
+                    field.push(c);
+                } else {
+                    record.push(std::mem::take(&mut field));
+                    records.push((record_line, std::mem::take(&mut record)));
+                    record_line = line;
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(lines: &[WordListLine]) -> Vec<&WordListRow> {
+        lines.iter().filter_map(|l| l.row.as_ref().ok()).collect()
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(ImportFormat::detect("apple\t苹果"), ImportFormat::Tsv);
+        assert_eq!(ImportFormat::detect("apple,苹果"), ImportFormat::Csv);
+        assert_eq!(ImportFormat::detect("apple\nbanana"), ImportFormat::Text);
+    }
+
+    #[test]
+    fn test_parse_csv_with_header() {
+        let content = "\u{feff}序号,单词,词性,释义\r\n2,apple,n.,苹果\n1,\"ice  cream\",n.,\"冰淇淋,雪糕\"\n\n3,x2,,\n";
+        let lines = parse_word_list(content, ImportFormat::Csv);
+        assert_eq!(lines.len(), 3);
+
+        let parsed = rows(&lines);
+        assert_eq!(parsed[0].word, "apple");
+        assert_eq!(parsed[0].sequence, Some(2));
+        assert_eq!(parsed[1].word, "ice cream");
+        assert_eq!(parsed[1].meaning.as_deref(), Some("冰淇淋,雪糕"));
+        assert_eq!(parsed[1].pos.as_deref(), Some("n."));
+
+        assert_eq!(lines[2].line, 5);
+        assert!(lines[2]
+            .row
+            .as_ref()
+            .unwrap_err()
+            .contains("invalid character"));
+    }
+
+    #[test]
+    fn test_parse_tsv_without_header() {
+        let lines = parse_word_list("apple\t苹果\t1\nbanana\t\tx\n", ImportFormat::Tsv);
+        assert_eq!(
+            lines[0].row.as_ref().unwrap().meaning.as_deref(),
+            Some("苹果")
+        );
+        assert_eq!(lines[0].row.as_ref().unwrap().sequence, Some(1));
+        assert_eq!(lines[1].row.as_ref().unwrap_err(), "invalid sequence: x");
+    }
+
+    #[test]
+    fn test_parse_pasted_text() {
+        let lines = parse_word_list(" apple 苹果\ndon't\n\n苹果\n", ImportFormat::Text);
+        assert_eq!(lines.len(), 3);
+        let apple = lines[0].row.as_ref().unwrap();
+        assert_eq!(apple.word, "apple");
+        assert_eq!(apple.meaning.as_deref(), Some("苹果"));
+        assert_eq!(lines[1].row.as_ref().unwrap().word, "don't");
+        assert_eq!(lines[2].line, 4);
+        assert!(lines[2].row.is_err());
+    }
+}
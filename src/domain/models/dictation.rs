@@ -0,0 +1,143 @@
+use crate::common::utils::spelling::{spelling_diff, spelling_matches, DiffSegment};
+use crate::domain::models::mistake::{MistakeAnswer, MistakeType};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// 听写的一个单词，index 从 0 开始
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictationEntry {
+    pub index: usize,
+    pub word_id: i32,
+    pub word: String,
+    pub pronunciation_us: Option<String>,
+    pub pronunciation_uk: Option<String>,
+}
+
+/// 一次单元听写
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DictationSession {
+    pub id: Option<i32>,
+    pub unit_id: i32,
+    /// 打乱顺序的种子，为空表示按单元顺序
+    pub seed: Option<i64>,
+    pub repeat_count: i32,
+    pub pause_ms: i32,
+    #[sqlx(json)]
+    pub entries: Vec<DictationEntry>,
+    pub created_by: Option<i32>,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+/// 一个单词的判分结果，diff 为作答与正确拼写的逐字母差异
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictationResult {
+    pub index: usize,
+    pub word_id: i32,
+    pub correct: bool,
+    /// 提交的答案，未作答时为空
+    pub answer: Option<String>,
+    pub expected: String,
+    pub diff: Vec<DiffSegment>,
+}
+
+/// 一次听写交卷
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DictationAttempt {
+    pub id: Option<i32>,
+    pub session_id: i32,
+    pub user_id: i32,
+    #[sqlx(json)]
+    pub results: Vec<DictationResult>,
+    pub correct: i32,
+    pub total: i32,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+impl DictationAttempt {
+    /// 按听写顺序判拼写，answers 中没有的单词记为未作答
+    pub fn grade(
+        session_id: i32,
+        user_id: i32,
+        entries: &[DictationEntry],
+        answers: &[(usize, String)],
+    ) -> Self {
+        let results: Vec<DictationResult> = entries
+            .iter()
+            .map(|entry| {
+                let answer = answers
+                    .iter()
+                    .find(|(index, _)| *index == entry.index)
+                    .map(|(_, answer)| answer.clone());
+                let given = answer.as_deref().unwrap_or_default();
+                DictationResult {
+                    index: entry.index,
+                    word_id: entry.word_id,
+                    correct: spelling_matches(given, &entry.word),
+                    diff: spelling_diff(given, &entry.word),
+                    answer,
+                    expected: entry.word.clone(),
+                }
+            })
+            .collect();
+        Self {
+            id: None,
+            session_id,
+            user_id,
+            correct: results.iter().filter(|result| result.correct).count() as i32,
+            total: results.len() as i32,
+            results,
+            created_at: None,
+        }
+    }
+
+    /// 每个单词的作答，写错和没写的记为拼写错误
+    pub fn answers(&self, unit_id: i32) -> Vec<MistakeAnswer> {
+        self.results
+            .iter()
+            .map(|result| MistakeAnswer {
+                word_id: result.word_id,
+                unit_id: Some(unit_id),
+                error_type: MistakeType::Spelling,
+                correct: result.correct,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(index: usize, word: &str) -> DictationEntry {
+        DictationEntry {
+            index,
+            word_id: index as i32 + 1,
+            word: word.to_string(),
+            pronunciation_us: None,
+            pronunciation_uk: None,
+        }
+    }
+
+    #[test]
+    fn test_grade_and_answers() {
+        let entries = vec![entry(0, "ice-cream"), entry(1, "friend"), entry(2, "apple")];
+        let answers = vec![(0, "Ice Cream".to_string()), (1, "frend".to_string())];
+
+        let attempt = DictationAttempt::grade(1, 2, &entries, &answers);
+        assert_eq!((attempt.correct, attempt.total), (1, 3));
+        assert!(attempt.results[0].correct);
+        assert_eq!(attempt.results[1].diff.len(), 3);
+        assert_eq!(attempt.results[2].answer, None);
+
+        let answers = attempt.answers(9);
+        let wrong: Vec<i32> = answers
+            .iter()
+            .filter(|answer| !answer.correct)
+            .map(|answer| answer.word_id)
+            .collect();
+        assert_eq!(wrong, vec![2, 3]);
+        assert!(answers
+            .iter()
+            .all(|m| m.unit_id == Some(9) && m.error_type == MistakeType::Spelling));
+    }
+}
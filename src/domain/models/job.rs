@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// 任务类型：为单个单词补全信息
+pub const CREATE_WORD_JOB: &str = "create_word";
+/// 任务类型：批量补全所有单词的音标、释义和例句
+pub const ENRICH_WORDS_JOB: &str = "enrich_words";
+/// 任务类型：新建单词并绑定到单元
+pub const CREATE_UNIT_WORD_JOB: &str = "create_unit_word";
+/// 任务类型：批量导入单元单词
+pub const IMPORT_UNIT_WORDS_JOB: &str = "import_unit_words";
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// 已结束的任务不会再被执行
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(JobStatus::Pending),
+            "running" => Ok(JobStatus::Running),
+            "succeeded" => Ok(JobStatus::Succeeded),
+            "failed" => Ok(JobStatus::Failed),
+            "cancelled" => Ok(JobStatus::Cancelled),
+            _ => Err(anyhow::anyhow!("Unknown job status: {}", s)),
+        }
+    }
+}
+
+/// 后台任务
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Job {
+    pub id: Option<i32>,
+    pub kind: String,
+    pub payload: Value,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub progress_done: i32,
+    pub progress_total: i32,
+    pub checkpoint: Option<Value>,
+    pub result: Option<Value>,
+    pub last_error: Option<String>,
+    pub run_at: OffsetDateTime,
+    pub started_at: Option<OffsetDateTime>,
+    /// 执行中任务的租约到期时间，worker 定期续租，过期后任务可被重新领取
+    pub locked_until: Option<OffsetDateTime>,
+    pub finished_at: Option<OffsetDateTime>,
+    pub created_at: Option<OffsetDateTime>,
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl Job {
+    pub fn new(kind: &str, payload: Value, max_attempts: i32) -> Self {
+        Self {
+            id: None,
+            kind: kind.to_string(),
+            payload,
+            status: JobStatus::Pending.to_string(),
+            attempts: 0,
+            max_attempts,
+            progress_done: 0,
+            progress_total: 0,
+            checkpoint: None,
+            result: None,
+            last_error: None,
+            run_at: OffsetDateTime::now_utc(),
+            started_at: None,
+            locked_until: None,
+            finished_at: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    pub fn status(&self) -> JobStatus {
+        self.status.parse().unwrap_or(JobStatus::Failed)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::infrastructure::llm::usage::UsageRecord;
+use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime};
+
+/// 一次大模型调用的用量
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LLMUsage {
+    pub id: Option<i32>,
+    pub provider: String,
+    pub model: String,
+    pub operation: String,
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+    pub latency_ms: i64,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<UsageRecord> for LLMUsage {
+    fn from(record: UsageRecord) -> Self {
+        Self {
+            id: None,
+            provider: record.provider,
+            model: record.model,
+            operation: record.operation,
+            prompt_tokens: record.prompt_tokens,
+            completion_tokens: record.completion_tokens,
+            latency_ms: record.latency_ms,
+            created_at: record.created_at,
+        }
+    }
+}
+
+/// 按 日期 + 模型服务 + 模型 + 操作 汇总的用量
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LLMUsageSummary {
+    pub day: Date,
+    pub provider: String,
+    pub model: String,
+    pub operation: String,
+    pub calls: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub avg_latency_ms: f64,
+}
@@ -0,0 +1,131 @@
+use crate::infrastructure::dto::WordMeaning;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// 复习权重的半衰期：最近一次答错每过这么多天，权重减半
+const RECENCY_HALF_LIFE_DAYS: f64 = 7.0;
+
+/// 错题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MistakeType {
+    /// 拼写错误，来自听写和拼写、填空题
+    Spelling,
+    /// 释义错误，来自释义选择题
+    Meaning,
+    /// 读音错误，来自音标选择题
+    Pronunciation,
+}
+
+impl MistakeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MistakeType::Spelling => "spelling",
+            MistakeType::Meaning => "meaning",
+            MistakeType::Pronunciation => "pronunciation",
+        }
+    }
+}
+
+impl fmt::Display for MistakeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MistakeType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spelling" => Ok(MistakeType::Spelling),
+            "meaning" => Ok(MistakeType::Meaning),
+            "pronunciation" => Ok(MistakeType::Pronunciation),
+            _ => Err(anyhow::anyhow!("Unknown mistake type: {}", s)),
+        }
+    }
+}
+
+/// 一次作答：答错时记入错题本并清零连续答对次数，答对时累加错题本中同类错误的连续答对次数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MistakeAnswer {
+    pub word_id: i32,
+    pub unit_id: Option<i32>,
+    pub error_type: MistakeType,
+    pub correct: bool,
+}
+
+/// 错题本中的一条错题，附带单词和单元信息
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Mistake {
+    pub id: i32,
+    pub student_id: i32,
+    pub word_id: i32,
+    pub word: String,
+    pub phonetic_us: Option<String>,
+    pub phonetic_uk: Option<String>,
+    pub pronunciation_us: Option<String>,
+    pub pronunciation_uk: Option<String>,
+    #[serde(default)]
+    #[sqlx(json)]
+    pub meanings: Vec<WordMeaning>,
+    /// 最近一次答错时所在的单元，单元删除后为空
+    pub unit_id: Option<i32>,
+    pub unit_name: Option<String>,
+    pub textbook_id: Option<i32>,
+    pub error_type: String,
+    pub wrong_count: i32,
+    pub correct_streak: i32,
+    pub last_wrong_at: OffsetDateTime,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+impl Mistake {
+    /// 复习权重：答错次数按最近一次答错的时间衰减，越常错、越近错的单词越靠前
+    pub fn weight(&self, now: OffsetDateTime) -> f64 {
+        let age_days = ((now - self.last_wrong_at).as_seconds_f64() / 86400.0).max(0.0);
+        self.wrong_count as f64 * 0.5f64.powf(age_days / RECENCY_HALF_LIFE_DAYS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    fn mistake(wrong_count: i32, last_wrong_at: OffsetDateTime) -> Mistake {
+        Mistake {
+            id: 1,
+            student_id: 1,
+            word_id: 1,
+            word: "apple".to_string(),
+            phonetic_us: None,
+            phonetic_uk: None,
+            pronunciation_us: None,
+            pronunciation_uk: None,
+            meanings: Vec::new(),
+            unit_id: None,
+            unit_name: None,
+            textbook_id: None,
+            error_type: MistakeType::Spelling.to_string(),
+            wrong_count,
+            correct_streak: 0,
+            last_wrong_at,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_weight() {
+        let now = OffsetDateTime::now_utc();
+        assert_eq!(mistake(3, now).weight(now), 3.0);
+        assert!((mistake(4, now - Duration::days(7)).weight(now) - 2.0).abs() < 1e-9);
+        // 两周前错了 3 次不如昨天错了 1 次
+        assert!(
+            mistake(3, now - Duration::days(14)).weight(now)
+                < mistake(1, now - Duration::days(1)).weight(now)
+        );
+    }
+}
@@ -1,8 +1,19 @@
+pub mod dictation;
 pub mod grade;
+pub mod job;
+pub mod llm_usage;
+pub mod mistake;
 pub mod model_provider;
+pub mod prompt_template;
+pub mod quiz;
+pub mod review;
 pub mod semester;
 pub mod textbook;
 pub mod textbook_version;
+pub mod trash;
 pub mod unit;
+pub mod user;
 pub mod word;
+pub mod word_edit;
+pub mod word_revision;
 pub mod word_unit_mapping;
@@ -1,4 +1,7 @@
+use crate::infrastructure::llm::usage::ModelPricing;
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use std::collections::HashMap;
 use time::OffsetDateTime;
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
@@ -11,6 +14,20 @@ pub struct ModelProvider {
     pub is_active: Option<bool>,
     pub created_at: Option<OffsetDateTime>,
     pub updated_at: Option<OffsetDateTime>,
+    pub provider_type: Option<String>,
+    pub model_name: Option<String>,
+    #[serde(skip)]
+    pub api_key_encrypted: Option<String>,
+    pub timeout_secs: Option<i32>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<i32>,
+    /// 每 1K 输入 token 的价格
+    pub prompt_price_per_1k: Option<f64>,
+    /// 每 1K 输出 token 的价格
+    pub completion_price_per_1k: Option<f64>,
+    /// 按模型名配置的价格，优先于服务的价格
+    pub model_prices: Option<Json<HashMap<String, ModelPricing>>>,
 }
 
 impl ModelProvider {
@@ -30,6 +47,16 @@ impl ModelProvider {
             is_active: Some(is_active),
             created_at: None,
             updated_at: None,
+            provider_type: None,
+            model_name: None,
+            api_key_encrypted: None,
+            timeout_secs: None,
+            temperature: None,
+            top_p: None,
+            max_tokens: None,
+            prompt_price_per_1k: None,
+            completion_price_per_1k: None,
+            model_prices: None,
         }
     }
 }
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// 提示词模板的一个版本，也是模板目录中 JSON 文件的格式
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PromptTemplate {
+    #[serde(default)]
+    pub id: Option<i32>,
+    pub name: String,
+    pub version: i32,
+    pub system_prompt: String,
+    pub user_prompt: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_active: bool,
+    #[serde(default)]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl PromptTemplate {
+    pub fn new(name: &str, version: i32, system_prompt: &str, user_prompt: &str) -> Self {
+        Self {
+            id: None,
+            name: name.to_string(),
+            version,
+            system_prompt: system_prompt.to_string(),
+            user_prompt: user_prompt.to_string(),
+            description: None,
+            is_active: false,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::common::utils::spelling::spelling_matches;
+use crate::domain::models::mistake::{MistakeAnswer, MistakeType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// 测验题型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionKind {
+    /// 看英文选中文释义，干扰项来自同一单元或同一年级
+    MeaningChoice,
+    /// 看中文释义拼写英文
+    Spelling,
+    /// 看音标选单词
+    PhoneticChoice,
+    /// 在例句中挖空填写单词
+    FillBlank,
+}
+
+impl QuestionKind {
+    pub const ALL: [QuestionKind; 4] = [
+        QuestionKind::MeaningChoice,
+        QuestionKind::Spelling,
+        QuestionKind::PhoneticChoice,
+        QuestionKind::FillBlank,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionKind::MeaningChoice => "meaning_choice",
+            QuestionKind::Spelling => "spelling",
+            QuestionKind::PhoneticChoice => "phonetic_choice",
+            QuestionKind::FillBlank => "fill_blank",
+        }
+    }
+
+    /// 选择题按选项原文判分，填写题按拼写判分
+    pub fn is_choice(&self) -> bool {
+        matches!(
+            self,
+            QuestionKind::MeaningChoice | QuestionKind::PhoneticChoice
+        )
+    }
+
+    /// 答错时记入错题本的错误类型
+    pub fn mistake_type(&self) -> MistakeType {
+        match self {
+            QuestionKind::MeaningChoice => MistakeType::Meaning,
+            QuestionKind::PhoneticChoice => MistakeType::Pronunciation,
+            QuestionKind::Spelling | QuestionKind::FillBlank => MistakeType::Spelling,
+        }
+    }
+}
+
+impl fmt::Display for QuestionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for QuestionKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "meaning_choice" => Ok(QuestionKind::MeaningChoice),
+            "spelling" => Ok(QuestionKind::Spelling),
+            "phonetic_choice" => Ok(QuestionKind::PhoneticChoice),
+            "fill_blank" => Ok(QuestionKind::FillBlank),
+            _ => Err(anyhow::anyhow!("Unknown question kind: {}", s)),
+        }
+    }
+}
+
+/// 一道题目及正确答案，index 从 0 开始
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuizQuestion {
+    pub index: usize,
+    pub kind: QuestionKind,
+    pub word_id: i32,
+    pub unit_id: Option<i32>,
+    pub prompt: String,
+    /// 选择题的选项，填写题为空
+    #[serde(default)]
+    pub options: Vec<String>,
+    pub answer: String,
+}
+
+impl QuizQuestion {
+    pub fn is_correct(&self, answer: &str) -> bool {
+        if self.kind.is_choice() {
+            answer.trim() == self.answer
+        } else {
+            spelling_matches(answer, &self.answer)
+        }
+    }
+}
+
+/// 生成的测验
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Quiz {
+    pub id: Option<i32>,
+    pub unit_ids: Vec<i32>,
+    pub seed: i64,
+    #[sqlx(json)]
+    pub questions: Vec<QuizQuestion>,
+    pub created_by: Option<i32>,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+/// 一道题的判分结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestionResult {
+    pub index: usize,
+    pub kind: QuestionKind,
+    pub word_id: i32,
+    pub correct: bool,
+    /// 提交的答案，未作答时为空
+    pub answer: Option<String>,
+    pub expected: String,
+}
+
+/// 一次交卷
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct QuizAttempt {
+    pub id: Option<i32>,
+    pub quiz_id: i32,
+    pub user_id: i32,
+    #[sqlx(json)]
+    pub results: Vec<QuestionResult>,
+    pub correct: i32,
+    pub total: i32,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+impl QuizAttempt {
+    /// 按题目顺序判分，answers 中没有的题目记为未作答
+    pub fn grade(
+        quiz_id: i32,
+        user_id: i32,
+        quiz: &[QuizQuestion],
+        answers: &[(usize, String)],
+    ) -> Self {
+        let results: Vec<QuestionResult> = quiz
+            .iter()
+            .map(|question| {
+                let answer = answers
+                    .iter()
+                    .find(|(index, _)| *index == question.index)
+                    .map(|(_, answer)| answer.clone());
+                QuestionResult {
+                    index: question.index,
+                    kind: question.kind,
+                    word_id: question.word_id,
+                    correct: answer
+                        .as_deref()
+                        .is_some_and(|answer| question.is_correct(answer)),
+                    answer,
+                    expected: question.answer.clone(),
+                }
+            })
+            .collect();
+        Self {
+            id: None,
+            quiz_id,
+            user_id,
+            correct: results.iter().filter(|result| result.correct).count() as i32,
+            total: results.len() as i32,
+            results,
+            created_at: None,
+        }
+    }
+
+    /// 每道题的作答，按题型记为对应的错误类型
+    pub fn answers(&self, questions: &[QuizQuestion]) -> Vec<MistakeAnswer> {
+        self.results
+            .iter()
+            .map(|result| MistakeAnswer {
+                word_id: result.word_id,
+                unit_id: questions
+                    .iter()
+                    .find(|question| question.index == result.index)
+                    .and_then(|question| question.unit_id),
+                error_type: result.kind.mistake_type(),
+                correct: result.correct,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(index: usize, kind: QuestionKind, answer: &str) -> QuizQuestion {
+        QuizQuestion {
+            index,
+            kind,
+            word_id: index as i32 + 1,
+            unit_id: Some(1),
+            prompt: String::new(),
+            options: Vec::new(),
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_grade() {
+        let questions = vec![
+            question(0, QuestionKind::MeaningChoice, "n. 苹果"),
+            question(1, QuestionKind::Spelling, "get up"),
+            question(2, QuestionKind::FillBlank, "pear"),
+        ];
+        let answers = vec![(0, "n. 苹果".to_string()), (1, " Get  Up ".to_string())];
+
+        let attempt = QuizAttempt::grade(1, 2, &questions, &answers);
+        assert_eq!((attempt.correct, attempt.total), (2, 3));
+        assert_eq!(attempt.results[2].answer, None);
+        assert!(!attempt.results[2].correct);
+        assert!(!question(0, QuestionKind::MeaningChoice, "n. 苹果").is_correct("n. 梨"));
+
+        let answers = attempt.answers(&questions);
+        assert_eq!(answers[0].error_type, MistakeType::Meaning);
+        assert!(answers[0].correct && !answers[2].correct);
+        assert_eq!(answers[2].error_type, MistakeType::Spelling);
+        assert_eq!(answers[2].unit_id, Some(1));
+    }
+}
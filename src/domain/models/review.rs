@@ -0,0 +1,152 @@
+use crate::infrastructure::dto::WordMeaning;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+/// 作答质量 0-5：0-2 为没记住，3 为勉强想起，4 为想起，5 为脱口而出
+pub const MAX_QUALITY: u8 = 5;
+/// 低于该质量视为遗忘，重新开始
+const PASS_QUALITY: u8 = 3;
+const DEFAULT_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+/// 复习间隔达到该天数视为已掌握
+pub const MASTERED_INTERVAL_DAYS: i32 = 21;
+
+/// 学生对一个单词的复习状态，按 SM-2 算法安排下次复习
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReviewState {
+    pub id: Option<i32>,
+    pub student_id: i32,
+    pub word_id: i32,
+    pub ease: f64,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub lapses: i32,
+    pub due_at: OffsetDateTime,
+    pub last_reviewed_at: Option<OffsetDateTime>,
+}
+
+impl ReviewState {
+    /// 还没复习过的单词，立即到期
+    pub fn new(student_id: i32, word_id: i32, now: OffsetDateTime) -> Self {
+        Self {
+            id: None,
+            student_id,
+            word_id,
+            ease: DEFAULT_EASE,
+            interval_days: 0,
+            repetitions: 0,
+            lapses: 0,
+            due_at: now,
+            last_reviewed_at: None,
+        }
+    }
+
+    /// 根据作答质量更新难度系数、间隔和下次复习时间
+    pub fn review(&mut self, quality: u8, now: OffsetDateTime) -> Result<()> {
+        if quality > MAX_QUALITY {
+            return Err(anyhow!(
+                "Quality must be between 0 and {}, got {}",
+                MAX_QUALITY,
+                quality
+            ));
+        }
+
+        if quality >= PASS_QUALITY {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval_days as f64 * self.ease).round() as i32,
+            };
+            self.repetitions += 1;
+        } else {
+            if self.repetitions > 0 {
+                self.lapses += 1;
+            }
+            self.repetitions = 0;
+            self.interval_days = 1;
+        }
+        let miss = (MAX_QUALITY - quality) as f64;
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.due_at = now + Duration::days(self.interval_days as i64);
+        self.last_reviewed_at = Some(now);
+        Ok(())
+    }
+}
+
+/// 布置给学生的单元
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UnitAssignment {
+    pub unit_id: i32,
+    pub unit_name: Option<String>,
+    pub textbook_id: Option<i32>,
+    pub student_id: i32,
+    pub assigned_by: Option<i32>,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+/// 复习队列中的一个单词，due_at 为空表示新词
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReviewItem {
+    pub word_id: i32,
+    pub word: String,
+    pub phonetic_us: Option<String>,
+    pub phonetic_uk: Option<String>,
+    #[serde(default)]
+    #[sqlx(json)]
+    pub meanings: Vec<WordMeaning>,
+    pub unit_id: i32,
+    pub unit_name: Option<String>,
+    pub due_at: Option<OffsetDateTime>,
+    pub repetitions: i32,
+    pub lapses: i32,
+}
+
+/// 学生在一个单元中的掌握情况
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UnitMastery {
+    pub unit_id: i32,
+    pub unit_name: Option<String>,
+    pub textbook_id: Option<i32>,
+    /// 单元中的单词数
+    pub total: i64,
+    /// 复习过且最近一次没有遗忘的单词数
+    pub learned: i64,
+    /// 复习间隔达到 MASTERED_INTERVAL_DAYS 的单词数
+    pub mastered: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sm2_schedule() {
+        let now = OffsetDateTime::now_utc();
+        let mut state = ReviewState::new(1, 1, now);
+
+        state.review(4, now).unwrap();
+        assert_eq!((state.repetitions, state.interval_days), (1, 1));
+        assert_eq!(state.due_at, now + Duration::days(1));
+        state.review(5, now).unwrap();
+        assert_eq!((state.repetitions, state.interval_days), (2, 6));
+        assert!((state.ease - 2.6).abs() < 1e-9);
+        state.review(3, now).unwrap();
+        assert_eq!(state.interval_days, 16);
+        assert!((state.ease - 2.46).abs() < 1e-9);
+
+        // 遗忘后从头开始，难度系数不低于下限
+        state.review(0, now).unwrap();
+        assert_eq!(
+            (state.repetitions, state.interval_days, state.lapses),
+            (0, 1, 1)
+        );
+        for _ in 0..5 {
+            state.review(1, now).unwrap();
+        }
+        assert_eq!(state.ease, MIN_EASE);
+        assert_eq!(state.lapses, 1);
+
+        assert!(state.review(6, now).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// 可以软删除并从回收站恢复的数据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashEntity {
+    /// 教材，连带删除全部单元和单元单词
+    Textbook,
+    /// 单元，连带删除单元中的单词
+    Unit,
+    /// 单元中的单词，即 word_unit_mappings 中的一行
+    UnitWord,
+    /// 单词，连带从所有单元中移除
+    Word,
+}
+
+impl TrashEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrashEntity::Textbook => "textbook",
+            TrashEntity::Unit => "unit",
+            TrashEntity::UnitWord => "unit_word",
+            TrashEntity::Word => "word",
+        }
+    }
+}
+
+impl fmt::Display for TrashEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TrashEntity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "textbook" => Ok(TrashEntity::Textbook),
+            "unit" => Ok(TrashEntity::Unit),
+            "unit_word" => Ok(TrashEntity::UnitWord),
+            "word" => Ok(TrashEntity::Word),
+            _ => Err(anyhow::anyhow!("Unknown trash entity: {}", s)),
+        }
+    }
+}
+
+/// 被删除（或将要删除）的一条数据及连带删除的单元和单元单词数量
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashItem {
+    pub entity: TrashEntity,
+    pub id: i32,
+    /// 教材或单元名称、单词
+    pub name: String,
+    /// 删除时间，预览时为空
+    pub deleted_at: Option<OffsetDateTime>,
+    pub units: i64,
+    pub unit_words: i64,
+}
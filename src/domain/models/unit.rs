@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Unit {
     pub id: Option<i32>,
     pub name: Option<String>,
@@ -10,6 +10,8 @@ pub struct Unit {
     pub created_at: Option<OffsetDateTime>,
     pub word_count: Option<i32>,
     pub updated_at: Option<OffsetDateTime>,
+    /// 软删除时间，为空表示未删除
+    pub deleted_at: Option<OffsetDateTime>,
 }
 
 //实现构造函数
@@ -23,6 +25,7 @@ impl Unit {
             created_at: None,
             word_count: None,
             updated_at: None,
+            deleted_at: None,
         }
     }
 }
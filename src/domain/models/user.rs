@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// 用户角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// 管理系统配置、模型和用户
+    Admin,
+    /// 维护课本、单元和单词
+    Editor,
+    /// 查看和导出单词表，给学生布置单元
+    Teacher,
+    Student,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Editor => "editor",
+            Role::Teacher => "teacher",
+            Role::Student => "student",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(Role::Admin),
+            "editor" => Ok(Role::Editor),
+            "teacher" => Ok(Role::Teacher),
+            "student" => Ok(Role::Student),
+            _ => Err(anyhow::anyhow!("Unknown role: {}", s)),
+        }
+    }
+}
+
+/// 路由的访问权限，在 `define_routes!` 中为每个路由声明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// 不需要登录
+    Public,
+    /// 任何已登录的用户
+    SignedIn,
+    /// 只允许列出的角色
+    Roles(&'static [Role]),
+}
+
+impl Access {
+    pub fn allows(&self, role: Role) -> bool {
+        match self {
+            Access::Public | Access::SignedIn => true,
+            Access::Roles(roles) => roles.contains(&role),
+        }
+    }
+}
+
+pub const PUBLIC: Access = Access::Public;
+pub const SIGNED_IN: Access = Access::SignedIn;
+pub const ADMIN: Access = Access::Roles(&[Role::Admin]);
+/// 可以修改课本、单元和单词内容的角色
+pub const EDITORS: Access = Access::Roles(&[Role::Admin, Role::Editor]);
+/// 教师及以上角色
+pub const STAFF: Access = Access::Roles(&[Role::Admin, Role::Editor, Role::Teacher]);
+
+/// 用户账号
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct User {
+    pub id: Option<i32>,
+    pub username: String,
+    #[serde(skip_serializing, default)]
+    pub password_hash: String,
+    pub display_name: Option<String>,
+    pub role: String,
+    pub disabled: bool,
+    pub created_at: Option<OffsetDateTime>,
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl User {
+    pub fn new(username: &str, password_hash: String, role: Role) -> Self {
+        Self {
+            id: None,
+            username: username.to_string(),
+            password_hash,
+            display_name: None,
+            role: role.to_string(),
+            disabled: false,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+/// 当前请求的登录用户，由认证中间件放入请求扩展
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthUser {
+    pub id: i32,
+    pub username: String,
+    pub role: Role,
+}
+
+impl AuthUser {
+    /// 要查询的学生，为空时为自己；只有教师及以上角色可以查询其他学生
+    pub fn student_id(&self, requested: Option<i32>) -> anyhow::Result<i32> {
+        match requested {
+            Some(id) if id != self.id && !STAFF.allows(self.role) => {
+                Err(anyhow::anyhow!("Not allowed to view other students"))
+            }
+            Some(id) => Ok(id),
+            None => Ok(self.id),
+        }
+    }
+
+    /// 是否可以查看和作答他人创建的测验、听写：创建者本人或教师及以上角色
+    pub fn can_access(&self, owner: Option<i32>) -> bool {
+        owner == Some(self.id) || STAFF.allows(self.role)
+    }
+}
+
+impl TryFrom<&User> for AuthUser {
+    type Error = anyhow::Error;
+
+    fn try_from(user: &User) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: user.id.ok_or_else(|| anyhow::anyhow!("User has no id"))?,
+            username: user.username.clone(),
+            role: user.role.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_allows() {
+        assert!(SIGNED_IN.allows(Role::Student));
+        assert!(EDITORS.allows(Role::Editor));
+        assert!(!EDITORS.allows(Role::Teacher));
+        assert!(STAFF.allows(Role::Teacher));
+        assert!(!STAFF.allows(Role::Student));
+        assert!(!ADMIN.allows(Role::Editor));
+        assert_eq!("teacher".parse::<Role>().unwrap(), Role::Teacher);
+        assert!("guest".parse::<Role>().is_err());
+    }
+
+    #[test]
+    fn test_students_only_view_themselves() {
+        let user = |role: Role| AuthUser {
+            id: 7,
+            username: role.to_string(),
+            role,
+        };
+        assert_eq!(user(Role::Student).student_id(None).unwrap(), 7);
+        assert!(user(Role::Student).student_id(Some(8)).is_err());
+        assert_eq!(user(Role::Student).student_id(Some(7)).unwrap(), 7);
+        assert_eq!(user(Role::Teacher).student_id(Some(8)).unwrap(), 8);
+        assert!(user(Role::Student).can_access(Some(7)));
+        assert!(!user(Role::Student).can_access(Some(8)));
+        assert!(!user(Role::Student).can_access(None));
+        assert!(user(Role::Teacher).can_access(Some(8)));
+    }
+}
@@ -1,7 +1,9 @@
+use crate::domain::models::word_edit::WordField;
+use crate::infrastructure::dto::WordMeaning;
 use serde::{Deserialize, Serialize};
-use time::PrimitiveDateTime;
+use time::{OffsetDateTime, PrimitiveDateTime};
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Word {
     pub word_id: Option<i32>,
     pub word: String,
@@ -11,8 +13,15 @@ pub struct Word {
     pub phonetic_uk: Option<String>,
     pub pronunciation_uk: Option<String>,
     pub updated_at: Option<PrimitiveDateTime>,
-    pub meaning: Option<String>,
+    /// 按顺序排列的释义，保存在 word_meanings 表中
+    #[serde(default)]
+    #[sqlx(json)]
+    pub meanings: Vec<WordMeaning>,
     pub example: Option<String>,
+    /// 生成释义的提示词版本，释义来自第三方词典时为空
+    pub meaning_prompt_version: Option<i32>,
+    /// 生成例句的提示词版本
+    pub example_prompt_version: Option<i32>,
 }
 
 impl Word {
@@ -26,8 +35,52 @@ impl Word {
             phonetic_uk: None,
             pronunciation_uk: None,
             updated_at: None,
-            meaning: None,
+            meanings: Vec::new(),
             example: None,
+            meaning_prompt_version: None,
+            example_prompt_version: None,
         }
     }
+
+    /// 可人工修改的字段的当前值，释义为 `WordMeaning` 数组的 JSON，没有释义时为空
+    pub fn field(&self, field: WordField) -> Option<String> {
+        match field {
+            WordField::PhoneticUs => self.phonetic_us.clone(),
+            WordField::PhoneticUk => self.phonetic_uk.clone(),
+            WordField::Meaning if self.meanings.is_empty() => None,
+            WordField::Meaning => serde_json::to_string(&self.meanings).ok(),
+            WordField::Example => self.example.clone(),
+        }
+    }
+
+    /// 修改字段的值；释义和例句由人工填写后不再对应提示词版本
+    pub fn set_field(&mut self, field: WordField, value: Option<String>) -> anyhow::Result<()> {
+        match field {
+            WordField::PhoneticUs => self.phonetic_us = value,
+            WordField::PhoneticUk => self.phonetic_uk = value,
+            WordField::Meaning => {
+                self.meanings = match value {
+                    Some(json) => serde_json::from_str(&json)?,
+                    None => Vec::new(),
+                };
+                self.meaning_prompt_version = None;
+            }
+            WordField::Example => {
+                self.example = value;
+                self.example_prompt_version = None;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 迁移到 word_meanings 时无法解析的释义原文，resolved 表示单词之后已有新的释义
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MeaningParseFailure {
+    pub word_id: i32,
+    pub word: String,
+    pub meaning: String,
+    pub error: String,
+    pub resolved: bool,
+    pub created_at: OffsetDateTime,
 }
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// 编辑可以手动修改并锁定的单词字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordField {
+    PhoneticUs,
+    PhoneticUk,
+    /// 释义，修改记录中保存为 `WordMeaning` 数组的 JSON
+    Meaning,
+    Example,
+}
+
+impl WordField {
+    /// 同时也是 words.locked_fields 中保存的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WordField::PhoneticUs => "phonetic_us",
+            WordField::PhoneticUk => "phonetic_uk",
+            WordField::Meaning => "meaning",
+            WordField::Example => "example",
+        }
+    }
+}
+
+impl fmt::Display for WordField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WordField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "phonetic_us" => Ok(WordField::PhoneticUs),
+            "phonetic_uk" => Ok(WordField::PhoneticUk),
+            "meaning" => Ok(WordField::Meaning),
+            "example" => Ok(WordField::Example),
+            _ => Err(anyhow::anyhow!("Unknown word field: {}", s)),
+        }
+    }
+}
+
+/// 一次人工修改：要写入的字段值、是否锁定以及修改人
+#[derive(Debug, Clone, Default)]
+pub struct WordPatch {
+    pub changes: Vec<(WordField, Option<String>)>,
+    /// 为 true 时锁定本次修改的字段
+    pub lock: bool,
+    /// 解除锁定的字段
+    pub unlock: Vec<WordField>,
+    pub editor: Option<String>,
+}
+
+/// 单词某个字段的一次人工修改记录
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WordEdit {
+    pub id: Option<i32>,
+    pub word_id: i32,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub editor: Option<String>,
+    pub created_at: Option<OffsetDateTime>,
+}
@@ -0,0 +1,161 @@
+use crate::domain::models::word::Word;
+use crate::infrastructure::dto::WordMeaning;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// 单词修订的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionSource {
+    /// 大模型生成
+    Llm,
+    /// 第三方词典；同时生成的例句仍由大模型提供，可从 example_prompt_version 区分
+    Dictionary,
+    /// 编辑人工修改
+    Editor,
+    /// 回滚到历史版本
+    Rollback,
+    /// 未指明来源的保存，以及迁移时为已有单词记录的初始版本
+    System,
+}
+
+impl RevisionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevisionSource::Llm => "llm",
+            RevisionSource::Dictionary => "dictionary",
+            RevisionSource::Editor => "editor",
+            RevisionSource::Rollback => "rollback",
+            RevisionSource::System => "system",
+        }
+    }
+}
+
+impl fmt::Display for RevisionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RevisionSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "llm" => Ok(RevisionSource::Llm),
+            "dictionary" => Ok(RevisionSource::Dictionary),
+            "editor" => Ok(RevisionSource::Editor),
+            "rollback" => Ok(RevisionSource::Rollback),
+            "system" => Ok(RevisionSource::System),
+            _ => Err(anyhow::anyhow!("Unknown revision source: {}", s)),
+        }
+    }
+}
+
+/// 修订中保存的单词内容，回滚时整体写回
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WordContent {
+    pub phonetic_us: Option<String>,
+    pub phonetic_uk: Option<String>,
+    pub pronunciation_us: Option<String>,
+    pub pronunciation_uk: Option<String>,
+    #[serde(default)]
+    pub meanings: Vec<WordMeaning>,
+    pub example: Option<String>,
+    pub meaning_prompt_version: Option<i32>,
+    pub example_prompt_version: Option<i32>,
+    /// 迁移到 word_meanings 时无法解析的释义原文，有值时这个版本不能回滚
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meaning_raw: Option<String>,
+}
+
+impl From<&Word> for WordContent {
+    fn from(word: &Word) -> Self {
+        Self {
+            phonetic_us: word.phonetic_us.clone(),
+            phonetic_uk: word.phonetic_uk.clone(),
+            pronunciation_us: word.pronunciation_us.clone(),
+            pronunciation_uk: word.pronunciation_uk.clone(),
+            meanings: word.meanings.clone(),
+            example: word.example.clone(),
+            meaning_prompt_version: word.meaning_prompt_version,
+            example_prompt_version: word.example_prompt_version,
+            meaning_raw: None,
+        }
+    }
+}
+
+impl WordContent {
+    /// 与另一版本内容的差异：`{字段: {"old": 旧值, "new": 新值}}`，只包含有变化的字段
+    pub fn diff(&self, other: &WordContent) -> Map<String, Value> {
+        let as_map = |content: &WordContent| match serde_json::to_value(content) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        let old = as_map(self);
+        let new = as_map(other);
+        new.into_iter()
+            .filter_map(|(field, new_value)| {
+                let old_value = old.get(&field).cloned().unwrap_or(Value::Null);
+                (old_value != new_value)
+                    .then(|| (field, json!({ "old": old_value, "new": new_value })))
+            })
+            .collect()
+    }
+}
+
+/// 单词的一个修订版本：修改后的完整内容及与上一版本的差异
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WordRevision {
+    pub id: Option<i32>,
+    pub word_id: i32,
+    pub source: String,
+    pub editor: Option<String>,
+    pub content: Value,
+    pub changes: Value,
+    pub created_at: Option<OffsetDateTime>,
+}
+
+impl WordRevision {
+    pub fn content(&self) -> anyhow::Result<WordContent> {
+        Ok(serde_json::from_value(self.content.clone())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_only_contains_changed_fields() {
+        let meaning = |definition: &str| WordMeaning {
+            pos: "n.".to_string(),
+            definition: definition.to_string(),
+            ..Default::default()
+        };
+        let mut word = Word::new("apple");
+        word.meanings = vec![meaning("old")];
+        let before = WordContent::from(&word);
+        word.meanings = vec![meaning("new")];
+        word.meaning_prompt_version = Some(2);
+        let after = WordContent::from(&word);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(
+            diff["meanings"],
+            json!({
+                "old": [{"pos": "n.", "definition": "old"}],
+                "new": [{"pos": "n.", "definition": "new"}]
+            })
+        );
+        assert_eq!(
+            diff["meaning_prompt_version"],
+            json!({"old": null, "new": 2})
+        );
+        assert!(after.diff(&after).is_empty());
+    }
+}
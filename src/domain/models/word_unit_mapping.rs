@@ -1,3 +1,4 @@
+use crate::infrastructure::dto::WordMeaning;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -6,8 +7,20 @@ pub struct WordUnitMapping {
     pub id: Option<i32>,
     pub word_id: Option<i32>,
     pub unit_id: Option<i32>,
+    /// 单词在该单元中的具体含义，为空时使用单词的通用释义
+    pub meaning: Option<String>,
+    /// 单元含义对应的词性，如 n. / v.
+    pub pos: Option<String>,
+    /// 单词在单元中的顺序，为空时按添加顺序排在后面
+    pub sequence: Option<i32>,
+    /// 按教材年级生成的例句，为空时使用单词的通用例句
+    pub example: Option<String>,
+    /// 生成例句时使用的提示词版本
+    pub example_prompt_version: Option<i32>,
     pub created_at: Option<OffsetDateTime>,
     pub updated_at: Option<OffsetDateTime>,
+    /// 软删除时间，为空表示未删除
+    pub deleted_at: Option<OffsetDateTime>,
 }
 
 impl WordUnitMapping {
@@ -16,8 +29,23 @@ impl WordUnitMapping {
             id: None,
             word_id: None,
             unit_id: None,
+            meaning: None,
+            pos: None,
+            sequence: None,
+            example: None,
+            example_prompt_version: None,
             created_at: None,
             updated_at: None,
+            deleted_at: None,
         }
     }
+
+    /// 单元含义，设置后代替单词的通用释义
+    pub fn unit_meaning(&self) -> Option<WordMeaning> {
+        Some(WordMeaning {
+            pos: self.pos.clone().unwrap_or_default(),
+            definition: self.meaning.clone()?,
+            ..Default::default()
+        })
+    }
 }
@@ -0,0 +1,210 @@
+use crate::api::dto::dictation_dto::{
+    DictationResultDTO, DictationSessionDTO, StartDictationDTO, SubmitDictationDTO,
+};
+use crate::api::dto::unit_word_dto::WordDTO;
+use crate::domain::models::dictation::{DictationAttempt, DictationEntry, DictationSession};
+use crate::domain::models::user::AuthUser;
+use crate::domain::services::interfaces::dictation_service::DictationService;
+use crate::domain::services::interfaces::mistake_service::MistakeService;
+use crate::infrastructure::database::repositories::{
+    DictationRepository, UnitRepository, WordUnitMappingRepository,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::sync::Arc;
+use tracing::info;
+
+/// 每个单词默认播放的次数和上限
+const DEFAULT_REPEAT_COUNT: i32 = 2;
+const MAX_REPEAT_COUNT: i32 = 5;
+/// 每次播放后默认停顿的毫秒数和上限
+const DEFAULT_PAUSE_MS: i32 = 3000;
+const MAX_PAUSE_MS: i32 = 30000;
+
+/// 按单元顺序排列单词，指定 seed 时按种子打乱；没有拼写的单词跳过
+fn build_entries(words: &[WordDTO], seed: Option<u64>) -> Vec<DictationEntry> {
+    let mut words: Vec<&WordDTO> = words
+        .iter()
+        .filter(|word| word.word_id.is_some())
+        .filter(|word| word.word.as_deref().is_some_and(|w| !w.trim().is_empty()))
+        .collect();
+    if let Some(seed) = seed {
+        words.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+    }
+    words
+        .into_iter()
+        .enumerate()
+        .map(|(index, word)| DictationEntry {
+            index,
+            word_id: word.word_id.unwrap_or_default(),
+            word: word.word.as_deref().unwrap_or_default().trim().to_string(),
+            pronunciation_us: word.pronunciation_us.clone(),
+            pronunciation_uk: word.pronunciation_uk.clone(),
+        })
+        .collect()
+}
+
+pub struct DictationServiceImpl {
+    dictation_repository: Arc<dyn DictationRepository>,
+    mistake_service: Arc<dyn MistakeService>,
+    word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
+    unit_repository: Arc<dyn UnitRepository>,
+}
+
+impl DictationServiceImpl {
+    pub fn new(
+        dictation_repository: Arc<dyn DictationRepository>,
+        mistake_service: Arc<dyn MistakeService>,
+        word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
+        unit_repository: Arc<dyn UnitRepository>,
+    ) -> Self {
+        Self {
+            dictation_repository,
+            mistake_service,
+            word_unit_mapping_repository,
+            unit_repository,
+        }
+    }
+
+    async fn find_session(&self, user: &AuthUser, id: i32) -> Result<DictationSession> {
+        let session = self
+            .dictation_repository
+            .find_session(id)
+            .await?
+            .ok_or_else(|| anyhow!("Dictation {} not found", id))?;
+        if !user.can_access(session.created_by) {
+            return Err(anyhow!("Not allowed to access dictation {}", id));
+        }
+        Ok(session)
+    }
+}
+
+#[async_trait]
+impl DictationService for DictationServiceImpl {
+    async fn start(&self, user: &AuthUser, dto: &StartDictationDTO) -> Result<DictationSessionDTO> {
+        self.unit_repository
+            .find_by_id(dto.unit_id)
+            .await?
+            .ok_or_else(|| anyhow!("Unit {} not found", dto.unit_id))?;
+        let words = self
+            .word_unit_mapping_repository
+            .find_word_dto_by_unit_id(dto.unit_id)
+            .await?;
+
+        // 要求打乱但未指定种子时随机生成，返回给客户端以便重现
+        let seed = match (dto.seed, dto.shuffle.unwrap_or(false)) {
+            (Some(seed), _) => Some(seed),
+            (None, true) => Some(rand::random::<u32>() as i64),
+            (None, false) => None,
+        };
+        let entries = build_entries(&words, seed.map(|seed| seed as u64));
+        if entries.is_empty() {
+            return Err(anyhow!("Unit {} has no words to dictate", dto.unit_id));
+        }
+
+        let session = self
+            .dictation_repository
+            .save_session(&DictationSession {
+                id: None,
+                unit_id: dto.unit_id,
+                seed,
+                repeat_count: dto
+                    .repeat_count
+                    .unwrap_or(DEFAULT_REPEAT_COUNT)
+                    .clamp(1, MAX_REPEAT_COUNT),
+                pause_ms: dto
+                    .pause_ms
+                    .unwrap_or(DEFAULT_PAUSE_MS)
+                    .clamp(0, MAX_PAUSE_MS),
+                entries,
+                created_by: Some(user.id),
+                created_at: None,
+            })
+            .await?;
+        info!(
+            "{} started dictation {:?} for unit {} with {} words",
+            user.username,
+            session.id,
+            session.unit_id,
+            session.entries.len()
+        );
+        Ok(DictationSessionDTO::try_from(session)?)
+    }
+
+    async fn get_session(&self, user: &AuthUser, id: i32) -> Result<DictationSessionDTO> {
+        let session = self.find_session(user, id).await?;
+        Ok(DictationSessionDTO::try_from(session)?)
+    }
+
+    async fn submit(
+        &self,
+        user: &AuthUser,
+        dto: &SubmitDictationDTO,
+    ) -> Result<DictationResultDTO> {
+        let session = self.find_session(user, dto.session_id).await?;
+        let answers: Vec<(usize, String)> = dto
+            .answers
+            .iter()
+            .map(|answer| (answer.index, answer.answer.clone()))
+            .collect();
+        let attempt = DictationAttempt::grade(dto.session_id, user.id, &session.entries, &answers);
+        // 已交过卷时不再判分，避免看过拼写后重复提交清除错题
+        let attempt = self
+            .dictation_repository
+            .save_attempt(&attempt)
+            .await?
+            .ok_or_else(|| anyhow!("Dictation {} has already been submitted", dto.session_id))?;
+
+        self.mistake_service
+            .record_answers(user.id, &attempt.answers(session.unit_id))
+            .await?;
+        let mistakes = (attempt.total - attempt.correct) as usize;
+        Ok(DictationResultDTO::new(attempt, mistakes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(word_id: i32, word: &str) -> WordDTO {
+        serde_json::from_value(serde_json::json!({
+            "word_id": word_id,
+            "word": word,
+            "pronunciation_us": format!("{}-us.mp3", word),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_entries() {
+        let words: Vec<WordDTO> = ["apple", "pear", " ", "plum", "peach", "grape"]
+            .iter()
+            .enumerate()
+            .map(|(i, w)| word(i as i32 + 1, w))
+            .collect();
+
+        let ordered = build_entries(&words, None);
+        let ids: Vec<i32> = ordered.iter().map(|entry| entry.word_id).collect();
+        assert_eq!(ids, vec![1, 2, 4, 5, 6]);
+        assert_eq!(ordered[2].index, 2);
+        assert_eq!(ordered[2].pronunciation_us.as_deref(), Some("plum-us.mp3"));
+
+        let shuffled = build_entries(&words, Some(42));
+        assert_eq!(shuffled, build_entries(&words, Some(42)));
+        assert_ne!(
+            shuffled
+                .iter()
+                .map(|entry| entry.word_id)
+                .collect::<Vec<_>>(),
+            ids
+        );
+        assert!(shuffled
+            .iter()
+            .enumerate()
+            .all(|(i, entry)| entry.index == i));
+    }
+}
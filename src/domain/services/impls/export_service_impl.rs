@@ -0,0 +1,247 @@
+use crate::api::dto::export_dto::{ExportFormat, ExportQueryDTO};
+use crate::api::dto::unit_word_dto::WordDTO;
+use crate::domain::models::textbook::Textbook;
+use crate::domain::models::unit::Unit;
+use crate::domain::services::interfaces::export_service::ExportService;
+use crate::infrastructure::database::repositories::{
+    TextbookRepository, UnitRepository, WordUnitMappingRepository,
+};
+use crate::infrastructure::export::{
+    anki, csv, file_stem, format_meaning, html, ExportFile, ExportUnit, ExportWord, WordSheet,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct ExportServiceImpl {
+    textbook_repository: Arc<dyn TextbookRepository>,
+    unit_repository: Arc<dyn UnitRepository>,
+    word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
+}
+
+impl ExportServiceImpl {
+    pub fn new(
+        textbook_repository: Arc<dyn TextbookRepository>,
+        unit_repository: Arc<dyn UnitRepository>,
+        word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
+    ) -> Self {
+        Self {
+            textbook_repository,
+            unit_repository,
+            word_unit_mapping_repository,
+        }
+    }
+
+    async fn find_textbook(&self, id: Option<i32>) -> Result<Textbook> {
+        let id = id.ok_or_else(|| anyhow!("Unit has no textbook"))?;
+        self.textbook_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("Textbook not found: {}", id))
+    }
+
+    async fn export_unit(&self, unit: &Unit) -> Result<ExportUnit> {
+        let unit_id = unit.id.ok_or_else(|| anyhow!("Unit has no id"))?;
+        let words = self
+            .word_unit_mapping_repository
+            .find_word_dto_by_unit_id(unit_id)
+            .await?;
+        Ok(ExportUnit {
+            name: unit_name(unit),
+            words: words.iter().filter_map(export_word).collect(),
+        })
+    }
+
+    /// 按 unit_id 或 textbook_id 收集要导出的单词
+    async fn word_sheet(&self, query: &ExportQueryDTO) -> Result<WordSheet> {
+        match (query.unit_id, query.textbook_id) {
+            (Some(unit_id), None) => {
+                let unit = self
+                    .unit_repository
+                    .find_by_id(unit_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Unit not found: {}", unit_id))?;
+                let textbook = self.find_textbook(unit.textbook_id).await?;
+                let unit = self.export_unit(&unit).await?;
+                Ok(WordSheet {
+                    title: format!("{} {}", textbook.name, unit.name),
+                    units: vec![unit],
+                })
+            }
+            (None, Some(textbook_id)) => {
+                let textbook = self.find_textbook(Some(textbook_id)).await?;
+                let mut units = self
+                    .unit_repository
+                    .find_by_textbook_id(Some(textbook_id))
+                    .await?;
+                units.sort_by_key(|unit| (unit.sequence_number, unit.id));
+
+                let mut sheet = WordSheet {
+                    title: textbook.name,
+                    units: Vec::with_capacity(units.len()),
+                };
+                for unit in &units {
+                    sheet.units.push(self.export_unit(unit).await?);
+                }
+                Ok(sheet)
+            }
+            _ => Err(anyhow!(
+                "Exactly one of textbook_id and unit_id is required"
+            )),
+        }
+    }
+}
+
+fn unit_name(unit: &Unit) -> String {
+    match (&unit.name, unit.sequence_number) {
+        (Some(name), _) if !name.trim().is_empty() => name.trim().to_string(),
+        (_, Some(sequence)) => format!("Unit {}", sequence),
+        _ => format!("Unit {}", unit.id.unwrap_or_default()),
+    }
+}
+
+fn export_word(word: &WordDTO) -> Option<ExportWord> {
+    Some(ExportWord {
+        word: word.word.clone()?,
+        phonetic_us: word.phonetic_us.clone(),
+        phonetic_uk: word.phonetic_uk.clone(),
+        meaning: format_meaning(&word.meanings),
+        example: word.example.clone(),
+    })
+}
+
+#[async_trait]
+impl ExportService for ExportServiceImpl {
+    async fn export_words(&self, query: &ExportQueryDTO) -> Result<ExportFile> {
+        let sheet = self.word_sheet(query).await?;
+        let stem = file_stem(&sheet.title);
+
+        let file = match query.format {
+            ExportFormat::Html => ExportFile {
+                file_name: if query.dictation {
+                    format!("{} 默写.html", stem)
+                } else {
+                    format!("{}.html", stem)
+                },
+                content_type: "text/html; charset=utf-8",
+                content: html::render_html(&sheet, query.dictation).into_bytes(),
+            },
+            ExportFormat::Csv => ExportFile {
+                file_name: format!("{}.csv", stem),
+                content_type: "text/csv; charset=utf-8",
+                content: csv::render_csv(&sheet).into_bytes(),
+            },
+            ExportFormat::Anki => ExportFile {
+                file_name: format!("{}.apkg", stem),
+                content_type: "application/octet-stream",
+                // 生成牌组需要读写 SQLite 文件，放到阻塞线程池中执行
+                content: tokio::task::spawn_blocking(move || anki::build_apkg(&sheet)).await??,
+            },
+        };
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::{
+        InMemoryTextbookRepository, InMemoryUnitRepository, InMemoryWordRepository,
+        InMemoryWordUnitMappingRepository,
+    };
+    use crate::domain::models::word::Word;
+    use crate::domain::models::word_unit_mapping::WordUnitMapping;
+    use crate::infrastructure::database::repositories::Repository;
+    use crate::infrastructure::dto::WordMeaning;
+
+    async fn service() -> ExportServiceImpl {
+        let textbooks = Arc::new(InMemoryTextbookRepository::default());
+        let units = Arc::new(InMemoryUnitRepository::default());
+        let words = Arc::new(InMemoryWordRepository::default());
+        let mappings = Arc::new(InMemoryWordUnitMappingRepository::new(words.clone()));
+
+        let textbook = textbooks
+            .save(&Textbook {
+                id: None,
+                version_id: None,
+                grade_id: Some(3),
+                semester_id: None,
+                created_at: None,
+                name: "人教版三年级上册".to_string(),
+                unit_count: Some(2),
+                word_count: Some(3),
+                textbook_version: None,
+                grade: None,
+                semester: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+        for (sequence, name, unit_words) in [
+            (2, "Unit 2 Colours", vec!["red"]),
+            (1, "Unit 1 Hello", vec!["hello", "goodbye"]),
+        ] {
+            let mut unit = Unit::new();
+            unit.name = Some(name.to_string());
+            unit.textbook_id = textbook.id;
+            unit.sequence_number = Some(sequence);
+            let unit = units.save(&unit).await.unwrap();
+            for text in unit_words {
+                let mut word = Word::new(text);
+                word.meanings = vec![WordMeaning {
+                    pos: "int.".to_string(),
+                    definition: "你好".to_string(),
+                    ..Default::default()
+                }];
+                let word = words.save(&word).await.unwrap();
+                let mut mapping = WordUnitMapping::new();
+                mapping.word_id = word.word_id;
+                mapping.unit_id = unit.id;
+                mappings.save(&mapping).await.unwrap();
+            }
+        }
+        ExportServiceImpl::new(textbooks, units, mappings)
+    }
+
+    #[tokio::test]
+    async fn test_export_textbook_handout() {
+        let service = service().await;
+        let query = ExportQueryDTO {
+            textbook_id: Some(1),
+            ..Default::default()
+        };
+
+        let file = service.export_words(&query).await.unwrap();
+        assert_eq!(file.file_name, "人教版三年级上册.html");
+        let html = String::from_utf8(file.content).unwrap();
+        let unit1 = html.find("Unit 1 Hello").unwrap();
+        let unit2 = html.find("Unit 2 Colours").unwrap();
+        assert!(unit1 < unit2);
+        assert!(html.contains("<td>int. 你好</td>"));
+    }
+
+    #[tokio::test]
+    async fn test_export_unit_csv_and_anki() {
+        let service = service().await;
+        let mut query = ExportQueryDTO {
+            unit_id: Some(2),
+            format: ExportFormat::Csv,
+            ..Default::default()
+        };
+
+        let file = service.export_words(&query).await.unwrap();
+        assert_eq!(file.file_name, "人教版三年级上册 Unit 1 Hello.csv");
+        let csv = String::from_utf8(file.content).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.contains("Unit 1 Hello,goodbye,,,int. 你好,"));
+
+        query.format = ExportFormat::Anki;
+        let file = service.export_words(&query).await.unwrap();
+        assert!(file.file_name.ends_with(".apkg"));
+        assert!(file.content.starts_with(b"PK"));
+
+        query.textbook_id = Some(1);
+        let error = service.export_words(&query).await.unwrap_err();
+        assert!(error.to_string().contains("Exactly one"));
+    }
+}
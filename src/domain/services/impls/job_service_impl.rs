@@ -0,0 +1,598 @@
+use crate::api::dto::job_dto::{JobDTO, JobQueryDTO};
+use crate::domain::models::job::{Job, JobStatus};
+use crate::domain::services::interfaces::job_service::{JobContext, JobRunner, JobService};
+use crate::infrastructure::database::repositories::JobRepository;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+
+/// 默认最大尝试次数（含首次执行）
+const DEFAULT_MAX_ATTEMPTS: i32 = 3;
+const MAX_ATTEMPTS_LIMIT: i32 = 10;
+/// 失败重试的退避时间：5s、10s、20s ... 最长 5 分钟
+const RETRY_BASE_DELAY_SECS: i64 = 5;
+const RETRY_MAX_DELAY_SECS: i64 = 300;
+const DEFAULT_LIST_LIMIT: i64 = 50;
+const MAX_LIST_LIMIT: i64 = 200;
+/// 执行中任务的默认租约时长，worker 每过租约的三分之一续租一次
+const DEFAULT_LEASE_SECS: u64 = 60;
+
+/// 从 JOB_LEASE_SECS 读取任务租约时长
+pub fn job_lease_from_env() -> Duration {
+    let seconds = env::var("JOB_LEASE_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|seconds| *seconds > 0)
+        .unwrap_or(DEFAULT_LEASE_SECS);
+    Duration::from_secs(seconds)
+}
+
+pub struct JobServiceImpl {
+    job_repository: Arc<dyn JobRepository>,
+    runners: HashMap<&'static str, Arc<dyn JobRunner>>,
+    lease: Duration,
+}
+
+impl JobServiceImpl {
+    pub fn new(
+        job_repository: Arc<dyn JobRepository>,
+        runners: Vec<Arc<dyn JobRunner>>,
+        lease: Duration,
+    ) -> Self {
+        Self {
+            job_repository,
+            runners: runners
+                .into_iter()
+                .map(|runner| (runner.kind(), runner))
+                .collect(),
+            lease,
+        }
+    }
+
+    async fn find_job(&self, dto: &JobDTO) -> anyhow::Result<Job> {
+        let id = dto
+            .id
+            .ok_or_else(|| anyhow::anyhow!("Job id is required"))?;
+        self.job_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Job not found: {}", id))
+    }
+
+    /// 第 n 次执行失败后的等待时间，按指数增长
+    fn retry_delay(attempts: i32) -> time::Duration {
+        let exponent = (attempts.max(1) - 1).min(16) as u32;
+        let seconds = (RETRY_BASE_DELAY_SECS << exponent).min(RETRY_MAX_DELAY_SECS);
+        time::Duration::seconds(seconds)
+    }
+
+    /// 在单独的 tokio 任务中执行，期间定期续租；任务 panic 时按执行失败处理，
+    /// 续租失败（任务被取消或已被重新排队）时中止执行并返回 None
+    async fn execute(
+        &self,
+        id: i32,
+        job: &Job,
+        runner: Arc<dyn JobRunner>,
+    ) -> Option<anyhow::Result<Option<Value>>> {
+        let context = JobContext::new(id, self.job_repository.clone());
+        let claimed = job.clone();
+        let mut task = tokio::spawn(async move { runner.run(&claimed, &context).await });
+
+        let mut heartbeat = tokio::time::interval(self.lease / 3);
+        heartbeat.tick().await;
+        loop {
+            tokio::select! {
+                joined = &mut task => {
+                    return Some(joined.unwrap_or_else(|e| {
+                        Err(anyhow::anyhow!("Job runner panicked: {}", e))
+                    }));
+                }
+                _ = heartbeat.tick() => match self.job_repository.heartbeat(id, self.lease).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        task.abort();
+                        warn!("Job {} ({}) is no longer running, stopped", id, job.kind);
+                        return None;
+                    }
+                    Err(e) => warn!("Failed to renew lease of job {}: {}", id, e),
+                },
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl JobService for JobServiceImpl {
+    async fn enqueue(&self, dto: &JobDTO) -> anyhow::Result<JobDTO> {
+        let kind = dto.kind.as_deref().unwrap_or_default().trim();
+        if kind.is_empty() {
+            anyhow::bail!("Job kind is required");
+        }
+        let runner = self
+            .runners
+            .get(kind)
+            .ok_or_else(|| anyhow::anyhow!("Unknown job kind: {}", kind))?;
+        let max_attempts = dto.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        if !(1..=MAX_ATTEMPTS_LIMIT).contains(&max_attempts) {
+            anyhow::bail!("max_attempts must be between 1 and {}", MAX_ATTEMPTS_LIMIT);
+        }
+
+        let payload = dto.payload.clone().unwrap_or_else(|| json!({}));
+        let job = Job::new(runner.kind(), payload, max_attempts);
+        let job = self.job_repository.create(&job).await?;
+        info!("Enqueued {} job {:?}", job.kind, job.id);
+        Ok(JobDTO::try_from(job)?)
+    }
+
+    async fn get_jobs(&self, query: &JobQueryDTO) -> anyhow::Result<Vec<JobDTO>> {
+        let status = match query.status.as_deref() {
+            Some(status) => Some(status.parse::<JobStatus>()?),
+            None => None,
+        };
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_LIST_LIMIT)
+            .clamp(1, MAX_LIST_LIMIT);
+        let jobs = self
+            .job_repository
+            .find_recent(
+                status.as_ref().map(JobStatus::as_str),
+                query.kind.as_deref(),
+                limit,
+            )
+            .await?;
+        Ok(jobs
+            .into_iter()
+            .map(JobDTO::try_from)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn get_job(&self, dto: &JobDTO) -> anyhow::Result<JobDTO> {
+        Ok(JobDTO::try_from(self.find_job(dto).await?)?)
+    }
+
+    async fn cancel_job(&self, dto: &JobDTO) -> anyhow::Result<JobDTO> {
+        let job = self.find_job(dto).await?;
+        let id = job.id.unwrap_or_default();
+        // 任务可能在查询后刚好结束，以数据库的更新结果为准
+        if job.status().is_finished() || !self.job_repository.cancel(id).await? {
+            anyhow::bail!("Job {} is already finished", id);
+        }
+        self.get_job(dto).await
+    }
+
+    async fn recover(&self) -> anyhow::Result<u64> {
+        self.job_repository.requeue_expired().await
+    }
+
+    async fn run_next(&self) -> anyhow::Result<bool> {
+        let Some(job) = self.job_repository.claim_next(self.lease).await? else {
+            return Ok(false);
+        };
+        let id = job
+            .id
+            .ok_or_else(|| anyhow::anyhow!("Claimed job has no id"))?;
+
+        let Some(runner) = self.runners.get(job.kind.as_str()) else {
+            let error = format!("Unknown job kind: {}", job.kind);
+            self.job_repository.fail(id, &error, None).await?;
+            return Ok(true);
+        };
+
+        let Some(result) = self.execute(id, &job, runner.clone()).await else {
+            return Ok(true);
+        };
+        match result {
+            Ok(result) => {
+                self.job_repository.complete(id, result.as_ref()).await?;
+                info!("Job {} ({}) succeeded", id, job.kind);
+            }
+            Err(e) => {
+                let error = format!("{:#}", e);
+                let retry_at = (job.attempts < job.max_attempts)
+                    .then(|| OffsetDateTime::now_utc() + Self::retry_delay(job.attempts));
+                warn!(
+                    "Job {} ({}) failed on attempt {}/{}: {}",
+                    id, job.kind, job.attempts, job.max_attempts, error
+                );
+                self.job_repository.fail(id, &error, retry_at).await?;
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// 后台 worker 配置
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    pub workers: usize,
+    /// 没有任务时的轮询间隔
+    pub poll_interval: Duration,
+    /// 检查并重新排队租约过期任务的间隔
+    pub reclaim_interval: Duration,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            workers: 2,
+            poll_interval: Duration::from_millis(1000),
+            reclaim_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl WorkerConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let read = |key: &str| env::var(key).ok().and_then(|v| v.parse::<u64>().ok());
+
+        Self {
+            workers: read("JOB_WORKERS")
+                .map(|v| v as usize)
+                .unwrap_or(default.workers),
+            poll_interval: read("JOB_POLL_INTERVAL_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.poll_interval),
+            reclaim_interval: read("JOB_RECLAIM_INTERVAL_SECS")
+                .filter(|v| *v > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default.reclaim_interval),
+        }
+    }
+}
+
+/// 在当前 tokio 运行时中启动后台 worker，持续领取并执行任务，并定期恢复租约过期的任务
+pub fn spawn_job_workers(service: Arc<dyn JobService>, config: WorkerConfig) {
+    let reclaimer = service.clone();
+    let reclaim_interval = config.reclaim_interval;
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(reclaim_interval);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            match reclaimer.recover().await {
+                Ok(0) => {}
+                Ok(count) => info!("Requeued {} jobs with expired leases", count),
+                Err(e) => warn!("Failed to requeue jobs with expired leases: {}", e),
+            }
+        }
+    });
+
+    for worker in 0..config.workers {
+        let service = service.clone();
+        let poll_interval = config.poll_interval;
+        tokio::spawn(async move {
+            loop {
+                match service.run_next().await {
+                    Ok(true) => {}
+                    Ok(false) => tokio::time::sleep(poll_interval).await,
+                    Err(e) => {
+                        warn!("Job worker {} failed to run job: {}", worker, e);
+                        tokio::time::sleep(poll_interval).await;
+                    }
+                }
+            }
+        });
+    }
+    info!("Started {} job workers", config.workers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::{
+        self, FixedModelConfig, InMemoryJobRepository, InMemoryWordRepository, OfflineThirdParty,
+    };
+    use crate::domain::models::job::{CREATE_WORD_JOB, ENRICH_WORDS_JOB};
+    use crate::domain::models::word::Word;
+    use crate::domain::services::impls::word_jobs::{CreateWordJob, EnrichWordsJob};
+    use crate::domain::services::impls::word_service_impl::WordServiceImpl;
+    use crate::infrastructure::database::repositories::Repository;
+    use crate::infrastructure::dto::WordMeaning;
+
+    struct Fixture {
+        service: JobServiceImpl,
+        jobs: Arc<InMemoryJobRepository>,
+        words: Arc<InMemoryWordRepository>,
+    }
+
+    fn fixture(model: &str) -> Fixture {
+        testing::init_mock_llm(model);
+        let jobs = Arc::new(InMemoryJobRepository::default());
+        let words = Arc::new(InMemoryWordRepository::default());
+        let word_service = Arc::new(WordServiceImpl::new(
+            words.clone(),
+            FixedModelConfig::new(model),
+            Arc::new(OfflineThirdParty),
+        ));
+        let service = JobServiceImpl::new(
+            jobs.clone(),
+            vec![
+                Arc::new(CreateWordJob::new(word_service.clone())),
+                Arc::new(EnrichWordsJob::new(words.clone(), word_service)),
+            ],
+            Duration::from_secs(60),
+        );
+        Fixture {
+            service,
+            jobs,
+            words,
+        }
+    }
+
+    fn job_dto(kind: &str, payload: Value) -> JobDTO {
+        JobDTO {
+            kind: Some(kind.to_string()),
+            payload: Some(payload),
+            ..Default::default()
+        }
+    }
+
+    fn id_dto(id: Option<i32>) -> JobDTO {
+        JobDTO {
+            id,
+            ..Default::default()
+        }
+    }
+
+    /// 把等待重试的任务提前到现在执行
+    fn make_due(jobs: &InMemoryJobRepository, id: i32) {
+        let mut job = jobs.table.find(id).unwrap();
+        job.run_at = OffsetDateTime::now_utc();
+        jobs.table.upsert(&job);
+    }
+
+    /// 模拟执行任务的实例崩溃，租约不再续期而过期
+    fn expire_lease(jobs: &InMemoryJobRepository, id: i32) {
+        let mut job = jobs.table.find(id).unwrap();
+        job.locked_until = Some(OffsetDateTime::now_utc() - time::Duration::seconds(1));
+        jobs.table.upsert(&job);
+    }
+
+    /// 测试用的任务：panic 或者一直执行到被中止
+    struct MisbehavingJob {
+        kind: &'static str,
+    }
+
+    #[async_trait]
+    impl JobRunner for MisbehavingJob {
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+
+        async fn run(&self, _job: &Job, _context: &JobContext) -> anyhow::Result<Option<Value>> {
+            if self.kind == "panic" {
+                panic!("runner bug");
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(None)
+        }
+    }
+
+    fn misbehaving_service(jobs: Arc<InMemoryJobRepository>) -> JobServiceImpl {
+        JobServiceImpl::new(
+            jobs,
+            vec![
+                Arc::new(MisbehavingJob { kind: "panic" }),
+                Arc::new(MisbehavingJob { kind: "hang" }),
+            ],
+            Duration::from_millis(30),
+        )
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(JobServiceImpl::retry_delay(1), time::Duration::seconds(5));
+        assert_eq!(JobServiceImpl::retry_delay(3), time::Duration::seconds(20));
+        assert_eq!(
+            JobServiceImpl::retry_delay(30),
+            time::Duration::seconds(300)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_word_job() {
+        let fixture = fixture("mock");
+
+        let job = fixture
+            .service
+            .enqueue(&job_dto(CREATE_WORD_JOB, json!({"word": "hello"})))
+            .await
+            .unwrap();
+        assert_eq!(job.status.as_deref(), Some("pending"));
+        assert!(fixture.service.run_next().await.unwrap());
+        assert!(!fixture.service.run_next().await.unwrap());
+
+        let job = fixture.service.get_job(&id_dto(job.id)).await.unwrap();
+        assert_eq!(job.status.as_deref(), Some("succeeded"));
+        assert_eq!(job.attempts, Some(1));
+        assert_eq!((job.progress_done, job.progress_total), (Some(1), Some(1)));
+        assert_eq!(job.result.unwrap()["word"], "hello");
+        assert_eq!(fixture.words.table.all().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_validation() {
+        let fixture = fixture("mock");
+
+        let error = fixture
+            .service
+            .enqueue(&job_dto("unknown", json!({})))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Unknown job kind: unknown");
+
+        let mut dto = job_dto(ENRICH_WORDS_JOB, json!({}));
+        dto.max_attempts = Some(0);
+        assert!(fixture.service.enqueue(&dto).await.is_err());
+        assert!(fixture.jobs.table.all().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_is_retried_with_backoff() {
+        let fixture = fixture("mock-job-retry");
+
+        let mut dto = job_dto(CREATE_WORD_JOB, json!({"word": "unavailable"}));
+        dto.max_attempts = Some(2);
+        let id = fixture.service.enqueue(&dto).await.unwrap().id.unwrap();
+
+        assert!(fixture.service.run_next().await.unwrap());
+        let job = fixture.jobs.table.find(id).unwrap();
+        assert_eq!(job.status(), JobStatus::Pending);
+        assert_eq!(job.attempts, 1);
+        assert!(job.last_error.unwrap().contains("503"));
+        assert!(job.run_at > OffsetDateTime::now_utc() + time::Duration::seconds(3));
+        // 退避时间未到，不会被领取
+        assert!(!fixture.service.run_next().await.unwrap());
+
+        make_due(&fixture.jobs, id);
+        assert!(fixture.service.run_next().await.unwrap());
+        let job = fixture.jobs.table.find(id).unwrap();
+        assert_eq!(job.status(), JobStatus::Failed);
+        assert_eq!(job.attempts, 2);
+        assert!(job.finished_at.is_some());
+        assert!(fixture.words.table.all().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_enrich_words_resumes_from_checkpoint() {
+        let fixture = fixture("mock-job-enrich");
+        for word in ["hello", "unavailable"] {
+            fixture.words.save(&Word::new(word)).await.unwrap();
+        }
+
+        let id = fixture
+            .service
+            .enqueue(&job_dto(ENRICH_WORDS_JOB, json!({})))
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        assert!(fixture.service.run_next().await.unwrap());
+
+        let job = fixture.jobs.table.find(id).unwrap();
+        assert_eq!(job.status(), JobStatus::Pending);
+        assert!(job.last_error.unwrap().contains("unavailable"));
+        assert_eq!((job.progress_done, job.progress_total), (1, 2));
+        assert_eq!(
+            job.checkpoint,
+            Some(json!({"last_word_id": 2, "failed": [2]}))
+        );
+        let hello = fixture.words.table.find(1).unwrap();
+        assert_eq!(hello.phonetic_us.as_deref(), Some("/həˈloʊ/"));
+        assert!(hello.example.is_some());
+        assert!(hello.pronunciation_us.is_some());
+
+        // 重试时只处理上次失败的单词
+        let mut unavailable = fixture.words.table.find(2).unwrap();
+        unavailable.meanings = vec![WordMeaning {
+            pos: "n.".to_string(),
+            definition: "不可用".to_string(),
+            ..Default::default()
+        }];
+        unavailable.example = Some("example".to_string());
+        fixture.words.table.upsert(&unavailable);
+        let updated_at = hello.updated_at;
+        make_due(&fixture.jobs, id);
+        assert!(fixture.service.run_next().await.unwrap());
+
+        let job = fixture.jobs.table.find(id).unwrap();
+        assert_eq!(job.status(), JobStatus::Succeeded);
+        assert_eq!(job.result, Some(json!({"total": 2, "enriched": 2})));
+        assert_eq!(fixture.words.table.find(1).unwrap().updated_at, updated_at);
+        assert!(fixture
+            .words
+            .table
+            .find(2)
+            .unwrap()
+            .pronunciation_uk
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_recover() {
+        let fixture = fixture("mock");
+        let dto = job_dto(CREATE_WORD_JOB, json!({"word": "hello"}));
+
+        let cancelled = fixture.service.enqueue(&dto).await.unwrap();
+        let job = fixture.service.cancel_job(&cancelled).await.unwrap();
+        assert_eq!(job.status.as_deref(), Some("cancelled"));
+        let error = fixture.service.cancel_job(&cancelled).await.unwrap_err();
+        assert!(error.to_string().contains("already finished"));
+        assert!(!fixture.service.run_next().await.unwrap());
+
+        // 租约未过期的任务可能正由其他实例执行，不会被重新排队
+        let interrupted = fixture.service.enqueue(&dto).await.unwrap();
+        let lease = Duration::from_secs(60);
+        fixture.jobs.claim_next(lease).await.unwrap().unwrap();
+        assert_eq!(fixture.service.recover().await.unwrap(), 0);
+        // 模拟执行任务的实例崩溃
+        expire_lease(&fixture.jobs, interrupted.id.unwrap());
+        assert_eq!(fixture.service.recover().await.unwrap(), 1);
+        let job = fixture.service.get_job(&interrupted).await.unwrap();
+        assert_eq!(job.status.as_deref(), Some("pending"));
+        assert_eq!(job.attempts, Some(0));
+
+        let query = JobQueryDTO {
+            status: Some("cancelled".to_string()),
+            ..Default::default()
+        };
+        let jobs = fixture.service.get_jobs(&query).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].id, cancelled.id);
+        let query = JobQueryDTO {
+            status: Some("unknown".to_string()),
+            ..Default::default()
+        };
+        assert!(fixture.service.get_jobs(&query).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_is_retried() {
+        let jobs = Arc::new(InMemoryJobRepository::default());
+        let service = misbehaving_service(jobs.clone());
+
+        let id = service
+            .enqueue(&job_dto("panic", json!({})))
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+        assert!(service.run_next().await.unwrap());
+        let job = jobs.table.find(id).unwrap();
+        assert_eq!(job.status(), JobStatus::Pending);
+        assert!(job.last_error.unwrap().contains("panicked"));
+        assert!(job.locked_until.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_running_job_renews_lease_until_cancelled() {
+        let jobs = Arc::new(InMemoryJobRepository::default());
+        let service = Arc::new(misbehaving_service(jobs.clone()));
+
+        let dto = service.enqueue(&job_dto("hang", json!({}))).await.unwrap();
+        let id = dto.id.unwrap();
+        let worker = tokio::spawn({
+            let service = service.clone();
+            async move { service.run_next().await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        // 执行时间已超过租约时长，但一直在续租，不会被当作过期任务
+        let job = jobs.table.find(id).unwrap();
+        assert_eq!(job.status(), JobStatus::Running);
+        assert!(job.locked_until.unwrap() > OffsetDateTime::now_utc());
+        assert_eq!(service.recover().await.unwrap(), 0);
+
+        // 取消后在下次续租时中止执行
+        service.cancel_job(&dto).await.unwrap();
+        let finished = tokio::time::timeout(Duration::from_secs(1), worker).await;
+        assert!(finished.unwrap().unwrap().unwrap());
+        assert_eq!(jobs.table.find(id).unwrap().status(), JobStatus::Cancelled);
+    }
+}
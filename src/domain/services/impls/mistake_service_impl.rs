@@ -0,0 +1,132 @@
+use crate::api::dto::mistake_dto::{MistakeDTO, MistakeQueryDTO, MistakeReviewQueryDTO};
+use crate::domain::models::mistake::{Mistake, MistakeAnswer};
+use crate::domain::models::user::AuthUser;
+use crate::domain::services::interfaces::mistake_service::MistakeService;
+use crate::infrastructure::database::repositories::MistakeRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::env;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+/// 默认连续答对多少次后移出错题本
+const DEFAULT_CLEAR_STREAK: i32 = 3;
+/// 针对性复习默认返回的错题数和上限
+const DEFAULT_REVIEW_LIMIT: usize = 20;
+const MAX_REVIEW_LIMIT: usize = 100;
+
+/// 从 MISTAKE_CLEAR_STREAK 读取移出错题本需要的连续答对次数
+pub fn clear_streak_from_env() -> i32 {
+    env::var("MISTAKE_CLEAR_STREAK")
+        .ok()
+        .and_then(|value| value.parse::<i32>().ok())
+        .filter(|streak| *streak > 0)
+        .unwrap_or(DEFAULT_CLEAR_STREAK)
+}
+
+/// 按复习权重从高到低取前 limit 条，权重相同时最近答错的在前
+fn review_set(mut mistakes: Vec<Mistake>, now: OffsetDateTime, limit: usize) -> Vec<Mistake> {
+    mistakes.sort_by(|a, b| {
+        b.weight(now)
+            .total_cmp(&a.weight(now))
+            .then(b.last_wrong_at.cmp(&a.last_wrong_at))
+    });
+    mistakes.truncate(limit);
+    mistakes
+}
+
+pub struct MistakeServiceImpl {
+    mistake_repository: Arc<dyn MistakeRepository>,
+    clear_streak: i32,
+}
+
+impl MistakeServiceImpl {
+    pub fn new(mistake_repository: Arc<dyn MistakeRepository>, clear_streak: i32) -> Self {
+        Self {
+            mistake_repository,
+            clear_streak,
+        }
+    }
+}
+
+#[async_trait]
+impl MistakeService for MistakeServiceImpl {
+    async fn get_mistakes(
+        &self,
+        user: &AuthUser,
+        query: &MistakeQueryDTO,
+    ) -> Result<Vec<MistakeDTO>> {
+        let student_id = user.student_id(query.student_id)?;
+        let now = OffsetDateTime::now_utc();
+        self.mistake_repository
+            .find_by_student(student_id, query.error_type)
+            .await?
+            .into_iter()
+            .map(|mistake| MistakeDTO::new(mistake, now).map_err(Into::into))
+            .collect()
+    }
+
+    async fn get_review(
+        &self,
+        user: &AuthUser,
+        query: &MistakeReviewQueryDTO,
+    ) -> Result<Vec<MistakeDTO>> {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_REVIEW_LIMIT)
+            .clamp(1, MAX_REVIEW_LIMIT);
+        let now = OffsetDateTime::now_utc();
+        let mistakes = self
+            .mistake_repository
+            .find_by_student(user.id, None)
+            .await?;
+        review_set(mistakes, now, limit)
+            .into_iter()
+            .map(|mistake| MistakeDTO::new(mistake, now).map_err(Into::into))
+            .collect()
+    }
+
+    async fn record_answers(&self, student_id: i32, answers: &[MistakeAnswer]) -> Result<u64> {
+        self.mistake_repository
+            .record_answers(student_id, answers, self.clear_streak)
+            .await
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    #[test]
+    fn test_review_set() {
+        let now = OffsetDateTime::now_utc();
+        let mistake = |id: i32, wrong_count: i32, days_ago: i64| -> Mistake {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "student_id": 1,
+                "word_id": id,
+                "word": format!("word-{}", id),
+                "error_type": "spelling",
+                "wrong_count": wrong_count,
+                "correct_streak": 0,
+                "last_wrong_at": now - Duration::days(days_ago),
+            }))
+            .unwrap()
+        };
+        let mistakes = vec![
+            mistake(1, 1, 30),
+            mistake(2, 5, 7),
+            mistake(3, 1, 0),
+            mistake(4, 2, 0),
+        ];
+
+        let ids: Vec<i32> = review_set(mistakes.clone(), now, 10)
+            .iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, vec![2, 4, 3, 1]);
+        assert_eq!(review_set(mistakes, now, 2).len(), 2);
+    }
+}
@@ -1,9 +1,20 @@
+pub(crate) mod dictation_service_impl;
+pub(crate) mod export_service_impl;
 pub mod grade_service_impl;
+pub(crate) mod job_service_impl;
+pub(crate) mod mistake_service_impl;
 pub(crate) mod model_provider_service_impl;
+pub(crate) mod prompt_template_service_impl;
+pub(crate) mod quiz_generator;
+pub(crate) mod quiz_service_impl;
 pub mod semester_service_impl;
+pub(crate) mod study_service_impl;
 pub(crate) mod system_config_service_impl;
 pub mod textbook_service_impl;
 pub mod textbook_version_service_impl;
+pub(crate) mod trash_service_impl;
 pub mod unit_service_impl;
+pub(crate) mod user_service_impl;
+pub(crate) mod word_jobs;
 pub mod word_service_impl;
 pub mod word_unit_service_impl;
@@ -1,22 +1,541 @@
+use crate::api::dto::llm_usage_dto::{LLMUsageQueryDTO, LLMUsageReportDTO, LLMUsageRowDTO};
+use crate::api::dto::model_provider_dto::ModelProviderDTO;
+use crate::common::utils::crypto;
+use crate::domain::models::llm_usage::{LLMUsage, LLMUsageSummary};
 use crate::domain::models::model_provider::ModelProvider;
 use crate::domain::services::interfaces::model_provider_service::ModelProviderService;
 use crate::infrastructure::database::repositories::model_provider_repository::ModelProviderRepository;
+use crate::infrastructure::database::repositories::LLMUsageRepository;
+use crate::infrastructure::llm;
+use crate::infrastructure::llm::factory::LLMServiceFactory;
+use crate::infrastructure::llm::manager::LLMHealthReport;
+use crate::infrastructure::llm::provider::{LLMConfig, LLMProvider};
+use crate::infrastructure::llm::structured::StructuredAttempt;
+use crate::infrastructure::llm::usage::{ModelPricing, UsageRecord};
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use sqlx::types::Json;
 use std::sync::Arc;
+use time::{Date, Duration, OffsetDateTime};
+use tracing::{info, warn};
+
+/// 用量报表未指定开始日期时的默认天数
+const DEFAULT_USAGE_REPORT_DAYS: i64 = 30;
+/// 健康报告中展示的最近结构化输出尝试数量
+const RECENT_ATTEMPT_LIMIT: i64 = 100;
 
 pub struct ModelProviderServiceImpl {
     repository: Arc<dyn ModelProviderRepository>,
+    usage_repository: Arc<dyn LLMUsageRepository>,
 }
 
 impl ModelProviderServiceImpl {
-    pub fn new(repository: Arc<dyn ModelProviderRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn ModelProviderRepository>,
+        usage_repository: Arc<dyn LLMUsageRepository>,
+    ) -> Self {
+        Self {
+            repository,
+            usage_repository,
+        }
+    }
+
+    async fn find_required(&self, dto: &ModelProviderDTO) -> Result<ModelProvider> {
+        let id = dto
+            .provider_id
+            .ok_or_else(|| anyhow!("provider_id is required"))?;
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("No model provider found with id: {}", id))
+    }
+
+    /// 将请求中的字段合并到实体上，未提供的字段保持原值，可选字段传 null 时清除
+    fn apply_dto(provider: &mut ModelProvider, dto: &ModelProviderDTO) -> Result<()> {
+        if let Some(name) = &dto.provider_name {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(anyhow!("provider_name must not be empty"));
+            }
+            provider.provider_name = Some(name.to_string());
+        }
+        if let Some(value) = &dto.provider_type {
+            provider.provider_type = value.clone();
+        }
+        if let Some(value) = &dto.api_base_url {
+            provider.api_base_url = value.clone();
+        }
+        if dto.api_key_required.is_some() {
+            provider.api_key_required = dto.api_key_required;
+        }
+        if let Some(value) = &dto.model_name {
+            provider.model_name = value.clone();
+        }
+        if let Some(value) = &dto.model_types {
+            provider.model_types = value.clone();
+        }
+        if let Some(value) = dto.timeout_secs {
+            provider.timeout_secs = value;
+        }
+        if let Some(value) = dto.temperature {
+            provider.temperature = value;
+        }
+        if let Some(value) = dto.top_p {
+            provider.top_p = value;
+        }
+        if let Some(value) = dto.max_tokens {
+            provider.max_tokens = value;
+        }
+        if let Some(value) = dto.prompt_price_per_1k {
+            provider.prompt_price_per_1k = value;
+        }
+        if let Some(value) = dto.completion_price_per_1k {
+            provider.completion_price_per_1k = value;
+        }
+        if let Some(value) = &dto.model_prices {
+            provider.model_prices = value.clone().map(Json);
+        }
+        if dto.is_active.is_some() {
+            provider.is_active = dto.is_active;
+        }
+        // 空字符串表示清除已保存的 key
+        if let Some(api_key) = &dto.api_key {
+            provider.api_key_encrypted = match api_key.trim() {
+                "" => None,
+                key => Some(crypto::encrypt_secret(key)?),
+            };
+        }
+
+        // 提前校验，避免保存无法使用的配置
+        Self::provider_type(provider)?;
+        Ok(())
+    }
+
+    /// 厂商类型未配置时按名称推断，例如名称为 deepseek 的服务
+    fn provider_type(provider: &ModelProvider) -> Result<LLMProvider> {
+        provider
+            .provider_type
+            .as_deref()
+            .or(provider.provider_name.as_deref())
+            .ok_or_else(|| anyhow!("provider_type is required"))?
+            .parse()
+    }
+
+    fn to_llm_config(provider: &ModelProvider) -> Result<LLMConfig> {
+        let mut config = LLMConfig::new(Self::provider_type(provider)?);
+        if let Some(base_url) = &provider.api_base_url {
+            config.with_base_url(base_url);
+        }
+        if let Some(model_name) = &provider.model_name {
+            config.with_model(model_name);
+        }
+        if let Some(encrypted) = &provider.api_key_encrypted {
+            config.with_api_key(crypto::decrypt_secret(encrypted)?);
+        }
+        if let Some(timeout) = provider.timeout_secs {
+            config.with_timeout(timeout.max(1) as u64);
+        }
+        if let Some(temperature) = provider.temperature {
+            config.with_temperature(temperature);
+        }
+        if let Some(top_p) = provider.top_p {
+            config.with_top_p(top_p);
+        }
+        if let Some(max_tokens) = provider.max_tokens {
+            config.with_max_tokens(max_tokens.max(0) as u32);
+        }
+        Ok(config)
+    }
+
+    /// 只有启用且配置了模型的服务才会接管，旧数据仍使用环境变量配置
+    fn is_configured(provider: &ModelProvider) -> bool {
+        provider.is_active.unwrap_or(false) && provider.model_name.is_some()
+    }
+
+    /// 同步单个服务到 LLM 工厂，停用或未配置的服务会被移除
+    fn register(provider: &ModelProvider) -> Result<()> {
+        let name = match &provider.provider_name {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+        if Self::is_configured(provider) {
+            LLMServiceFactory::register_config(name, Self::to_llm_config(provider)?);
+        } else {
+            LLMServiceFactory::unregister_config(name);
+        }
+        Ok(())
+    }
+
+    /// 优先使用模型的价格，没有时使用服务的价格；数据库中配置的价格优先于环境变量
+    fn pricing(provider: Option<&ModelProvider>, name: &str, model: &str) -> Option<ModelPricing> {
+        provider
+            .and_then(|provider| provider.model_prices.as_ref())
+            .and_then(|prices| prices.get(model).copied())
+            .or_else(|| ModelPricing::from_env_for_model(name, model))
+            .or_else(|| Self::provider_pricing(provider, name))
+    }
+
+    /// 服务的价格，数据库中配置的价格优先，否则读取 LLM_{NAME}_*_PRICE_PER_1K 环境变量
+    fn provider_pricing(provider: Option<&ModelProvider>, name: &str) -> Option<ModelPricing> {
+        match provider {
+            Some(provider)
+                if provider.prompt_price_per_1k.is_some()
+                    || provider.completion_price_per_1k.is_some() =>
+            {
+                Some(ModelPricing {
+                    prompt_per_1k: provider.prompt_price_per_1k.unwrap_or_default(),
+                    completion_per_1k: provider.completion_price_per_1k.unwrap_or_default(),
+                })
+            }
+            _ => ModelPricing::from_env(name),
+        }
+    }
+
+    /// 查询范围默认为最近 30 天（含今天）
+    fn usage_range(query: &LLMUsageQueryDTO) -> Result<(Date, Date)> {
+        let to = match query.to.as_deref() {
+            Some(to) => LLMUsageQueryDTO::parse_date(to)?,
+            None => OffsetDateTime::now_utc().date(),
+        };
+        let from = match query.from.as_deref() {
+            Some(from) => LLMUsageQueryDTO::parse_date(from)?,
+            None => to - Duration::days(DEFAULT_USAGE_REPORT_DAYS - 1),
+        };
+        if from > to {
+            return Err(anyhow!("from must not be later than to"));
+        }
+        Ok((from, to))
+    }
+
+    fn build_usage_report(
+        from: Date,
+        to: Date,
+        summaries: Vec<LLMUsageSummary>,
+        pricing: impl Fn(&str, &str) -> Option<ModelPricing>,
+    ) -> Result<LLMUsageReportDTO> {
+        let mut report = LLMUsageReportDTO {
+            from: LLMUsageQueryDTO::format_date(from)?,
+            to: LLMUsageQueryDTO::format_date(to)?,
+            calls: 0,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+            estimated_cost: 0.0,
+            rows: Vec::with_capacity(summaries.len()),
+        };
+
+        for summary in summaries {
+            let mut row = LLMUsageRowDTO::try_from(summary)?;
+            row.estimated_cost = pricing(&row.provider, &row.model)
+                .map(|price| price.estimate(row.prompt_tokens, row.completion_tokens));
+
+            report.calls += row.calls;
+            report.prompt_tokens += row.prompt_tokens;
+            report.completion_tokens += row.completion_tokens;
+            report.total_tokens += row.total_tokens;
+            report.estimated_cost += row.estimated_cost.unwrap_or_default();
+            report.rows.push(row);
+        }
+        Ok(report)
+    }
+
+    fn to_dto(provider: ModelProvider) -> Result<ModelProviderDTO> {
+        let api_key_masked = match &provider.api_key_encrypted {
+            Some(encrypted) => match crypto::decrypt_secret(encrypted) {
+                Ok(key) => Some(crypto::mask_secret(&key)),
+                Err(e) => {
+                    warn!(
+                        "Failed to decrypt api key of provider {:?}: {}",
+                        provider.provider_name, e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+        let mut dto = ModelProviderDTO::try_from(provider)?;
+        dto.api_key_masked = api_key_masked;
+        Ok(dto)
     }
 }
 
 #[async_trait]
 impl ModelProviderService for ModelProviderServiceImpl {
-    async fn get_all_providers(&self) -> anyhow::Result<Vec<ModelProvider>> {
-        self.repository.find_all().await
+    async fn get_all_providers(&self) -> anyhow::Result<Vec<ModelProviderDTO>> {
+        let providers = self.repository.find_all().await?;
+        providers.into_iter().map(Self::to_dto).collect()
+    }
+
+    async fn get_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<ModelProviderDTO> {
+        Self::to_dto(self.find_required(dto).await?)
+    }
+
+    async fn create_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<ModelProviderDTO> {
+        let name = dto
+            .provider_name
+            .as_deref()
+            .ok_or_else(|| anyhow!("provider_name is required"))?;
+        if self.repository.find_by_name(name.trim()).await?.is_some() {
+            return Err(anyhow!("Model provider '{}' already exists", name.trim()));
+        }
+
+        let mut provider = ModelProvider::new(
+            name.to_string(),
+            None,
+            dto.api_key.is_some(),
+            None,
+            true,
+        );
+        Self::apply_dto(&mut provider, dto)?;
+
+        let saved = self.repository.save(&provider).await?;
+        Self::register(&saved)?;
+        Self::to_dto(saved)
+    }
+
+    async fn update_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<ModelProviderDTO> {
+        let mut provider = self.find_required(dto).await?;
+        let old_name = provider.provider_name.clone();
+        Self::apply_dto(&mut provider, dto)?;
+
+        if let (Some(old_name), Some(new_name)) = (&old_name, &provider.provider_name) {
+            if !old_name.eq_ignore_ascii_case(new_name) {
+                if self.repository.find_by_name(new_name).await?.is_some() {
+                    return Err(anyhow!("Model provider '{}' already exists", new_name));
+                }
+                LLMServiceFactory::unregister_config(old_name);
+            }
+        }
+
+        let saved = self.repository.save(&provider).await?;
+        Self::register(&saved)?;
+        Self::to_dto(saved)
+    }
+
+    async fn delete_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<()> {
+        let provider = self.find_required(dto).await?;
+        self.repository
+            .delete(provider.provider_id.unwrap_or_default())
+            .await?;
+        if let Some(name) = &provider.provider_name {
+            LLMServiceFactory::unregister_config(name);
+        }
+        Ok(())
+    }
+
+    async fn sync_llm_configs(&self) -> anyhow::Result<usize> {
+        let providers = self.repository.find_all().await?;
+        let mut loaded = 0;
+        for provider in providers.iter() {
+            match Self::register(provider) {
+                Ok(()) if Self::is_configured(provider) => loaded += 1,
+                Ok(()) => {}
+                Err(e) => warn!(
+                    "Skip model provider {:?} with invalid config: {}",
+                    provider.provider_name, e
+                ),
+            }
+        }
+        info!("Loaded {} model provider configs from database", loaded);
+        Ok(loaded)
+    }
+
+    async fn get_health(&self) -> anyhow::Result<LLMHealthReport> {
+        let attempts = self
+            .usage_repository
+            .recent_attempts(RECENT_ATTEMPT_LIMIT)
+            .await?;
+        Ok(llm::get_llm_manager().health_report(attempts))
+    }
+
+    async fn record_usage(&self, record: UsageRecord) -> anyhow::Result<()> {
+        self.usage_repository.save(&LLMUsage::from(record)).await?;
+        Ok(())
+    }
+
+    async fn record_structured_attempt(&self, attempt: StructuredAttempt) -> anyhow::Result<()> {
+        self.usage_repository.save_attempt(&attempt).await
+    }
+
+    async fn get_usage_report(
+        &self,
+        query: &LLMUsageQueryDTO,
+    ) -> anyhow::Result<LLMUsageReportDTO> {
+        let (from, to) = Self::usage_range(query)?;
+        let summaries = self.usage_repository.summarize(from, to).await?;
+        let providers = self.repository.find_all().await?;
+
+        Self::build_usage_report(from, to, summaries, |name, model| {
+            let provider = providers.iter().find(|provider| {
+                provider
+                    .provider_name
+                    .as_deref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            });
+            Self::pricing(provider, name, model)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use time::Month;
+
+    fn date(day: u8) -> Date {
+        Date::from_calendar_date(2026, Month::October, day).unwrap()
+    }
+
+    fn summary(
+        day: u8,
+        provider: &str,
+        prompt_tokens: i64,
+        completion_tokens: i64,
+    ) -> LLMUsageSummary {
+        LLMUsageSummary {
+            day: date(day),
+            provider: provider.to_string(),
+            model: format!("{}-model", provider),
+            operation: "get_word_info".to_string(),
+            calls: 2,
+            prompt_tokens,
+            completion_tokens,
+            avg_latency_ms: 800.0,
+        }
+    }
+
+    #[test]
+    fn test_usage_range() {
+        let query = LLMUsageQueryDTO {
+            from: Some("2026-10-01".to_string()),
+            to: Some("2026-10-18".to_string()),
+        };
+        assert_eq!(
+            ModelProviderServiceImpl::usage_range(&query).unwrap(),
+            (date(1), date(18))
+        );
+
+        let query = LLMUsageQueryDTO {
+            from: None,
+            to: Some("2026-10-30".to_string()),
+        };
+        assert_eq!(
+            ModelProviderServiceImpl::usage_range(&query).unwrap(),
+            (date(1), date(30))
+        );
+
+        let reversed = LLMUsageQueryDTO {
+            from: Some("2026-10-18".to_string()),
+            to: Some("2026-10-01".to_string()),
+        };
+        assert!(ModelProviderServiceImpl::usage_range(&reversed).is_err());
+        let invalid = LLMUsageQueryDTO {
+            from: Some("18/10/2026".to_string()),
+            to: None,
+        };
+        assert!(ModelProviderServiceImpl::usage_range(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_apply_dto_clears_null_fields() {
+        let mut provider = ModelProvider::new("qwen".to_string(), None, true, None, true);
+        provider.provider_type = Some("openai-compatible".to_string());
+        let update =
+            |json: serde_json::Value| -> ModelProviderDTO { serde_json::from_value(json).unwrap() };
+
+        let dto = update(serde_json::json!({
+            "api_base_url": "http://localhost:8000/v1",
+            "prompt_price_per_1k": 0.002,
+        }));
+        ModelProviderServiceImpl::apply_dto(&mut provider, &dto).unwrap();
+        assert_eq!(
+            provider.api_base_url.as_deref(),
+            Some("http://localhost:8000/v1")
+        );
+        assert_eq!(provider.prompt_price_per_1k, Some(0.002));
+
+        // 缺省的字段不修改，传 null 的字段被清除
+        let dto = update(serde_json::json!({"prompt_price_per_1k": null}));
+        ModelProviderServiceImpl::apply_dto(&mut provider, &dto).unwrap();
+        assert_eq!(provider.prompt_price_per_1k, None);
+        assert!(provider.api_base_url.is_some());
+        let dto = update(serde_json::json!({"api_base_url": null}));
+        ModelProviderServiceImpl::apply_dto(&mut provider, &dto).unwrap();
+        assert_eq!(provider.api_base_url, None);
+        assert_eq!(provider.provider_type.as_deref(), Some("openai-compatible"));
+    }
+
+    #[test]
+    fn test_pricing_prefers_database() {
+        let mut provider = ModelProvider::new("qwen".to_string(), None, true, None, true);
+        assert_eq!(
+            ModelProviderServiceImpl::pricing(Some(&provider), "qwen", "qwen-plus"),
+            None
+        );
+
+        provider.completion_price_per_1k = Some(0.006);
+        assert_eq!(
+            ModelProviderServiceImpl::pricing(Some(&provider), "qwen", "qwen-plus"),
+            Some(ModelPricing {
+                prompt_per_1k: 0.0,
+                completion_per_1k: 0.006,
+            })
+        );
+    }
+
+    #[test]
+    fn test_pricing_prefers_model_price() {
+        let mut provider = ModelProvider::new("deepseek".to_string(), None, true, None, true);
+        provider.prompt_price_per_1k = Some(0.002);
+        provider.completion_price_per_1k = Some(0.003);
+        let reasoner = ModelPricing {
+            prompt_per_1k: 0.004,
+            completion_per_1k: 0.016,
+        };
+        provider.model_prices = Some(Json(HashMap::from([(
+            "deepseek-reasoner".to_string(),
+            reasoner,
+        )])));
+
+        let pricing = |model| ModelProviderServiceImpl::pricing(Some(&provider), "deepseek", model);
+        assert_eq!(pricing("deepseek-reasoner"), Some(reasoner));
+        assert_eq!(
+            pricing("deepseek-chat"),
+            Some(ModelPricing {
+                prompt_per_1k: 0.002,
+                completion_per_1k: 0.003,
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_usage_report() {
+        let summaries = vec![
+            summary(18, "deepseek", 3000, 1000),
+            summary(17, "yi", 500, 500),
+        ];
+        let report = ModelProviderServiceImpl::build_usage_report(
+            date(1),
+            date(18),
+            summaries,
+            |name, model| {
+                (name == "deepseek" && model == "deepseek-model").then_some(ModelPricing {
+                    prompt_per_1k: 0.001,
+                    completion_per_1k: 0.002,
+                })
+            },
+        )
+        .unwrap();
+
+        assert_eq!(report.from, "2026-10-01");
+        assert_eq!(report.to, "2026-10-18");
+        assert_eq!(report.calls, 4);
+        assert_eq!(report.total_tokens, 5000);
+        assert_eq!(report.rows[0].day, "2026-10-18");
+        assert_eq!(report.rows[0].total_tokens, 4000);
+        assert!((report.rows[0].estimated_cost.unwrap() - 0.005).abs() < 1e-9);
+        assert_eq!(report.rows[1].estimated_cost, None);
+        assert!((report.estimated_cost - 0.005).abs() < 1e-9);
     }
 }
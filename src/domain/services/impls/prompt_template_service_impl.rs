@@ -0,0 +1,303 @@
+use crate::api::dto::prompt_template_dto::{PromptPreviewDTO, PromptTemplateDTO};
+use crate::domain::models::prompt_template::PromptTemplate;
+use crate::domain::services::interfaces::prompt_template_service::PromptTemplateService;
+use crate::infrastructure::database::repositories::PromptTemplateRepository;
+use crate::infrastructure::llm::prompts::registry::{self, template_variables};
+use crate::infrastructure::llm::prompts::{
+    prompt_registry, LanguagePrompts, PromptRegistry, RenderedPrompt, BUILTIN_VERSION,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::env;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::info;
+
+pub struct PromptTemplateServiceImpl {
+    repository: Arc<dyn PromptTemplateRepository>,
+    registry: &'static PromptRegistry,
+}
+
+impl PromptTemplateServiceImpl {
+    pub fn new(repository: Arc<dyn PromptTemplateRepository>) -> Self {
+        Self::with_registry(repository, prompt_registry())
+    }
+
+    pub fn with_registry(
+        repository: Arc<dyn PromptTemplateRepository>,
+        registry: &'static PromptRegistry,
+    ) -> Self {
+        Self {
+            repository,
+            registry,
+        }
+    }
+
+    fn required_name(dto: &PromptTemplateDTO) -> Result<&str> {
+        dto.name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| anyhow!("name is required"))
+    }
+
+    /// 只允许为已知模板新增版本，且只能使用该模板支持的变量
+    fn validate(&self, template: &PromptTemplate) -> Result<()> {
+        if !self.registry.contains(&template.name) {
+            return Err(anyhow!("Unknown prompt template: {}", template.name));
+        }
+        if template.system_prompt.trim().is_empty() || template.user_prompt.trim().is_empty() {
+            return Err(anyhow!("system_prompt and user_prompt must not be empty"));
+        }
+
+        let allowed = LanguagePrompts::variables(&template.name);
+        let mut variables = template_variables(&template.system_prompt);
+        variables.extend(template_variables(&template.user_prompt));
+        if let Some(unknown) = variables.iter().find(|v| !allowed.contains(&v.as_str())) {
+            return Err(anyhow!(
+                "Unsupported variable {{{}}} in {}, available: {}",
+                unknown,
+                template.name,
+                allowed.join(", ")
+            ));
+        }
+        if !template_variables(&template.user_prompt).contains(&"word".to_string()) {
+            return Err(anyhow!("user_prompt must contain {{word}}"));
+        }
+        Ok(())
+    }
+
+    /// 把模板目录中数据库还没有的版本导入数据库，导入的版本默认不启用
+    async fn import_dir(&self, dir: &Path) -> Result<usize> {
+        let mut imported = 0;
+        for mut template in registry::load_dir(dir)? {
+            if template.version <= BUILTIN_VERSION {
+                return Err(anyhow!(
+                    "Prompt template {} in {} must have a version greater than {}",
+                    template.name,
+                    dir.display(),
+                    BUILTIN_VERSION
+                ));
+            }
+            self.validate(&template)?;
+            if self
+                .repository
+                .find_by_name_version(&template.name, template.version)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            template.is_active = false;
+            self.repository.save(&template).await?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    fn to_dto(template: PromptTemplate) -> Result<PromptTemplateDTO> {
+        Ok(PromptTemplateDTO::try_from(template)?)
+    }
+}
+
+#[async_trait]
+impl PromptTemplateService for PromptTemplateServiceImpl {
+    async fn sync_templates(&self) -> anyhow::Result<usize> {
+        if let Ok(dir) = env::var("PROMPT_TEMPLATE_DIR") {
+            let imported = self.import_dir(Path::new(&dir)).await?;
+            info!("Imported {} prompt templates from {}", imported, dir);
+        }
+
+        let templates = self.repository.find_all().await?;
+        let loaded = templates.len();
+        self.registry.load(templates);
+        info!("Loaded {} prompt templates from database", loaded);
+        Ok(loaded)
+    }
+
+    async fn get_templates(&self) -> anyhow::Result<Vec<PromptTemplateDTO>> {
+        self.registry.list().into_iter().map(Self::to_dto).collect()
+    }
+
+    async fn create_template(&self, dto: &PromptTemplateDTO) -> anyhow::Result<PromptTemplateDTO> {
+        let name = Self::required_name(dto)?;
+        let version = self.repository.max_version(name).await?.unwrap_or_default() + 1;
+
+        let mut template = PromptTemplate::new(
+            name,
+            version,
+            dto.system_prompt.as_deref().unwrap_or_default(),
+            dto.user_prompt.as_deref().unwrap_or_default(),
+        );
+        template.description = dto.description.clone();
+        self.validate(&template)?;
+
+        let saved = self.repository.save(&template).await?;
+        self.registry.upsert(saved.clone());
+        Self::to_dto(saved)
+    }
+
+    async fn preview_template(&self, dto: &PromptPreviewDTO) -> anyhow::Result<RenderedPrompt> {
+        let vars: Vec<(&str, &str)> = dto
+            .variables
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        self.registry
+            .render_version(dto.name.trim(), dto.version, &vars)
+    }
+
+    async fn activate_template(
+        &self,
+        dto: &PromptTemplateDTO,
+    ) -> anyhow::Result<PromptTemplateDTO> {
+        let name = Self::required_name(dto)?;
+        let version = dto.version.ok_or_else(|| anyhow!("version is required"))?;
+        // 先确认版本存在，避免数据库和注册表不一致
+        let mut template = self.registry.get(name, Some(version))?.as_ref().clone();
+
+        let stored = (version != BUILTIN_VERSION).then_some(version);
+        self.repository.activate(name, stored).await?;
+        self.registry.activate(name, version)?;
+
+        template.is_active = true;
+        Self::to_dto(template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::InMemoryPromptTemplateRepository;
+    use crate::infrastructure::llm::prompts::{EXAMPLE_SENTENCES_PROMPT, WORD_INFO_PROMPT};
+    use std::collections::HashMap;
+
+    fn service() -> (
+        PromptTemplateServiceImpl,
+        Arc<InMemoryPromptTemplateRepository>,
+    ) {
+        let repository = Arc::new(InMemoryPromptTemplateRepository::default());
+        // 测试使用独立的注册表，避免影响其他测试使用的全局模板
+        let registry = Box::leak(Box::new(PromptRegistry::new()));
+        let service = PromptTemplateServiceImpl::with_registry(repository.clone(), registry);
+        (service, repository)
+    }
+
+    fn template_dto(name: &str, user_prompt: &str) -> PromptTemplateDTO {
+        PromptTemplateDTO {
+            name: Some(name.to_string()),
+            system_prompt: Some("You are a friendly teacher.".to_string()),
+            user_prompt: Some(user_prompt.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_preview_and_activate() {
+        let (service, repository) = service();
+
+        let created = service
+            .create_template(&template_dto(
+                EXAMPLE_SENTENCES_PROMPT,
+                "Write {count} easy sentences with \"{word}\".",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(created.version, Some(1));
+        assert_eq!(created.is_active, Some(false));
+        assert_eq!(
+            created.variables,
+            Some(vec!["count".to_string(), "word".to_string()])
+        );
+
+        let preview = service
+            .preview_template(&PromptPreviewDTO {
+                name: EXAMPLE_SENTENCES_PROMPT.to_string(),
+                version: Some(1),
+                variables: HashMap::from([
+                    ("word".to_string(), "happy".to_string()),
+                    ("count".to_string(), "3".to_string()),
+                ]),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            preview.user_prompt,
+            "Write 3 easy sentences with \"happy\"."
+        );
+
+        service
+            .activate_template(&PromptTemplateDTO {
+                name: Some(EXAMPLE_SENTENCES_PROMPT.to_string()),
+                version: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            service.registry.active_version(EXAMPLE_SENTENCES_PROMPT),
+            Some(1)
+        );
+        assert!(repository.table.find(1).unwrap().is_active);
+
+        // 版本 0 恢复内置模板
+        service
+            .activate_template(&PromptTemplateDTO {
+                name: Some(EXAMPLE_SENTENCES_PROMPT.to_string()),
+                version: Some(BUILTIN_VERSION),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            service.registry.active_version(EXAMPLE_SENTENCES_PROMPT),
+            Some(BUILTIN_VERSION)
+        );
+        assert!(!repository.table.find(1).unwrap().is_active);
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_template() {
+        let (service, _) = service();
+
+        let unknown = service
+            .create_template(&template_dto("greeting", "Say hi to {word}"))
+            .await;
+        assert!(unknown.is_err());
+
+        let error = service
+            .create_template(&template_dto(
+                WORD_INFO_PROMPT,
+                "Describe {word} for {count}",
+            ))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("{count}"), "{}", error);
+
+        let error = service
+            .create_template(&template_dto(WORD_INFO_PROMPT, "Describe the word"))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("{word}"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_sync_loads_active_versions() {
+        let (service, repository) = service();
+        let mut template = PromptTemplate::new(WORD_INFO_PROMPT, 3, "system", "word {word}");
+        template.is_active = true;
+        repository.save(&template).await.unwrap();
+
+        assert_eq!(service.sync_templates().await.unwrap(), 1);
+        assert_eq!(service.registry.active_version(WORD_INFO_PROMPT), Some(3));
+
+        let templates = service.get_templates().await.unwrap();
+        let word_info: Vec<_> = templates
+            .iter()
+            .filter(|t| t.name.as_deref() == Some(WORD_INFO_PROMPT))
+            .collect();
+        assert_eq!(word_info.len(), 2);
+        assert_eq!(word_info[0].builtin, Some(true));
+        assert_eq!(word_info[1].is_active, Some(true));
+    }
+}
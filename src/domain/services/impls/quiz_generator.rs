@@ -0,0 +1,339 @@
+//! 根据单元单词生成测验题目，相同的单词、题型、数量和种子总是生成相同的题目
+
+use crate::api::dto::unit_word_dto::WordDTO;
+use crate::domain::models::quiz::{QuestionKind, QuizQuestion};
+use crate::infrastructure::dto::WordMeaning;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// 选择题的选项数
+const OPTION_COUNT: usize = 4;
+/// 填空题中替换单词的占位符
+const BLANK: &str = "____";
+
+/// 释义显示为一行，如 “n. 苹果；v. 吃”
+fn meaning_text(meanings: &[WordMeaning]) -> Option<String> {
+    let parts: Vec<String> = meanings
+        .iter()
+        .map(|m| format!("{} {}", m.pos.trim(), m.definition.trim()))
+        .map(|part| part.trim().to_string())
+        .filter(|part| !part.is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("；"))
+}
+
+fn phonetic(word: &WordDTO) -> Option<&str> {
+    [&word.phonetic_us, &word.phonetic_uk]
+        .into_iter()
+        .filter_map(|phonetic| phonetic.as_deref())
+        .map(str::trim)
+        .find(|phonetic| !phonetic.is_empty())
+}
+
+/// 把一句话中出现的所有单词替换为占位符，忽略大小写，单词前后不能紧接字母或数字
+fn blank_line(line: &str, word: &str) -> Option<String> {
+    let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+    let matches_at = |start: usize| {
+        let end = start + word.len();
+        line.is_char_boundary(start)
+            && line
+                .get(start..end)
+                .is_some_and(|found| found.eq_ignore_ascii_case(word))
+            && !is_word_char(line[..start].chars().next_back())
+            && !is_word_char(line[end..].chars().next())
+    };
+
+    let mut blanked = String::with_capacity(line.len());
+    let mut copied = 0;
+    let mut start = 0;
+    while start < line.len() {
+        if matches_at(start) {
+            blanked.push_str(&line[copied..start]);
+            blanked.push_str(BLANK);
+            start += word.len();
+            copied = start;
+        } else {
+            start += 1;
+        }
+    }
+    if copied == 0 {
+        return None;
+    }
+    blanked.push_str(&line[copied..]);
+    Some(blanked)
+}
+
+/// 例句按 “英文\n中文\n” 交替排列，取第一句含有该单词的英文句子，挖掉其中所有的单词作为题目，
+/// 中文翻译和其他例句不出现在题目中
+pub(crate) fn blank_out(example: &str, word: &str) -> Option<String> {
+    let word = word.trim();
+    if word.is_empty() {
+        return None;
+    }
+    example
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .step_by(2)
+        .find_map(|line| blank_line(line, word))
+}
+
+struct Generator<'a> {
+    words: Vec<&'a WordDTO>,
+    extra: &'a [WordDTO],
+    rng: ChaCha8Rng,
+}
+
+impl<'a> Generator<'a> {
+    /// 干扰项依次取自同一单元、所选的其他单元和同一年级的其他单元，去掉与答案相同的值
+    fn distractors(
+        &mut self,
+        target: &WordDTO,
+        answer: &str,
+        value: impl Fn(&WordDTO) -> Option<String>,
+    ) -> Vec<String> {
+        let (mut same_unit, mut other_units): (Vec<&WordDTO>, Vec<&WordDTO>) = self
+            .words
+            .iter()
+            .copied()
+            .filter(|word| word.word_id != target.word_id)
+            .partition(|word| word.unit_id == target.unit_id);
+        let mut grade: Vec<&WordDTO> = self.extra.iter().collect();
+        same_unit.shuffle(&mut self.rng);
+        other_units.shuffle(&mut self.rng);
+        grade.shuffle(&mut self.rng);
+
+        let mut options: Vec<String> = Vec::new();
+        for word in same_unit.into_iter().chain(other_units).chain(grade) {
+            if options.len() == OPTION_COUNT - 1 {
+                break;
+            }
+            if let Some(option) = value(word) {
+                if option != answer && !options.contains(&option) {
+                    options.push(option);
+                }
+            }
+        }
+        options
+    }
+
+    fn choice(
+        &mut self,
+        target: &WordDTO,
+        prompt: String,
+        answer: String,
+        value: impl Fn(&WordDTO) -> Option<String>,
+    ) -> Option<(String, Vec<String>, String)> {
+        let mut options = self.distractors(target, &answer, value);
+        if options.is_empty() {
+            return None;
+        }
+        options.push(answer.clone());
+        options.shuffle(&mut self.rng);
+        Some((prompt, options, answer))
+    }
+
+    fn question(
+        &mut self,
+        kind: QuestionKind,
+        target: &WordDTO,
+    ) -> Option<(String, Vec<String>, String)> {
+        let word = target.word.as_deref()?.trim().to_string();
+        match kind {
+            QuestionKind::MeaningChoice => {
+                let meaning = meaning_text(&target.meanings)?;
+                self.choice(target, word, meaning, |other| meaning_text(&other.meanings))
+            }
+            QuestionKind::Spelling => {
+                let meaning = meaning_text(&target.meanings)?;
+                Some((meaning, Vec::new(), word))
+            }
+            QuestionKind::PhoneticChoice => {
+                let prompt = phonetic(target)?.to_string();
+                self.choice(target, prompt, word, |other| {
+                    other.word.as_deref().map(|word| word.trim().to_string())
+                })
+            }
+            QuestionKind::FillBlank => {
+                let prompt = blank_out(target.example.as_deref()?, &word)?;
+                Some((prompt, Vec::new(), word))
+            }
+        }
+    }
+}
+
+/// 从 words 中随机抽取最多 count 个单词出题，题型按 kinds 轮换，单词缺少出题所需的释义、
+/// 音标或例句时改用下一个题型，都不满足时跳过；extra 为同一年级其他单元的单词，只用作干扰项
+pub(crate) fn generate(
+    words: &[WordDTO],
+    extra: &[WordDTO],
+    kinds: &[QuestionKind],
+    count: usize,
+    seed: u64,
+) -> Vec<QuizQuestion> {
+    let kinds = if kinds.is_empty() {
+        &QuestionKind::ALL[..]
+    } else {
+        kinds
+    };
+    // 同一单词出现在多个单元时只出一道题
+    let mut unique: Vec<&WordDTO> = Vec::new();
+    for word in words.iter().filter(|word| word.word_id.is_some()) {
+        if !unique.iter().any(|other| other.word_id == word.word_id) {
+            unique.push(word);
+        }
+    }
+    let mut generator = Generator {
+        words: unique.clone(),
+        extra,
+        rng: ChaCha8Rng::seed_from_u64(seed),
+    };
+    unique.shuffle(&mut generator.rng);
+
+    let mut questions = Vec::new();
+    for target in unique {
+        if questions.len() == count {
+            break;
+        }
+        let start = questions.len();
+        let built = (0..kinds.len())
+            .map(|offset| kinds[(start + offset) % kinds.len()])
+            .find_map(|kind| {
+                generator
+                    .question(kind, target)
+                    .map(|question| (kind, question))
+            });
+        if let Some((kind, (prompt, options, answer))) = built {
+            questions.push(QuizQuestion {
+                index: questions.len(),
+                kind,
+                word_id: target.word_id.unwrap_or_default(),
+                unit_id: target.unit_id,
+                prompt,
+                options,
+                answer,
+            });
+        }
+    }
+    questions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(word_id: i32, unit_id: i32, word: &str, meaning: &str, example: &str) -> WordDTO {
+        serde_json::from_value(serde_json::json!({
+            "word_id": word_id,
+            "unit_id": unit_id,
+            "word": word,
+            "meanings": [{"pos": "n.", "definition": meaning}],
+            "example": example,
+            "phonetic_us": format!("/{}/", word),
+        }))
+        .unwrap()
+    }
+
+    fn words() -> Vec<WordDTO> {
+        vec![
+            word(1, 1, "apple", "苹果", "I eat an apple every day."),
+            word(2, 1, "pear", "梨", "This pear is sweet."),
+            word(3, 1, "plum", "李子", ""),
+            word(4, 2, "peach", "桃", "Peaches are soft."),
+            word(5, 2, "grape", "葡萄", "Grapes grow on vines."),
+        ]
+    }
+
+    #[test]
+    fn test_same_seed_same_quiz() {
+        let words = words();
+        let first = generate(&words, &[], &[], 5, 42);
+        assert_eq!(first, generate(&words, &[], &[], 5, 42));
+        assert_ne!(first, generate(&words, &[], &[], 5, 43));
+        assert_eq!(first.len(), 5);
+        assert_eq!(generate(&words, &[], &[], 3, 42).len(), 3);
+    }
+
+    #[test]
+    fn test_question_kinds() {
+        let words = words();
+        let quiz = generate(&words, &[], &[QuestionKind::MeaningChoice], 5, 7);
+        for question in &quiz {
+            assert_eq!(question.kind, QuestionKind::MeaningChoice);
+            assert_eq!(question.options.len(), OPTION_COUNT);
+            assert!(question.options.contains(&question.answer));
+        }
+
+        // 只有挖得出单词的例句才能出填空题，没有其他题型时跳过其余单词
+        let quiz = generate(&words, &[], &[QuestionKind::FillBlank], 5, 7);
+        let mut fill: Vec<_> = quiz
+            .iter()
+            .map(|question| question.prompt.as_str())
+            .collect();
+        fill.sort();
+        assert_eq!(
+            fill,
+            vec!["I eat an ____ every day.", "This ____ is sweet."]
+        );
+
+        // 题型轮换，出不了填空题的单词改出拼写题
+        let quiz = generate(
+            &words,
+            &[],
+            &[QuestionKind::FillBlank, QuestionKind::Spelling],
+            5,
+            7,
+        );
+        assert_eq!(quiz.len(), 5);
+        assert!(quiz
+            .iter()
+            .all(|question| question.kind != QuestionKind::FillBlank
+                || question.prompt.contains(BLANK)));
+        assert!(quiz
+            .iter()
+            .any(|question| question.kind == QuestionKind::Spelling));
+    }
+
+    #[test]
+    fn test_distractors_from_grade() {
+        let single = vec![word(1, 1, "apple", "苹果", "")];
+        assert!(generate(&single, &[], &[QuestionKind::PhoneticChoice], 1, 1).is_empty());
+
+        let grade = vec![word(9, 3, "melon", "瓜", "")];
+        let quiz = generate(&single, &grade, &[QuestionKind::PhoneticChoice], 1, 1);
+        assert_eq!(quiz[0].prompt, "/apple/");
+        let mut options = quiz[0].options.clone();
+        options.sort();
+        assert_eq!(options, vec!["apple", "melon"]);
+    }
+
+    #[test]
+    fn test_blank_out() {
+        assert_eq!(
+            blank_out("Apples and an Apple.", "apple").as_deref(),
+            Some("Apples and an ____.")
+        );
+        assert_eq!(
+            blank_out("Please get up early.", "get up").as_deref(),
+            Some("Please ____ early.")
+        );
+        assert_eq!(blank_out("Pineapple", "apple"), None);
+    }
+
+    #[test]
+    fn test_blank_out_stored_example() {
+        // 与模型生成并保存的例句格式相同：两组英文和中文交替
+        let example = "I like apples.\n我喜欢苹果。\nAn apple a day, an Apple for me.\n一天一个苹果，给我一个苹果。\n";
+        assert_eq!(
+            blank_out(example, "apple").as_deref(),
+            Some("An ____ a day, an ____ for me.")
+        );
+        assert_eq!(
+            blank_out(example, "like").as_deref(),
+            Some("I ____ apples.")
+        );
+        assert_eq!(blank_out("我喜欢 apple。\n", "pear"), None);
+        assert_eq!(blank_out("Pears.\napple 梨\n", "apple"), None);
+    }
+}
@@ -0,0 +1,438 @@
+use crate::api::dto::quiz_dto::{
+    AssignQuizDTO, GenerateQuizDTO, QuizDTO, QuizResultDTO, SubmitQuizDTO,
+};
+use crate::api::dto::study_dto::AssignResultDTO;
+use crate::domain::models::quiz::{Quiz, QuizAttempt};
+use crate::domain::models::unit::Unit;
+use crate::domain::models::user::{AuthUser, Role};
+use crate::domain::services::impls::quiz_generator;
+use crate::domain::services::interfaces::mistake_service::MistakeService;
+use crate::domain::services::interfaces::quiz_service::QuizService;
+use crate::infrastructure::database::repositories::{
+    QuizRepository, UnitRepository, UserRepository, WordUnitMappingRepository,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::info;
+
+/// 每次测验默认的题目数和上限
+const DEFAULT_QUESTION_COUNT: usize = 20;
+const MAX_QUESTION_COUNT: usize = 100;
+/// 从同一年级其他单元最多取多少个单词作干扰项
+const GRADE_WORD_LIMIT: i64 = 200;
+
+/// 教材中序号在 from 到 to 之间的单元，为空表示不限
+fn units_in_range(units: &[Unit], from: Option<i32>, to: Option<i32>) -> Vec<i32> {
+    units
+        .iter()
+        .filter(|unit| {
+            let sequence = unit.sequence_number.unwrap_or_default();
+            from.is_none_or(|from| sequence >= from) && to.is_none_or(|to| sequence <= to)
+        })
+        .filter_map(|unit| unit.id)
+        .collect()
+}
+
+pub struct QuizServiceImpl {
+    quiz_repository: Arc<dyn QuizRepository>,
+    word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
+    unit_repository: Arc<dyn UnitRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    mistake_service: Arc<dyn MistakeService>,
+}
+
+impl QuizServiceImpl {
+    pub fn new(
+        quiz_repository: Arc<dyn QuizRepository>,
+        word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
+        unit_repository: Arc<dyn UnitRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        mistake_service: Arc<dyn MistakeService>,
+    ) -> Self {
+        Self {
+            quiz_repository,
+            word_unit_mapping_repository,
+            unit_repository,
+            user_repository,
+            mistake_service,
+        }
+    }
+
+    async fn resolve_units(&self, dto: &GenerateQuizDTO) -> Result<Vec<i32>> {
+        if let Some(unit_id) = dto.unit_id {
+            self.unit_repository
+                .find_by_id(unit_id)
+                .await?
+                .ok_or_else(|| anyhow!("Unit {} not found", unit_id))?;
+            return Ok(vec![unit_id]);
+        }
+        let textbook_id = dto
+            .textbook_id
+            .ok_or_else(|| anyhow!("unit_id or textbook_id is required"))?;
+        let units = self
+            .unit_repository
+            .find_by_textbook_id(Some(textbook_id))
+            .await?;
+        let unit_ids = units_in_range(&units, dto.from_unit, dto.to_unit);
+        if unit_ids.is_empty() {
+            return Err(anyhow!(
+                "Textbook {} has no units in the selected range",
+                textbook_id
+            ));
+        }
+        Ok(unit_ids)
+    }
+
+    /// 创建者、教师及以上角色和被布置的学生可以查看和作答测验
+    async fn find_quiz(&self, user: &AuthUser, id: i32) -> Result<Quiz> {
+        let quiz = self
+            .quiz_repository
+            .find_quiz(id)
+            .await?
+            .ok_or_else(|| anyhow!("Quiz {} not found", id))?;
+        if !user.can_access(quiz.created_by)
+            && !self.quiz_repository.is_quiz_assigned(id, user.id).await?
+        {
+            return Err(anyhow!("Not allowed to access quiz {}", id));
+        }
+        Ok(quiz)
+    }
+}
+
+#[async_trait]
+impl QuizService for QuizServiceImpl {
+    async fn generate(&self, user: &AuthUser, dto: &GenerateQuizDTO) -> Result<QuizDTO> {
+        let unit_ids = self.resolve_units(dto).await?;
+        let mut words = Vec::new();
+        for &unit_id in &unit_ids {
+            words.extend(
+                self.word_unit_mapping_repository
+                    .find_word_dto_by_unit_id(unit_id)
+                    .await?,
+            );
+        }
+        let extra = self
+            .quiz_repository
+            .find_grade_words(&unit_ids, GRADE_WORD_LIMIT)
+            .await?;
+
+        let count = dto
+            .count
+            .unwrap_or(DEFAULT_QUESTION_COUNT)
+            .clamp(1, MAX_QUESTION_COUNT);
+        // 未指定种子时随机生成，返回给客户端以便重现
+        let seed = dto.seed.unwrap_or_else(|| rand::random::<u32>() as i64);
+        let kinds = dto.kinds.clone().unwrap_or_default();
+        let questions = quiz_generator::generate(&words, &extra, &kinds, count, seed as u64);
+        if questions.is_empty() {
+            return Err(anyhow!(
+                "No questions could be generated from the selected units"
+            ));
+        }
+
+        let quiz = self
+            .quiz_repository
+            .save_quiz(&Quiz {
+                id: None,
+                unit_ids,
+                seed,
+                questions,
+                created_by: Some(user.id),
+                created_at: None,
+            })
+            .await?;
+        info!(
+            "{} generated quiz {:?} with {} questions",
+            user.username,
+            quiz.id,
+            quiz.questions.len()
+        );
+        Ok(QuizDTO::try_from(quiz)?)
+    }
+
+    async fn get_quiz(&self, user: &AuthUser, id: i32) -> Result<QuizDTO> {
+        let quiz = self.find_quiz(user, id).await?;
+        Ok(QuizDTO::try_from(quiz)?)
+    }
+
+    async fn assign(&self, teacher: &AuthUser, dto: &AssignQuizDTO) -> Result<AssignResultDTO> {
+        self.find_quiz(teacher, dto.quiz_id).await?;
+        if dto.student_ids.is_empty() {
+            return Err(anyhow!("student_ids must not be empty"));
+        }
+        for &student_id in &dto.student_ids {
+            let student = self
+                .user_repository
+                .find_by_id(student_id)
+                .await?
+                .ok_or_else(|| anyhow!("User {} not found", student_id))?;
+            if student.disabled || student.role.parse::<Role>()? != Role::Student {
+                return Err(anyhow!(
+                    "User {} is not an active student",
+                    student.username
+                ));
+            }
+        }
+
+        let assigned = self
+            .quiz_repository
+            .assign_quiz(dto.quiz_id, &dto.student_ids, Some(teacher.id))
+            .await?;
+        info!(
+            "{} assigned quiz {} to {} students",
+            teacher.username, dto.quiz_id, assigned
+        );
+        Ok(AssignResultDTO { assigned })
+    }
+
+    async fn submit(&self, user: &AuthUser, dto: &SubmitQuizDTO) -> Result<QuizResultDTO> {
+        let quiz = self.find_quiz(user, dto.quiz_id).await?;
+        let answers: Vec<(usize, String)> = dto
+            .answers
+            .iter()
+            .map(|answer| (answer.index, answer.answer.clone()))
+            .collect();
+        let attempt = QuizAttempt::grade(dto.quiz_id, user.id, &quiz.questions, &answers);
+        // 已交过卷时不再判分，避免看过答案后重复提交清除错题
+        let attempt = self
+            .quiz_repository
+            .save_attempt(&attempt)
+            .await?
+            .ok_or_else(|| anyhow!("Quiz {} has already been submitted", dto.quiz_id))?;
+        self.mistake_service
+            .record_answers(user.id, &attempt.answers(&quiz.questions))
+            .await?;
+        Ok(QuizResultDTO::try_from(attempt)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::dto::mistake_dto::{MistakeDTO, MistakeQueryDTO, MistakeReviewQueryDTO};
+    use crate::api::dto::quiz_dto::QuizAnswerDTO;
+    use crate::api::dto::unit_word_dto::WordDTO;
+    use crate::app::testing::{
+        InMemoryUnitRepository, InMemoryUserRepository, InMemoryWordRepository,
+        InMemoryWordUnitMappingRepository,
+    };
+    use crate::domain::models::mistake::MistakeAnswer;
+    use crate::domain::models::quiz::{QuestionKind, QuizQuestion};
+    use crate::domain::models::user::User;
+    use crate::infrastructure::database::repositories::Repository;
+    use std::sync::Mutex;
+
+    /// 只保存测验、交卷记录和布置关系，不出题
+    #[derive(Default)]
+    struct FakeQuizzes {
+        quizzes: Mutex<Vec<Quiz>>,
+        attempts: Mutex<Vec<QuizAttempt>>,
+        assignments: Mutex<Vec<(i32, i32)>>,
+    }
+
+    #[async_trait]
+    impl QuizRepository for FakeQuizzes {
+        async fn find_grade_words(&self, _unit_ids: &[i32], _limit: i64) -> Result<Vec<WordDTO>> {
+            Ok(Vec::new())
+        }
+
+        async fn save_quiz(&self, quiz: &Quiz) -> Result<Quiz> {
+            let mut quizzes = self.quizzes.lock().unwrap();
+            let saved = Quiz {
+                id: Some(quizzes.len() as i32 + 1),
+                ..quiz.clone()
+            };
+            quizzes.push(saved.clone());
+            Ok(saved)
+        }
+
+        async fn find_quiz(&self, id: i32) -> Result<Option<Quiz>> {
+            let quizzes = self.quizzes.lock().unwrap();
+            Ok(quizzes.iter().find(|quiz| quiz.id == Some(id)).cloned())
+        }
+
+        async fn save_attempt(&self, attempt: &QuizAttempt) -> Result<Option<QuizAttempt>> {
+            let mut attempts = self.attempts.lock().unwrap();
+            if attempts
+                .iter()
+                .any(|a| a.quiz_id == attempt.quiz_id && a.user_id == attempt.user_id)
+            {
+                return Ok(None);
+            }
+            attempts.push(attempt.clone());
+            Ok(Some(attempt.clone()))
+        }
+
+        async fn assign_quiz(
+            &self,
+            quiz_id: i32,
+            student_ids: &[i32],
+            _assigned_by: Option<i32>,
+        ) -> Result<u64> {
+            let mut assignments = self.assignments.lock().unwrap();
+            let mut assigned = 0;
+            for &student_id in student_ids {
+                if !assignments.contains(&(quiz_id, student_id)) {
+                    assignments.push((quiz_id, student_id));
+                    assigned += 1;
+                }
+            }
+            Ok(assigned)
+        }
+
+        async fn is_quiz_assigned(&self, quiz_id: i32, student_id: i32) -> Result<bool> {
+            Ok(self
+                .assignments
+                .lock()
+                .unwrap()
+                .contains(&(quiz_id, student_id)))
+        }
+    }
+
+    /// 不记录错题
+    struct NoMistakes;
+
+    #[async_trait]
+    impl MistakeService for NoMistakes {
+        async fn get_mistakes(
+            &self,
+            _user: &AuthUser,
+            _query: &MistakeQueryDTO,
+        ) -> Result<Vec<MistakeDTO>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_review(
+            &self,
+            _user: &AuthUser,
+            _query: &MistakeReviewQueryDTO,
+        ) -> Result<Vec<MistakeDTO>> {
+            Ok(Vec::new())
+        }
+
+        async fn record_answers(
+            &self,
+            _student_id: i32,
+            _answers: &[MistakeAnswer],
+        ) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    fn auth(user: &User) -> AuthUser {
+        AuthUser::try_from(user).unwrap()
+    }
+
+    fn unit(id: i32, sequence_number: i32) -> Unit {
+        Unit {
+            id: Some(id),
+            sequence_number: Some(sequence_number),
+            ..Unit::new()
+        }
+    }
+
+    #[test]
+    fn test_units_in_range() {
+        let units = vec![unit(11, 1), unit(12, 2), unit(13, 3), unit(14, 4)];
+        assert_eq!(units_in_range(&units, Some(2), Some(3)), vec![12, 13]);
+        assert_eq!(units_in_range(&units, None, Some(2)), vec![11, 12]);
+        assert_eq!(units_in_range(&units, Some(4), None), vec![14]);
+        assert!(units_in_range(&units, Some(5), None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_assigned_student_answers_teacher_quiz() {
+        let users = Arc::new(InMemoryUserRepository::default());
+        let mut accounts = Vec::new();
+        for (username, role) in [
+            ("teacher", Role::Teacher),
+            ("student", Role::Student),
+            ("other", Role::Student),
+        ] {
+            let user = users
+                .save(&User::new(username, String::new(), role))
+                .await
+                .unwrap();
+            accounts.push(auth(&user));
+        }
+        let (teacher, student, other) = (&accounts[0], &accounts[1], &accounts[2]);
+
+        let quizzes = Arc::new(FakeQuizzes::default());
+        let service = QuizServiceImpl::new(
+            quizzes.clone(),
+            Arc::new(InMemoryWordUnitMappingRepository::new(Arc::new(
+                InMemoryWordRepository::default(),
+            ))),
+            Arc::new(InMemoryUnitRepository::default()),
+            users,
+            Arc::new(NoMistakes),
+        );
+        let quiz_id = quizzes
+            .save_quiz(&Quiz {
+                id: None,
+                unit_ids: vec![1],
+                seed: 42,
+                questions: vec![QuizQuestion {
+                    index: 0,
+                    kind: QuestionKind::Spelling,
+                    word_id: 10,
+                    unit_id: Some(1),
+                    prompt: "n. 苹果".to_string(),
+                    options: Vec::new(),
+                    answer: "apple".to_string(),
+                }],
+                created_by: Some(teacher.id),
+                created_at: None,
+            })
+            .await
+            .unwrap()
+            .id
+            .unwrap();
+
+        // 布置前学生不能查看教师创建的测验，也不能布置给教师
+        assert!(service.get_quiz(student, quiz_id).await.is_err());
+        let error = service
+            .assign(
+                teacher,
+                &AssignQuizDTO {
+                    quiz_id,
+                    student_ids: vec![teacher.id],
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("not an active student"),
+            "{}",
+            error
+        );
+
+        let dto = AssignQuizDTO {
+            quiz_id,
+            student_ids: vec![student.id],
+        };
+        assert_eq!(service.assign(teacher, &dto).await.unwrap().assigned, 1);
+        assert_eq!(service.assign(teacher, &dto).await.unwrap().assigned, 0);
+
+        let quiz = service.get_quiz(student, quiz_id).await.unwrap();
+        assert_eq!(quiz.questions.len(), 1);
+        let result = service
+            .submit(
+                student,
+                &SubmitQuizDTO {
+                    quiz_id,
+                    answers: vec![QuizAnswerDTO {
+                        index: 0,
+                        answer: "Apple".to_string(),
+                    }],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!((result.correct, result.total), (1, 1));
+
+        // 没有被布置的学生仍然不能查看和作答
+        assert!(service.get_quiz(other, quiz_id).await.is_err());
+    }
+}
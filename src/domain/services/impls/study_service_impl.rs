@@ -0,0 +1,336 @@
+use crate::api::dto::study_dto::{
+    AssignResultDTO, AssignUnitDTO, ReviewAnswerDTO, ReviewItemDTO, ReviewQueueQueryDTO,
+    ReviewStateDTO, StudentQueryDTO, UnassignUnitDTO, UnitAssignmentDTO, UnitMasteryDTO,
+};
+use crate::domain::models::review::{ReviewState, MASTERED_INTERVAL_DAYS};
+use crate::domain::models::user::{AuthUser, Role};
+use crate::domain::services::interfaces::study_service::StudyService;
+use crate::infrastructure::database::repositories::{
+    StudyRepository, UnitRepository, UserRepository,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::env;
+use std::sync::Arc;
+use time::{Duration, OffsetDateTime, Time, UtcOffset};
+use tracing::info;
+
+/// 复习队列默认最多返回的单词数和上限
+const DEFAULT_QUEUE_LIMIT: i64 = 100;
+const MAX_QUEUE_LIMIT: i64 = 500;
+/// 每天默认最多学习的新词数
+const DEFAULT_NEW_LIMIT: i64 = 20;
+/// 默认按北京时间划分每天
+const DEFAULT_UTC_OFFSET_MINUTES: i32 = 8 * 60;
+
+/// 与 UTC 相差的分钟数转换为时区偏移，超出 ±18 小时时报错
+fn utc_offset(minutes: i32) -> Result<UtcOffset> {
+    if minutes.abs() > 18 * 60 {
+        return Err(anyhow!("Invalid UTC offset: {} minutes", minutes));
+    }
+    Ok(UtcOffset::from_whole_seconds(minutes * 60)?)
+}
+
+/// 从 STUDY_UTC_OFFSET_MINUTES 读取划分每天使用的时区
+pub fn utc_offset_from_env() -> UtcOffset {
+    env::var("STUDY_UTC_OFFSET_MINUTES")
+        .ok()
+        .and_then(|value| value.parse::<i32>().ok())
+        .and_then(|minutes| utc_offset(minutes).ok())
+        .unwrap_or_else(|| utc_offset(DEFAULT_UTC_OFFSET_MINUTES).unwrap())
+}
+
+/// 指定时区中今天的开始时间
+fn start_of_day(now: OffsetDateTime, offset: UtcOffset) -> OffsetDateTime {
+    now.to_offset(offset).replace_time(Time::MIDNIGHT)
+}
+
+pub struct StudyServiceImpl {
+    study_repository: Arc<dyn StudyRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    unit_repository: Arc<dyn UnitRepository>,
+    utc_offset: UtcOffset,
+}
+
+impl StudyServiceImpl {
+    pub fn new(
+        study_repository: Arc<dyn StudyRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        unit_repository: Arc<dyn UnitRepository>,
+    ) -> Self {
+        Self {
+            study_repository,
+            user_repository,
+            unit_repository,
+            utc_offset: utc_offset_from_env(),
+        }
+    }
+}
+
+#[async_trait]
+impl StudyService for StudyServiceImpl {
+    async fn assign_unit(
+        &self,
+        teacher: &AuthUser,
+        dto: &AssignUnitDTO,
+    ) -> Result<AssignResultDTO> {
+        self.unit_repository
+            .find_by_id(dto.unit_id)
+            .await?
+            .ok_or_else(|| anyhow!("Unit {} not found", dto.unit_id))?;
+        if dto.student_ids.is_empty() {
+            return Err(anyhow!("student_ids must not be empty"));
+        }
+        for &student_id in &dto.student_ids {
+            let student = self
+                .user_repository
+                .find_by_id(student_id)
+                .await?
+                .ok_or_else(|| anyhow!("User {} not found", student_id))?;
+            if student.disabled || student.role.parse::<Role>()? != Role::Student {
+                return Err(anyhow!(
+                    "User {} is not an active student",
+                    student.username
+                ));
+            }
+        }
+
+        let assigned = self
+            .study_repository
+            .assign_unit(dto.unit_id, &dto.student_ids, Some(teacher.id))
+            .await?;
+        info!(
+            "{} assigned unit {} to {} students",
+            teacher.username, dto.unit_id, assigned
+        );
+        Ok(AssignResultDTO { assigned })
+    }
+
+    async fn unassign_unit(&self, dto: &UnassignUnitDTO) -> Result<()> {
+        if !self
+            .study_repository
+            .unassign_unit(dto.unit_id, dto.student_id)
+            .await?
+        {
+            return Err(anyhow!(
+                "Unit {} is not assigned to user {}",
+                dto.unit_id,
+                dto.student_id
+            ));
+        }
+        Ok(())
+    }
+
+    async fn get_assignments(
+        &self,
+        user: &AuthUser,
+        query: &StudentQueryDTO,
+    ) -> Result<Vec<UnitAssignmentDTO>> {
+        let student_id = user.student_id(query.student_id)?;
+        self.study_repository
+            .find_assignments(student_id)
+            .await?
+            .into_iter()
+            .map(|assignment| UnitAssignmentDTO::try_from(assignment).map_err(Into::into))
+            .collect()
+    }
+
+    async fn get_review_queue(
+        &self,
+        user: &AuthUser,
+        query: &ReviewQueueQueryDTO,
+    ) -> Result<Vec<ReviewItemDTO>> {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_QUEUE_LIMIT)
+            .clamp(1, MAX_QUEUE_LIMIT);
+        let offset = match query.utc_offset_minutes {
+            Some(minutes) => utc_offset(minutes)?,
+            None => self.utc_offset,
+        };
+        let today = start_of_day(OffsetDateTime::now_utc(), offset);
+        let learned_today = self
+            .study_repository
+            .count_new_since(user.id, today)
+            .await?;
+        let new_limit = (query.new_limit.unwrap_or(DEFAULT_NEW_LIMIT) - learned_today).max(0);
+
+        self.study_repository
+            .find_review_queue(user.id, today + Duration::days(1), limit, new_limit)
+            .await?
+            .into_iter()
+            .map(|item| ReviewItemDTO::try_from(item).map_err(Into::into))
+            .collect()
+    }
+
+    async fn answer(&self, user: &AuthUser, dto: &ReviewAnswerDTO) -> Result<ReviewStateDTO> {
+        if !self
+            .study_repository
+            .is_word_assigned(user.id, dto.word_id)
+            .await?
+        {
+            return Err(anyhow!(
+                "Word {} is not in any unit assigned to you",
+                dto.word_id
+            ));
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let mut state = self
+            .study_repository
+            .find_state(user.id, dto.word_id)
+            .await?
+            .unwrap_or_else(|| ReviewState::new(user.id, dto.word_id, now));
+        state.review(dto.quality, now)?;
+        let state = self
+            .study_repository
+            .save_review(&state, dto.quality)
+            .await?;
+        Ok(ReviewStateDTO::try_from(state)?)
+    }
+
+    async fn get_mastery(
+        &self,
+        user: &AuthUser,
+        query: &StudentQueryDTO,
+    ) -> Result<Vec<UnitMasteryDTO>> {
+        let student_id = user.student_id(query.student_id)?;
+        Ok(self
+            .study_repository
+            .find_mastery(student_id, MASTERED_INTERVAL_DAYS)
+            .await?
+            .into_iter()
+            .map(UnitMasteryDTO::from)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::{
+        InMemoryStudyRepository, InMemoryUnitRepository, InMemoryUserRepository,
+    };
+
+    fn student() -> AuthUser {
+        AuthUser {
+            id: 7,
+            username: "student".to_string(),
+            role: Role::Student,
+        }
+    }
+
+    fn service(study: Arc<InMemoryStudyRepository>) -> StudyServiceImpl {
+        StudyServiceImpl::new(
+            study,
+            Arc::new(InMemoryUserRepository::default()),
+            Arc::new(InMemoryUnitRepository::default()),
+        )
+    }
+
+    #[test]
+    fn test_start_of_day() {
+        // 2023-11-14 22:13:20 UTC，北京时间已经是 11 月 15 日
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        assert_eq!(
+            start_of_day(now, utc_offset(480).unwrap()).unix_timestamp(),
+            1_699_977_600
+        );
+        assert_eq!(
+            start_of_day(now, UtcOffset::UTC).unix_timestamp(),
+            1_699_920_000
+        );
+        assert!(utc_offset(19 * 60).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_answer_rejects_unassigned_word() {
+        let study = Arc::new(InMemoryStudyRepository::default());
+        study.add_unit(1, &[10, 11]);
+        study.add_unit(2, &[20]);
+        study.assign_unit(1, &[7], None).await.unwrap();
+        let service = service(study.clone());
+
+        let error = service
+            .answer(
+                &student(),
+                &ReviewAnswerDTO {
+                    word_id: 20,
+                    quality: 4,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("not in any unit"), "{}", error);
+        assert!(study.find_state(7, 20).await.unwrap().is_none());
+
+        let state = service
+            .answer(
+                &student(),
+                &ReviewAnswerDTO {
+                    word_id: 10,
+                    quality: 4,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(state.repetitions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_review_queue_new_word_limit() {
+        let study = Arc::new(InMemoryStudyRepository::default());
+        study.add_unit(1, &[10, 11, 12, 13, 14, 15]);
+        study.assign_unit(1, &[7], None).await.unwrap();
+        let service = service(study.clone());
+        let now = OffsetDateTime::now_utc();
+        let offset = utc_offset(480).unwrap();
+        let today = start_of_day(now, offset);
+        // 今天已经学了一个新词，昨天学的不计入今天的数量
+        let mut learned = ReviewState::new(7, 10, now);
+        learned.due_at = now + Duration::days(1);
+        study.add_state(learned, today + Duration::minutes(1));
+        let mut yesterday = ReviewState::new(7, 11, now);
+        yesterday.due_at = now + Duration::days(1);
+        study.add_state(yesterday, today - Duration::minutes(1));
+
+        let queue = service
+            .get_review_queue(
+                &student(),
+                &ReviewQueueQueryDTO {
+                    limit: None,
+                    new_limit: Some(3),
+                    utc_offset_minutes: Some(480),
+                },
+            )
+            .await
+            .unwrap();
+        let ids: Vec<i32> = queue.iter().map(|item| item.word_id).collect();
+        assert_eq!(ids, vec![12, 13]);
+
+        // 今天的新词已经学满时只返回到期的复习
+        let queue = service
+            .get_review_queue(
+                &student(),
+                &ReviewQueueQueryDTO {
+                    limit: None,
+                    new_limit: Some(1),
+                    utc_offset_minutes: Some(480),
+                },
+            )
+            .await
+            .unwrap();
+        assert!(queue.is_empty());
+
+        assert!(service
+            .get_review_queue(
+                &student(),
+                &ReviewQueueQueryDTO {
+                    utc_offset_minutes: Some(24 * 60),
+                    ..Default::default()
+                },
+            )
+            .await
+            .is_err());
+    }
+}
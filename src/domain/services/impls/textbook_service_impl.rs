@@ -1,14 +1,21 @@
-use crate::api::dto::textbook_dto::TextbookDTO;
+use crate::api::dto::textbook_dto::{
+    MovedWordDTO, RecountReportDTO, TextbookCloneDTO, TextbookDTO, TextbookDiffDTO,
+    TextbookDiffQueryDTO, UnitDiffDTO,
+};
 use crate::api::dto::unit_dto::UnitDTO;
+use crate::api::dto::unit_word_dto::WordDTO;
 use crate::domain::models::textbook::Textbook;
-use anyhow::Result;
+use crate::domain::models::unit::Unit;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
+use tracing::warn;
 
 use crate::domain::services::interfaces::textbook_service::TextbookService;
 use crate::infrastructure::database::repositories::{
     GradeRepository, SemesterRepository, TextbookRepository, TextbookVersionRepository,
-    UnitRepository,
+    UnitRepository, WordUnitMappingRepository,
 };
 
 pub struct TextbookServiceImpl {
@@ -17,6 +24,7 @@ pub struct TextbookServiceImpl {
     grade_repository: Arc<dyn GradeRepository>,
     semester_repository: Arc<dyn SemesterRepository>,
     unit_repository: Arc<dyn UnitRepository>,
+    word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
 }
 
 impl TextbookServiceImpl {
@@ -26,6 +34,7 @@ impl TextbookServiceImpl {
         grade_repository: Arc<dyn GradeRepository>,
         semester_repository: Arc<dyn SemesterRepository>,
         unit_repository: Arc<dyn UnitRepository>,
+        word_unit_mapping_repository: Arc<dyn WordUnitMappingRepository>,
     ) -> Self {
         Self {
             repository,
@@ -33,8 +42,144 @@ impl TextbookServiceImpl {
             grade_repository,
             semester_repository,
             unit_repository,
+            word_unit_mapping_repository,
+        }
+    }
+
+    async fn find_textbook(&self, id: i32) -> Result<Textbook> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("Textbook not found: {}", id))
+    }
+
+    /// 教材的单元及单元中的单词，单元按序号排序
+    async fn units_with_words(&self, textbook_id: i32) -> Result<Vec<UnitWords>> {
+        let mut units = self
+            .unit_repository
+            .find_by_textbook_id(Some(textbook_id))
+            .await?;
+        units.sort_by_key(|unit| {
+            (
+                unit.sequence_number.is_none(),
+                unit.sequence_number,
+                unit.id,
+            )
+        });
+
+        let mut result = Vec::with_capacity(units.len());
+        for unit in units {
+            let words = match unit.id {
+                Some(id) => {
+                    self.word_unit_mapping_repository
+                        .find_word_dto_by_unit_id(id)
+                        .await?
+                }
+                None => Vec::new(),
+            };
+            result.push((unit, words));
+        }
+        Ok(result)
+    }
+}
+
+fn unit_label(unit: &Unit, sequence_number: i32) -> String {
+    unit.name
+        .clone()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| format!("Unit {}", sequence_number))
+}
+
+/// 单元及单元中的单词
+type UnitWords = (Unit, Vec<WordDTO>);
+
+/// 按单元序号对应单元，序号为空的单元按排列位置编号
+fn by_sequence(units: &[UnitWords]) -> BTreeMap<i32, &UnitWords> {
+    let mut map = BTreeMap::new();
+    for (index, entry) in units.iter().enumerate() {
+        let sequence = entry.0.sequence_number.unwrap_or(index as i32 + 1);
+        map.entry(sequence).or_insert(entry);
+    }
+    map
+}
+
+/// 单词 id 到所在单元序号的索引，同一单词出现在多个单元时取第一个
+fn word_locations(units: &BTreeMap<i32, &UnitWords>) -> HashMap<i32, i32> {
+    let mut index = HashMap::new();
+    for (sequence, (_, words)) in units {
+        for word_id in words.iter().filter_map(|word| word.word_id) {
+            index.entry(word_id).or_insert(*sequence);
         }
     }
+    index
+}
+
+fn word_ids(entry: Option<&&UnitWords>) -> HashSet<i32> {
+    entry
+        .iter()
+        .flat_map(|(_, words)| words)
+        .filter_map(|word| word.word_id)
+        .collect()
+}
+
+/// 逐个单元对比两本教材：另一本教材中完全没有的单词记为新增或删除，换了单元的单词记在新单元的调整中
+fn diff_units(source: &[UnitWords], target: &[UnitWords]) -> Vec<UnitDiffDTO> {
+    let source = by_sequence(source);
+    let target = by_sequence(target);
+    let source_locations = word_locations(&source);
+    let target_locations = word_locations(&target);
+
+    let sequences: Vec<i32> = source
+        .keys()
+        .chain(target.keys())
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    sequences
+        .into_iter()
+        .map(|sequence| {
+            let (old, new) = (source.get(&sequence), target.get(&sequence));
+            let (old_ids, new_ids) = (word_ids(old), word_ids(new));
+            let mut diff = UnitDiffDTO {
+                sequence_number: sequence,
+                source_unit_id: old.and_then(|(unit, _)| unit.id),
+                source_unit: old.map(|(unit, _)| unit_label(unit, sequence)),
+                target_unit_id: new.and_then(|(unit, _)| unit.id),
+                target_unit: new.map(|(unit, _)| unit_label(unit, sequence)),
+                added: Vec::new(),
+                removed: Vec::new(),
+                moved: Vec::new(),
+                unchanged: old_ids.intersection(&new_ids).count(),
+            };
+
+            for word in new.iter().flat_map(|(_, words)| words) {
+                let (Some(word_id), Some(text)) = (word.word_id, word.word.clone()) else {
+                    continue;
+                };
+                if old_ids.contains(&word_id) {
+                    continue;
+                }
+                match source_locations.get(&word_id) {
+                    Some(from) => diff.moved.push(MovedWordDTO {
+                        word: text,
+                        from_unit: unit_label(&source[from].0, *from),
+                    }),
+                    None => diff.added.push(text),
+                }
+            }
+            for word in old.iter().flat_map(|(_, words)| words) {
+                let (Some(word_id), Some(text)) = (word.word_id, word.word.clone()) else {
+                    continue;
+                };
+                if !target_locations.contains_key(&word_id) {
+                    diff.removed.push(text);
+                }
+            }
+            diff
+        })
+        .collect()
 }
 
 #[async_trait]
@@ -104,4 +249,285 @@ impl TextbookService for TextbookServiceImpl {
             .map(|unit| UnitDTO::try_from(unit).map_err(Into::into))
             .collect::<Result<Vec<_>, _>>()
     }
+
+    async fn clone_textbook(&self, dto: &TextbookCloneDTO) -> Result<TextbookDTO> {
+        let source = self.find_textbook(dto.source_id).await?;
+        let version_id = dto.version_id.or(source.version_id);
+        let grade_id = dto.grade_id.or(source.grade_id);
+        let semester_id = dto.semester_id.or(source.semester_id);
+        if (version_id, grade_id, semester_id)
+            == (source.version_id, source.grade_id, source.semester_id)
+        {
+            return Err(anyhow!(
+                "Target version, grade or semester must differ from the source textbook"
+            ));
+        }
+
+        let existing = self
+            .repository
+            .find_by_dto(&TextbookDTO {
+                version_id,
+                grade_id,
+                semester_id,
+                ..TextbookDTO::new()
+            })
+            .await?;
+        if let Some(textbook) = existing.first() {
+            return Err(anyhow!(
+                "Textbook already exists for this version, grade and semester: {}",
+                textbook.name
+            ));
+        }
+
+        let textbook_version = match version_id {
+            Some(id) => {
+                self.textbook_version_repository
+                    .find_by_id(id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Textbook version not found: {}", id))?
+                    .name
+            }
+            None => None,
+        };
+        let grade = match grade_id {
+            Some(id) => Some(
+                self.grade_repository
+                    .find_by_id(id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Grade not found: {}", id))?
+                    .name,
+            ),
+            None => None,
+        };
+        let semester = match semester_id {
+            Some(id) => Some(
+                self.semester_repository
+                    .find_by_id(id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Semester not found: {}", id))?
+                    .name,
+            ),
+            None => None,
+        };
+
+        let name = match dto.name.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => [&textbook_version, &grade, &semester]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<String>(),
+        };
+        let target = Textbook {
+            id: None,
+            version_id,
+            grade_id,
+            semester_id,
+            created_at: None,
+            name: if name.is_empty() { source.name } else { name },
+            unit_count: source.unit_count,
+            word_count: source.word_count,
+            textbook_version,
+            grade,
+            semester,
+            updated_at: None,
+        };
+
+        let textbook = self
+            .repository
+            .clone_textbook(dto.source_id, &target)
+            .await?;
+        Ok(TextbookDTO::try_from(textbook)?)
+    }
+
+    async fn diff_textbooks(&self, dto: &TextbookDiffQueryDTO) -> Result<TextbookDiffDTO> {
+        self.find_textbook(dto.source_id).await?;
+        self.find_textbook(dto.target_id).await?;
+        let source = self.units_with_words(dto.source_id).await?;
+        let target = self.units_with_words(dto.target_id).await?;
+        Ok(TextbookDiffDTO::new(
+            dto.source_id,
+            dto.target_id,
+            diff_units(&source, &target),
+        ))
+    }
+
+    async fn recount_counters(&self) -> Result<RecountReportDTO> {
+        let discrepancies = self.repository.recount_counters().await?;
+        for discrepancy in &discrepancies {
+            warn!(
+                "Fixed {} {} {}: {:?} -> {}",
+                discrepancy.entity,
+                discrepancy.id,
+                discrepancy.field,
+                discrepancy.stored,
+                discrepancy.actual
+            );
+        }
+        Ok(RecountReportDTO::from(discrepancies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::{
+        InMemoryGradeRepository, InMemorySemesterRepository, InMemoryTextbookRepository,
+        InMemoryTextbookVersionRepository, InMemoryUnitRepository, InMemoryWordRepository,
+        InMemoryWordUnitMappingRepository,
+    };
+    use crate::domain::models::word::Word;
+    use crate::domain::models::word_unit_mapping::WordUnitMapping;
+    use crate::infrastructure::database::repositories::{Repository, WordRepository};
+
+    struct Fixture {
+        service: TextbookServiceImpl,
+        textbooks: Arc<InMemoryTextbookRepository>,
+        units: Arc<InMemoryUnitRepository>,
+        words: Arc<InMemoryWordRepository>,
+        mappings: Arc<InMemoryWordUnitMappingRepository>,
+    }
+
+    fn fixture() -> Fixture {
+        let textbooks = Arc::new(InMemoryTextbookRepository::default());
+        let units = Arc::new(InMemoryUnitRepository::default());
+        let words = Arc::new(InMemoryWordRepository::default());
+        let mappings = Arc::new(InMemoryWordUnitMappingRepository::new(words.clone()));
+        let service = TextbookServiceImpl::new(
+            textbooks.clone(),
+            Arc::new(InMemoryTextbookVersionRepository::default()),
+            Arc::new(InMemoryGradeRepository::default()),
+            Arc::new(InMemorySemesterRepository::default()),
+            units.clone(),
+            mappings.clone(),
+        );
+        Fixture {
+            service,
+            textbooks,
+            units,
+            words,
+            mappings,
+        }
+    }
+
+    impl Fixture {
+        /// 新建教材，units 为 (单元序号, 单元名称, 单词)
+        async fn textbook(&self, grade_id: i32, units: &[(i32, &str, &[&str])]) -> i32 {
+            let textbook = self
+                .textbooks
+                .save(&Textbook {
+                    id: None,
+                    version_id: Some(1),
+                    grade_id: Some(grade_id),
+                    semester_id: Some(1),
+                    created_at: None,
+                    name: format!("{}年级上册", grade_id),
+                    unit_count: Some(units.len() as i32),
+                    word_count: None,
+                    textbook_version: None,
+                    grade: None,
+                    semester: None,
+                    updated_at: None,
+                })
+                .await
+                .unwrap();
+            for (sequence, name, unit_words) in units {
+                let mut unit = Unit::new();
+                unit.name = Some(name.to_string());
+                unit.textbook_id = textbook.id;
+                unit.sequence_number = Some(*sequence);
+                let unit = self.units.save(&unit).await.unwrap();
+                for text in *unit_words {
+                    let word = match self.words.find_by_word(text).await.unwrap() {
+                        Some(word) => word,
+                        None => self.words.save(&Word::new(text)).await.unwrap(),
+                    };
+                    let mut mapping = WordUnitMapping::new();
+                    mapping.word_id = word.word_id;
+                    mapping.unit_id = unit.id;
+                    self.mappings.save(&mapping).await.unwrap();
+                }
+            }
+            textbook.id.unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_textbooks() {
+        let fixture = fixture();
+        let old = fixture
+            .textbook(
+                3,
+                &[
+                    (1, "Unit 1 Hello", &["hello", "goodbye", "name"]),
+                    (2, "Unit 2 Colours", &["red", "blue"]),
+                    (3, "Unit 3 Body", &["head"]),
+                ],
+            )
+            .await;
+        let new = fixture
+            .textbook(
+                4,
+                &[
+                    (1, "Unit 1 Hello!", &["hello", "goodbye", "red"]),
+                    (2, "Unit 2 Colours", &["blue", "green", "name"]),
+                ],
+            )
+            .await;
+
+        let diff = fixture
+            .service
+            .diff_textbooks(&TextbookDiffQueryDTO {
+                source_id: old,
+                target_id: new,
+            })
+            .await
+            .unwrap();
+        assert_eq!((diff.added, diff.removed, diff.moved), (1, 1, 2));
+        assert_eq!(diff.units.len(), 3);
+
+        let unit1 = &diff.units[0];
+        assert_eq!(unit1.source_unit.as_deref(), Some("Unit 1 Hello"));
+        assert_eq!(unit1.target_unit.as_deref(), Some("Unit 1 Hello!"));
+        assert_eq!(unit1.unchanged, 2);
+        assert_eq!(
+            unit1.moved,
+            vec![MovedWordDTO {
+                word: "red".to_string(),
+                from_unit: "Unit 2 Colours".to_string(),
+            }]
+        );
+        assert!(unit1.removed.is_empty());
+
+        let unit2 = &diff.units[1];
+        assert_eq!(unit2.added, vec!["green"]);
+        assert_eq!(unit2.moved[0].word, "name");
+        assert!(unit2.removed.is_empty());
+
+        let unit3 = &diff.units[2];
+        assert_eq!(unit3.target_unit_id, None);
+        assert_eq!(unit3.removed, vec!["head"]);
+    }
+
+    #[tokio::test]
+    async fn test_clone_textbook_validation() {
+        let fixture = fixture();
+        let source = fixture.textbook(3, &[]).await;
+        fixture.textbook(4, &[]).await;
+
+        let mut dto = TextbookCloneDTO {
+            source_id: source,
+            ..Default::default()
+        };
+        let error = fixture.service.clone_textbook(&dto).await.unwrap_err();
+        assert!(error.to_string().contains("must differ"), "{}", error);
+
+        dto.grade_id = Some(4);
+        let error = fixture.service.clone_textbook(&dto).await.unwrap_err();
+        assert!(error.to_string().contains("already exists"), "{}", error);
+
+        dto.grade_id = Some(5);
+        let error = fixture.service.clone_textbook(&dto).await.unwrap_err();
+        assert_eq!(error.to_string(), "Textbook version not found: 1");
+    }
 }
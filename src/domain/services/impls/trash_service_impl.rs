@@ -0,0 +1,233 @@
+use crate::api::dto::trash_dto::{TrashItemDTO, TrashPurgeDTO, TrashQueryDTO, TrashTargetDTO};
+use crate::domain::models::trash::TrashItem;
+use crate::domain::services::interfaces::trash_service::TrashService;
+use crate::infrastructure::database::repositories::TrashRepository;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::env;
+use std::sync::Arc;
+use time::{Duration, OffsetDateTime};
+use tracing::{info, warn};
+
+/// 默认保留 30 天，超过后不能恢复
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+/// 默认每 24 小时清理一次过期数据
+const DEFAULT_PURGE_INTERVAL_HOURS: u64 = 24;
+
+/// 从 TRASH_RETENTION_DAYS 读取回收站保留天数
+pub fn retention_from_env() -> Duration {
+    let days = env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+    Duration::days(days)
+}
+
+/// 从 TRASH_PURGE_INTERVAL_HOURS 读取清理过期数据的间隔
+pub fn purge_interval_from_env() -> std::time::Duration {
+    let hours = env::var("TRASH_PURGE_INTERVAL_HOURS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|hours| *hours > 0)
+        .unwrap_or(DEFAULT_PURGE_INTERVAL_HOURS);
+    std::time::Duration::from_secs(hours * 3600)
+}
+
+/// 在当前 tokio 运行时中启动后台任务，立即并按 interval 定期永久删除超过保留期的数据
+pub fn spawn_trash_purger(service: Arc<dyn TrashService>, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            match service.purge_expired().await {
+                Ok(result) if result.purged > 0 => {
+                    info!("Purged {} expired trash rows", result.purged)
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to purge expired trash: {}", e),
+            }
+        }
+    });
+}
+
+pub struct TrashServiceImpl {
+    trash_repository: Arc<dyn TrashRepository>,
+    retention: Duration,
+}
+
+impl TrashServiceImpl {
+    pub fn new(trash_repository: Arc<dyn TrashRepository>, retention: Duration) -> Self {
+        Self {
+            trash_repository,
+            retention,
+        }
+    }
+
+    /// 保留期的起点，早于该时间删除的数据已过期
+    fn cutoff(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc() - self.retention
+    }
+
+    fn to_dto(&self, item: TrashItem) -> Result<TrashItemDTO> {
+        Ok(TrashItemDTO::new(item, self.retention)?)
+    }
+}
+
+#[async_trait]
+impl TrashService for TrashServiceImpl {
+    async fn preview_delete(&self, dto: &TrashTargetDTO) -> Result<TrashItemDTO> {
+        let item = self
+            .trash_repository
+            .preview(dto.entity, dto.id)
+            .await?
+            .ok_or_else(|| anyhow!("{} {} not found", dto.entity, dto.id))?;
+        self.to_dto(item)
+    }
+
+    async fn get_trash(&self, query: &TrashQueryDTO) -> Result<Vec<TrashItemDTO>> {
+        self.trash_repository
+            .find_deleted(query.entity, self.cutoff())
+            .await?
+            .into_iter()
+            .map(|item| self.to_dto(item))
+            .collect()
+    }
+
+    async fn restore(&self, dto: &TrashTargetDTO) -> Result<TrashItemDTO> {
+        let item = self
+            .trash_repository
+            .restore(dto.entity, dto.id, self.cutoff())
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "{} {} is not in the trash or its retention period has expired",
+                    dto.entity,
+                    dto.id
+                )
+            })?;
+        info!(
+            "Restored {} {} with {} units and {} unit words",
+            item.entity, item.id, item.units, item.unit_words
+        );
+        self.to_dto(item)
+    }
+
+    async fn purge_expired(&self) -> Result<TrashPurgeDTO> {
+        let purged = self.trash_repository.purge(self.cutoff()).await?;
+        Ok(TrashPurgeDTO { purged })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::trash::TrashEntity;
+    use std::sync::Mutex;
+
+    /// 只按删除时间筛选的回收站，级联逻辑由数据库实现
+    #[derive(Default)]
+    struct FakeTrash {
+        items: Mutex<Vec<TrashItem>>,
+    }
+
+    #[async_trait]
+    impl TrashRepository for FakeTrash {
+        async fn preview(&self, _entity: TrashEntity, _id: i32) -> Result<Option<TrashItem>> {
+            Ok(None)
+        }
+
+        async fn find_deleted(
+            &self,
+            entity: Option<TrashEntity>,
+            since: OffsetDateTime,
+        ) -> Result<Vec<TrashItem>> {
+            let items = self.items.lock().unwrap();
+            Ok(items
+                .iter()
+                .filter(|item| entity.is_none_or(|entity| item.entity == entity))
+                .filter(|item| item.deleted_at.is_some_and(|at| at >= since))
+                .cloned()
+                .collect())
+        }
+
+        async fn restore(
+            &self,
+            entity: TrashEntity,
+            id: i32,
+            since: OffsetDateTime,
+        ) -> Result<Option<TrashItem>> {
+            let mut items = self.items.lock().unwrap();
+            let position = items.iter().position(|item| {
+                item.entity == entity
+                    && item.id == id
+                    && item.deleted_at.is_some_and(|at| at >= since)
+            });
+            Ok(position.map(|index| {
+                let mut item = items.remove(index);
+                item.deleted_at = None;
+                item
+            }))
+        }
+
+        async fn purge(&self, before: OffsetDateTime) -> Result<u64> {
+            let mut items = self.items.lock().unwrap();
+            let count = items.len();
+            items.retain(|item| item.deleted_at.is_some_and(|at| at >= before));
+            Ok((count - items.len()) as u64)
+        }
+    }
+
+    fn deleted(entity: TrashEntity, id: i32, days_ago: i64) -> TrashItem {
+        TrashItem {
+            entity,
+            id,
+            name: format!("{} {}", entity, id),
+            deleted_at: Some(OffsetDateTime::now_utc() - Duration::days(days_ago)),
+            units: 0,
+            unit_words: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trash_retention() {
+        let repository = Arc::new(FakeTrash::default());
+        *repository.items.lock().unwrap() = vec![
+            deleted(TrashEntity::Unit, 1, 1),
+            deleted(TrashEntity::UnitWord, 2, 3),
+            deleted(TrashEntity::Textbook, 3, 40),
+        ];
+        let service = TrashServiceImpl::new(repository.clone(), Duration::days(30));
+
+        let trash = service.get_trash(&TrashQueryDTO::default()).await.unwrap();
+        assert_eq!(
+            trash.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        let unit_words = service
+            .get_trash(&TrashQueryDTO {
+                entity: Some(TrashEntity::UnitWord),
+            })
+            .await
+            .unwrap();
+        assert_eq!(unit_words.len(), 1);
+        assert!(unit_words[0].expires_at > unit_words[0].deleted_at);
+
+        let expired = TrashTargetDTO {
+            entity: TrashEntity::Textbook,
+            id: 3,
+        };
+        assert!(service.restore(&expired).await.is_err());
+        let restored = service
+            .restore(&TrashTargetDTO {
+                entity: TrashEntity::Unit,
+                id: 1,
+            })
+            .await
+            .unwrap();
+        assert_eq!((restored.deleted_at, restored.expires_at), (None, None));
+
+        assert_eq!(service.purge_expired().await.unwrap().purged, 1);
+        assert_eq!(repository.items.lock().unwrap().len(), 1);
+    }
+}
@@ -1,8 +1,10 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use std::sync::Arc;
 
+use crate::api::dto::sequence_dto::{MoveDTO, SwapDTO};
 use crate::api::dto::unit_dto::UnitDTO;
+use crate::common::utils::sequence::Reorder;
 use crate::domain::models::unit::Unit;
 use crate::domain::services::interfaces::unit_service::UnitService;
 use crate::infrastructure::database::repositories::UnitRepository;
@@ -15,6 +17,29 @@ impl UnitServiceImpl {
     pub fn new(unit_repository: Arc<dyn UnitRepository>) -> Self {
         Self { unit_repository }
     }
+
+    /// 单元所属的课本
+    async fn textbook_of(&self, unit_id: i32) -> Result<i32> {
+        self.unit_repository
+            .find_by_id(unit_id)
+            .await?
+            .ok_or_else(|| anyhow!("Unit {} not found", unit_id))?
+            .textbook_id
+            .ok_or_else(|| anyhow!("Unit {} does not belong to a textbook", unit_id))
+    }
+
+    async fn reorder(&self, textbook_id: i32, op: Reorder) -> Result<Vec<UnitDTO>> {
+        self.unit_repository.reorder(textbook_id, op).await?;
+        let units = self
+            .unit_repository
+            .find_by_textbook_id(Some(textbook_id))
+            .await?;
+
+        units
+            .into_iter()
+            .map(|unit| UnitDTO::try_from(&unit).map_err(Into::into))
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -40,4 +65,84 @@ impl UnitService for UnitServiceImpl {
     async fn delete_unit(&self, id: i32) -> Result<()> {
         self.unit_repository.delete(id).await
     }
+
+    async fn move_unit(&self, dto: &MoveDTO) -> Result<Vec<UnitDTO>> {
+        let textbook_id = self.textbook_of(dto.id).await?;
+        self.reorder(textbook_id, dto.into()).await
+    }
+
+    async fn swap_units(&self, dto: &SwapDTO) -> Result<Vec<UnitDTO>> {
+        let textbook_id = self.textbook_of(dto.id).await?;
+        if self.textbook_of(dto.other_id).await? != textbook_id {
+            return Err(anyhow!(
+                "Units {} and {} belong to different textbooks",
+                dto.id,
+                dto.other_id
+            ));
+        }
+        self.reorder(textbook_id, dto.into()).await
+    }
+
+    async fn renumber_units(&self, textbook_id: i32) -> Result<Vec<UnitDTO>> {
+        self.reorder(textbook_id, Reorder::Renumber).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::InMemoryUnitRepository;
+    use crate::infrastructure::database::repositories::Repository;
+
+    fn unit_ids(units: &[UnitDTO]) -> Vec<(i32, i32)> {
+        units
+            .iter()
+            .map(|unit| (unit.id.unwrap(), unit.sequence_number.unwrap()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_move_swap_and_renumber_units() {
+        let units = Arc::new(InMemoryUnitRepository::default());
+        let service = UnitServiceImpl::new(units.clone());
+        let mut ids = Vec::new();
+        // 序号重复且有空缺，整理时按序号、ID 的顺序编号
+        for (textbook_id, sequence_number) in [(1, 5), (1, 2), (1, 2), (2, 1)] {
+            let mut unit = Unit::new();
+            unit.textbook_id = Some(textbook_id);
+            unit.sequence_number = Some(sequence_number);
+            ids.push(units.save(&unit).await.unwrap().id.unwrap());
+        }
+        let (a, b, c, other) = (ids[0], ids[1], ids[2], ids[3]);
+
+        let renumbered = service.renumber_units(1).await.unwrap();
+        assert_eq!(unit_ids(&renumbered), vec![(b, 1), (c, 2), (a, 3)]);
+
+        let moved = service
+            .move_unit(&MoveDTO { id: a, position: 1 })
+            .await
+            .unwrap();
+        assert_eq!(unit_ids(&moved), vec![(a, 1), (b, 2), (c, 3)]);
+
+        let swapped = service
+            .swap_units(&SwapDTO { id: a, other_id: c })
+            .await
+            .unwrap();
+        assert_eq!(unit_ids(&swapped), vec![(c, 1), (b, 2), (a, 3)]);
+
+        assert!(service
+            .swap_units(&SwapDTO {
+                id: a,
+                other_id: other,
+            })
+            .await
+            .is_err());
+        assert!(service
+            .move_unit(&MoveDTO {
+                id: 999,
+                position: 1
+            })
+            .await
+            .is_err());
+    }
 }
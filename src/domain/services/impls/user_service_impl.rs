@@ -0,0 +1,364 @@
+use crate::api::dto::user_dto::{
+    ChangePasswordDTO, CreateUserDTO, LinkTokenDTO, LoginDTO, LoginResultDTO, UpdateUserDTO,
+    UserDTO,
+};
+use crate::common::utils::crypto;
+use crate::domain::models::user::{AuthUser, Role, User};
+use crate::domain::services::interfaces::user_service::UserService;
+use crate::infrastructure::database::repositories::UserRepository;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::env;
+use std::sync::Arc;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+use tracing::info;
+
+/// 登录令牌默认 7 天有效
+const DEFAULT_TOKEN_TTL_HOURS: i64 = 24 * 7;
+const MIN_PASSWORD_LEN: usize = 8;
+const MAX_USERNAME_LEN: usize = 50;
+/// 链接令牌只用于打开一次下载或事件流，有效期很短
+const LINK_TOKEN_TTL_SECONDS: i64 = 120;
+/// 用户名或密码错误时不区分具体原因
+const INVALID_CREDENTIALS: &str = "Invalid username or password";
+
+/// 从 AUTH_TOKEN_TTL_HOURS 读取登录令牌的有效期
+pub fn token_ttl_from_env() -> Duration {
+    let hours = env::var("AUTH_TOKEN_TTL_HOURS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|hours| *hours > 0)
+        .unwrap_or(DEFAULT_TOKEN_TTL_HOURS);
+    Duration::hours(hours)
+}
+
+fn validate_password(password: &str) -> Result<()> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(anyhow!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// 密码哈希计算量较大，放到阻塞线程池中执行
+async fn hash_password(password: &str) -> Result<String> {
+    validate_password(password)?;
+    let password = password.to_string();
+    Ok(tokio::task::spawn_blocking(move || crypto::hash_password(&password)).await?)
+}
+
+async fn verify_password(password: &str, hash: &str) -> Result<bool> {
+    let (password, hash) = (password.to_string(), hash.to_string());
+    Ok(tokio::task::spawn_blocking(move || crypto::verify_password(&password, &hash)).await?)
+}
+
+pub struct UserServiceImpl {
+    user_repository: Arc<dyn UserRepository>,
+    token_ttl: Duration,
+}
+
+impl UserServiceImpl {
+    pub fn new(user_repository: Arc<dyn UserRepository>, token_ttl: Duration) -> Self {
+        Self {
+            user_repository,
+            token_ttl,
+        }
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<AuthUser>> {
+        let user = self.user_repository.find_by_session(token_hash).await?;
+        match user {
+            Some(user) if !user.disabled => Ok(Some(AuthUser::try_from(&user)?)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn find_user(&self, id: i32) -> Result<User> {
+        self.user_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("User {} not found", id))
+    }
+
+    /// 修改后是否还有可用的管理员
+    async fn keeps_an_admin(&self, updated: &User) -> Result<bool> {
+        let is_active_admin =
+            |user: &User| !user.disabled && user.role.parse::<Role>().ok() == Some(Role::Admin);
+        let others = self
+            .user_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .filter(|user| user.id != updated.id)
+            .any(|user| is_active_admin(&user));
+        Ok(others || is_active_admin(updated))
+    }
+}
+
+#[async_trait]
+impl UserService for UserServiceImpl {
+    async fn login(&self, dto: &LoginDTO) -> Result<LoginResultDTO> {
+        let user = self
+            .user_repository
+            .find_by_username(dto.username.trim())
+            .await?;
+        // 用户名不存在时也校验一次密码，响应时间不泄露用户名是否存在
+        let hash = user
+            .as_ref()
+            .map_or(crypto::dummy_password_hash(), |user| &user.password_hash);
+        let verified = verify_password(&dto.password, hash).await?;
+        let user = match user {
+            Some(user) if verified => user,
+            _ => return Err(anyhow!(INVALID_CREDENTIALS)),
+        };
+        if user.disabled {
+            return Err(anyhow!("User {} is disabled", user.username));
+        }
+
+        let token = crypto::generate_token();
+        let expires_at = OffsetDateTime::now_utc() + self.token_ttl;
+        let user_id = user.id.ok_or_else(|| anyhow!("User has no id"))?;
+        self.user_repository
+            .create_session(user_id, &crypto::hash_token(&token), expires_at)
+            .await?;
+        info!("User {} signed in", user.username);
+        Ok(LoginResultDTO {
+            token,
+            expires_at: expires_at.format(&Rfc3339)?,
+            user: UserDTO::try_from(user)?,
+        })
+    }
+
+    async fn logout(&self, token: &str) -> Result<()> {
+        self.user_repository
+            .delete_session(&crypto::hash_token(token))
+            .await
+    }
+
+    async fn authenticate(&self, token: &str) -> Result<Option<AuthUser>> {
+        self.find_by_token_hash(&crypto::hash_token(token)).await
+    }
+
+    async fn issue_link_token(&self, user: &AuthUser) -> Result<LinkTokenDTO> {
+        let token = crypto::generate_token();
+        let expires_at = OffsetDateTime::now_utc() + Duration::seconds(LINK_TOKEN_TTL_SECONDS);
+        self.user_repository
+            .create_session(user.id, &crypto::hash_link_token(&token), expires_at)
+            .await?;
+        Ok(LinkTokenDTO {
+            token,
+            expires_at: expires_at.format(&Rfc3339)?,
+        })
+    }
+
+    async fn authenticate_link(&self, token: &str) -> Result<Option<AuthUser>> {
+        self.find_by_token_hash(&crypto::hash_link_token(token))
+            .await
+    }
+
+    async fn get_user(&self, id: i32) -> Result<UserDTO> {
+        Ok(UserDTO::try_from(self.find_user(id).await?)?)
+    }
+
+    async fn get_users(&self) -> Result<Vec<UserDTO>> {
+        self.user_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|user| UserDTO::try_from(user).map_err(Into::into))
+            .collect()
+    }
+
+    async fn create_user(&self, dto: &CreateUserDTO) -> Result<UserDTO> {
+        let username = dto.username.trim();
+        if username.is_empty() || username.chars().count() > MAX_USERNAME_LEN {
+            return Err(anyhow!(
+                "Username must be 1 to {} characters",
+                MAX_USERNAME_LEN
+            ));
+        }
+        if self
+            .user_repository
+            .find_by_username(username)
+            .await?
+            .is_some()
+        {
+            return Err(anyhow!("User {} already exists", username));
+        }
+
+        let mut user = User::new(username, hash_password(&dto.password).await?, dto.role);
+        user.display_name = dto.display_name.clone();
+        let user = self.user_repository.save(&user).await?;
+        info!("Created {} user {}", user.role, user.username);
+        Ok(UserDTO::try_from(user)?)
+    }
+
+    async fn update_user(&self, dto: &UpdateUserDTO) -> Result<UserDTO> {
+        let mut user = self.find_user(dto.id).await?;
+        if let Some(display_name) = &dto.display_name {
+            user.display_name = Some(display_name.trim().to_string()).filter(|n| !n.is_empty());
+        }
+        if let Some(role) = dto.role {
+            user.role = role.to_string();
+        }
+        if let Some(disabled) = dto.disabled {
+            user.disabled = disabled;
+        }
+        if let Some(password) = &dto.password {
+            user.password_hash = hash_password(password).await?;
+        }
+        if !self.keeps_an_admin(&user).await? {
+            return Err(anyhow!("At least one enabled admin is required"));
+        }
+
+        let user = self.user_repository.save(&user).await?;
+        if user.disabled || dto.password.is_some() {
+            self.user_repository.delete_sessions(dto.id).await?;
+        }
+        Ok(UserDTO::try_from(user)?)
+    }
+
+    async fn change_password(&self, current: &AuthUser, dto: &ChangePasswordDTO) -> Result<()> {
+        let mut user = self.find_user(current.id).await?;
+        if !verify_password(&dto.old_password, &user.password_hash).await? {
+            return Err(anyhow!("Old password is incorrect"));
+        }
+        user.password_hash = hash_password(&dto.new_password).await?;
+        self.user_repository.save(&user).await?;
+        self.user_repository.delete_sessions(current.id).await
+    }
+
+    async fn ensure_admin(&self, username: &str, password: &str) -> Result<bool> {
+        if self.user_repository.count().await? > 0 {
+            return Ok(false);
+        }
+        self.create_user(&CreateUserDTO {
+            username: username.to_string(),
+            password: password.to_string(),
+            display_name: None,
+            role: Role::Admin,
+        })
+        .await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::InMemoryUserRepository;
+
+    fn service() -> UserServiceImpl {
+        UserServiceImpl::new(
+            Arc::new(InMemoryUserRepository::default()),
+            Duration::hours(1),
+        )
+    }
+
+    fn login_dto(username: &str, password: &str) -> LoginDTO {
+        LoginDTO {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_and_logout() {
+        let service = service();
+        assert!(service.ensure_admin("admin", "secret-pass").await.unwrap());
+        assert!(!service.ensure_admin("other", "secret-pass").await.unwrap());
+
+        let error = service
+            .login(&login_dto("admin", "wrong-pass"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), INVALID_CREDENTIALS);
+        let error = service
+            .login(&login_dto("nobody", "secret-pass"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), INVALID_CREDENTIALS);
+
+        let result = service
+            .login(&login_dto("admin", "secret-pass"))
+            .await
+            .unwrap();
+        assert_eq!(result.user.role, "admin");
+        let user = service.authenticate(&result.token).await.unwrap().unwrap();
+        assert_eq!(user.role, Role::Admin);
+
+        // 链接令牌和登录令牌不能互换使用
+        let link = service.issue_link_token(&user).await.unwrap();
+        assert_eq!(
+            service.authenticate_link(&link.token).await.unwrap(),
+            Some(user.clone())
+        );
+        assert!(service.authenticate(&link.token).await.unwrap().is_none());
+        assert!(service
+            .authenticate_link(&result.token)
+            .await
+            .unwrap()
+            .is_none());
+
+        service.logout(&result.token).await.unwrap();
+        assert!(service.authenticate(&result.token).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_last_admin_is_kept() {
+        let service = service();
+        service.ensure_admin("admin", "secret-pass").await.unwrap();
+        let admin_id = service.get_users().await.unwrap()[0].id.unwrap();
+
+        let demote = UpdateUserDTO {
+            id: admin_id,
+            role: Some(Role::Editor),
+            ..Default::default()
+        };
+        assert!(service.update_user(&demote).await.is_err());
+
+        let second = service
+            .create_user(&CreateUserDTO {
+                username: "root".to_string(),
+                password: "secret-pass".to_string(),
+                display_name: None,
+                role: Role::Admin,
+            })
+            .await
+            .unwrap();
+        assert_eq!(second.role, "admin");
+        let demoted = service.update_user(&demote).await.unwrap();
+        assert_eq!(demoted.role, "editor");
+    }
+
+    #[tokio::test]
+    async fn test_change_password_ends_sessions() {
+        let service = service();
+        service.ensure_admin("admin", "secret-pass").await.unwrap();
+        let result = service
+            .login(&login_dto("admin", "secret-pass"))
+            .await
+            .unwrap();
+        let user = service.authenticate(&result.token).await.unwrap().unwrap();
+
+        let short = ChangePasswordDTO {
+            old_password: "secret-pass".to_string(),
+            new_password: "short".to_string(),
+        };
+        assert!(service.change_password(&user, &short).await.is_err());
+
+        let dto = ChangePasswordDTO {
+            old_password: "secret-pass".to_string(),
+            new_password: "another-pass".to_string(),
+        };
+        service.change_password(&user, &dto).await.unwrap();
+        assert!(service.authenticate(&result.token).await.unwrap().is_none());
+        assert!(service
+            .login(&login_dto("admin", "another-pass"))
+            .await
+            .is_ok());
+    }
+}
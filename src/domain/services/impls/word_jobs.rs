@@ -0,0 +1,195 @@
+use crate::api::dto::unit_word_dto::{UnitWordImportDTO, WordDTO};
+use crate::domain::models::job::{
+    Job, CREATE_UNIT_WORD_JOB, CREATE_WORD_JOB, ENRICH_WORDS_JOB, IMPORT_UNIT_WORDS_JOB,
+};
+use crate::domain::services::interfaces::job_service::{JobContext, JobRunner};
+use crate::domain::services::interfaces::word_service::WordService;
+use crate::domain::services::interfaces::word_unit_service::WordUnitService;
+use crate::infrastructure::database::repositories::WordRepository;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::warn;
+
+#[derive(Debug, Deserialize)]
+struct CreateWordPayload {
+    word: String,
+}
+
+/// 新建单词并补全音标、释义和例句，payload 为 `{"word": "apple"}`
+pub struct CreateWordJob {
+    word_service: Arc<dyn WordService>,
+}
+
+impl CreateWordJob {
+    pub fn new(word_service: Arc<dyn WordService>) -> Self {
+        Self { word_service }
+    }
+}
+
+#[async_trait]
+impl JobRunner for CreateWordJob {
+    fn kind(&self) -> &'static str {
+        CREATE_WORD_JOB
+    }
+
+    async fn run(&self, job: &Job, context: &JobContext) -> anyhow::Result<Option<Value>> {
+        let payload: CreateWordPayload = serde_json::from_value(job.payload.clone())?;
+        let word = payload.word.trim();
+        if word.is_empty() {
+            anyhow::bail!("Word is required");
+        }
+
+        context.progress(0, 1, None).await?;
+        let word = self.word_service.create_word(word).await?;
+        context.progress(1, 1, None).await?;
+        Ok(Some(json!({ "word_id": word.word_id, "word": word.word })))
+    }
+}
+
+/// 批量补全任务的断点：已处理到的单词 id，以及之前执行失败、需要重试的单词
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EnrichCheckpoint {
+    last_word_id: i32,
+    #[serde(default)]
+    failed: Vec<i32>,
+}
+
+/// 补全所有单词缺失的信息，按单词 id 顺序处理并记录断点，重试或重启后只处理剩余和失败的单词
+pub struct EnrichWordsJob {
+    word_repository: Arc<dyn WordRepository>,
+    word_service: Arc<dyn WordService>,
+}
+
+impl EnrichWordsJob {
+    pub fn new(
+        word_repository: Arc<dyn WordRepository>,
+        word_service: Arc<dyn WordService>,
+    ) -> Self {
+        Self {
+            word_repository,
+            word_service,
+        }
+    }
+}
+
+#[async_trait]
+impl JobRunner for EnrichWordsJob {
+    fn kind(&self) -> &'static str {
+        ENRICH_WORDS_JOB
+    }
+
+    async fn run(&self, job: &Job, context: &JobContext) -> anyhow::Result<Option<Value>> {
+        let checkpoint: EnrichCheckpoint = match &job.checkpoint {
+            Some(checkpoint) => serde_json::from_value(checkpoint.clone())?,
+            None => EnrichCheckpoint::default(),
+        };
+        let mut words = self.word_repository.find_all().await?;
+        words.sort_by_key(|word| word.word_id);
+
+        let total = words.len() as i32;
+        let (pending, finished): (Vec<_>, Vec<_>) = words.into_iter().partition(|word| {
+            word.word_id
+                .is_some_and(|id| id > checkpoint.last_word_id || checkpoint.failed.contains(&id))
+        });
+        let mut done = finished.len() as i32;
+
+        let mut next = EnrichCheckpoint {
+            last_word_id: checkpoint.last_word_id,
+            failed: Vec::new(),
+        };
+        let mut errors = Vec::new();
+        for word in pending {
+            let id = word.word_id.unwrap_or_default();
+            match self.word_service.enrich_word(&word).await {
+                Ok(_) => done += 1,
+                Err(e) => {
+                    warn!("Failed to enrich word {}: {:#}", word.word, e);
+                    next.failed.push(id);
+                    errors.push(word.word);
+                }
+            }
+            next.last_word_id = next.last_word_id.max(id);
+            context
+                .progress(done, total, Some(serde_json::to_value(&next)?))
+                .await?;
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "Failed to enrich {} words: {}",
+                errors.len(),
+                errors.join(", ")
+            );
+        }
+        Ok(Some(json!({ "total": total, "enriched": done })))
+    }
+}
+
+/// 新建单词并绑定到单元，payload 为 `{"word": "apple", "unit_id": 1}`，结果为新建的单元单词
+pub struct CreateUnitWordJob {
+    word_unit_service: Arc<dyn WordUnitService>,
+}
+
+impl CreateUnitWordJob {
+    pub fn new(word_unit_service: Arc<dyn WordUnitService>) -> Self {
+        Self { word_unit_service }
+    }
+}
+
+#[async_trait]
+impl JobRunner for CreateUnitWordJob {
+    fn kind(&self) -> &'static str {
+        CREATE_UNIT_WORD_JOB
+    }
+
+    async fn run(&self, job: &Job, context: &JobContext) -> anyhow::Result<Option<Value>> {
+        let dto: WordDTO = serde_json::from_value(job.payload.clone())?;
+        if dto
+            .word
+            .as_deref()
+            .is_none_or(|word| word.trim().is_empty())
+        {
+            anyhow::bail!("Word is required");
+        }
+        if dto.unit_id.is_none() {
+            anyhow::bail!("unit_id is required");
+        }
+
+        context.progress(0, 1, None).await?;
+        let unit_word = self
+            .word_unit_service
+            .create_word_unit_mapping(&dto)
+            .await?;
+        context.progress(1, 1, None).await?;
+        Ok(Some(serde_json::to_value(unit_word)?))
+    }
+}
+
+/// 批量导入单元单词，payload 与 /api/unit-word/import 的请求相同，结果为每一行的导入结果；
+/// 重试时已导入的单词按单元中已有处理
+pub struct ImportUnitWordsJob {
+    word_unit_service: Arc<dyn WordUnitService>,
+}
+
+impl ImportUnitWordsJob {
+    pub fn new(word_unit_service: Arc<dyn WordUnitService>) -> Self {
+        Self { word_unit_service }
+    }
+}
+
+#[async_trait]
+impl JobRunner for ImportUnitWordsJob {
+    fn kind(&self) -> &'static str {
+        IMPORT_UNIT_WORDS_JOB
+    }
+
+    async fn run(&self, job: &Job, context: &JobContext) -> anyhow::Result<Option<Value>> {
+        let dto: UnitWordImportDTO = serde_json::from_value(job.payload.clone())?;
+        context.progress(0, 1, None).await?;
+        let report = self.word_unit_service.import_unit_words(&dto).await?;
+        context.progress(1, 1, None).await?;
+        Ok(Some(serde_json::to_value(report)?))
+    }
+}
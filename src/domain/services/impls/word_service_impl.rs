@@ -1,14 +1,33 @@
+use crate::api::dto::word_edit_dto::{WordDetailDTO, WordEditDTO, WordHistoryDTO, WordPatchDTO};
+use crate::api::dto::word_meaning_dto::MeaningParseFailureDTO;
+use crate::api::dto::word_revision_dto::{
+    WordRevisionCompareDTO, WordRevisionDTO, WordRevisionDiffDTO, WordRollbackDTO,
+};
 use crate::domain::models::word::Word;
+use crate::domain::models::word_edit::WordField;
+use crate::domain::models::word_revision::{RevisionSource, WordContent};
 use crate::domain::services::interfaces::word_service::WordService;
 use crate::infrastructure::database::repositories::{Paginated, WordRepository};
 use async_trait::async_trait;
 use std::sync::Arc;
 
 use crate::domain::services::interfaces::SystemConfigService;
+use crate::infrastructure::dto::{WordInfo, WordMeaning};
 use crate::infrastructure::llm;
+use crate::infrastructure::llm::prompts::{GradeProfile, UnitContext};
+use crate::infrastructure::llm::utils;
+use crate::infrastructure::llm::ChatStream;
 use crate::infrastructure::third_party::ThirdPartyService;
-use anyhow::Result;
-use tracing::debug;
+use anyhow::{anyhow, Result};
+use tracing::{debug, warn};
+
+/// 有道词典的美式、英式发音地址
+fn pronunciation_urls(word: &str) -> (String, String) {
+    (
+        format!("http://dict.youdao.com/dictvoice?type=0&audio={}", word),
+        format!("http://dict.youdao.com/dictvoice?type=1&audio={}", word),
+    )
+}
 
 pub struct WordServiceImpl {
     word_repository: Arc<dyn WordRepository>,
@@ -28,55 +47,104 @@ impl WordServiceImpl {
             third_party_service,
         }
     }
+
+    /// 通过模型降级链生成例句，同时返回使用的提示词版本
+    async fn llm_example_sentences(&self, model: &str, word: &str) -> Result<(String, i32)> {
+        llm::get_llm_manager()
+            .execute(model, "get_example_sentences", |service| {
+                let word = word.to_string();
+                async move { service.get_example_sentences(&word).await }
+            })
+            .await
+    }
+
+    /// 通过模型降级链获取单词音标与释义，同时返回使用的提示词版本
+    async fn llm_word_info(&self, model: &str, word: &str) -> Result<(WordInfo, i32)> {
+        let (mut word_info, version) = llm::get_llm_manager()
+            .execute(model, "get_word_info", |service| {
+                let word = word.to_string();
+                async move { service.get_word_info(&word).await }
+            })
+            .await?;
+        self.complete_phonetics(model, word, &mut word_info).await;
+        Ok((word_info, version))
+    }
+
+    /// 词典或模型没有给出音标时单独向模型查询，查询失败时保留原样
+    async fn complete_phonetics(&self, model: &str, word: &str, word_info: &mut WordInfo) {
+        if !word_info.us_phonetic.trim().is_empty() && !word_info.uk_phonetic.trim().is_empty() {
+            return;
+        }
+        let phonetics = llm::get_llm_manager()
+            .execute(model, "get_phonetics", |service| {
+                let word = word.to_string();
+                async move { service.get_phonetics(&word).await }
+            })
+            .await;
+        match phonetics {
+            Ok((us_phonetic, uk_phonetic)) => {
+                word_info.us_phonetic = us_phonetic;
+                word_info.uk_phonetic = uk_phonetic;
+            }
+            Err(e) => warn!("Failed to get phonetics for '{}': {}", word, e),
+        }
+    }
 }
 
 #[async_trait]
 impl WordService for WordServiceImpl {
     async fn create_word(&self, word: &str) -> Result<Word> {
-        //step1. 查询单词是否已存在
+        //step1. 查询单词是否已存在；没有释义时重新生成并更新原记录，单元中的引用保持不变
         let exist_word = self.word_repository.find_by_word(word).await?;
-        if exist_word.is_some() {
-            let word = exist_word.unwrap();
-            if word.meaning.as_ref().map_or(false, |m| !m.is_empty()) {
+        if let Some(word) = exist_word {
+            if !word.meanings.is_empty() {
                 return Ok(word);
             }
-            self.word_repository.delete(word.word_id.unwrap()).await;
-            debug!("Deleting word with ID: {}", word.word_id.unwrap());
+            debug!("Regenerating word with ID: {:?}", word.word_id);
         }
         //step2. 构造单词
         let mut word_entity = Word::new(word);
-        let pronunciation_us = format!("http://dict.youdao.com/dictvoice?type=0&audio={}", word);
-        let pronunciation_uk = format!("http://dict.youdao.com/dictvoice?type=1&audio={}", word);
+        let (pronunciation_us, pronunciation_uk) = pronunciation_urls(word);
         word_entity.pronunciation_uk = Some(pronunciation_uk);
         word_entity.pronunciation_us = Some(pronunciation_us);
 
         let model = self.system_config_service.get_use_model().await?;
-        let llm_service = llm::get_llm_manager().get_llm_service(&model)?;
-        let example = llm_service.get_example_sentences(word).await?;
+        let (example, example_version) = self.llm_example_sentences(&model, word).await?;
         word_entity.example = Some(example);
+        word_entity.example_prompt_version = Some(example_version);
 
         if word.contains(" ") {
-            let word_info = llm_service.get_word_info(word_entity.word.as_str()).await?;
+            let (word_info, meaning_version) = self
+                .llm_word_info(&model, word_entity.word.as_str())
+                .await?;
             word_entity.phonetic_uk = Some(format!("/{}/", word_info.uk_phonetic));
             word_entity.phonetic_us = Some(format!("/{}/", word_info.us_phonetic));
-            word_entity.meaning = Some(serde_json::to_string(&word_info.meanings)?)
+            word_entity.meanings = word_info.meanings;
+            word_entity.meaning_prompt_version = Some(meaning_version);
         } else {
             let word_info = self.third_party_service.fetch_word_info(word).await;
-            if word_info.is_ok() {
-                let word_info = word_info.unwrap();
+            if let Ok(mut word_info) = word_info {
+                self.complete_phonetics(&model, word, &mut word_info).await;
                 word_entity.phonetic_uk = Some(format!("/{}/", word_info.uk_phonetic));
                 word_entity.phonetic_us = Some(format!("/{}/", word_info.us_phonetic));
-                word_entity.meaning = Some(serde_json::to_string(&word_info.meanings)?)
+                word_entity.meanings = word_info.meanings;
             } else {
-                let word_info = llm_service.get_word_info(word_entity.word.as_str()).await?;
+                let (word_info, meaning_version) = self
+                    .llm_word_info(&model, word_entity.word.as_str())
+                    .await?;
                 word_entity.phonetic_uk = Some(format!("/{}/", word_info.uk_phonetic));
                 word_entity.phonetic_us = Some(format!("/{}/", word_info.us_phonetic));
-                word_entity.meaning = Some(serde_json::to_string(&word_info.meanings)?)
+                word_entity.meanings = word_info.meanings;
+                word_entity.meaning_prompt_version = Some(meaning_version);
             }
         }
 
-        //step3. 插入单词
-        self.word_repository.save(&word_entity).await
+        //step3. 插入单词，释义由大模型生成时记为 llm 版本，否则记为词典版本
+        let source = match word_entity.meaning_prompt_version {
+            Some(_) => RevisionSource::Llm,
+            None => RevisionSource::Dictionary,
+        };
+        self.word_repository.save_as(&word_entity, source).await
     }
 
     async fn get_word(&self, word: &str) -> Result<Word> {
@@ -88,38 +156,384 @@ impl WordService for WordServiceImpl {
         }
     }
 
-    async fn update_batch_words(&self) -> Result<()> {
-        //step1. 查询所有单词
-        let mut words: Vec<Word> = self.word_repository.find_all().await?;
-        //step2. 获取大模型服务
-        let model = self.system_config_service.get_use_model().await?;
-        let llm_service = llm::get_llm_manager().get_llm_service(&model)?;
-        //step3. 更新所有单词
-        for word in &mut words {
-            //如果单词没有音标
-            if word.meaning.is_none() {
-                let word_info = llm_service.get_word_info(word.word.as_str()).await?;
-                word.phonetic_uk = Some(word_info.uk_phonetic);
-                word.phonetic_us = Some(word_info.us_phonetic);
-                word.meaning = Some(serde_json::to_string(&word_info.meanings)?)
+    async fn delete_word(&self, word: &str) -> Result<()> {
+        let word = self.get_word(word).await?;
+        self.word_repository.delete(word.word_id.unwrap()).await
+    }
+
+    async fn update_word(&self, dto: &WordPatchDTO) -> Result<WordDetailDTO> {
+        let patch = dto.to_patch()?;
+        let word = self.word_repository.patch(dto.word_id, &patch).await?;
+        let locked_fields = self.word_repository.find_locked_fields(dto.word_id).await?;
+        Ok(WordDetailDTO {
+            word,
+            locked_fields,
+        })
+    }
+
+    async fn get_word_history(&self, word_id: i32) -> Result<WordHistoryDTO> {
+        self.word_repository
+            .find_by_id(word_id)
+            .await?
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        let locked_fields = self.word_repository.find_locked_fields(word_id).await?;
+        let edits = self
+            .word_repository
+            .find_edits(word_id)
+            .await?
+            .into_iter()
+            .map(WordEditDTO::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(WordHistoryDTO {
+            word_id,
+            locked_fields,
+            edits,
+        })
+    }
+
+    async fn get_word_revisions(&self, word_id: i32) -> Result<Vec<WordRevisionDTO>> {
+        self.word_repository
+            .find_revisions(word_id)
+            .await?
+            .into_iter()
+            .map(|revision| WordRevisionDTO::try_from(revision).map_err(Into::into))
+            .collect()
+    }
+
+    async fn compare_word_revisions(
+        &self,
+        dto: &WordRevisionCompareDTO,
+    ) -> Result<WordRevisionDiffDTO> {
+        let revisions = self.word_repository.find_revisions(dto.word_id).await?;
+        let content = |id: i32| {
+            revisions
+                .iter()
+                .find(|revision| revision.id == Some(id))
+                .ok_or_else(|| anyhow!("Revision {} of word {} not found", id, dto.word_id))?
+                .content()
+        };
+        let from = content(dto.from)?;
+        let to = match dto.to {
+            Some(id) => content(id)?,
+            None => {
+                let word = self
+                    .word_repository
+                    .find_by_id(dto.word_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Word {} not found", dto.word_id))?;
+                WordContent::from(&word)
             }
-            word.pronunciation_us = Some(format!(
-                "http://dict.youdao.com/dictvoice?type=0&audio={}",
-                word.word
-            ));
-            word.pronunciation_uk = Some(format!(
-                "http://dict.youdao.com/dictvoice?type=1&audio={}",
-                word.word
-            ));
-            //如果单词没有例句和释义
-            if word.meaning.is_none() {
-                let example = llm_service
-                    .get_example_sentences(word.word.as_str())
+        };
+        Ok(WordRevisionDiffDTO {
+            word_id: dto.word_id,
+            from: dto.from,
+            to: dto.to,
+            changes: serde_json::Value::Object(from.diff(&to)),
+        })
+    }
+
+    async fn rollback_word(&self, dto: &WordRollbackDTO) -> Result<Word> {
+        self.word_repository
+            .rollback(dto.word_id, dto.revision_id, dto.editor.as_deref())
+            .await
+    }
+
+    async fn get_meaning_parse_failures(&self) -> Result<Vec<MeaningParseFailureDTO>> {
+        self.word_repository
+            .find_meaning_parse_failures()
+            .await?
+            .into_iter()
+            .map(|failure| MeaningParseFailureDTO::try_from(failure).map_err(Into::into))
+            .collect()
+    }
+
+    async fn enrich_word(&self, word: &Word) -> Result<Word> {
+        let mut word = word.clone();
+        let locked = match word.word_id {
+            Some(id) => self.word_repository.find_locked_fields(id).await?,
+            None => Vec::new(),
+        };
+        let missing = |field: WordField| {
+            !locked.contains(&field) && word.field(field).is_none_or(|v| v.trim().is_empty())
+        };
+        let needs_meaning = missing(WordField::Meaning);
+        let needs_example = missing(WordField::Example);
+        let needs_pronunciation =
+            word.pronunciation_us.is_none() || word.pronunciation_uk.is_none();
+        if !needs_meaning && !needs_example && !needs_pronunciation {
+            return Ok(word);
+        }
+
+        let (pronunciation_us, pronunciation_uk) = pronunciation_urls(&word.word);
+        word.pronunciation_us = Some(pronunciation_us);
+        word.pronunciation_uk = Some(pronunciation_uk);
+        if needs_meaning || needs_example {
+            let model = self.system_config_service.get_use_model().await?;
+            //如果单词没有释义
+            if needs_meaning {
+                let (word_info, meaning_version) =
+                    self.llm_word_info(&model, word.word.as_str()).await?;
+                word.phonetic_uk = Some(format!("/{}/", word_info.uk_phonetic));
+                word.phonetic_us = Some(format!("/{}/", word_info.us_phonetic));
+                word.meanings = word_info.meanings;
+                word.meaning_prompt_version = Some(meaning_version);
+            }
+            //如果单词没有例句
+            if needs_example {
+                let (example, example_version) = self
+                    .llm_example_sentences(&model, word.word.as_str())
                     .await?;
                 word.example = Some(example);
+                word.example_prompt_version = Some(example_version);
             }
-            self.word_repository.save(&word).await?;
         }
-        Ok(())
+        // 只补全发音地址时不调用大模型，发音地址来自有道词典
+        let source = if needs_meaning || needs_example {
+            RevisionSource::Llm
+        } else {
+            RevisionSource::Dictionary
+        };
+        self.word_repository.save_as(&word, source).await
+    }
+
+    async fn generate_grade_examples(
+        &self,
+        word: &str,
+        profile: &GradeProfile,
+    ) -> Result<(String, i32)> {
+        let model = self.system_config_service.get_use_model().await?;
+        llm::get_llm_manager()
+            .execute(&model, "get_grade_example_sentences", |service| {
+                let word = word.to_string();
+                let profile = profile.clone();
+                async move { service.get_grade_example_sentences(&word, &profile).await }
+            })
+            .await
+    }
+
+    async fn suggest_unit_meaning(
+        &self,
+        word: &Word,
+        context: &UnitContext,
+    ) -> Result<Option<WordMeaning>> {
+        let senses = utils::split_senses(&word.meanings);
+        if senses.len() <= 1 {
+            return Ok(senses.into_iter().next());
+        }
+
+        let model = self.system_config_service.get_use_model().await?;
+        let index = llm::get_llm_manager()
+            .execute(&model, "pick_unit_meaning", |service| {
+                let word = word.word.clone();
+                let context = context.clone();
+                let senses = senses.clone();
+                async move { service.pick_unit_meaning(&word, &context, &senses).await }
+            })
+            .await?;
+        Ok(senses.into_iter().nth(index))
+    }
+
+    async fn explain_word_stream(&self, word: &str) -> Result<ChatStream> {
+        let model = self.system_config_service.get_use_model().await?;
+        llm::get_llm_manager()
+            .execute(&model, "explain_word_stream", |service| {
+                let word = word.trim().to_string();
+                async move { service.explain_word_stream(&word).await }
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::{self, FixedModelConfig, InMemoryWordRepository, OfflineThirdParty};
+    use crate::infrastructure::database::repositories::Repository;
+    use crate::infrastructure::llm::prompts::BUILTIN_VERSION;
+    use futures::StreamExt;
+
+    fn service(model: &str) -> (WordServiceImpl, Arc<InMemoryWordRepository>) {
+        testing::init_mock_llm(model);
+        let words = Arc::new(InMemoryWordRepository::default());
+        let service = WordServiceImpl::new(
+            words.clone(),
+            FixedModelConfig::new(model),
+            Arc::new(OfflineThirdParty),
+        );
+        (service, words)
+    }
+
+    #[tokio::test]
+    async fn test_create_word_with_mock_llm() {
+        let (service, words) = service("mock");
+
+        let word = service.create_word("hello").await.unwrap();
+        assert!(word.word_id.is_some());
+        assert_eq!(word.phonetic_us.as_deref(), Some("/həˈloʊ/"));
+        assert_eq!(word.phonetic_uk.as_deref(), Some("/həˈləʊ/"));
+        assert!(word.meanings.iter().any(|m| m.definition.contains("你好")));
+        assert_eq!(word.example.unwrap().lines().count(), 4);
+        // 第三方词典离线，释义和例句都来自内置提示词
+        assert_eq!(word.meaning_prompt_version, Some(BUILTIN_VERSION));
+        assert_eq!(word.example_prompt_version, Some(BUILTIN_VERSION));
+
+        // 已有释义的单词直接返回
+        let again = service.create_word("hello").await.unwrap();
+        assert_eq!(again.word_id, word.word_id);
+        assert_eq!(words.table.all().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_word_completes_missing_phonetics() {
+        let (service, _) = service("mock");
+
+        let word = service.create_word("psst").await.unwrap();
+        assert_eq!(word.phonetic_us.as_deref(), Some("/pst/"));
+        assert_eq!(word.phonetic_uk.as_deref(), Some("/pst/"));
+    }
+
+    #[tokio::test]
+    async fn test_create_word_repairs_malformed_output() {
+        let (service, _) = service("mock");
+
+        let word = service.create_word("flaky").await.unwrap();
+        assert!(word.example.unwrap().starts_with("The flaky pastry"));
+    }
+
+    #[tokio::test]
+    async fn test_create_word_fails_without_saving() {
+        let (service, words) = service("mock-faulty");
+
+        let error = service.create_word("unavailable").await.unwrap_err();
+        assert!(error.to_string().contains("503"), "{}", error);
+
+        let error = service.create_word("malformed").await.unwrap_err();
+        assert!(error.to_string().contains("attempts"), "{}", error);
+        assert!(words.table.all().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_locked_fields_survive_enrichment() {
+        let (service, words) = service("mock");
+        let word = service.create_word("hello").await.unwrap();
+        let word_id = word.word_id.unwrap();
+
+        let patch: WordPatchDTO = serde_json::from_value(serde_json::json!({
+            "word_id": word_id,
+            "phonetic_us": "/hɛˈloʊ/",
+            "meanings": [{"pos": "int.", "definition": "喂"}],
+            "example": "",
+            "editor": "editor-1"
+        }))
+        .unwrap();
+        let updated = service.update_word(&patch).await.unwrap();
+        assert_eq!(updated.word.phonetic_us.as_deref(), Some("/hɛˈloʊ/"));
+        assert_eq!(updated.word.meaning_prompt_version, None);
+        assert!(updated.word.example.is_none());
+        assert_eq!(
+            updated.locked_fields,
+            vec![
+                WordField::Example,
+                WordField::Meaning,
+                WordField::PhoneticUs
+            ]
+        );
+
+        // 补全任务只生成缺失且未锁定的字段，直接保存也不会覆盖锁定的字段
+        let enriched = service.enrich_word(&updated.word).await.unwrap();
+        assert!(enriched.example.is_none());
+        let mut overwrite = enriched.clone();
+        overwrite.meanings = Vec::new();
+        overwrite.phonetic_uk = Some("/x/".to_string());
+        let saved = words.save(&overwrite).await.unwrap();
+        assert_eq!(saved.meanings[0].definition, "喂");
+        assert_eq!(saved.phonetic_uk.as_deref(), Some("/x/"));
+
+        // 解除锁定后可以再次补全
+        let unlock: WordPatchDTO = serde_json::from_value(serde_json::json!({
+            "word_id": word_id,
+            "unlock": ["example"]
+        }))
+        .unwrap();
+        let unlocked = service.update_word(&unlock).await.unwrap();
+        assert!(!unlocked.locked_fields.contains(&WordField::Example));
+        let enriched = service.enrich_word(&unlocked.word).await.unwrap();
+        assert_eq!(enriched.example.unwrap().lines().count(), 4);
+
+        let history = service.get_word_history(word_id).await.unwrap();
+        let fields: Vec<&str> = history.edits.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["example", "meaning", "phonetic_us"]);
+        assert_eq!(history.edits[0].editor.as_deref(), Some("editor-1"));
+        assert!(service.update_word(&WordPatchDTO::default()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revisions_compare_and_rollback() {
+        let (service, _) = service("mock");
+        let word = service.create_word("hello").await.unwrap();
+        let word_id = word.word_id.unwrap();
+        let patch: WordPatchDTO = serde_json::from_value(serde_json::json!({
+            "word_id": word_id,
+            "example": "Hello there.",
+            "editor": "editor-1"
+        }))
+        .unwrap();
+        service.update_word(&patch).await.unwrap();
+
+        let revisions = service.get_word_revisions(word_id).await.unwrap();
+        let sources: Vec<&str> = revisions.iter().map(|r| r.source.as_str()).collect();
+        assert_eq!(sources, vec!["editor", "llm"]);
+        let (edited, created) = (revisions[0].id.unwrap(), revisions[1].id.unwrap());
+        assert_eq!(revisions[0].editor.as_deref(), Some("editor-1"));
+        assert_eq!(revisions[0].changes["example"]["new"], "Hello there.");
+
+        let diff = service
+            .compare_word_revisions(&WordRevisionCompareDTO {
+                word_id,
+                from: created,
+                to: Some(edited),
+            })
+            .await
+            .unwrap();
+        let changes = diff.changes.as_object().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(changes.contains_key("example"));
+        assert!(changes.contains_key("example_prompt_version"));
+
+        let rolled_back = service
+            .rollback_word(&WordRollbackDTO {
+                word_id,
+                revision_id: created,
+                editor: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(rolled_back.example, word.example);
+        assert_eq!(rolled_back.example_prompt_version, Some(BUILTIN_VERSION));
+        let revisions = service.get_word_revisions(word_id).await.unwrap();
+        assert_eq!(revisions[0].source, "rollback");
+        let diff = service
+            .compare_word_revisions(&WordRevisionCompareDTO {
+                word_id,
+                from: created,
+                to: None,
+            })
+            .await
+            .unwrap();
+        assert!(diff.changes.as_object().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_explain_word_stream() {
+        let (service, _) = service("mock");
+
+        let text: String = service
+            .explain_word_stream(" hello ")
+            .await
+            .unwrap()
+            .map(|delta| delta.unwrap())
+            .collect()
+            .await;
+        assert!(text.starts_with("**hello**"));
     }
 }
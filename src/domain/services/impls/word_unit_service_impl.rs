@@ -1,8 +1,19 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use futures::{stream, StreamExt};
+use std::collections::HashSet;
 use std::sync::Arc;
 
-use crate::api::dto::unit_word_dto::WordDTO;
+use crate::api::dto::sequence_dto::{MoveDTO, SwapDTO};
+use crate::api::dto::unit_word_dto::{
+    ImportRowStatus, UnitMeaningDTO, UnitWordImportDTO, UnitWordImportReportDTO,
+    UnitWordImportRowDTO, WordDTO,
+};
+use crate::common::utils::sequence::Reorder;
+use crate::common::utils::word_list::{self, ImportFormat, WordListRow};
+use crate::domain::models::textbook::Textbook;
+use crate::domain::models::unit::Unit;
+use crate::domain::models::word::Word;
 use crate::domain::models::word_unit_mapping::WordUnitMapping;
 use crate::domain::services::interfaces::word_service::WordService;
 
@@ -10,6 +21,14 @@ use crate::domain::services::interfaces::word_unit_service::WordUnitService;
 use crate::infrastructure::database::repositories::{
     TextbookRepository, UnitRepository, WordRepository, WordUnitMappingRepository,
 };
+use crate::infrastructure::dto::WordMeaning;
+use crate::infrastructure::llm::prompts::{GradeProfile, UnitContext};
+use tracing::warn;
+
+/// 批量导入时同时补全单词信息的最大并发数
+const IMPORT_CONCURRENCY: usize = 4;
+/// 单次导入的最大行数
+const MAX_IMPORT_ROWS: usize = 1000;
 
 pub struct WordUnitServiceImpl {
     word_unit_repository: Arc<dyn WordUnitMappingRepository>,
@@ -35,6 +54,170 @@ impl WordUnitServiceImpl {
             textbook_repository,
         }
     }
+
+    /// 单元单词所属的单元
+    async fn unit_of(&self, id: i32) -> Result<i32> {
+        self.word_unit_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("Unit word {} not found", id))?
+            .unit_id
+            .ok_or_else(|| anyhow!("Unit word {} does not belong to a unit", id))
+    }
+
+    async fn reorder(&self, unit_id: i32, op: Reorder) -> Result<Vec<WordDTO>> {
+        self.word_unit_repository.reorder(unit_id, op).await?;
+        self.get_unit_words(unit_id).await
+    }
+
+    /// 按课本年级生成例句；课本没有年级或生成失败时返回 None，单元内使用单词的通用例句
+    async fn grade_examples(&self, word: &str, textbook: &Textbook) -> Option<(String, i32)> {
+        let profile = GradeProfile::for_grade(textbook.grade_id?, textbook.grade.as_deref())?;
+        match self
+            .word_service
+            .generate_grade_examples(word, &profile)
+            .await
+        {
+            Ok(result) => Some(result),
+            Err(e) => {
+                warn!(
+                    "Failed to generate {} examples for '{}': {}",
+                    profile.grade, word, e
+                );
+                None
+            }
+        }
+    }
+
+    /// 单词所在的课本、单元名称以及同单元的其他单词
+    async fn unit_context(
+        &self,
+        word: &Word,
+        unit: &Unit,
+        textbook: &Textbook,
+    ) -> Result<UnitContext> {
+        let words = self
+            .word_unit_repository
+            .find_word_by_unit_id(unit.id.unwrap())
+            .await?
+            .into_iter()
+            .filter(|w| w.word_id != word.word_id)
+            .map(|w| w.word)
+            .collect();
+        Ok(UnitContext {
+            textbook: textbook.name.clone(),
+            unit: unit.name.clone().unwrap_or_default(),
+            words,
+        })
+    }
+
+    /// 推荐单元含义；失败时记录日志并返回 None，单元内使用单词的通用释义
+    async fn suggest_meaning(
+        &self,
+        word: &Word,
+        unit: &Unit,
+        textbook: &Textbook,
+    ) -> Option<WordMeaning> {
+        let result = match self.unit_context(word, unit, textbook).await {
+            Ok(context) => self.word_service.suggest_unit_meaning(word, &context).await,
+            Err(e) => Err(e),
+        };
+        result.unwrap_or_else(|e| {
+            warn!("Failed to suggest unit meaning for '{}': {}", word.word, e);
+            None
+        })
+    }
+
+    /// 新建单元单词：按课本年级生成例句，suggest_meaning 为 true 时按单元上下文推荐含义
+    async fn build_mapping(
+        &self,
+        word: &Word,
+        unit: &Unit,
+        textbook: &Textbook,
+        suggest_meaning: bool,
+    ) -> WordUnitMapping {
+        let mut mapping = WordUnitMapping::new();
+        mapping.word_id = word.word_id;
+        mapping.unit_id = unit.id;
+        if suggest_meaning {
+            if let Some(sense) = self.suggest_meaning(word, unit, textbook).await {
+                mapping.pos = Some(sense.pos);
+                mapping.meaning = Some(sense.definition);
+            }
+        }
+        if let Some((example, version)) = self.grade_examples(&word.word, textbook).await {
+            mapping.example = Some(example);
+            mapping.example_prompt_version = Some(version);
+        }
+        mapping
+    }
+
+    /// 导入一行：补全单词信息后绑定到单元，导入内容中的含义优先于推荐的含义
+    async fn import_row(
+        &self,
+        line: usize,
+        row: WordListRow,
+        unit: &Unit,
+        textbook: &Textbook,
+    ) -> UnitWordImportRowDTO {
+        let result =
+            UnitWordImportRowDTO::new(line, Some(row.word.clone()), ImportRowStatus::Created);
+        let word = match self.word_service.create_word(&row.word).await {
+            Ok(word) => word,
+            Err(e) => {
+                return UnitWordImportRowDTO {
+                    status: ImportRowStatus::EnrichmentFailed,
+                    ..result
+                }
+                .with_message(e.to_string())
+            }
+        };
+
+        let mut mapping = self
+            .build_mapping(&word, unit, textbook, row.meaning.is_none())
+            .await;
+        if row.meaning.is_some() {
+            mapping.meaning = row.meaning;
+            mapping.pos = row.pos;
+        }
+        mapping.sequence = row.sequence;
+        match self.word_unit_repository.save(&mapping).await {
+            Ok(mapping) => UnitWordImportRowDTO {
+                id: mapping.id,
+                ..result
+            },
+            Err(e) => UnitWordImportRowDTO {
+                status: ImportRowStatus::EnrichmentFailed,
+                ..result
+            }
+            .with_message(e.to_string()),
+        }
+    }
+
+    /// 查询单元单词以及对应的单词、单元和课本
+    async fn find_unit_word(&self, id: i32) -> Result<(WordUnitMapping, Word, Unit, Textbook)> {
+        let mapping = self
+            .word_unit_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("Unit word {} not found", id))?;
+        let word = self
+            .word_repository
+            .find_by_id(mapping.word_id.unwrap_or_default())
+            .await?
+            .ok_or_else(|| anyhow!("Word of unit word {} not found", id))?;
+        let unit = self
+            .unit_repository
+            .find_by_id(mapping.unit_id.unwrap_or_default())
+            .await?
+            .ok_or_else(|| anyhow!("Unit of unit word {} not found", id))?;
+        let textbook = self
+            .textbook_repository
+            .find_by_id(unit.textbook_id.unwrap_or_default())
+            .await?
+            .ok_or_else(|| anyhow!("Textbook of unit word {} not found", id))?;
+        Ok((mapping, word, unit, textbook))
+    }
 }
 
 #[async_trait]
@@ -50,49 +233,458 @@ impl WordUnitService for WordUnitServiceImpl {
         let word = unit_word_dto.word.clone().unwrap();
         let word_entity = self.word_service.create_word(word.as_str()).await?;
 
-        //step2. 绑定单元
-        let mut unit_word = WordUnitMapping::new();
-        unit_word.word_id = Some(word_entity.word_id.unwrap());
-        unit_word.unit_id = Some(unit_word_dto.unit_id.unwrap());
-        let unit_word = self.word_unit_repository.save(&unit_word).await?;
-        //step3. 更新单元单词数
-        let mut unit = self
+        let unit_id = unit_word_dto.unit_id.unwrap();
+        let unit = self
             .unit_repository
-            .find_by_id(unit_word_dto.unit_id.unwrap())
+            .find_by_id(unit_id)
             .await?
             .expect("unit not found");
-        unit.word_count = Some(unit.word_count.unwrap() + 1);
-        self.unit_repository.save(&unit).await?;
-        //step4. 更新课本单词数
-        let unit_id = self
-            .unit_repository
-            .find_by_id(unit_word_dto.unit_id.unwrap())
+        let textbook = self
+            .textbook_repository
+            .find_by_id(unit.textbook_id.unwrap())
             .await?
-            .unwrap()
-            .textbook_id
             .unwrap();
-        let mut textbook = self.textbook_repository.find_by_id(unit_id).await?.unwrap();
-        textbook.word_count = Some(textbook.word_count.unwrap() + 1);
-        self.textbook_repository.save(&textbook).await?;
+
+        //step2. 绑定单元，单元和课本的单词数随映射在同一事务中更新
+        let unit_word = self
+            .build_mapping(&word_entity, &unit, &textbook, true)
+            .await;
+        let unit_word = self.word_unit_repository.save(&unit_word).await?;
 
         Ok(WordDTO::new(&word_entity, &unit_word))
     }
 
-    async fn delete_unit_word(&self, id: i32) -> Result<()> {
-        let unit_id = self
+    async fn import_unit_words(&self, dto: &UnitWordImportDTO) -> Result<UnitWordImportReportDTO> {
+        let unit = self
+            .unit_repository
+            .find_by_id(dto.unit_id)
+            .await?
+            .ok_or_else(|| anyhow!("Unit {} not found", dto.unit_id))?;
+        let textbook = self
+            .textbook_repository
+            .find_by_id(unit.textbook_id.unwrap_or_default())
+            .await?
+            .ok_or_else(|| anyhow!("Textbook of unit {} not found", dto.unit_id))?;
+
+        let format = dto
+            .format
+            .unwrap_or_else(|| ImportFormat::detect(&dto.content));
+        let lines = word_list::parse_word_list(&dto.content, format);
+        if lines.len() > MAX_IMPORT_ROWS {
+            return Err(anyhow!(
+                "Too many rows: {}, at most {} rows per import",
+                lines.len(),
+                MAX_IMPORT_ROWS
+            ));
+        }
+
+        //step1. 校验并与单元已有单词、导入内容中前面的行去重
+        let mut present: HashSet<String> = self
             .word_unit_repository
-            .find_by_id(id)
+            .find_word_by_unit_id(dto.unit_id)
             .await?
-            .expect("unit not found")
-            .unit_id
-            .expect("unit not found");
-        let mut unit = self
-            .unit_repository
-            .find_by_id(unit_id)
+            .into_iter()
+            .map(|word| word.word.to_lowercase())
+            .collect();
+        let mut rows = Vec::new();
+        let mut pending = Vec::new();
+        for line in lines {
+            match line.row {
+                Err(message) => rows.push(
+                    UnitWordImportRowDTO::new(line.line, None, ImportRowStatus::Invalid)
+                        .with_message(message),
+                ),
+                Ok(row) if !present.insert(row.word.to_lowercase()) => {
+                    rows.push(UnitWordImportRowDTO::new(
+                        line.line,
+                        Some(row.word),
+                        ImportRowStatus::AlreadyPresent,
+                    ))
+                }
+                Ok(row) => pending.push((line.line, row)),
+            }
+        }
+
+        //step2. 并发补全新单词并绑定单元
+        let imported: Vec<UnitWordImportRowDTO> = stream::iter(pending)
+            .map(|(line, row)| self.import_row(line, row, &unit, &textbook))
+            .buffer_unordered(IMPORT_CONCURRENCY)
+            .collect()
+            .await;
+        rows.extend(imported);
+        Ok(UnitWordImportReportDTO::new(dto.unit_id, rows))
+    }
+
+    async fn suggest_unit_meaning(&self, id: i32) -> Result<UnitMeaningDTO> {
+        let (_, word, unit, textbook) = self.find_unit_word(id).await?;
+        let context = self.unit_context(&word, &unit, &textbook).await?;
+        let sense = self
+            .word_service
+            .suggest_unit_meaning(&word, &context)
             .await?
-            .expect("unit not found");
-        unit.word_count = Some(unit.word_count.unwrap() - 1);
-        self.unit_repository.save(&unit).await?;
+            .ok_or_else(|| anyhow!("Word '{}' has no meaning to choose from", word.word))?;
+        Ok(UnitMeaningDTO {
+            id: Some(id),
+            pos: Some(sense.pos),
+            meaning: Some(sense.definition),
+        })
+    }
+
+    async fn update_unit_meaning(&self, dto: &UnitMeaningDTO) -> Result<WordDTO> {
+        let id = dto.id.ok_or_else(|| anyhow!("id is required"))?;
+        let (mut mapping, word, _, _) = self.find_unit_word(id).await?;
+        let trimmed = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        mapping.meaning = trimmed(&dto.meaning);
+        mapping.pos = mapping.meaning.as_ref().and(trimmed(&dto.pos));
+        let mapping = self.word_unit_repository.save(&mapping).await?;
+        Ok(WordDTO::new(&word, &mapping))
+    }
+
+    async fn delete_unit_word(&self, id: i32) -> Result<()> {
+        self.word_unit_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("Unit word {} not found", id))?;
         self.word_unit_repository.delete(id).await
     }
+
+    async fn move_unit_word(&self, dto: &MoveDTO) -> Result<Vec<WordDTO>> {
+        let unit_id = self.unit_of(dto.id).await?;
+        self.reorder(unit_id, dto.into()).await
+    }
+
+    async fn swap_unit_words(&self, dto: &SwapDTO) -> Result<Vec<WordDTO>> {
+        let unit_id = self.unit_of(dto.id).await?;
+        if self.unit_of(dto.other_id).await? != unit_id {
+            return Err(anyhow!(
+                "Unit words {} and {} belong to different units",
+                dto.id,
+                dto.other_id
+            ));
+        }
+        self.reorder(unit_id, dto.into()).await
+    }
+
+    async fn renumber_unit_words(&self, unit_id: i32) -> Result<Vec<WordDTO>> {
+        self.reorder(unit_id, Reorder::Renumber).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::testing::{
+        self, FixedModelConfig, InMemoryTextbookRepository, InMemoryUnitRepository,
+        InMemoryWordRepository, InMemoryWordUnitMappingRepository, OfflineThirdParty,
+    };
+    use crate::domain::models::unit::Unit;
+    use crate::domain::services::impls::word_service_impl::WordServiceImpl;
+    use crate::domain::services::interfaces::SystemConfigService;
+    use crate::infrastructure::database::repositories::Repository;
+    use crate::infrastructure::llm::prompts::BUILTIN_VERSION;
+
+    struct Fixture {
+        service: WordUnitServiceImpl,
+        config: Arc<FixedModelConfig>,
+        mappings: Arc<InMemoryWordUnitMappingRepository>,
+        units: Arc<InMemoryUnitRepository>,
+        textbooks: Arc<InMemoryTextbookRepository>,
+        unit_id: i32,
+        textbook_id: i32,
+    }
+
+    async fn fixture(grade_id: Option<i32>) -> Fixture {
+        testing::init_mock_llm("mock");
+        let words = Arc::new(InMemoryWordRepository::default());
+        let units = Arc::new(InMemoryUnitRepository::default());
+        let textbooks = Arc::new(InMemoryTextbookRepository::default());
+        let mappings = Arc::new(
+            InMemoryWordUnitMappingRepository::new(words.clone())
+                .with_counters(units.clone(), textbooks.clone()),
+        );
+        let config = FixedModelConfig::new("mock");
+        let word_service = Arc::new(WordServiceImpl::new(
+            words.clone(),
+            config.clone(),
+            Arc::new(OfflineThirdParty),
+        ));
+
+        let textbook = textbooks
+            .save(&Textbook {
+                id: None,
+                version_id: None,
+                grade_id,
+                semester_id: None,
+                created_at: None,
+                name: "三年级上册".to_string(),
+                unit_count: Some(1),
+                word_count: Some(0),
+                textbook_version: None,
+                grade: None,
+                semester: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+        let mut unit = Unit::new();
+        unit.name = Some("Unit 1".to_string());
+        unit.textbook_id = textbook.id;
+        let unit = units.save(&unit).await.unwrap();
+
+        Fixture {
+            service: WordUnitServiceImpl::new(
+                mappings.clone(),
+                words,
+                word_service,
+                units.clone(),
+                textbooks.clone(),
+            ),
+            config,
+            mappings,
+            units,
+            textbooks,
+            unit_id: unit.id.unwrap(),
+            textbook_id: textbook.id.unwrap(),
+        }
+    }
+
+    fn unit_word(word: &str, unit_id: i32) -> WordDTO {
+        serde_json::from_value(serde_json::json!({"word": word, "unit_id": unit_id})).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_unit_word() {
+        let f = fixture(None).await;
+
+        let created = f
+            .service
+            .create_word_unit_mapping(&unit_word("apple", f.unit_id))
+            .await
+            .unwrap();
+        assert_eq!(created.word.as_deref(), Some("apple"));
+        assert_eq!(created.phonetic_us.as_deref(), Some("/apple/"));
+        // 课本没有年级时不单独生成例句
+        let mapping = f.mappings.table.find(created.id.unwrap()).unwrap();
+        assert!(mapping.example.is_none());
+        assert_eq!(created.example.unwrap().lines().count(), 4);
+
+        let words = f.service.get_unit_words(f.unit_id).await.unwrap();
+        assert_eq!(words.len(), 1);
+        assert_eq!(f.units.table.find(f.unit_id).unwrap().word_count, Some(1));
+        assert_eq!(
+            f.textbooks.table.find(f.textbook_id).unwrap().word_count,
+            Some(1)
+        );
+
+        f.service
+            .delete_unit_word(created.id.unwrap())
+            .await
+            .unwrap();
+        assert!(f
+            .service
+            .get_unit_words(f.unit_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(f.units.table.find(f.unit_id).unwrap().word_count, Some(0));
+        assert_eq!(
+            f.textbooks.table.find(f.textbook_id).unwrap().word_count,
+            Some(0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_move_and_swap_unit_words() {
+        let f = fixture(None).await;
+        let mut ids = Vec::new();
+        for word in ["apple", "hello"] {
+            let created = f
+                .service
+                .create_word_unit_mapping(&unit_word(word, f.unit_id))
+                .await
+                .unwrap();
+            ids.push(created.id.unwrap());
+        }
+        let order = |words: Vec<WordDTO>| -> Vec<(Option<String>, Option<i32>)> {
+            words.into_iter().map(|w| (w.word, w.sequence)).collect()
+        };
+
+        let moved = f
+            .service
+            .move_unit_word(&MoveDTO {
+                id: ids[1],
+                position: 1,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            order(moved),
+            vec![
+                (Some("hello".into()), Some(1)),
+                (Some("apple".into()), Some(2))
+            ]
+        );
+
+        let swapped = f
+            .service
+            .swap_unit_words(&SwapDTO {
+                id: ids[0],
+                other_id: ids[1],
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            order(swapped),
+            vec![
+                (Some("apple".into()), Some(1)),
+                (Some("hello".into()), Some(2))
+            ]
+        );
+        assert!(f
+            .service
+            .move_unit_word(&MoveDTO {
+                id: 999,
+                position: 1
+            })
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unit_word_uses_grade_examples() {
+        let f = fixture(Some(3)).await;
+
+        let created = f
+            .service
+            .create_word_unit_mapping(&unit_word("apple", f.unit_id))
+            .await
+            .unwrap();
+        let mapping = f.mappings.table.find(created.id.unwrap()).unwrap();
+        assert_eq!(mapping.example_prompt_version, Some(BUILTIN_VERSION));
+        assert_eq!(mapping.example, created.example);
+        assert!(mapping.example.unwrap().contains("apple"));
+
+        let words = f.service.get_unit_words(f.unit_id).await.unwrap();
+        assert_eq!(words[0].example, created.example);
+    }
+
+    #[tokio::test]
+    async fn test_unit_meaning_from_context() {
+        let f = fixture(None).await;
+
+        // hello 有四个义项，mock 按单元上下文选第三个
+        let created = f
+            .service
+            .create_word_unit_mapping(&unit_word("hello", f.unit_id))
+            .await
+            .unwrap();
+        let id = created.id.unwrap();
+        assert_eq!(created.pos.as_deref(), Some("n."));
+        assert_eq!(created.meanings.len(), 1);
+        assert_eq!(created.meanings[0].definition, "招呼");
+
+        let suggestion = f.service.suggest_unit_meaning(id).await.unwrap();
+        assert_eq!(suggestion.meaning.as_deref(), Some("招呼"));
+
+        let updated = f
+            .service
+            .update_unit_meaning(&UnitMeaningDTO {
+                id: Some(id),
+                pos: Some("int.".to_string()),
+                meaning: Some(" 喂 ".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(updated.pos.as_deref(), Some("int."));
+        let words = f.service.get_unit_words(f.unit_id).await.unwrap();
+        assert_eq!(words[0].meanings.len(), 1);
+        assert_eq!(words[0].meanings[0].pos, "int.");
+        assert_eq!(words[0].meanings[0].definition, "喂");
+
+        // 清空后恢复使用通用释义
+        let cleared = f
+            .service
+            .update_unit_meaning(&UnitMeaningDTO {
+                id: Some(id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(cleared.pos.is_none());
+        assert!(cleared
+            .meanings
+            .iter()
+            .any(|m| m.definition.contains("你好")));
+    }
+
+    #[tokio::test]
+    async fn test_import_unit_words() {
+        let f = fixture(None).await;
+        // 单独的模型名称，避免失败的请求影响其他测试的熔断器
+        testing::init_mock_llm("mock-import");
+        f.config.set_use_model("mock-import").await.unwrap();
+        f.service
+            .create_word_unit_mapping(&unit_word("banana", f.unit_id))
+            .await
+            .unwrap();
+
+        let content = "word,meaning,sequence\n\
+            apple,苹果,2\n\
+            Apple,,\n\
+            hello,,1\n\
+            banana,,\n\
+            unavailable,,3\n\
+            x1,,\n";
+        let report = f
+            .service
+            .import_unit_words(&UnitWordImportDTO {
+                unit_id: f.unit_id,
+                format: None,
+                content: content.to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(report.total, 6);
+        assert_eq!(report.created, 2);
+        assert_eq!(report.already_present, 2);
+        assert_eq!(report.enrichment_failed, 1);
+        assert_eq!(report.invalid, 1);
+        let statuses: Vec<_> = report
+            .rows
+            .iter()
+            .map(|row| (row.line, row.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (2, ImportRowStatus::Created),
+                (3, ImportRowStatus::AlreadyPresent),
+                (4, ImportRowStatus::Created),
+                (5, ImportRowStatus::AlreadyPresent),
+                (6, ImportRowStatus::EnrichmentFailed),
+                (7, ImportRowStatus::Invalid),
+            ]
+        );
+        assert!(report.rows[4].message.as_ref().unwrap().contains("503"));
+
+        // 按导入的顺序排列，没有顺序的排在后面
+        let words = f.service.get_unit_words(f.unit_id).await.unwrap();
+        let order: Vec<_> = words.iter().map(|w| w.word.as_deref().unwrap()).collect();
+        assert_eq!(order, vec!["hello", "apple", "banana"]);
+        assert_eq!(words[1].meanings.len(), 1);
+        assert_eq!(words[1].meanings[0].pos, "");
+        assert_eq!(words[1].meanings[0].definition, "苹果");
+        assert_eq!(words[0].pos.as_deref(), Some("n."));
+        assert_eq!(f.units.table.find(f.unit_id).unwrap().word_count, Some(3));
+        assert_eq!(
+            f.textbooks.table.find(f.textbook_id).unwrap().word_count,
+            Some(3)
+        );
+    }
 }
@@ -0,0 +1,17 @@
+use crate::api::dto::dictation_dto::{
+    DictationResultDTO, DictationSessionDTO, StartDictationDTO, SubmitDictationDTO,
+};
+use crate::domain::models::user::AuthUser;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait DictationService: Send + Sync {
+    /// 开始单元听写，返回按顺序播放的单词读音，不含拼写
+    async fn start(&self, user: &AuthUser, dto: &StartDictationDTO) -> Result<DictationSessionDTO>;
+    /// 查看听写的单词顺序，不含拼写；只有创建者和教师及以上角色可以查看
+    async fn get_session(&self, user: &AuthUser, id: i32) -> Result<DictationSessionDTO>;
+    /// 交卷，逐字母比较拼写并把写错的单词记入当前用户的错题本；每人每次听写只能交卷一次
+    async fn submit(&self, user: &AuthUser, dto: &SubmitDictationDTO)
+        -> Result<DictationResultDTO>;
+}
@@ -0,0 +1,9 @@
+use crate::api::dto::export_dto::ExportQueryDTO;
+use crate::infrastructure::export::ExportFile;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ExportService: Send + Sync {
+    /// 导出课本或单元的单词表，包含音标、释义和例句
+    async fn export_words(&self, query: &ExportQueryDTO) -> anyhow::Result<ExportFile>;
+}
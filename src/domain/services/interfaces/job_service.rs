@@ -0,0 +1,66 @@
+use crate::api::dto::job_dto::{JobDTO, JobQueryDTO};
+use crate::domain::models::job::Job;
+use crate::infrastructure::database::repositories::JobRepository;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait JobService: Send + Sync {
+    /// 新建任务，任务由后台 worker 异步执行
+    async fn enqueue(&self, dto: &JobDTO) -> anyhow::Result<JobDTO>;
+
+    /// 按状态和类型查询最近的任务
+    async fn get_jobs(&self, query: &JobQueryDTO) -> anyhow::Result<Vec<JobDTO>>;
+
+    /// 查询任务状态和进度
+    async fn get_job(&self, dto: &JobDTO) -> anyhow::Result<JobDTO>;
+
+    /// 取消待执行或执行中的任务，执行中的任务在下次上报进度或续租时停止
+    async fn cancel_job(&self, dto: &JobDTO) -> anyhow::Result<JobDTO>;
+
+    /// 把租约已过期的执行中任务重新排队，返回恢复的任务数；其他实例正在执行的任务不受影响
+    async fn recover(&self) -> anyhow::Result<u64>;
+
+    /// 领取并执行一个到期的任务，没有可执行的任务时返回 false
+    async fn run_next(&self) -> anyhow::Result<bool>;
+}
+
+/// 某一类任务的执行逻辑
+#[async_trait]
+pub trait JobRunner: Send + Sync {
+    /// 处理的任务类型，对应 jobs.kind
+    fn kind(&self) -> &'static str;
+
+    /// 执行任务，返回值保存到 jobs.result；返回错误时按退避时间重试
+    async fn run(&self, job: &Job, context: &JobContext) -> anyhow::Result<Option<Value>>;
+}
+
+/// 任务执行期间上报进度和断点
+pub struct JobContext {
+    job_id: i32,
+    repository: Arc<dyn JobRepository>,
+}
+
+impl JobContext {
+    pub fn new(job_id: i32, repository: Arc<dyn JobRepository>) -> Self {
+        Self { job_id, repository }
+    }
+
+    /// 保存进度和断点，任务已被取消时返回错误以便尽快停止执行
+    pub async fn progress(
+        &self,
+        done: i32,
+        total: i32,
+        checkpoint: Option<Value>,
+    ) -> anyhow::Result<()> {
+        let running = self
+            .repository
+            .update_progress(self.job_id, done, total, checkpoint.as_ref())
+            .await?;
+        if !running {
+            anyhow::bail!("Job {} is no longer running", self.job_id);
+        }
+        Ok(())
+    }
+}
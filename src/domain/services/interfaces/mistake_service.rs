@@ -0,0 +1,23 @@
+use crate::api::dto::mistake_dto::{MistakeDTO, MistakeQueryDTO, MistakeReviewQueryDTO};
+use crate::domain::models::mistake::MistakeAnswer;
+use crate::domain::models::user::AuthUser;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait MistakeService: Send + Sync {
+    /// 学生的错题本，最近答错的在前
+    async fn get_mistakes(
+        &self,
+        user: &AuthUser,
+        query: &MistakeQueryDTO,
+    ) -> Result<Vec<MistakeDTO>>;
+    /// 当前用户的针对性复习：按答错次数和最近答错的时间加权，权重高的在前
+    async fn get_review(
+        &self,
+        user: &AuthUser,
+        query: &MistakeReviewQueryDTO,
+    ) -> Result<Vec<MistakeDTO>>;
+    /// 记录测验和听写中的作答，返回移出错题本的条数
+    async fn record_answers(&self, student_id: i32, answers: &[MistakeAnswer]) -> Result<u64>;
+}
@@ -1,10 +1,19 @@
+pub(crate) mod dictation_service;
+pub(crate) mod export_service;
 pub mod grade_service;
+pub(crate) mod job_service;
+pub(crate) mod mistake_service;
 pub(crate) mod model_provider_service;
+pub(crate) mod prompt_template_service;
+pub(crate) mod quiz_service;
 pub mod semester_service;
+pub(crate) mod study_service;
 pub(crate) mod system_config_service;
 pub mod textbook_service;
 pub mod textbook_version_service;
+pub(crate) mod trash_service;
 pub mod unit_service;
+pub(crate) mod user_service;
 pub mod word_service;
 pub mod word_unit_service;
 
@@ -1,8 +1,40 @@
-use crate::domain::models::model_provider::ModelProvider;
+use crate::api::dto::llm_usage_dto::{LLMUsageQueryDTO, LLMUsageReportDTO};
+use crate::api::dto::model_provider_dto::ModelProviderDTO;
+use crate::infrastructure::llm::manager::LLMHealthReport;
+use crate::infrastructure::llm::structured::StructuredAttempt;
+use crate::infrastructure::llm::usage::UsageRecord;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait ModelProviderService: Send + Sync {
     /// Get all model providers
-    async fn get_all_providers(&self) -> anyhow::Result<Vec<ModelProvider>>;
+    async fn get_all_providers(&self) -> anyhow::Result<Vec<ModelProviderDTO>>;
+
+    /// Get a model provider by id
+    async fn get_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<ModelProviderDTO>;
+
+    /// Create a model provider, the api key is stored encrypted
+    async fn create_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<ModelProviderDTO>;
+
+    /// Update a model provider, the stored api key is kept when none is given
+    async fn update_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<ModelProviderDTO>;
+
+    /// Delete a model provider
+    async fn delete_provider(&self, dto: &ModelProviderDTO) -> anyhow::Result<()>;
+
+    /// Load active providers from the database into the LLM factory, returns the number loaded
+    async fn sync_llm_configs(&self) -> anyhow::Result<usize>;
+
+    /// Get circuit breaker state and recent failover events of LLM providers
+    async fn get_health(&self) -> anyhow::Result<LLMHealthReport>;
+
+    /// Persist the token usage of one LLM call
+    async fn record_usage(&self, record: UsageRecord) -> anyhow::Result<()>;
+
+    /// Persist one structured output attempt and its validation result
+    async fn record_structured_attempt(&self, attempt: StructuredAttempt) -> anyhow::Result<()>;
+
+    /// Get token usage and estimated cost aggregated by day, provider, model and operation
+    async fn get_usage_report(&self, query: &LLMUsageQueryDTO)
+        -> anyhow::Result<LLMUsageReportDTO>;
 }
@@ -0,0 +1,23 @@
+use crate::api::dto::prompt_template_dto::{PromptPreviewDTO, PromptTemplateDTO};
+use crate::infrastructure::llm::prompts::RenderedPrompt;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait PromptTemplateService: Send + Sync {
+    /// Import templates from PROMPT_TEMPLATE_DIR and load all stored versions into the registry,
+    /// returns the number of stored versions
+    async fn sync_templates(&self) -> anyhow::Result<usize>;
+
+    /// List all versions of all templates, including the built-in ones
+    async fn get_templates(&self) -> anyhow::Result<Vec<PromptTemplateDTO>>;
+
+    /// Create a new, inactive version of a template
+    async fn create_template(&self, dto: &PromptTemplateDTO) -> anyhow::Result<PromptTemplateDTO>;
+
+    /// Render a template version with the given variables
+    async fn preview_template(&self, dto: &PromptPreviewDTO) -> anyhow::Result<RenderedPrompt>;
+
+    /// Activate a template version, version 0 restores the built-in template
+    async fn activate_template(&self, dto: &PromptTemplateDTO)
+        -> anyhow::Result<PromptTemplateDTO>;
+}
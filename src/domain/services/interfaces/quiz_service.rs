@@ -0,0 +1,19 @@
+use crate::api::dto::quiz_dto::{
+    AssignQuizDTO, GenerateQuizDTO, QuizDTO, QuizResultDTO, SubmitQuizDTO,
+};
+use crate::api::dto::study_dto::AssignResultDTO;
+use crate::domain::models::user::AuthUser;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait QuizService: Send + Sync {
+    /// 从单元或教材中一段单元的单词生成测验并保存，返回不含答案的题目
+    async fn generate(&self, user: &AuthUser, dto: &GenerateQuizDTO) -> Result<QuizDTO>;
+    /// 查看测验题目，不含答案；只有创建者、教师及以上角色和被布置的学生可以查看
+    async fn get_quiz(&self, user: &AuthUser, id: i32) -> Result<QuizDTO>;
+    /// 把测验布置给学生，被布置的学生可以查看和作答；已经布置过的学生跳过
+    async fn assign(&self, teacher: &AuthUser, dto: &AssignQuizDTO) -> Result<AssignResultDTO>;
+    /// 交卷，按保存的答案判分并记录，答错的题目记入当前用户的错题本；每人每份测验只能交卷一次
+    async fn submit(&self, user: &AuthUser, dto: &SubmitQuizDTO) -> Result<QuizResultDTO>;
+}
@@ -0,0 +1,36 @@
+use crate::api::dto::study_dto::{
+    AssignResultDTO, AssignUnitDTO, ReviewAnswerDTO, ReviewItemDTO, ReviewQueueQueryDTO,
+    ReviewStateDTO, StudentQueryDTO, UnassignUnitDTO, UnitAssignmentDTO, UnitMasteryDTO,
+};
+use crate::domain::models::user::AuthUser;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait StudyService: Send + Sync {
+    /// 把单元布置给学生，只能布置给未禁用的学生账号
+    async fn assign_unit(&self, teacher: &AuthUser, dto: &AssignUnitDTO)
+        -> Result<AssignResultDTO>;
+    /// 取消布置，已有的复习状态保留
+    async fn unassign_unit(&self, dto: &UnassignUnitDTO) -> Result<()>;
+    /// 学生被布置的单元
+    async fn get_assignments(
+        &self,
+        user: &AuthUser,
+        query: &StudentQueryDTO,
+    ) -> Result<Vec<UnitAssignmentDTO>>;
+    /// 当前用户今天的复习队列：今天到期的复习和今天还能学习的新词
+    async fn get_review_queue(
+        &self,
+        user: &AuthUser,
+        query: &ReviewQueueQueryDTO,
+    ) -> Result<Vec<ReviewItemDTO>>;
+    /// 提交作答并按 SM-2 安排下次复习，单词必须在布置的单元中
+    async fn answer(&self, user: &AuthUser, dto: &ReviewAnswerDTO) -> Result<ReviewStateDTO>;
+    /// 学生每个布置单元的掌握百分比
+    async fn get_mastery(
+        &self,
+        user: &AuthUser,
+        query: &StudentQueryDTO,
+    ) -> Result<Vec<UnitMasteryDTO>>;
+}
@@ -1,4 +1,6 @@
-use crate::api::dto::textbook_dto::TextbookDTO;
+use crate::api::dto::textbook_dto::{
+    RecountReportDTO, TextbookCloneDTO, TextbookDTO, TextbookDiffDTO, TextbookDiffQueryDTO,
+};
 use crate::api::dto::unit_dto::UnitDTO;
 use crate::domain::models::textbook::Textbook;
 use anyhow::Result;
@@ -10,4 +12,10 @@ pub trait TextbookService: Send + Sync {
     async fn get_textbooks(&self) -> Result<Vec<TextbookDTO>>;
     async fn delete_textbook(&self, textbook_dto: &TextbookDTO) -> Result<()>;
     async fn get_unit_by_textbook(&self, textbook_dto: &TextbookDTO) -> Result<Vec<UnitDTO>>;
+    // 复制教材到其他版本、年级或学期，包括全部单元和单词
+    async fn clone_textbook(&self, dto: &TextbookCloneDTO) -> Result<TextbookDTO>;
+    // 按单元对比两本教材新增、删除和调整单元的单词
+    async fn diff_textbooks(&self, dto: &TextbookDiffQueryDTO) -> Result<TextbookDiffDTO>;
+    // 重新统计全部单元和课本的计数，修正并返回不一致的记录
+    async fn recount_counters(&self) -> Result<RecountReportDTO>;
 }
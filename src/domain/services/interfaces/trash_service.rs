@@ -0,0 +1,15 @@
+use crate::api::dto::trash_dto::{TrashItemDTO, TrashPurgeDTO, TrashQueryDTO, TrashTargetDTO};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait TrashService: Send + Sync {
+    /// 预览删除一条数据会连带删除的单元和单元单词，不修改数据
+    async fn preview_delete(&self, dto: &TrashTargetDTO) -> Result<TrashItemDTO>;
+    /// 列出保留期内可以恢复的数据，按删除时间倒序
+    async fn get_trash(&self, query: &TrashQueryDTO) -> Result<Vec<TrashItemDTO>>;
+    /// 恢复数据及同一次删除的子数据
+    async fn restore(&self, dto: &TrashTargetDTO) -> Result<TrashItemDTO>;
+    /// 永久删除超过保留期的数据
+    async fn purge_expired(&self) -> Result<TrashPurgeDTO>;
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
+use crate::api::dto::sequence_dto::{MoveDTO, SwapDTO};
 use crate::api::dto::unit_dto::UnitDTO;
 
 #[async_trait]
@@ -8,4 +9,10 @@ pub trait UnitService: Send + Sync {
     async fn create_unit(&self, unit: &UnitDTO) -> Result<UnitDTO>;
     async fn get_units(&self, unit_dto: &UnitDTO) -> Result<Vec<UnitDTO>>;
     async fn delete_unit(&self, id: i32) -> Result<()>;
+    /// 把单元移动到课本中的指定位置，返回调整后的单元列表
+    async fn move_unit(&self, dto: &MoveDTO) -> Result<Vec<UnitDTO>>;
+    /// 交换同一课本中两个单元的位置，返回调整后的单元列表
+    async fn swap_units(&self, dto: &SwapDTO) -> Result<Vec<UnitDTO>>;
+    /// 按当前顺序把课本中的单元重新编号为 1..n
+    async fn renumber_units(&self, textbook_id: i32) -> Result<Vec<UnitDTO>>;
 }
@@ -0,0 +1,33 @@
+use crate::api::dto::user_dto::{
+    ChangePasswordDTO, CreateUserDTO, LinkTokenDTO, LoginDTO, LoginResultDTO, UpdateUserDTO,
+    UserDTO,
+};
+use crate::domain::models::user::AuthUser;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait UserService: Send + Sync {
+    /// 校验用户名和密码并创建登录会话
+    async fn login(&self, dto: &LoginDTO) -> Result<LoginResultDTO>;
+    /// 退出登录，令牌随即失效
+    async fn logout(&self, token: &str) -> Result<()>;
+    /// 根据令牌查询登录用户，令牌无效、过期或账号被禁用时返回空
+    async fn authenticate(&self, token: &str) -> Result<Option<AuthUser>>;
+    /// 签发短期有效的链接令牌，用于无法设置请求头的下载链接和事件流
+    async fn issue_link_token(&self, user: &AuthUser) -> Result<LinkTokenDTO>;
+    /// 根据查询参数中的链接令牌查询登录用户，登录令牌不能在这里使用
+    async fn authenticate_link(&self, token: &str) -> Result<Option<AuthUser>>;
+    /// 查询单个用户
+    async fn get_user(&self, id: i32) -> Result<UserDTO>;
+    /// 列出全部用户
+    async fn get_users(&self) -> Result<Vec<UserDTO>>;
+    /// 创建用户
+    async fn create_user(&self, dto: &CreateUserDTO) -> Result<UserDTO>;
+    /// 修改用户的角色、名称、禁用状态或重置密码，不能移除最后一个可用的管理员
+    async fn update_user(&self, dto: &UpdateUserDTO) -> Result<UserDTO>;
+    /// 校验原密码后修改自己的密码，原有的登录会话全部失效，需要重新登录
+    async fn change_password(&self, user: &AuthUser, dto: &ChangePasswordDTO) -> Result<()>;
+    /// 还没有任何用户时创建初始管理员，返回是否创建
+    async fn ensure_admin(&self, username: &str, password: &str) -> Result<bool>;
+}
@@ -1,6 +1,14 @@
 use async_trait::async_trait;
 
+use crate::api::dto::word_edit_dto::{WordDetailDTO, WordHistoryDTO, WordPatchDTO};
+use crate::api::dto::word_meaning_dto::MeaningParseFailureDTO;
+use crate::api::dto::word_revision_dto::{
+    WordRevisionCompareDTO, WordRevisionDTO, WordRevisionDiffDTO, WordRollbackDTO,
+};
 use crate::domain::models::word::Word;
+use crate::infrastructure::dto::WordMeaning;
+use crate::infrastructure::llm::prompts::{GradeProfile, UnitContext};
+use crate::infrastructure::llm::ChatStream;
 
 use anyhow::Result;
 
@@ -8,5 +16,37 @@ use anyhow::Result;
 pub trait WordService: Send + Sync {
     async fn create_word(&self, word: &str) -> Result<Word>;
     async fn get_word(&self, word: &str) -> Result<Word>;
-    async fn update_batch_words(&self) -> Result<()>;
+    // 软删除单词并从所有单元中移除，可在回收站中恢复
+    async fn delete_word(&self, word: &str) -> Result<()>;
+    // 人工修改单词的音标、释义和例句，修改的字段默认锁定并记录修改历史
+    async fn update_word(&self, dto: &WordPatchDTO) -> Result<WordDetailDTO>;
+    // 查询单词锁定的字段和人工修改历史
+    async fn get_word_history(&self, word_id: i32) -> Result<WordHistoryDTO>;
+    // 查询单词的修订版本，最新的在前
+    async fn get_word_revisions(&self, word_id: i32) -> Result<Vec<WordRevisionDTO>>;
+    // 比较单词的两个版本
+    async fn compare_word_revisions(
+        &self,
+        dto: &WordRevisionCompareDTO,
+    ) -> Result<WordRevisionDiffDTO>;
+    // 把单词回滚到指定版本，回滚本身也记录为一个新版本
+    async fn rollback_word(&self, dto: &WordRollbackDTO) -> Result<Word>;
+    // 查询迁移到 word_meanings 时无法解析的释义，供人工核对
+    async fn get_meaning_parse_failures(&self) -> Result<Vec<MeaningParseFailureDTO>>;
+    // 补全单词缺失的音标、释义、例句和发音地址，信息已完整时不调用大模型；被锁定的字段保持不变
+    async fn enrich_word(&self, word: &Word) -> Result<Word>;
+    // 按年级生成例句，同时返回使用的提示词版本
+    async fn generate_grade_examples(
+        &self,
+        word: &str,
+        profile: &GradeProfile,
+    ) -> Result<(String, i32)>;
+    // 根据教材上下文从单词的释义中选出最合适的义项，单词没有释义时返回 None
+    async fn suggest_unit_meaning(
+        &self,
+        word: &Word,
+        context: &UnitContext,
+    ) -> Result<Option<WordMeaning>>;
+    // 流式生成单词讲解
+    async fn explain_word_stream(&self, word: &str) -> Result<ChatStream>;
 }
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::api::dto::unit_word_dto::WordDTO;
+use crate::api::dto::sequence_dto::{MoveDTO, SwapDTO};
+use crate::api::dto::unit_word_dto::{
+    UnitMeaningDTO, UnitWordImportDTO, UnitWordImportReportDTO, WordDTO,
+};
 
 #[async_trait]
 pub trait WordUnitService: Send + Sync {
@@ -9,6 +12,18 @@ pub trait WordUnitService: Send + Sync {
     async fn get_unit_words(&self, unit_id: i32) -> Result<Vec<WordDTO>>;
     // 绑定单词与单元
     async fn create_word_unit_mapping(&self, unit_word_dto: &WordDTO) -> Result<WordDTO>;
+    // 批量导入单元单词，返回每一行的导入结果
+    async fn import_unit_words(&self, dto: &UnitWordImportDTO) -> Result<UnitWordImportReportDTO>;
+    // 根据教材上下文推荐单词在单元中的含义，不保存
+    async fn suggest_unit_meaning(&self, id: i32) -> Result<UnitMeaningDTO>;
+    // 修改单词在单元中的含义
+    async fn update_unit_meaning(&self, dto: &UnitMeaningDTO) -> Result<WordDTO>;
     // 删除单元中单词
     async fn delete_unit_word(&self, id: i32) -> Result<()>;
+    // 把单词移动到单元中的指定位置，返回调整后的单元单词
+    async fn move_unit_word(&self, dto: &MoveDTO) -> Result<Vec<WordDTO>>;
+    // 交换同一单元中两个单词的位置，返回调整后的单元单词
+    async fn swap_unit_words(&self, dto: &SwapDTO) -> Result<Vec<WordDTO>>;
+    // 按当前顺序把单元中的单词重新编号为 1..n
+    async fn renumber_unit_words(&self, unit_id: i32) -> Result<Vec<WordDTO>>;
 }
@@ -1,5 +1,27 @@
 pub mod impls;
 pub mod interfaces;
 
+pub use impls::dictation_service_impl::DictationServiceImpl;
+pub use impls::export_service_impl::ExportServiceImpl;
+pub use impls::job_service_impl::{
+    job_lease_from_env, spawn_job_workers, JobServiceImpl, WorkerConfig,
+};
+pub use impls::mistake_service_impl::{clear_streak_from_env, MistakeServiceImpl};
 pub use impls::model_provider_service_impl::ModelProviderServiceImpl;
+pub use impls::prompt_template_service_impl::PromptTemplateServiceImpl;
+pub use impls::quiz_service_impl::QuizServiceImpl;
+pub use impls::study_service_impl::StudyServiceImpl;
+pub use impls::trash_service_impl::{
+    purge_interval_from_env, retention_from_env, spawn_trash_purger, TrashServiceImpl,
+};
+pub use impls::user_service_impl::{token_ttl_from_env, UserServiceImpl};
+pub use interfaces::dictation_service::DictationService;
+pub use interfaces::export_service::ExportService;
+pub use interfaces::job_service::JobService;
+pub use interfaces::mistake_service::MistakeService;
 pub use interfaces::model_provider_service::ModelProviderService;
+pub use interfaces::prompt_template_service::PromptTemplateService;
+pub use interfaces::quiz_service::QuizService;
+pub use interfaces::study_service::StudyService;
+pub use interfaces::trash_service::TrashService;
+pub use interfaces::user_service::UserService;
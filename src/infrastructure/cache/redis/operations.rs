@@ -97,6 +97,7 @@ mod tests {
     use crate::infrastructure::cache::redis::RedisConfig;
 
     #[tokio::test]
+    #[ignore = "requires a running Redis server"]
     async fn test_redis_operations() -> Result<(), RedisServiceError> {
         let config = RedisConfig::from_env()?;
         let client = RedisClient::new(config)?;
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domain::models::dictation::{DictationAttempt, DictationSession};
+
+/// 听写和交卷记录
+#[async_trait]
+pub trait DictationRepository: Send + Sync {
+    async fn save_session(&self, session: &DictationSession) -> Result<DictationSession>;
+
+    async fn find_session(&self, id: i32) -> Result<Option<DictationSession>>;
+
+    /// 保存交卷记录，该用户已交过这次听写时返回 None
+    async fn save_attempt(&self, attempt: &DictationAttempt) -> Result<Option<DictationAttempt>>;
+}
+
+pub struct DictationRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl DictationRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DictationRepository for DictationRepositoryImpl {
+    async fn save_session(&self, session: &DictationSession) -> Result<DictationSession> {
+        let saved = sqlx::query_as::<_, DictationSession>(
+            r#"
+            INSERT INTO dictation_sessions (unit_id, seed, repeat_count, pause_ms, entries, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(session.unit_id)
+        .bind(session.seed)
+        .bind(session.repeat_count)
+        .bind(session.pause_ms)
+        .bind(sqlx::types::Json(&session.entries))
+        .bind(session.created_by)
+        .fetch_one(&*self.pool)
+        .await?;
+        Ok(saved)
+    }
+
+    async fn find_session(&self, id: i32) -> Result<Option<DictationSession>> {
+        let session =
+            sqlx::query_as::<_, DictationSession>("SELECT * FROM dictation_sessions WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&*self.pool)
+                .await?;
+        Ok(session)
+    }
+
+    async fn save_attempt(&self, attempt: &DictationAttempt) -> Result<Option<DictationAttempt>> {
+        let saved = sqlx::query_as::<_, DictationAttempt>(
+            r#"
+            INSERT INTO dictation_attempts (session_id, user_id, results, correct, total)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (session_id, user_id) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(attempt.session_id)
+        .bind(attempt.user_id)
+        .bind(sqlx::types::Json(&attempt.results))
+        .bind(attempt.correct)
+        .bind(attempt.total)
+        .fetch_optional(&*self.pool)
+        .await?;
+        Ok(saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::dictation::DictationEntry;
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_single_attempt() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let dictations = DictationRepositoryImpl::new(pool.clone());
+
+        let (unit_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO units (name, sequence_number, word_count) VALUES ('dictation-unit', 1, 1) RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        let (word_id,): (i32,) =
+            sqlx::query_as("INSERT INTO words (word) VALUES ('dictation-apple') RETURNING word_id")
+                .fetch_one(&*pool)
+                .await?;
+        let (user_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, password_hash, role) VALUES ('dictation-student', '', 'student') RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+
+        let session = dictations
+            .save_session(&DictationSession {
+                id: None,
+                unit_id,
+                seed: None,
+                repeat_count: 2,
+                pause_ms: 3000,
+                entries: vec![DictationEntry {
+                    index: 0,
+                    word_id,
+                    word: "dictation-apple".to_string(),
+                    pronunciation_us: None,
+                    pronunciation_uk: None,
+                }],
+                created_by: Some(user_id),
+                created_at: None,
+            })
+            .await?;
+        let session_id = session.id.unwrap();
+        assert_eq!(
+            dictations.find_session(session_id).await?.unwrap().entries,
+            session.entries
+        );
+
+        let attempt = DictationAttempt::grade(
+            session_id,
+            user_id,
+            &session.entries,
+            &[(0, "dictation-aple".to_string())],
+        );
+        let saved = dictations.save_attempt(&attempt).await?.unwrap();
+        assert_eq!((saved.correct, saved.total), (0, 1));
+        // 同一用户不能重复交卷
+        assert!(dictations.save_attempt(&attempt).await?.is_none());
+
+        sqlx::query("DELETE FROM units WHERE id = $1")
+            .bind(unit_id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM words WHERE word_id = $1")
+            .bind(word_id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use crate::domain::models::job::Job;
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    /// 新建任务
+    async fn create(&self, job: &Job) -> anyhow::Result<Job>;
+
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<Job>>;
+
+    /// 按状态和类型查询最近的任务
+    async fn find_recent(
+        &self,
+        status: Option<&str>,
+        kind: Option<&str>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Job>>;
+
+    /// 领取一个到期的待执行任务并标记为 running，租约在 lease 后到期；没有任务时返回 None
+    async fn claim_next(&self, lease: Duration) -> anyhow::Result<Option<Job>>;
+
+    /// 续租执行中的任务，任务已不在执行中（如被取消或被重新排队）时返回 false
+    async fn heartbeat(&self, id: i32, lease: Duration) -> anyhow::Result<bool>;
+
+    /// 更新进度和断点，任务已不在执行中（如被取消）时返回 false
+    async fn update_progress(
+        &self,
+        id: i32,
+        done: i32,
+        total: i32,
+        checkpoint: Option<&Value>,
+    ) -> anyhow::Result<bool>;
+
+    /// 标记任务成功，只对执行中的任务生效
+    async fn complete(&self, id: i32, result: Option<&Value>) -> anyhow::Result<()>;
+
+    /// 记录失败原因，retry_at 不为空时重新排队，否则标记为失败；只对执行中的任务生效
+    async fn fail(
+        &self,
+        id: i32,
+        error: &str,
+        retry_at: Option<OffsetDateTime>,
+    ) -> anyhow::Result<()>;
+
+    /// 取消待执行或执行中的任务，任务已结束时返回 false
+    async fn cancel(&self, id: i32) -> anyhow::Result<bool>;
+
+    /// 把租约已过期的执行中任务重新排队，用于恢复崩溃或退出的实例遗留的任务
+    async fn requeue_expired(&self) -> anyhow::Result<u64>;
+}
+
+pub struct JobRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl JobRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        JobRepositoryImpl { pool }
+    }
+}
+
+#[async_trait]
+impl JobRepository for JobRepositoryImpl {
+    async fn create(&self, job: &Job) -> anyhow::Result<Job> {
+        let job = sqlx::query_as::<_, Job>(
+            r#"
+            INSERT INTO jobs (kind, payload, status, max_attempts, run_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(&job.kind)
+        .bind(&job.payload)
+        .bind(&job.status)
+        .bind(job.max_attempts)
+        .bind(job.run_at)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn find_by_id(&self, id: i32) -> anyhow::Result<Option<Job>> {
+        let job = sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await?;
+
+        Ok(job)
+    }
+
+    async fn find_recent(
+        &self,
+        status: Option<&str>,
+        kind: Option<&str>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Job>> {
+        let jobs = sqlx::query_as::<_, Job>(
+            r#"
+            SELECT * FROM jobs
+            WHERE ($1::VARCHAR IS NULL OR status = $1)
+              AND ($2::VARCHAR IS NULL OR kind = $2)
+            ORDER BY id DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(status)
+        .bind(kind)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    async fn claim_next(&self, lease: Duration) -> anyhow::Result<Option<Job>> {
+        // SKIP LOCKED 保证多个 worker 不会领取同一个任务
+        let job = sqlx::query_as::<_, Job>(
+            r#"
+            UPDATE jobs
+            SET status = 'running', attempts = attempts + 1,
+                locked_until = CURRENT_TIMESTAMP + $1 * INTERVAL '1 second',
+                started_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+            WHERE id = (
+                SELECT id FROM jobs
+                WHERE status = 'pending' AND run_at <= CURRENT_TIMESTAMP
+                ORDER BY run_at, id
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(lease.as_secs_f64())
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn heartbeat(&self, id: i32, lease: Duration) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET locked_until = CURRENT_TIMESTAMP + $2 * INTERVAL '1 second'
+            WHERE id = $1 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(lease.as_secs_f64())
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_progress(
+        &self,
+        id: i32,
+        done: i32,
+        total: i32,
+        checkpoint: Option<&Value>,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET progress_done = $2, progress_total = $3,
+                checkpoint = COALESCE($4, checkpoint), updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(done)
+        .bind(total)
+        .bind(checkpoint)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn complete(&self, id: i32, result: Option<&Value>) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'succeeded', result = $2, last_error = NULL, locked_until = NULL,
+                finished_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(result)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fail(
+        &self,
+        id: i32,
+        error: &str,
+        retry_at: Option<OffsetDateTime>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = CASE WHEN $3::TIMESTAMPTZ IS NULL THEN 'failed' ELSE 'pending' END,
+                run_at = COALESCE($3, run_at),
+                finished_at = CASE WHEN $3::TIMESTAMPTZ IS NULL THEN CURRENT_TIMESTAMP END,
+                last_error = $2, locked_until = NULL, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status = 'running'
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn cancel(&self, id: i32) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'cancelled', locked_until = NULL,
+                finished_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status IN ('pending', 'running')
+            "#,
+        )
+        .bind(id)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn requeue_expired(&self) -> anyhow::Result<u64> {
+        // 被中断的执行不计入重试次数；其他实例仍在续租的任务不受影响
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'pending', attempts = GREATEST(attempts - 1, 0), locked_until = NULL,
+                run_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+            WHERE status = 'running'
+              AND (locked_until IS NULL OR locked_until < CURRENT_TIMESTAMP)
+            "#,
+        )
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::domain::models::llm_usage::{LLMUsage, LLMUsageSummary};
+use crate::infrastructure::llm::structured::StructuredAttempt;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use time::Date;
+
+#[async_trait]
+pub trait LLMUsageRepository: Send + Sync {
+    /// 保存一次调用的用量
+    async fn save(&self, usage: &LLMUsage) -> anyhow::Result<LLMUsage>;
+
+    /// 按天、模型服务、模型和操作汇总 [from, to] 日期范围内（UTC）的用量
+    async fn summarize(&self, from: Date, to: Date) -> anyhow::Result<Vec<LLMUsageSummary>>;
+
+    /// 保存一次结构化输出尝试
+    async fn save_attempt(&self, attempt: &StructuredAttempt) -> anyhow::Result<()>;
+
+    /// 最近的结构化输出尝试，按时间倒序
+    async fn recent_attempts(&self, limit: i64) -> anyhow::Result<Vec<StructuredAttempt>>;
+}
+
+pub struct LLMUsageRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl LLMUsageRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        LLMUsageRepositoryImpl { pool }
+    }
+}
+
+#[async_trait]
+impl LLMUsageRepository for LLMUsageRepositoryImpl {
+    async fn save(&self, usage: &LLMUsage) -> anyhow::Result<LLMUsage> {
+        let saved = sqlx::query_as::<_, LLMUsage>(
+            r#"
+            INSERT INTO llm_usage (
+                provider, model, operation, prompt_tokens,
+                completion_tokens, latency_ms, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(&usage.provider)
+        .bind(&usage.model)
+        .bind(&usage.operation)
+        .bind(usage.prompt_tokens)
+        .bind(usage.completion_tokens)
+        .bind(usage.latency_ms)
+        .bind(usage.created_at)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(saved)
+    }
+
+    async fn summarize(&self, from: Date, to: Date) -> anyhow::Result<Vec<LLMUsageSummary>> {
+        let summaries = sqlx::query_as::<_, LLMUsageSummary>(
+            r#"
+            SELECT
+                (created_at AT TIME ZONE 'UTC')::date AS day,
+                provider,
+                model,
+                operation,
+                COUNT(*) AS calls,
+                COALESCE(SUM(prompt_tokens), 0)::BIGINT AS prompt_tokens,
+                COALESCE(SUM(completion_tokens), 0)::BIGINT AS completion_tokens,
+                COALESCE(AVG(latency_ms), 0)::DOUBLE PRECISION AS avg_latency_ms
+            FROM llm_usage
+            WHERE (created_at AT TIME ZONE 'UTC')::date BETWEEN $1 AND $2
+            GROUP BY day, provider, model, operation
+            ORDER BY day DESC, provider, model, operation
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(summaries)
+    }
+
+    async fn save_attempt(&self, attempt: &StructuredAttempt) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO llm_structured_attempts (task, subject, attempt, success, error, occurred_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(&attempt.task)
+        .bind(&attempt.subject)
+        .bind(attempt.attempt)
+        .bind(attempt.success)
+        .bind(&attempt.error)
+        .bind(attempt.occurred_at)
+        .execute(&*self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn recent_attempts(&self, limit: i64) -> anyhow::Result<Vec<StructuredAttempt>> {
+        let attempts = sqlx::query_as::<_, StructuredAttempt>(
+            r#"
+            SELECT task, subject, attempt, success, error, occurred_at
+            FROM llm_structured_attempts
+            ORDER BY occurred_at DESC, id DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(attempts)
+    }
+}
@@ -0,0 +1,237 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domain::models::mistake::{Mistake, MistakeAnswer, MistakeType};
+
+/// 学生的错题本
+#[async_trait]
+pub trait MistakeRepository: Send + Sync {
+    /// 在一个事务中记录作答：答错的单词累加答错次数，更新最近答错的时间和单元并清零连续答对次数；
+    /// 答对的单词累加错题本中同类错误的连续答对次数，达到 clear_after 时移出错题本。返回移出的条数
+    async fn record_answers(
+        &self,
+        student_id: i32,
+        answers: &[MistakeAnswer],
+        clear_after: i32,
+    ) -> Result<u64>;
+
+    /// 学生的错题，可按错误类型筛选，最近答错的在前；单词已删除的不返回
+    async fn find_by_student(
+        &self,
+        student_id: i32,
+        error_type: Option<MistakeType>,
+    ) -> Result<Vec<Mistake>>;
+}
+
+pub struct MistakeRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl MistakeRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MistakeRepository for MistakeRepositoryImpl {
+    async fn record_answers(
+        &self,
+        student_id: i32,
+        answers: &[MistakeAnswer],
+        clear_after: i32,
+    ) -> Result<u64> {
+        // 同一单词的同类错误只处理一次，一次作答中既有答对又有答错时按答错处理
+        let mut wrong: Vec<&MistakeAnswer> = Vec::new();
+        let mut correct: Vec<&MistakeAnswer> = Vec::new();
+        for answer in answers {
+            let same = |other: &&MistakeAnswer| {
+                other.word_id == answer.word_id && other.error_type == answer.error_type
+            };
+            if answer.correct {
+                if !wrong.iter().any(same) && !correct.iter().any(same) {
+                    correct.push(answer);
+                }
+            } else if !wrong.iter().any(same) {
+                correct.retain(|other| !same(other));
+                wrong.push(answer);
+            }
+        }
+        if wrong.is_empty() && correct.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        if !wrong.is_empty() {
+            let word_ids: Vec<i32> = wrong.iter().map(|m| m.word_id).collect();
+            let unit_ids: Vec<Option<i32>> = wrong.iter().map(|m| m.unit_id).collect();
+            let error_types: Vec<&str> = wrong.iter().map(|m| m.error_type.as_str()).collect();
+            sqlx::query(
+                r#"
+                INSERT INTO mistakes (student_id, word_id, unit_id, error_type)
+                SELECT $1, m.word_id, m.unit_id, m.error_type
+                FROM UNNEST($2::int[], $3::int[], $4::text[]) AS m(word_id, unit_id, error_type)
+                ON CONFLICT (student_id, word_id, error_type) DO UPDATE SET
+                    wrong_count = mistakes.wrong_count + 1,
+                    correct_streak = 0,
+                    unit_id = COALESCE(EXCLUDED.unit_id, mistakes.unit_id),
+                    last_wrong_at = CURRENT_TIMESTAMP,
+                    updated_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(student_id)
+            .bind(&word_ids)
+            .bind(&unit_ids)
+            .bind(&error_types)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let mut removed = 0;
+        if !correct.is_empty() {
+            let word_ids: Vec<i32> = correct.iter().map(|m| m.word_id).collect();
+            let error_types: Vec<&str> = correct.iter().map(|m| m.error_type.as_str()).collect();
+            sqlx::query(
+                r#"
+                UPDATE mistakes mi SET
+                    correct_streak = mi.correct_streak + 1,
+                    updated_at = CURRENT_TIMESTAMP
+                FROM UNNEST($2::int[], $3::text[]) AS m(word_id, error_type)
+                WHERE mi.student_id = $1 AND mi.word_id = m.word_id AND mi.error_type = m.error_type
+                "#,
+            )
+            .bind(student_id)
+            .bind(&word_ids)
+            .bind(&error_types)
+            .execute(&mut *tx)
+            .await?;
+
+            removed =
+                sqlx::query("DELETE FROM mistakes WHERE student_id = $1 AND correct_streak >= $2")
+                    .bind(student_id)
+                    .bind(clear_after)
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(removed)
+    }
+
+    async fn find_by_student(
+        &self,
+        student_id: i32,
+        error_type: Option<MistakeType>,
+    ) -> Result<Vec<Mistake>> {
+        let mistakes = sqlx::query_as::<_, Mistake>(
+            r#"
+            SELECT mi.id, mi.student_id, mi.word_id, w.word,
+                   w.phonetic_us, w.phonetic_uk, w.pronunciation_us, w.pronunciation_uk,
+                   COALESCE((
+                       SELECT jsonb_agg(jsonb_strip_nulls(jsonb_build_object(
+                           'pos', m.pos, 'definition', m.definition, 'gloss', m.gloss
+                       )) ORDER BY m.sequence)
+                       FROM word_meanings m
+                       WHERE m.word_id = mi.word_id
+                   ), '[]') AS meanings,
+                   mi.unit_id, u.name AS unit_name, u.textbook_id,
+                   mi.error_type, mi.wrong_count, mi.correct_streak,
+                   mi.last_wrong_at, mi.created_at
+            FROM mistakes mi
+            JOIN words w ON w.word_id = mi.word_id AND w.deleted_at IS NULL
+            LEFT JOIN units u ON u.id = mi.unit_id
+            WHERE mi.student_id = $1 AND ($2::text IS NULL OR mi.error_type = $2)
+            ORDER BY mi.last_wrong_at DESC, mi.id
+            "#,
+        )
+        .bind(student_id)
+        .bind(error_type.map(|error_type| error_type.as_str()))
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(mistakes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_record_answers() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let mistakes = MistakeRepositoryImpl::new(pool.clone());
+
+        let (student_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, password_hash, role) VALUES ('mistake-student', '', 'student') RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        let (word_id,): (i32,) =
+            sqlx::query_as("INSERT INTO words (word) VALUES ('mistake-apple') RETURNING word_id")
+                .fetch_one(&*pool)
+                .await?;
+        let answer = |error_type, correct| MistakeAnswer {
+            word_id,
+            unit_id: None,
+            error_type,
+            correct,
+        };
+
+        assert_eq!(mistakes.record_answers(student_id, &[], 2).await?, 0);
+        mistakes
+            .record_answers(
+                student_id,
+                &[
+                    answer(MistakeType::Spelling, false),
+                    answer(MistakeType::Meaning, false),
+                ],
+                2,
+            )
+            .await?;
+        mistakes
+            .record_answers(student_id, &[answer(MistakeType::Spelling, false)], 2)
+            .await?;
+
+        // 同一单词的同类错误累加次数
+        let book = mistakes.find_by_student(student_id, None).await?;
+        let mut counts: Vec<(String, i32)> = book
+            .iter()
+            .map(|m| (m.error_type.clone(), m.wrong_count))
+            .collect();
+        counts.sort();
+        assert_eq!(
+            counts,
+            vec![("meaning".to_string(), 1), ("spelling".to_string(), 2)]
+        );
+        assert_eq!(book[0].word, "mistake-apple");
+        let spelling = mistakes
+            .find_by_student(student_id, Some(MistakeType::Spelling))
+            .await?;
+        assert_eq!(spelling.len(), 1);
+
+        // 答错会清零连续答对次数，连续答对两次后移出错题本
+        let correct = [answer(MistakeType::Meaning, true)];
+        assert_eq!(mistakes.record_answers(student_id, &correct, 2).await?, 0);
+        mistakes
+            .record_answers(student_id, &[answer(MistakeType::Meaning, false)], 2)
+            .await?;
+        assert_eq!(mistakes.record_answers(student_id, &correct, 2).await?, 0);
+        assert_eq!(mistakes.record_answers(student_id, &correct, 2).await?, 1);
+        let book = mistakes.find_by_student(student_id, None).await?;
+        assert_eq!(book.len(), 1);
+        assert_eq!(book[0].error_type, "spelling");
+
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(student_id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM words WHERE word_id = $1")
+            .bind(word_id)
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,19 +1,37 @@
 mod base;
+mod dictation_repository;
 mod grade_repository;
+mod job_repository;
+mod llm_usage_repository;
+mod mistake_repository;
 pub(crate) mod model_provider_repository;
+mod prompt_template_repository;
+mod quiz_repository;
 mod semester_repository;
+mod study_repository;
 mod textbook_repository;
 mod textbook_version_repository;
+mod trash_repository;
 mod unit_repository;
+mod user_repository;
 mod word_repository;
 mod word_unit_mapping_repository;
 
 pub use base::{Paginated, Repository};
+pub use dictation_repository::{DictationRepository, DictationRepositoryImpl};
 pub use grade_repository::{GradeRepository, GradeRepositoryImpl};
+pub use job_repository::{JobRepository, JobRepositoryImpl};
+pub use llm_usage_repository::{LLMUsageRepository, LLMUsageRepositoryImpl};
+pub use mistake_repository::{MistakeRepository, MistakeRepositoryImpl};
 pub use model_provider_repository::{ModelProviderRepository, ModelProviderRepositoryImpl};
+pub use prompt_template_repository::{PromptTemplateRepository, PromptTemplateRepositoryImpl};
+pub use quiz_repository::{QuizRepository, QuizRepositoryImpl};
 pub use semester_repository::{SemesterRepository, SemesterRepositoryImpl};
+pub use study_repository::{StudyRepository, StudyRepositoryImpl};
 pub use textbook_repository::{TextbookRepository, TextbookRepositoryImpl};
 pub use textbook_version_repository::{TextbookVersionRepository, TextbookVersionRepositoryImpl};
+pub use trash_repository::{TrashRepository, TrashRepositoryImpl};
 pub use unit_repository::{UnitRepository, UnitRepositoryImpl};
+pub use user_repository::{UserRepository, UserRepositoryImpl};
 pub use word_repository::{WordRepository, WordRepositoryImpl};
 pub use word_unit_mapping_repository::{WordUnitMappingRepository, WordUnitMappingRepositoryImpl};
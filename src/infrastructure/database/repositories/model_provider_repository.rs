@@ -6,7 +6,10 @@ use std::sync::Arc;
 use time::OffsetDateTime;
 
 #[async_trait]
-pub trait ModelProviderRepository: Repository<ModelProvider, i32> + Send + Sync {}
+pub trait ModelProviderRepository: Repository<ModelProvider, i32> + Send + Sync {
+    /// 根据名称查找模型服务
+    async fn find_by_name(&self, name: &str) -> anyhow::Result<Option<ModelProvider>>;
+}
 
 pub struct ModelProviderRepositoryImpl {
     pool: Arc<PgPool>,
@@ -55,8 +58,18 @@ impl Repository<ModelProvider, i32> for ModelProviderRepositoryImpl {
                         api_key_required = $3,
                         model_types = $4,
                         is_active = $5,
-                        updated_at = $6
-                    WHERE provider_id = $7
+                        updated_at = $6,
+                        provider_type = $7,
+                        model_name = $8,
+                        api_key_encrypted = $9,
+                        timeout_secs = $10,
+                        temperature = $11,
+                        top_p = $12,
+                        max_tokens = $13,
+                        prompt_price_per_1k = $14,
+                        completion_price_per_1k = $15,
+                        model_prices = $16
+                    WHERE provider_id = $17
                     RETURNING *
                     "#,
                 )
@@ -66,6 +79,16 @@ impl Repository<ModelProvider, i32> for ModelProviderRepositoryImpl {
                 .bind(&entity.model_types)
                 .bind(&entity.is_active)
                 .bind(now)
+                .bind(&entity.provider_type)
+                .bind(&entity.model_name)
+                .bind(&entity.api_key_encrypted)
+                .bind(entity.timeout_secs)
+                .bind(entity.temperature)
+                .bind(entity.top_p)
+                .bind(entity.max_tokens)
+                .bind(entity.prompt_price_per_1k)
+                .bind(entity.completion_price_per_1k)
+                .bind(&entity.model_prices)
                 .bind(id)
                 .fetch_one(&*self.pool)
                 .await?
@@ -76,8 +99,11 @@ impl Repository<ModelProvider, i32> for ModelProviderRepositoryImpl {
                     r#"
                     INSERT INTO model_providers (
                         provider_name, api_base_url, api_key_required, 
-                        model_types, is_active, created_at, updated_at
-                    ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+                        model_types, is_active, created_at, updated_at,
+                        provider_type, model_name, api_key_encrypted,
+                        timeout_secs, temperature, top_p, max_tokens,
+                        prompt_price_per_1k, completion_price_per_1k, model_prices
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
                     RETURNING *
                     "#,
                 )
@@ -88,6 +114,16 @@ impl Repository<ModelProvider, i32> for ModelProviderRepositoryImpl {
                 .bind(&entity.is_active)
                 .bind(now)
                 .bind(now)
+                .bind(&entity.provider_type)
+                .bind(&entity.model_name)
+                .bind(&entity.api_key_encrypted)
+                .bind(entity.timeout_secs)
+                .bind(entity.temperature)
+                .bind(entity.top_p)
+                .bind(entity.max_tokens)
+                .bind(entity.prompt_price_per_1k)
+                .bind(entity.completion_price_per_1k)
+                .bind(&entity.model_prices)
                 .fetch_one(&*self.pool)
                 .await?
             }
@@ -112,4 +148,15 @@ impl Repository<ModelProvider, i32> for ModelProviderRepositoryImpl {
 }
 
 #[async_trait]
-impl ModelProviderRepository for ModelProviderRepositoryImpl {}
+impl ModelProviderRepository for ModelProviderRepositoryImpl {
+    async fn find_by_name(&self, name: &str) -> anyhow::Result<Option<ModelProvider>> {
+        let provider = sqlx::query_as::<_, ModelProvider>(
+            "SELECT * FROM model_providers WHERE LOWER(provider_name) = LOWER($1)",
+        )
+        .bind(name)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(provider)
+    }
+}
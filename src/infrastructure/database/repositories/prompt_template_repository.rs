@@ -0,0 +1,132 @@
+use crate::domain::models::prompt_template::PromptTemplate;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+#[async_trait]
+pub trait PromptTemplateRepository: Send + Sync {
+    /// 查询所有模板版本
+    async fn find_all(&self) -> anyhow::Result<Vec<PromptTemplate>>;
+
+    /// 根据名称和版本查询
+    async fn find_by_name_version(
+        &self,
+        name: &str,
+        version: i32,
+    ) -> anyhow::Result<Option<PromptTemplate>>;
+
+    /// 查询模板已有的最大版本号
+    async fn max_version(&self, name: &str) -> anyhow::Result<Option<i32>>;
+
+    /// 新增一个模板版本
+    async fn save(&self, template: &PromptTemplate) -> anyhow::Result<PromptTemplate>;
+
+    /// 启用指定版本并停用同名的其他版本，version 为空时全部停用
+    async fn activate(&self, name: &str, version: Option<i32>) -> anyhow::Result<()>;
+}
+
+pub struct PromptTemplateRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl PromptTemplateRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        PromptTemplateRepositoryImpl { pool }
+    }
+}
+
+#[async_trait]
+impl PromptTemplateRepository for PromptTemplateRepositoryImpl {
+    async fn find_all(&self) -> anyhow::Result<Vec<PromptTemplate>> {
+        let templates = sqlx::query_as::<_, PromptTemplate>(
+            "SELECT * FROM prompt_templates ORDER BY name, version",
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(templates)
+    }
+
+    async fn find_by_name_version(
+        &self,
+        name: &str,
+        version: i32,
+    ) -> anyhow::Result<Option<PromptTemplate>> {
+        let template = sqlx::query_as::<_, PromptTemplate>(
+            "SELECT * FROM prompt_templates WHERE name = $1 AND version = $2",
+        )
+        .bind(name)
+        .bind(version)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    async fn max_version(&self, name: &str) -> anyhow::Result<Option<i32>> {
+        let version: Option<i32> =
+            sqlx::query_scalar("SELECT MAX(version) FROM prompt_templates WHERE name = $1")
+                .bind(name)
+                .fetch_one(&*self.pool)
+                .await?;
+
+        Ok(version)
+    }
+
+    async fn save(&self, template: &PromptTemplate) -> anyhow::Result<PromptTemplate> {
+        let now = OffsetDateTime::now_utc();
+        let saved = sqlx::query_as::<_, PromptTemplate>(
+            r#"
+            INSERT INTO prompt_templates (
+                name, version, system_prompt, user_prompt,
+                description, is_active, created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(&template.name)
+        .bind(template.version)
+        .bind(&template.system_prompt)
+        .bind(&template.user_prompt)
+        .bind(&template.description)
+        .bind(template.is_active)
+        .bind(now)
+        .bind(now)
+        .fetch_one(&*self.pool)
+        .await?;
+
+        Ok(saved)
+    }
+
+    async fn activate(&self, name: &str, version: Option<i32>) -> anyhow::Result<()> {
+        let now = OffsetDateTime::now_utc();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE prompt_templates SET is_active = FALSE, updated_at = $2 WHERE name = $1 AND is_active",
+        )
+        .bind(name)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(version) = version {
+            let result = sqlx::query(
+                "UPDATE prompt_templates SET is_active = TRUE, updated_at = $3 WHERE name = $1 AND version = $2",
+            )
+            .bind(name)
+            .bind(version)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                anyhow::bail!("Prompt template {} has no version {}", name, version);
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use super::word_unit_mapping_repository::UNIT_WORD_COLUMNS;
+use crate::api::dto::unit_word_dto::WordDTO;
+use crate::domain::models::quiz::{Quiz, QuizAttempt};
+
+/// 测验和交卷记录
+#[async_trait]
+pub trait QuizRepository: Send + Sync {
+    /// 与所选单元同一年级的其他单元中的单词，用作选择题的干扰项，按单词 id 取前 limit 个
+    async fn find_grade_words(&self, unit_ids: &[i32], limit: i64) -> Result<Vec<WordDTO>>;
+
+    async fn save_quiz(&self, quiz: &Quiz) -> Result<Quiz>;
+
+    async fn find_quiz(&self, id: i32) -> Result<Option<Quiz>>;
+
+    /// 保存交卷记录，该用户已交过这份测验时返回 None
+    async fn save_attempt(&self, attempt: &QuizAttempt) -> Result<Option<QuizAttempt>>;
+
+    /// 把测验布置给学生，已经布置过的跳过，返回新布置的数量
+    async fn assign_quiz(
+        &self,
+        quiz_id: i32,
+        student_ids: &[i32],
+        assigned_by: Option<i32>,
+    ) -> Result<u64>;
+
+    /// 测验是否布置给了该学生
+    async fn is_quiz_assigned(&self, quiz_id: i32, student_id: i32) -> Result<bool>;
+}
+
+pub struct QuizRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl QuizRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl QuizRepository for QuizRepositoryImpl {
+    async fn find_grade_words(&self, unit_ids: &[i32], limit: i64) -> Result<Vec<WordDTO>> {
+        let words = sqlx::query_as::<_, WordDTO>(&format!(
+            r#"
+            SELECT DISTINCT ON (w.word_id) {}
+            FROM units source
+            JOIN textbooks source_textbook ON source_textbook.id = source.textbook_id
+            JOIN textbooks t ON t.grade_id = source_textbook.grade_id AND t.deleted_at IS NULL
+            JOIN units u ON u.textbook_id = t.id AND u.deleted_at IS NULL AND u.id <> ALL($1)
+            JOIN word_unit_mappings wum ON wum.unit_id = u.id AND wum.deleted_at IS NULL
+            JOIN words w ON w.word_id = wum.word_id AND w.deleted_at IS NULL
+            WHERE source.id = ANY($1)
+            ORDER BY w.word_id, wum.id
+            LIMIT $2
+            "#,
+            UNIT_WORD_COLUMNS
+        ))
+        .bind(unit_ids)
+        .bind(limit)
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(words)
+    }
+
+    async fn save_quiz(&self, quiz: &Quiz) -> Result<Quiz> {
+        let saved = sqlx::query_as::<_, Quiz>(
+            r#"
+            INSERT INTO quizzes (unit_ids, seed, questions, created_by)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(&quiz.unit_ids)
+        .bind(quiz.seed)
+        .bind(sqlx::types::Json(&quiz.questions))
+        .bind(quiz.created_by)
+        .fetch_one(&*self.pool)
+        .await?;
+        Ok(saved)
+    }
+
+    async fn find_quiz(&self, id: i32) -> Result<Option<Quiz>> {
+        let quiz = sqlx::query_as::<_, Quiz>("SELECT * FROM quizzes WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await?;
+        Ok(quiz)
+    }
+
+    async fn save_attempt(&self, attempt: &QuizAttempt) -> Result<Option<QuizAttempt>> {
+        let saved = sqlx::query_as::<_, QuizAttempt>(
+            r#"
+            INSERT INTO quiz_attempts (quiz_id, user_id, results, correct, total)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (quiz_id, user_id) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(attempt.quiz_id)
+        .bind(attempt.user_id)
+        .bind(sqlx::types::Json(&attempt.results))
+        .bind(attempt.correct)
+        .bind(attempt.total)
+        .fetch_optional(&*self.pool)
+        .await?;
+        Ok(saved)
+    }
+
+    async fn assign_quiz(
+        &self,
+        quiz_id: i32,
+        student_ids: &[i32],
+        assigned_by: Option<i32>,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO quiz_assignments (quiz_id, student_id, assigned_by)
+            SELECT $1, student_id, $3 FROM UNNEST($2::int[]) AS student_id
+            ON CONFLICT (quiz_id, student_id) DO NOTHING
+            "#,
+        )
+        .bind(quiz_id)
+        .bind(student_ids)
+        .bind(assigned_by)
+        .execute(&*self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn is_quiz_assigned(&self, quiz_id: i32, student_id: i32) -> Result<bool> {
+        let assigned: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM quiz_assignments WHERE quiz_id = $1 AND student_id = $2)",
+        )
+        .bind(quiz_id)
+        .bind(student_id)
+        .fetch_one(&*self.pool)
+        .await?;
+        Ok(assigned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::quiz::{QuestionKind, QuizQuestion};
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_grade_words_and_attempts() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let quizzes = QuizRepositoryImpl::new(pool.clone());
+
+        let (grade_id,): (i32,) =
+            sqlx::query_as("INSERT INTO grades (name) VALUES ('quiz-grade') RETURNING id")
+                .fetch_one(&*pool)
+                .await?;
+        let mut unit_ids = Vec::new();
+        let mut word_ids = Vec::new();
+        let mut textbook_ids = Vec::new();
+        // 同一年级两本教材各一个单元，另一年级一个单元
+        for (textbook, grade, word) in [
+            ("quiz-a", Some(grade_id), "quiz-apple"),
+            ("quiz-b", Some(grade_id), "quiz-pear"),
+            ("quiz-c", None, "quiz-plum"),
+        ] {
+            let (textbook_id,): (i32,) = sqlx::query_as(
+                "INSERT INTO textbooks (name, grade_id) VALUES ($1, $2) RETURNING id",
+            )
+            .bind(textbook)
+            .bind(grade)
+            .fetch_one(&*pool)
+            .await?;
+            let (unit_id,): (i32,) = sqlx::query_as(
+                "INSERT INTO units (name, textbook_id, sequence_number, word_count) VALUES ($1, $2, 1, 1) RETURNING id",
+            )
+            .bind(textbook)
+            .bind(textbook_id)
+            .fetch_one(&*pool)
+            .await?;
+            let (word_id,): (i32,) =
+                sqlx::query_as("INSERT INTO words (word) VALUES ($1) RETURNING word_id")
+                    .bind(word)
+                    .fetch_one(&*pool)
+                    .await?;
+            sqlx::query(
+                "INSERT INTO word_unit_mappings (word_id, unit_id, sequence) VALUES ($1, $2, 1)",
+            )
+            .bind(word_id)
+            .bind(unit_id)
+            .execute(&*pool)
+            .await?;
+            textbook_ids.push(textbook_id);
+            unit_ids.push(unit_id);
+            word_ids.push(word_id);
+        }
+
+        let extra = quizzes.find_grade_words(&unit_ids[..1], 10).await?;
+        let ids: Vec<Option<i32>> = extra.iter().map(|word| word.word_id).collect();
+        assert_eq!(ids, vec![Some(word_ids[1])]);
+
+        let (user_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, password_hash, role) VALUES ('quiz-student', '', 'student') RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        let question = QuizQuestion {
+            index: 0,
+            kind: QuestionKind::Spelling,
+            word_id: word_ids[0],
+            unit_id: Some(unit_ids[0]),
+            prompt: "n. 苹果".to_string(),
+            options: Vec::new(),
+            answer: "quiz-apple".to_string(),
+        };
+        let quiz = quizzes
+            .save_quiz(&Quiz {
+                id: None,
+                unit_ids: unit_ids[..1].to_vec(),
+                seed: 42,
+                questions: vec![question],
+                created_by: Some(user_id),
+                created_at: None,
+            })
+            .await?;
+        let quiz_id = quiz.id.unwrap();
+        let found = quizzes.find_quiz(quiz_id).await?.unwrap();
+        assert_eq!(found.questions, quiz.questions);
+
+        let attempt = QuizAttempt::grade(
+            quiz_id,
+            user_id,
+            &found.questions,
+            &[(0, "Quiz-Apple".to_string())],
+        );
+        let saved = quizzes.save_attempt(&attempt).await?.unwrap();
+        assert!(saved.id.is_some());
+        assert_eq!((saved.correct, saved.total), (1, 1));
+        assert_eq!(saved.results, attempt.results);
+        // 同一用户不能重复交卷
+        assert!(quizzes.save_attempt(&attempt).await?.is_none());
+
+        assert!(!quizzes.is_quiz_assigned(quiz_id, user_id).await?);
+        assert_eq!(quizzes.assign_quiz(quiz_id, &[user_id], None).await?, 1);
+        assert_eq!(quizzes.assign_quiz(quiz_id, &[user_id], None).await?, 0);
+        assert!(quizzes.is_quiz_assigned(quiz_id, user_id).await?);
+
+        sqlx::query("DELETE FROM quizzes WHERE id = $1")
+            .bind(quiz_id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM word_unit_mappings WHERE unit_id = ANY($1)")
+            .bind(&unit_ids)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM units WHERE id = ANY($1)")
+            .bind(&unit_ids)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM textbooks WHERE id = ANY($1)")
+            .bind(&textbook_ids)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM words WHERE word_id = ANY($1)")
+            .bind(&word_ids)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM grades WHERE id = $1")
+            .bind(grade_id)
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,383 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+use crate::domain::models::review::{ReviewItem, ReviewState, UnitAssignment, UnitMastery};
+
+/// 学习记录：单元布置、单词复习状态和作答记录
+#[async_trait]
+pub trait StudyRepository: Send + Sync {
+    /// 把单元布置给学生，已经布置过的跳过，返回新布置的数量
+    async fn assign_unit(
+        &self,
+        unit_id: i32,
+        student_ids: &[i32],
+        assigned_by: Option<i32>,
+    ) -> Result<u64>;
+
+    /// 取消布置，返回是否存在
+    async fn unassign_unit(&self, unit_id: i32, student_id: i32) -> Result<bool>;
+
+    /// 学生被布置的单元，已删除的单元不返回
+    async fn find_assignments(&self, student_id: i32) -> Result<Vec<UnitAssignment>>;
+
+    /// 单词是否在布置给学生的单元中
+    async fn is_word_assigned(&self, student_id: i32, word_id: i32) -> Result<bool>;
+
+    /// 复习队列：先返回 due_before 之前到期的单词，按到期时间排序，最多 limit 个；
+    /// 再按布置顺序和单元内顺序补充最多 new_limit 个新词，总数不超过 limit
+    async fn find_review_queue(
+        &self,
+        student_id: i32,
+        due_before: OffsetDateTime,
+        limit: i64,
+        new_limit: i64,
+    ) -> Result<Vec<ReviewItem>>;
+
+    /// since 之后开始学习的新词数
+    async fn count_new_since(&self, student_id: i32, since: OffsetDateTime) -> Result<i64>;
+
+    async fn find_state(&self, student_id: i32, word_id: i32) -> Result<Option<ReviewState>>;
+
+    /// 在一个事务中保存复习状态并记录本次作答
+    async fn save_review(&self, state: &ReviewState, quality: u8) -> Result<ReviewState>;
+
+    /// 学生每个布置单元的掌握情况，mastered_interval 为视为掌握的复习间隔天数
+    async fn find_mastery(
+        &self,
+        student_id: i32,
+        mastered_interval: i32,
+    ) -> Result<Vec<UnitMastery>>;
+}
+
+pub struct StudyRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl StudyRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+/// 布置给学生且未删除的单元单词，同一单词出现在多个单元时取最早布置的单元
+const ASSIGNED_WORDS: &str = r#"
+    SELECT DISTINCT ON (m.word_id)
+        m.word_id, u.id AS unit_id, u.name AS unit_name,
+        a.created_at AS assigned_at, u.sequence_number AS unit_sequence, m.sequence
+    FROM unit_assignments a
+    JOIN units u ON u.id = a.unit_id AND u.deleted_at IS NULL
+    JOIN word_unit_mappings m ON m.unit_id = u.id AND m.deleted_at IS NULL
+    JOIN words w ON w.word_id = m.word_id AND w.deleted_at IS NULL
+    WHERE a.student_id = $1
+    ORDER BY m.word_id, a.created_at, u.sequence_number, m.sequence
+"#;
+
+#[async_trait]
+impl StudyRepository for StudyRepositoryImpl {
+    async fn assign_unit(
+        &self,
+        unit_id: i32,
+        student_ids: &[i32],
+        assigned_by: Option<i32>,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO unit_assignments (unit_id, student_id, assigned_by)
+            SELECT $1, student_id, $3 FROM UNNEST($2::int[]) AS student_id
+            ON CONFLICT (unit_id, student_id) DO NOTHING
+            "#,
+        )
+        .bind(unit_id)
+        .bind(student_ids)
+        .bind(assigned_by)
+        .execute(&*self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn unassign_unit(&self, unit_id: i32, student_id: i32) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM unit_assignments WHERE unit_id = $1 AND student_id = $2")
+                .bind(unit_id)
+                .bind(student_id)
+                .execute(&*self.pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_assignments(&self, student_id: i32) -> Result<Vec<UnitAssignment>> {
+        let assignments = sqlx::query_as::<_, UnitAssignment>(
+            r#"
+            SELECT a.unit_id, u.name AS unit_name, u.textbook_id,
+                   a.student_id, a.assigned_by, a.created_at
+            FROM unit_assignments a
+            JOIN units u ON u.id = a.unit_id AND u.deleted_at IS NULL
+            WHERE a.student_id = $1
+            ORDER BY a.created_at, a.id
+            "#,
+        )
+        .bind(student_id)
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(assignments)
+    }
+
+    async fn is_word_assigned(&self, student_id: i32, word_id: i32) -> Result<bool> {
+        let assigned: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS (SELECT 1 FROM ({}) assigned WHERE word_id = $2)",
+            ASSIGNED_WORDS
+        ))
+        .bind(student_id)
+        .bind(word_id)
+        .fetch_one(&*self.pool)
+        .await?;
+        Ok(assigned)
+    }
+
+    async fn find_review_queue(
+        &self,
+        student_id: i32,
+        due_before: OffsetDateTime,
+        limit: i64,
+        new_limit: i64,
+    ) -> Result<Vec<ReviewItem>> {
+        let items = sqlx::query_as::<_, ReviewItem>(&format!(
+            r#"
+            WITH assigned AS ({}),
+            candidates AS (
+                SELECT a.*, s.due_at,
+                       COALESCE(s.repetitions, 0) AS repetitions, COALESCE(s.lapses, 0) AS lapses
+                FROM assigned a
+                LEFT JOIN review_states s ON s.student_id = $1 AND s.word_id = a.word_id
+            ),
+            queue AS (
+                (SELECT * FROM candidates WHERE due_at < $2 ORDER BY due_at LIMIT $3)
+                UNION ALL
+                (SELECT * FROM candidates WHERE due_at IS NULL
+                 ORDER BY assigned_at, unit_sequence, sequence LIMIT $4)
+            )
+            SELECT q.word_id, w.word, w.phonetic_us, w.phonetic_uk,
+                   COALESCE((
+                       SELECT jsonb_agg(jsonb_strip_nulls(jsonb_build_object(
+                           'pos', m.pos, 'definition', m.definition, 'gloss', m.gloss
+                       )) ORDER BY m.sequence)
+                       FROM word_meanings m
+                       WHERE m.word_id = q.word_id
+                   ), '[]') AS meanings,
+                   q.unit_id, q.unit_name, q.due_at, q.repetitions, q.lapses
+            FROM queue q
+            JOIN words w ON w.word_id = q.word_id
+            ORDER BY q.due_at IS NULL, q.due_at, q.assigned_at, q.unit_sequence, q.sequence
+            LIMIT $3
+            "#,
+            ASSIGNED_WORDS
+        ))
+        .bind(student_id)
+        .bind(due_before)
+        .bind(limit)
+        .bind(new_limit)
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(items)
+    }
+
+    async fn count_new_since(&self, student_id: i32, since: OffsetDateTime) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM review_states WHERE student_id = $1 AND created_at >= $2",
+        )
+        .bind(student_id)
+        .bind(since)
+        .fetch_one(&*self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    async fn find_state(&self, student_id: i32, word_id: i32) -> Result<Option<ReviewState>> {
+        let state = sqlx::query_as::<_, ReviewState>(
+            "SELECT * FROM review_states WHERE student_id = $1 AND word_id = $2",
+        )
+        .bind(student_id)
+        .bind(word_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+        Ok(state)
+    }
+
+    async fn save_review(&self, state: &ReviewState, quality: u8) -> Result<ReviewState> {
+        let mut tx = self.pool.begin().await?;
+        let saved = sqlx::query_as::<_, ReviewState>(
+            r#"
+            INSERT INTO review_states
+                (student_id, word_id, ease, interval_days, repetitions, lapses, due_at, last_reviewed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (student_id, word_id) DO UPDATE
+            SET ease = EXCLUDED.ease, interval_days = EXCLUDED.interval_days,
+                repetitions = EXCLUDED.repetitions, lapses = EXCLUDED.lapses,
+                due_at = EXCLUDED.due_at, last_reviewed_at = EXCLUDED.last_reviewed_at,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING *
+            "#,
+        )
+        .bind(state.student_id)
+        .bind(state.word_id)
+        .bind(state.ease)
+        .bind(state.interval_days)
+        .bind(state.repetitions)
+        .bind(state.lapses)
+        .bind(state.due_at)
+        .bind(state.last_reviewed_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO review_logs (student_id, word_id, quality, ease, interval_days, reviewed_at)
+            VALUES ($1, $2, $3, $4, $5, COALESCE($6, CURRENT_TIMESTAMP))
+            "#,
+        )
+        .bind(state.student_id)
+        .bind(state.word_id)
+        .bind(quality as i16)
+        .bind(state.ease)
+        .bind(state.interval_days)
+        .bind(state.last_reviewed_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(saved)
+    }
+
+    async fn find_mastery(
+        &self,
+        student_id: i32,
+        mastered_interval: i32,
+    ) -> Result<Vec<UnitMastery>> {
+        let mastery = sqlx::query_as::<_, UnitMastery>(
+            r#"
+            SELECT u.id AS unit_id, u.name AS unit_name, u.textbook_id,
+                   COUNT(w.word_id) AS total,
+                   COUNT(s.id) FILTER (WHERE s.repetitions > 0) AS learned,
+                   COUNT(s.id) FILTER (WHERE s.interval_days >= $2) AS mastered
+            FROM unit_assignments a
+            JOIN units u ON u.id = a.unit_id AND u.deleted_at IS NULL
+            LEFT JOIN word_unit_mappings m ON m.unit_id = u.id AND m.deleted_at IS NULL
+            LEFT JOIN words w ON w.word_id = m.word_id AND w.deleted_at IS NULL
+            LEFT JOIN review_states s ON s.student_id = a.student_id AND s.word_id = w.word_id
+            WHERE a.student_id = $1
+            GROUP BY a.id, u.id
+            ORDER BY a.created_at, a.id
+            "#,
+        )
+        .bind(student_id)
+        .bind(mastered_interval)
+        .fetch_all(&*self.pool)
+        .await?;
+        Ok(mastery)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_review_queue_and_mastery() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let study = StudyRepositoryImpl::new(pool.clone());
+        let now = OffsetDateTime::now_utc();
+
+        let (student_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO users (username, password_hash, role) VALUES ('study-student', '', 'student') RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        let (unit_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO units (name, sequence_number, word_count) VALUES ('Study', 1, 3) RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        let mut word_ids = Vec::new();
+        for (sequence, word) in ["study-apple", "study-pear", "study-plum"]
+            .iter()
+            .enumerate()
+        {
+            let (word_id,): (i32,) =
+                sqlx::query_as("INSERT INTO words (word) VALUES ($1) RETURNING word_id")
+                    .bind(word)
+                    .fetch_one(&*pool)
+                    .await?;
+            sqlx::query(
+                "INSERT INTO word_unit_mappings (word_id, unit_id, sequence) VALUES ($1, $2, $3)",
+            )
+            .bind(word_id)
+            .bind(unit_id)
+            .bind(sequence as i32 + 1)
+            .execute(&*pool)
+            .await?;
+            word_ids.push(word_id);
+        }
+
+        // 布置前没有可复习的单词，重复布置不重复计算
+        assert!(!study.is_word_assigned(student_id, word_ids[0]).await?);
+        assert_eq!(study.assign_unit(unit_id, &[student_id], None).await?, 1);
+        assert_eq!(study.assign_unit(unit_id, &[student_id], None).await?, 0);
+        assert!(study.is_word_assigned(student_id, word_ids[0]).await?);
+
+        let tomorrow = now + Duration::days(1);
+        let queue = study.find_review_queue(student_id, tomorrow, 10, 2).await?;
+        let ids: Vec<i32> = queue.iter().map(|item| item.word_id).collect();
+        assert_eq!(ids, word_ids[..2]);
+        assert!(queue.iter().all(|item| item.due_at.is_none()));
+
+        // 答对的单词明天到期，答错的今天到期排在新词前面
+        let mut apple = ReviewState::new(student_id, word_ids[0], now);
+        apple.review(5, now)?;
+        study.save_review(&apple, 5).await?;
+        let mut pear = ReviewState::new(student_id, word_ids[1], now);
+        pear.review(1, now)?;
+        pear.due_at = now;
+        study.save_review(&pear, 1).await?;
+        assert_eq!(
+            study
+                .count_new_since(student_id, now - Duration::minutes(1))
+                .await?,
+            2
+        );
+
+        let queue = study.find_review_queue(student_id, tomorrow, 10, 5).await?;
+        let ids: Vec<i32> = queue.iter().map(|item| item.word_id).collect();
+        assert_eq!(ids, vec![word_ids[1], word_ids[2]]);
+        assert!(queue[0].due_at.is_some() && queue[1].due_at.is_none());
+
+        let mut plum = ReviewState::new(student_id, word_ids[2], now);
+        plum.interval_days = 30;
+        study.save_review(&plum, 5).await?;
+        let mastery = study.find_mastery(student_id, 21).await?;
+        assert_eq!(mastery.len(), 1);
+        assert_eq!(
+            (mastery[0].total, mastery[0].learned, mastery[0].mastered),
+            (3, 1, 1)
+        );
+
+        assert!(study.unassign_unit(unit_id, student_id).await?);
+        assert!(study.find_assignments(student_id).await?.is_empty());
+
+        sqlx::query("DELETE FROM units WHERE id = $1")
+            .bind(unit_id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM words WHERE word_id = ANY($1)")
+            .bind(&word_ids)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(student_id)
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
+}
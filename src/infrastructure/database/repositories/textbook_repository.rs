@@ -3,8 +3,10 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use std::sync::Arc;
 
-use crate::api::dto::textbook_dto::TextbookDTO;
+use super::trash_repository::soft_delete;
+use crate::api::dto::textbook_dto::{CounterDiscrepancyDTO, TextbookDTO};
 use crate::domain::models::textbook::Textbook;
+use crate::domain::models::trash::TrashEntity;
 
 #[async_trait]
 pub trait TextbookRepository: Send + Sync {
@@ -17,11 +19,17 @@ pub trait TextbookRepository: Send + Sync {
     /// 保存或更新教材
     async fn save(&self, textbook: &Textbook) -> Result<Textbook>;
 
-    /// 根据ID删除教材
+    /// 根据ID软删除教材，连带删除全部单元和单元单词
     async fn delete(&self, id: i32) -> Result<()>;
 
     /// 查询所有教材
     async fn find_all(&self) -> Result<Vec<Textbook>>;
+
+    /// 在一个事务中复制教材及其全部单元和单词关联，target 提供新教材的版本、年级、学期和名称
+    async fn clone_textbook(&self, source_id: i32, target: &Textbook) -> Result<Textbook>;
+
+    /// 在一个事务中按未删除的单词映射和单元重新统计单元单词数、课本单元数和单词数，返回修正前不一致的记录
+    async fn recount_counters(&self) -> Result<Vec<CounterDiscrepancyDTO>>;
 }
 
 pub struct TextbookRepositoryImpl {
@@ -41,7 +49,7 @@ impl TextbookRepository for TextbookRepositoryImpl {
             r#"
             SELECT id, version_id, name, unit_count, word_count, textbook_version, grade, semester, grade_id, semester_id, created_at, updated_at
             FROM textbooks
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(id)
@@ -52,7 +60,7 @@ impl TextbookRepository for TextbookRepositoryImpl {
 
     async fn find_by_dto(&self, dto: &TextbookDTO) -> Result<Vec<Textbook>> {
         let mut query = sqlx::QueryBuilder::new(
-            "SELECT id, version_id, name, unit_count, word_count, textbook_version, grade, semester, grade_id, semester_id, created_at, updated_at FROM textbooks WHERE 1=1",
+            "SELECT id, version_id, name, unit_count, word_count, textbook_version, grade, semester, grade_id, semester_id, created_at, updated_at FROM textbooks WHERE deleted_at IS NULL",
         );
 
         if let Some(id) = dto.id {
@@ -122,11 +130,9 @@ impl TextbookRepository for TextbookRepositoryImpl {
     }
 
     async fn delete(&self, id: i32) -> Result<()> {
-        sqlx::query("DELETE FROM textbooks WHERE id = $1")
-            .bind(id)
-            .execute(&*self.pool)
-            .await
-            .map_err(anyhow::Error::from)?;
+        let mut tx = self.pool.begin().await?;
+        soft_delete(&mut tx, TrashEntity::Textbook, id).await?;
+        tx.commit().await?;
         Ok(())
     }
 
@@ -135,10 +141,337 @@ impl TextbookRepository for TextbookRepositoryImpl {
             r#"
             SELECT id, version_id, name, unit_count, word_count, textbook_version, grade, semester, grade_id, semester_id, created_at, updated_at
             FROM textbooks
+            WHERE deleted_at IS NULL
             "#,
         )
         .fetch_all(&*self.pool)
         .await
         .map_err(anyhow::Error::from)
     }
+
+    async fn clone_textbook(&self, source_id: i32, target: &Textbook) -> Result<Textbook> {
+        let mut tx = self.pool.begin().await?;
+
+        let textbook = sqlx::query_as::<_, Textbook>(
+            r#"
+            INSERT INTO textbooks (version_id, grade_id, semester_id, name,
+                                   textbook_version, grade, semester, unit_count, word_count)
+            SELECT $2, $3, $4, $5, $6, $7, $8, unit_count, word_count
+            FROM textbooks
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(source_id)
+        .bind(target.version_id)
+        .bind(target.grade_id)
+        .bind(target.semester_id)
+        .bind(&target.name)
+        .bind(&target.textbook_version)
+        .bind(&target.grade)
+        .bind(&target.semester)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Textbook not found: {}", source_id))?;
+
+        let unit_ids: Vec<(i32,)> = sqlx::query_as(
+            "SELECT id FROM units WHERE textbook_id = $1 AND deleted_at IS NULL ORDER BY sequence_number NULLS LAST, id",
+        )
+        .bind(source_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for (unit_id,) in unit_ids {
+            let (new_unit_id,): (i32,) = sqlx::query_as(
+                r#"
+                INSERT INTO units (name, textbook_id, sequence_number, word_count)
+                SELECT name, $2, sequence_number, word_count
+                FROM units
+                WHERE id = $1
+                RETURNING id
+                "#,
+            )
+            .bind(unit_id)
+            .bind(textbook.id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO word_unit_mappings (
+                    word_id, unit_id, example, example_prompt_version, meaning, pos, sequence
+                )
+                SELECT word_id, $2, example, example_prompt_version, meaning, pos, sequence
+                FROM word_unit_mappings
+                WHERE unit_id = $1 AND deleted_at IS NULL
+                ORDER BY id
+                "#,
+            )
+            .bind(unit_id)
+            .bind(new_unit_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(textbook)
+    }
+
+    async fn recount_counters(&self) -> Result<Vec<CounterDiscrepancyDTO>> {
+        let mut tx = self.pool.begin().await?;
+        // 与增删单词、单元的事务使用相同的加锁顺序，统计期间阻止新的变更
+        sqlx::query("LOCK TABLE word_unit_mappings, units, textbooks IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await?;
+
+        let units: Vec<(i32, Option<i32>, i32)> = sqlx::query_as(
+            r#"
+            WITH actual AS (
+                SELECT u.id, u.word_count AS stored, COUNT(wum.id)::int AS actual
+                FROM units u
+                LEFT JOIN word_unit_mappings wum ON wum.unit_id = u.id AND wum.deleted_at IS NULL
+                WHERE u.deleted_at IS NULL
+                GROUP BY u.id
+            )
+            UPDATE units u SET word_count = a.actual
+            FROM actual a
+            WHERE u.id = a.id AND u.word_count IS DISTINCT FROM a.actual
+            RETURNING u.id, a.stored, a.actual
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // 单元的单词数已在上一步修正，课本单词数为各单元之和
+        let textbooks: Vec<(i32, Option<i32>, i32, Option<i32>, i32)> = sqlx::query_as(
+            r#"
+            WITH actual AS (
+                SELECT t.id, t.unit_count AS stored_units, COUNT(u.id)::int AS units,
+                       t.word_count AS stored_words, COALESCE(SUM(u.word_count), 0)::int AS words
+                FROM textbooks t
+                LEFT JOIN units u ON u.textbook_id = t.id AND u.deleted_at IS NULL
+                WHERE t.deleted_at IS NULL
+                GROUP BY t.id
+            )
+            UPDATE textbooks t SET unit_count = a.units, word_count = a.words
+            FROM actual a
+            WHERE t.id = a.id
+              AND (t.unit_count IS DISTINCT FROM a.units OR t.word_count IS DISTINCT FROM a.words)
+            RETURNING t.id, a.stored_units, a.units, a.stored_words, a.words
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let mut discrepancies: Vec<CounterDiscrepancyDTO> = units
+            .into_iter()
+            .map(|(id, stored, actual)| {
+                CounterDiscrepancyDTO::new("unit", id, "word_count", stored, actual)
+            })
+            .collect();
+        for (id, stored_units, units, stored_words, words) in textbooks {
+            if stored_units != Some(units) {
+                discrepancies.push(CounterDiscrepancyDTO::new(
+                    "textbook",
+                    id,
+                    "unit_count",
+                    stored_units,
+                    units,
+                ));
+            }
+            if stored_words != Some(words) {
+                discrepancies.push(CounterDiscrepancyDTO::new(
+                    "textbook",
+                    id,
+                    "word_count",
+                    stored_words,
+                    words,
+                ));
+            }
+        }
+        Ok(discrepancies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::unit::Unit;
+    use crate::domain::models::word_unit_mapping::WordUnitMapping;
+    use crate::infrastructure::database::repositories::{
+        Repository, UnitRepositoryImpl, WordUnitMappingRepository, WordUnitMappingRepositoryImpl,
+    };
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_clone_textbook() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let repository = TextbookRepositoryImpl::new(pool.clone());
+
+        let (source_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO textbooks (name, unit_count, word_count) VALUES ('clone source', 2, 3) RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        for (sequence, words) in [(2, vec![3, 4]), (1, vec![5])] {
+            let (unit_id,): (i32,) = sqlx::query_as(
+                "INSERT INTO units (name, textbook_id, sequence_number, word_count) VALUES ($1, $2, $3, $4) RETURNING id",
+            )
+            .bind(format!("Unit {}", sequence))
+            .bind(source_id)
+            .bind(sequence)
+            .bind(words.len() as i32)
+            .fetch_one(&*pool)
+            .await?;
+            for (index, word_id) in words.into_iter().enumerate() {
+                sqlx::query(
+                    "INSERT INTO word_unit_mappings (word_id, unit_id, meaning, sequence) VALUES ($1, $2, 'meaning', $3)",
+                )
+                .bind(word_id)
+                .bind(unit_id)
+                .bind(index as i32 + 1)
+                .execute(&*pool)
+                .await?;
+            }
+        }
+
+        let mut target = repository.find_by_id(source_id).await?.unwrap();
+        target.name = "clone target".to_string();
+        target.grade_id = Some(99);
+        let cloned = repository.clone_textbook(source_id, &target).await?;
+        assert_ne!(cloned.id, Some(source_id));
+        assert_eq!(cloned.grade_id, Some(99));
+        assert_eq!((cloned.unit_count, cloned.word_count), (Some(2), Some(3)));
+
+        // 单元序号、单词 id、单元含义、单词顺序
+        type Row = (Option<i32>, Option<i32>, Option<String>, Option<i32>);
+        let rows: Vec<Row> = sqlx::query_as(
+            r#"
+            SELECT u.sequence_number, wum.word_id, wum.meaning, wum.sequence
+            FROM units u JOIN word_unit_mappings wum ON wum.unit_id = u.id
+            WHERE u.textbook_id = $1
+            ORDER BY u.sequence_number, wum.sequence
+            "#,
+        )
+        .bind(cloned.id)
+        .fetch_all(&*pool)
+        .await?;
+        assert_eq!(
+            rows,
+            vec![
+                (Some(1), Some(5), Some("meaning".to_string()), Some(1)),
+                (Some(2), Some(3), Some("meaning".to_string()), Some(1)),
+                (Some(2), Some(4), Some("meaning".to_string()), Some(2)),
+            ]
+        );
+
+        for id in [source_id, cloned.id.unwrap()] {
+            sqlx::query("DELETE FROM word_unit_mappings WHERE unit_id IN (SELECT id FROM units WHERE textbook_id = $1)")
+                .bind(id)
+                .execute(&*pool)
+                .await?;
+            sqlx::query("DELETE FROM units WHERE textbook_id = $1")
+                .bind(id)
+                .execute(&*pool)
+                .await?;
+            sqlx::query("DELETE FROM textbooks WHERE id = $1")
+                .bind(id)
+                .execute(&*pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_counters_and_recount() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let repository = TextbookRepositoryImpl::new(pool.clone());
+        let units = UnitRepositoryImpl::new(pool.clone());
+        let mappings = WordUnitMappingRepositoryImpl::new(pool.clone());
+        let counts = |id: i32| {
+            let pool = pool.clone();
+            async move {
+                let (unit_count, word_count): (Option<i32>, Option<i32>) =
+                    sqlx::query_as("SELECT unit_count, word_count FROM textbooks WHERE id = $1")
+                        .bind(id)
+                        .fetch_one(&*pool)
+                        .await?;
+                anyhow::Ok((unit_count, word_count))
+            }
+        };
+
+        let (textbook_id,): (i32,) =
+            sqlx::query_as("INSERT INTO textbooks (name) VALUES ('counters') RETURNING id")
+                .fetch_one(&*pool)
+                .await?;
+        let mut unit = Unit::new();
+        unit.textbook_id = Some(textbook_id);
+        unit.word_count = Some(42);
+        let mut unit = units.save(&unit).await?;
+        assert_eq!(unit.word_count, Some(0));
+        assert_eq!(counts(textbook_id).await?, (Some(1), Some(0)));
+
+        let mut mapping = WordUnitMapping::new();
+        mapping.word_id = Some(1);
+        mapping.unit_id = unit.id;
+        let first = mappings.save(&mapping).await?;
+        mapping.word_id = Some(2);
+        mappings.batch_save(&[mapping]).await?;
+        assert_eq!(counts(textbook_id).await?, (Some(1), Some(2)));
+        // 更新单元不会覆盖映射维护的单词数
+        unit.name = Some("renamed".to_string());
+        unit.word_count = Some(0);
+        assert_eq!(units.save(&unit).await?.word_count, Some(2));
+
+        mappings.delete(first.id.unwrap()).await?;
+        assert_eq!(
+            units
+                .find_by_id(unit.id.unwrap())
+                .await?
+                .unwrap()
+                .word_count,
+            Some(1)
+        );
+        assert_eq!(counts(textbook_id).await?, (Some(1), Some(1)));
+
+        sqlx::query("UPDATE units SET word_count = 7 WHERE id = $1")
+            .bind(unit.id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("UPDATE textbooks SET unit_count = 3, word_count = 7 WHERE id = $1")
+            .bind(textbook_id)
+            .execute(&*pool)
+            .await?;
+        let fixed: Vec<CounterDiscrepancyDTO> = repository
+            .recount_counters()
+            .await?
+            .into_iter()
+            .filter(|d| {
+                d.id == unit.id.unwrap() && d.entity == "unit"
+                    || d.id == textbook_id && d.entity == "textbook"
+            })
+            .collect();
+        assert_eq!(
+            fixed,
+            vec![
+                CounterDiscrepancyDTO::new("unit", unit.id.unwrap(), "word_count", Some(7), 1),
+                CounterDiscrepancyDTO::new("textbook", textbook_id, "unit_count", Some(3), 1),
+                CounterDiscrepancyDTO::new("textbook", textbook_id, "word_count", Some(7), 1),
+            ]
+        );
+        assert_eq!(counts(textbook_id).await?, (Some(1), Some(1)));
+
+        units.delete(unit.id.unwrap()).await?;
+        assert_eq!(counts(textbook_id).await?, (Some(0), Some(0)));
+        for sql in [
+            "DELETE FROM word_unit_mappings WHERE unit_id IN (SELECT id FROM units WHERE textbook_id = $1)",
+            "DELETE FROM units WHERE textbook_id = $1",
+            "DELETE FROM textbooks WHERE id = $1",
+        ] {
+            sqlx::query(sql).bind(textbook_id).execute(&*pool).await?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,788 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+use super::unit_repository::{adjust_textbook_counts, claim_sequence};
+use super::word_unit_mapping_repository::adjust_word_count;
+use crate::domain::models::trash::{TrashEntity, TrashItem};
+
+/// 回收站：软删除的教材、单元、单元单词和单词
+///
+/// 删除时子数据和父数据记录相同的 deleted_at，恢复父数据时只恢复同一次删除的子数据，
+/// 之前单独删除的子数据仍留在回收站中。
+#[async_trait]
+pub trait TrashRepository: Send + Sync {
+    /// 统计删除一条数据时会连带删除的单元和单元单词，不修改数据；数据不存在或已删除时返回 None
+    async fn preview(&self, entity: TrashEntity, id: i32) -> Result<Option<TrashItem>>;
+
+    /// 查询 since 之后直接删除的数据，级联删除的子数据算在父数据中
+    async fn find_deleted(
+        &self,
+        entity: Option<TrashEntity>,
+        since: OffsetDateTime,
+    ) -> Result<Vec<TrashItem>>;
+
+    /// 在一个事务中恢复 since 之后删除的数据及同一次删除的子数据，并重新统计计数；不在回收站中时返回 None
+    async fn restore(
+        &self,
+        entity: TrashEntity,
+        id: i32,
+        since: OffsetDateTime,
+    ) -> Result<Option<TrashItem>>;
+
+    /// 永久删除 before 之前删除的数据，返回删除的行数
+    async fn purge(&self, before: OffsetDateTime) -> Result<u64>;
+}
+
+pub struct TrashRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl TrashRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+fn trash_item(
+    entity: TrashEntity,
+    id: i32,
+    name: Option<String>,
+    deleted_at: Option<OffsetDateTime>,
+    units: i64,
+    unit_words: i64,
+) -> TrashItem {
+    TrashItem {
+        entity,
+        id,
+        name: name.unwrap_or_default(),
+        deleted_at,
+        units,
+        unit_words,
+    }
+}
+
+/// 在事务中软删除一条数据并级联删除子数据，同步调整仍然存在的单元和课本的计数；
+/// 数据不存在或已删除时返回 None
+pub(super) async fn soft_delete(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: TrashEntity,
+    id: i32,
+) -> Result<Option<TrashItem>> {
+    // 与新增单元单词的事务一样按单元、单词映射、课本的顺序加锁
+    let item = match entity {
+        TrashEntity::Textbook => {
+            let name: Option<(String,)> =
+                sqlx::query_as("SELECT name FROM textbooks WHERE id = $1 AND deleted_at IS NULL")
+                    .bind(id)
+                    .fetch_optional(&mut **tx)
+                    .await?;
+            let Some((name,)) = name else {
+                return Ok(None);
+            };
+            let unit_ids: Vec<i32> = sqlx::query_scalar(
+                "UPDATE units SET deleted_at = now() WHERE textbook_id = $1 AND deleted_at IS NULL RETURNING id",
+            )
+            .bind(id)
+            .fetch_all(&mut **tx)
+            .await?;
+            let unit_words = sqlx::query(
+                "UPDATE word_unit_mappings SET deleted_at = now() WHERE unit_id = ANY($1) AND deleted_at IS NULL",
+            )
+            .bind(&unit_ids)
+            .execute(&mut **tx)
+            .await?
+            .rows_affected();
+            let deleted_at: OffsetDateTime = sqlx::query_scalar(
+                "UPDATE textbooks SET deleted_at = now() WHERE id = $1 RETURNING deleted_at",
+            )
+            .bind(id)
+            .fetch_one(&mut **tx)
+            .await?;
+            trash_item(
+                entity,
+                id,
+                Some(name),
+                Some(deleted_at),
+                unit_ids.len() as i64,
+                unit_words as i64,
+            )
+        }
+        TrashEntity::Unit => {
+            let unit: Option<(Option<String>, Option<i32>, OffsetDateTime)> = sqlx::query_as(
+                r#"
+                UPDATE units SET deleted_at = now()
+                WHERE id = $1 AND deleted_at IS NULL
+                RETURNING name, textbook_id, deleted_at
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+            let Some((name, textbook_id, deleted_at)) = unit else {
+                return Ok(None);
+            };
+            let unit_words = sqlx::query(
+                "UPDATE word_unit_mappings SET deleted_at = now() WHERE unit_id = $1 AND deleted_at IS NULL",
+            )
+            .bind(id)
+            .execute(&mut **tx)
+            .await?
+            .rows_affected() as i64;
+            adjust_textbook_counts(tx, textbook_id, -1, -(unit_words as i32)).await?;
+            trash_item(entity, id, name, Some(deleted_at), 1, unit_words)
+        }
+        TrashEntity::UnitWord => {
+            let mapping: Option<(Option<i32>, Option<String>, OffsetDateTime)> = sqlx::query_as(
+                r#"
+                UPDATE word_unit_mappings m SET deleted_at = now()
+                WHERE m.id = $1 AND m.deleted_at IS NULL
+                RETURNING m.unit_id, (SELECT word FROM words w WHERE w.word_id = m.word_id), m.deleted_at
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+            let Some((unit_id, word, deleted_at)) = mapping else {
+                return Ok(None);
+            };
+            adjust_word_count(tx, unit_id, -1).await?;
+            trash_item(entity, id, word, Some(deleted_at), 0, 1)
+        }
+        TrashEntity::Word => {
+            let word: Option<(String, OffsetDateTime)> = sqlx::query_as(
+                r#"
+                UPDATE words SET deleted_at = now()
+                WHERE word_id = $1 AND deleted_at IS NULL
+                RETURNING word, deleted_at
+                "#,
+            )
+            .bind(id)
+            .fetch_optional(&mut **tx)
+            .await?;
+            let Some((word, deleted_at)) = word else {
+                return Ok(None);
+            };
+            let unit_ids: Vec<Option<i32>> = sqlx::query_scalar(
+                "UPDATE word_unit_mappings SET deleted_at = now() WHERE word_id = $1 AND deleted_at IS NULL RETURNING unit_id",
+            )
+            .bind(id)
+            .fetch_all(&mut **tx)
+            .await?;
+            for unit_id in &unit_ids {
+                adjust_word_count(tx, *unit_id, -1).await?;
+            }
+            trash_item(
+                entity,
+                id,
+                Some(word),
+                Some(deleted_at),
+                0,
+                unit_ids.len() as i64,
+            )
+        }
+    };
+    Ok(Some(item))
+}
+
+/// 按未删除的数据重新统计单元的单词数以及课本的单元数和单词数
+async fn refresh_counts(
+    tx: &mut Transaction<'_, Postgres>,
+    unit_ids: &[i32],
+    textbook_id: Option<i32>,
+) -> Result<()> {
+    // 先锁住要统计的行，并发新增的单元单词会在本事务提交后再累加计数
+    sqlx::query("SELECT id FROM units WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+        .bind(unit_ids)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query("SELECT id FROM textbooks WHERE id = $1 FOR UPDATE")
+        .bind(textbook_id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query(
+        r#"
+        UPDATE units u SET word_count = (
+            SELECT COUNT(*) FROM word_unit_mappings m
+            WHERE m.unit_id = u.id AND m.deleted_at IS NULL
+        )
+        WHERE u.id = ANY($1)
+        "#,
+    )
+    .bind(unit_ids)
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query(
+        r#"
+        UPDATE textbooks t
+        SET unit_count = (
+                SELECT COUNT(*) FROM units u WHERE u.textbook_id = t.id AND u.deleted_at IS NULL
+            ),
+            word_count = (
+                SELECT COALESCE(SUM(u.word_count), 0) FROM units u
+                WHERE u.textbook_id = t.id AND u.deleted_at IS NULL
+            )
+        WHERE t.id = $1
+        "#,
+    )
+    .bind(textbook_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// 恢复与父数据同一次删除、且单词仍然存在的单元单词，返回恢复的行数
+async fn restore_unit_words(
+    tx: &mut Transaction<'_, Postgres>,
+    unit_ids: &[i32],
+    deleted_at: OffsetDateTime,
+) -> Result<i64> {
+    let restored = sqlx::query(
+        r#"
+        UPDATE word_unit_mappings m SET deleted_at = NULL
+        WHERE m.unit_id = ANY($1) AND m.deleted_at = $2
+          AND EXISTS (SELECT 1 FROM words w WHERE w.word_id = m.word_id AND w.deleted_at IS NULL)
+        "#,
+    )
+    .bind(unit_ids)
+    .bind(deleted_at)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+    Ok(restored as i64)
+}
+
+#[async_trait]
+impl TrashRepository for TrashRepositoryImpl {
+    async fn preview(&self, entity: TrashEntity, id: i32) -> Result<Option<TrashItem>> {
+        let sql = match entity {
+            TrashEntity::Textbook => {
+                r#"
+                SELECT t.name,
+                       (SELECT COUNT(*) FROM units u
+                        WHERE u.textbook_id = t.id AND u.deleted_at IS NULL),
+                       (SELECT COUNT(*) FROM word_unit_mappings m JOIN units u ON u.id = m.unit_id
+                        WHERE u.textbook_id = t.id AND u.deleted_at IS NULL AND m.deleted_at IS NULL)
+                FROM textbooks t
+                WHERE t.id = $1 AND t.deleted_at IS NULL
+                "#
+            }
+            TrashEntity::Unit => {
+                r#"
+                SELECT u.name, 1::BIGINT,
+                       (SELECT COUNT(*) FROM word_unit_mappings m
+                        WHERE m.unit_id = u.id AND m.deleted_at IS NULL)
+                FROM units u
+                WHERE u.id = $1 AND u.deleted_at IS NULL
+                "#
+            }
+            TrashEntity::UnitWord => {
+                r#"
+                SELECT w.word, 0::BIGINT, 1::BIGINT
+                FROM word_unit_mappings m LEFT JOIN words w ON w.word_id = m.word_id
+                WHERE m.id = $1 AND m.deleted_at IS NULL
+                "#
+            }
+            TrashEntity::Word => {
+                r#"
+                SELECT w.word, 0::BIGINT,
+                       (SELECT COUNT(*) FROM word_unit_mappings m
+                        WHERE m.word_id = w.word_id AND m.deleted_at IS NULL)
+                FROM words w
+                WHERE w.word_id = $1 AND w.deleted_at IS NULL
+                "#
+            }
+        };
+        let row: Option<(Option<String>, i64, i64)> = sqlx::query_as(sql)
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await?;
+        Ok(row
+            .map(|(name, units, unit_words)| trash_item(entity, id, name, None, units, unit_words)))
+    }
+
+    async fn find_deleted(
+        &self,
+        entity: Option<TrashEntity>,
+        since: OffsetDateTime,
+    ) -> Result<Vec<TrashItem>> {
+        type Row = (String, i32, Option<String>, OffsetDateTime, i64, i64);
+        // 子数据的删除时间与父数据相同时说明是级联删除，不单独列出
+        let rows: Vec<Row> = sqlx::query_as(
+            r#"
+            SELECT * FROM (
+                SELECT 'textbook' AS entity, t.id, t.name, t.deleted_at,
+                       (SELECT COUNT(*) FROM units u
+                        WHERE u.textbook_id = t.id AND u.deleted_at = t.deleted_at) AS units,
+                       (SELECT COUNT(*) FROM word_unit_mappings m JOIN units u ON u.id = m.unit_id
+                        WHERE u.textbook_id = t.id AND u.deleted_at = t.deleted_at
+                          AND m.deleted_at = t.deleted_at) AS unit_words
+                FROM textbooks t
+                WHERE t.deleted_at >= $1
+                UNION ALL
+                SELECT 'unit', u.id, u.name, u.deleted_at, 1,
+                       (SELECT COUNT(*) FROM word_unit_mappings m
+                        WHERE m.unit_id = u.id AND m.deleted_at = u.deleted_at)
+                FROM units u LEFT JOIN textbooks t ON t.id = u.textbook_id
+                WHERE u.deleted_at >= $1 AND t.deleted_at IS DISTINCT FROM u.deleted_at
+                UNION ALL
+                SELECT 'unit_word', m.id, w.word, m.deleted_at, 0, 1
+                FROM word_unit_mappings m
+                LEFT JOIN units u ON u.id = m.unit_id
+                LEFT JOIN words w ON w.word_id = m.word_id
+                WHERE m.deleted_at >= $1
+                  AND u.deleted_at IS DISTINCT FROM m.deleted_at
+                  AND w.deleted_at IS DISTINCT FROM m.deleted_at
+                UNION ALL
+                SELECT 'word', w.word_id, w.word, w.deleted_at, 0,
+                       (SELECT COUNT(*) FROM word_unit_mappings m
+                        WHERE m.word_id = w.word_id AND m.deleted_at = w.deleted_at)
+                FROM words w
+                WHERE w.deleted_at >= $1
+            ) trash
+            WHERE $2::TEXT IS NULL OR entity = $2
+            ORDER BY deleted_at DESC, entity, id
+            "#,
+        )
+        .bind(since)
+        .bind(entity.map(|entity| entity.as_str()))
+        .fetch_all(&*self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(entity, id, name, deleted_at, units, unit_words)| {
+                Ok(trash_item(
+                    entity.parse()?,
+                    id,
+                    name,
+                    Some(deleted_at),
+                    units,
+                    unit_words,
+                ))
+            })
+            .collect()
+    }
+
+    async fn restore(
+        &self,
+        entity: TrashEntity,
+        id: i32,
+        since: OffsetDateTime,
+    ) -> Result<Option<TrashItem>> {
+        let mut tx = self.pool.begin().await?;
+        let item = match entity {
+            TrashEntity::Textbook => {
+                let textbook: Option<(String, OffsetDateTime)> = sqlx::query_as(
+                    "SELECT name, deleted_at FROM textbooks WHERE id = $1 AND deleted_at >= $2 FOR UPDATE",
+                )
+                .bind(id)
+                .bind(since)
+                .fetch_optional(&mut *tx)
+                .await?;
+                let Some((name, deleted_at)) = textbook else {
+                    return Ok(None);
+                };
+                // 删除后同一版本、年级、学期已有新的课本时不能恢复
+                let occupant: Option<String> = sqlx::query_scalar(
+                    r#"
+                    SELECT other.name FROM textbooks t
+                    JOIN textbooks other ON other.version_id = t.version_id
+                        AND other.grade_id = t.grade_id AND other.semester_id = t.semester_id
+                    WHERE t.id = $1 AND other.id <> t.id AND other.deleted_at IS NULL
+                    "#,
+                )
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+                if let Some(occupant) = occupant {
+                    return Err(anyhow!(
+                        "Cannot restore textbook {}: textbook {} already exists for this version, grade and semester",
+                        name,
+                        occupant
+                    ));
+                }
+                let unit_ids: Vec<i32> = sqlx::query_scalar(
+                    "UPDATE units SET deleted_at = NULL WHERE textbook_id = $1 AND deleted_at = $2 RETURNING id",
+                )
+                .bind(id)
+                .bind(deleted_at)
+                .fetch_all(&mut *tx)
+                .await?;
+                let unit_words = restore_unit_words(&mut tx, &unit_ids, deleted_at).await?;
+                sqlx::query("UPDATE textbooks SET deleted_at = NULL WHERE id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                refresh_counts(&mut tx, &unit_ids, Some(id)).await?;
+                trash_item(
+                    entity,
+                    id,
+                    Some(name),
+                    None,
+                    unit_ids.len() as i64,
+                    unit_words,
+                )
+            }
+            TrashEntity::Unit => {
+                type Row = (Option<String>, Option<i32>, Option<i32>, OffsetDateTime);
+                let unit: Option<Row> = sqlx::query_as(
+                    "SELECT name, textbook_id, sequence_number, deleted_at FROM units WHERE id = $1 AND deleted_at >= $2 FOR UPDATE",
+                )
+                .bind(id)
+                .bind(since)
+                .fetch_optional(&mut *tx)
+                .await?;
+                let Some((name, textbook_id, sequence_number, deleted_at)) = unit else {
+                    return Ok(None);
+                };
+                let textbook_deleted: Option<bool> = sqlx::query_scalar(
+                    "SELECT deleted_at IS NOT NULL FROM textbooks WHERE id = $1",
+                )
+                .bind(textbook_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+                if textbook_deleted == Some(true) {
+                    return Err(anyhow!(
+                        "Textbook {} of unit {} is deleted, restore the textbook first",
+                        textbook_id.unwrap_or_default(),
+                        id
+                    ));
+                }
+                // 原来的序号已被其他单元占用时，把这些单元后移，恢复到原来的位置
+                let sequence_number =
+                    claim_sequence(&mut tx, textbook_id, sequence_number, Some(id)).await?;
+                sqlx::query(
+                    "UPDATE units SET deleted_at = NULL, sequence_number = $2 WHERE id = $1",
+                )
+                .bind(id)
+                .bind(sequence_number)
+                .execute(&mut *tx)
+                .await?;
+                let unit_words = restore_unit_words(&mut tx, &[id], deleted_at).await?;
+                refresh_counts(&mut tx, &[id], textbook_id).await?;
+                trash_item(entity, id, name, None, 1, unit_words)
+            }
+            TrashEntity::UnitWord => {
+                let mapping: Option<(Option<i32>, Option<i32>)> = sqlx::query_as(
+                    "SELECT unit_id, word_id FROM word_unit_mappings WHERE id = $1 AND deleted_at >= $2 FOR UPDATE",
+                )
+                .bind(id)
+                .bind(since)
+                .fetch_optional(&mut *tx)
+                .await?;
+                let Some((unit_id, word_id)) = mapping else {
+                    return Ok(None);
+                };
+                let word: Option<(String, bool)> = sqlx::query_as(
+                    "SELECT word, deleted_at IS NOT NULL FROM words WHERE word_id = $1",
+                )
+                .bind(word_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+                let (word, word_deleted) =
+                    word.ok_or_else(|| anyhow!("Word of unit word {} not found", id))?;
+                if word_deleted {
+                    return Err(anyhow!(
+                        "Word '{}' is deleted, restore the word first",
+                        word
+                    ));
+                }
+                let unit_live: Option<i32> =
+                    sqlx::query_scalar("SELECT id FROM units WHERE id = $1 AND deleted_at IS NULL")
+                        .bind(unit_id)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                if unit_live.is_none() {
+                    return Err(anyhow!(
+                        "Unit {} is deleted, restore the unit first",
+                        unit_id.unwrap_or_default()
+                    ));
+                }
+                let duplicate: Option<i32> = sqlx::query_scalar(
+                    "SELECT id FROM word_unit_mappings WHERE word_id = $1 AND unit_id = $2 AND deleted_at IS NULL",
+                )
+                .bind(word_id)
+                .bind(unit_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+                if duplicate.is_some() {
+                    return Err(anyhow!(
+                        "Word '{}' is already in unit {}",
+                        word,
+                        unit_id.unwrap_or_default()
+                    ));
+                }
+                sqlx::query("UPDATE word_unit_mappings SET deleted_at = NULL WHERE id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                adjust_word_count(&mut tx, unit_id, 1).await?;
+                trash_item(entity, id, Some(word), None, 0, 1)
+            }
+            TrashEntity::Word => {
+                let word: Option<(String, OffsetDateTime)> = sqlx::query_as(
+                    "SELECT word, deleted_at FROM words WHERE word_id = $1 AND deleted_at >= $2 FOR UPDATE",
+                )
+                .bind(id)
+                .bind(since)
+                .fetch_optional(&mut *tx)
+                .await?;
+                let Some((word, deleted_at)) = word else {
+                    return Ok(None);
+                };
+                sqlx::query("UPDATE words SET deleted_at = NULL WHERE word_id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                // 单元已删除或单元中已重新加入该单词时不恢复
+                let unit_ids: Vec<Option<i32>> = sqlx::query_scalar(
+                    r#"
+                    UPDATE word_unit_mappings m SET deleted_at = NULL
+                    WHERE m.word_id = $1 AND m.deleted_at = $2
+                      AND EXISTS (SELECT 1 FROM units u WHERE u.id = m.unit_id AND u.deleted_at IS NULL)
+                      AND NOT EXISTS (
+                          SELECT 1 FROM word_unit_mappings d
+                          WHERE d.word_id = m.word_id AND d.unit_id = m.unit_id AND d.deleted_at IS NULL
+                      )
+                    RETURNING m.unit_id
+                    "#,
+                )
+                .bind(id)
+                .bind(deleted_at)
+                .fetch_all(&mut *tx)
+                .await?;
+                for unit_id in &unit_ids {
+                    adjust_word_count(&mut tx, *unit_id, 1).await?;
+                }
+                trash_item(entity, id, Some(word), None, 0, unit_ids.len() as i64)
+            }
+        };
+        tx.commit().await?;
+        Ok(Some(item))
+    }
+
+    async fn purge(&self, before: OffsetDateTime) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut purged = 0;
+        for sql in [
+            r#"
+            DELETE FROM word_unit_mappings
+            WHERE deleted_at < $1
+               OR word_id IN (SELECT word_id FROM words WHERE deleted_at < $1)
+               OR unit_id IN (SELECT id FROM units WHERE deleted_at < $1)
+            "#,
+            r#"
+            DELETE FROM units
+            WHERE deleted_at < $1
+               OR textbook_id IN (SELECT id FROM textbooks WHERE deleted_at < $1)
+            "#,
+            "DELETE FROM textbooks WHERE deleted_at < $1",
+            "DELETE FROM words WHERE deleted_at < $1",
+        ] {
+            purged += sqlx::query(sql)
+                .bind(before)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::repositories::{
+        Repository, TextbookRepository, TextbookRepositoryImpl, WordRepositoryImpl,
+        WordUnitMappingRepositoryImpl,
+    };
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_cascade_delete_and_restore() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let trash = TrashRepositoryImpl::new(pool.clone());
+        let textbooks = TextbookRepositoryImpl::new(pool.clone());
+        let mappings = WordUnitMappingRepositoryImpl::new(pool.clone());
+        let words = WordRepositoryImpl::new(pool.clone());
+        let since = OffsetDateTime::now_utc() - time::Duration::minutes(1);
+        let counts = |id: i32| {
+            let pool = pool.clone();
+            async move {
+                let counts: (Option<i32>, Option<i32>) =
+                    sqlx::query_as("SELECT unit_count, word_count FROM textbooks WHERE id = $1")
+                        .bind(id)
+                        .fetch_one(&*pool)
+                        .await?;
+                anyhow::Ok(counts)
+            }
+        };
+
+        let (textbook_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO textbooks (name, unit_count, word_count) VALUES ('trash', 2, 3) RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        let mut word_ids = Vec::new();
+        for word in ["trash-apple", "trash-pear", "trash-plum"] {
+            let (word_id,): (i32,) =
+                sqlx::query_as("INSERT INTO words (word) VALUES ($1) RETURNING word_id")
+                    .bind(word)
+                    .fetch_one(&*pool)
+                    .await?;
+            word_ids.push(word_id);
+        }
+        let mut unit_ids = Vec::new();
+        let mut mapping_ids = Vec::new();
+        for (sequence, unit_words) in [(1, &word_ids[..2]), (2, &word_ids[2..])] {
+            let (unit_id,): (i32,) = sqlx::query_as(
+                "INSERT INTO units (name, textbook_id, sequence_number, word_count) VALUES ('Unit', $1, $2, $3) RETURNING id",
+            )
+            .bind(textbook_id)
+            .bind(sequence)
+            .bind(unit_words.len() as i32)
+            .fetch_one(&*pool)
+            .await?;
+            unit_ids.push(unit_id);
+            for word_id in unit_words {
+                let (mapping_id,): (i32,) = sqlx::query_as(
+                    "INSERT INTO word_unit_mappings (word_id, unit_id) VALUES ($1, $2) RETURNING id",
+                )
+                .bind(word_id)
+                .bind(unit_id)
+                .fetch_one(&*pool)
+                .await?;
+                mapping_ids.push(mapping_id);
+            }
+        }
+
+        let preview = trash
+            .preview(TrashEntity::Textbook, textbook_id)
+            .await?
+            .unwrap();
+        assert_eq!((preview.units, preview.unit_words), (2, 3));
+
+        // 先单独删除一个单元单词，再删除教材
+        mappings.delete(mapping_ids[0]).await?;
+        assert_eq!(counts(textbook_id).await?, (Some(2), Some(2)));
+        textbooks.delete(textbook_id).await?;
+        assert!(textbooks.find_by_id(textbook_id).await?.is_none());
+        assert!(trash
+            .preview(TrashEntity::Unit, unit_ids[0])
+            .await?
+            .is_none());
+
+        let deleted: Vec<(TrashEntity, i32, i64, i64)> = trash
+            .find_deleted(None, since)
+            .await?
+            .into_iter()
+            .filter(|item| {
+                (item.entity, item.id) == (TrashEntity::Textbook, textbook_id)
+                    || (item.entity, item.id) == (TrashEntity::UnitWord, mapping_ids[0])
+                    || unit_ids.contains(&item.id) && item.entity == TrashEntity::Unit
+            })
+            .map(|item| (item.entity, item.id, item.units, item.unit_words))
+            .collect();
+        assert_eq!(deleted.len(), 2);
+        assert!(deleted.contains(&(TrashEntity::Textbook, textbook_id, 2, 2)));
+        assert!(deleted.contains(&(TrashEntity::UnitWord, mapping_ids[0], 0, 1)));
+
+        // 单元单词所在的单元还在回收站中，不能单独恢复
+        assert!(trash
+            .restore(TrashEntity::UnitWord, mapping_ids[0], since)
+            .await
+            .is_err());
+        let restored = trash
+            .restore(TrashEntity::Textbook, textbook_id, since)
+            .await?
+            .unwrap();
+        assert_eq!((restored.units, restored.unit_words), (2, 2));
+        assert_eq!(counts(textbook_id).await?, (Some(2), Some(2)));
+        trash
+            .restore(TrashEntity::UnitWord, mapping_ids[0], since)
+            .await?
+            .unwrap();
+        assert_eq!(counts(textbook_id).await?, (Some(2), Some(3)));
+
+        // 删除单词会从单元中移除，恢复单词时一起恢复
+        words.delete(word_ids[2]).await?;
+        assert_eq!(counts(textbook_id).await?, (Some(2), Some(2)));
+        let restored = trash
+            .restore(TrashEntity::Word, word_ids[2], since)
+            .await?
+            .unwrap();
+        assert_eq!(restored.unit_words, 1);
+        assert_eq!(counts(textbook_id).await?, (Some(2), Some(3)));
+        assert!(trash
+            .restore(TrashEntity::Word, word_ids[2], since)
+            .await?
+            .is_none());
+
+        // 过期的数据不能恢复，清理时永久删除
+        textbooks.delete(textbook_id).await?;
+        let future = OffsetDateTime::now_utc() + time::Duration::minutes(1);
+        assert!(trash
+            .restore(TrashEntity::Textbook, textbook_id, future)
+            .await?
+            .is_none());
+        words.delete(word_ids[0]).await?;
+        words.delete(word_ids[1]).await?;
+        words.delete(word_ids[2]).await?;
+        assert!(trash.purge(future).await? >= 9);
+        let (remaining,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM units WHERE textbook_id = $1 OR id = ANY($2)")
+                .bind(textbook_id)
+                .bind(&unit_ids)
+                .fetch_one(&*pool)
+                .await?;
+        assert_eq!(remaining, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_restore_reused_textbook_slot() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let trash = TrashRepositoryImpl::new(pool.clone());
+        let textbooks = TextbookRepositoryImpl::new(pool.clone());
+        let since = OffsetDateTime::now_utc() - time::Duration::minutes(1);
+        let insert = |name: &'static str| {
+            let pool = pool.clone();
+            async move {
+                let (id,): (i32,) = sqlx::query_as(
+                    "INSERT INTO textbooks (name, version_id, grade_id, semester_id) VALUES ($1, -901, -901, -901) RETURNING id",
+                )
+                .bind(name)
+                .fetch_one(&*pool)
+                .await?;
+                anyhow::Ok(id)
+            }
+        };
+
+        // 已删除的课本不占用版本、年级、学期，可以在同一位置新建
+        let old_id = insert("trash-slot-old").await?;
+        textbooks.delete(old_id).await?;
+        let new_id = insert("trash-slot-new").await?;
+
+        let error = trash
+            .restore(TrashEntity::Textbook, old_id, since)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("trash-slot-new"), "{}", error);
+
+        textbooks.delete(new_id).await?;
+        assert!(trash
+            .restore(TrashEntity::Textbook, old_id, since)
+            .await?
+            .is_some());
+
+        sqlx::query("DELETE FROM textbooks WHERE id = ANY($1)")
+            .bind(vec![old_id, new_id])
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use sqlx::{PgPool, Postgres, QueryBuilder};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
 use std::sync::Arc;
 
 use super::base::Repository;
+use super::trash_repository::soft_delete;
 use crate::api::dto::unit_dto::UnitDTO;
+use crate::common::utils::sequence::Reorder;
+use crate::domain::models::trash::TrashEntity;
 use crate::domain::models::unit::Unit;
 
 #[async_trait]
@@ -14,6 +17,9 @@ pub trait UnitRepository: Repository<Unit, i32> + Send + Sync {
 
     /// 根据教材ID查询单元列表
     async fn find_by_textbook_id(&self, textbook_id: Option<i32>) -> Result<Vec<Unit>>;
+
+    /// 在同一事务中调整课本中单元的顺序，并把序号整理为连续的 1..n
+    async fn reorder(&self, textbook_id: i32, op: Reorder) -> Result<()>;
 }
 
 pub struct UnitRepositoryImpl {
@@ -26,43 +32,23 @@ impl UnitRepositoryImpl {
     }
 
     fn build_query_from_dto(&self, dto: &UnitDTO) -> QueryBuilder<'_, Postgres> {
-        let mut query = QueryBuilder::new("SELECT * FROM units");
-        let mut first_condition = true;
-
-        if dto.id.is_some()
-            || dto.name.is_some()
-            || dto.textbook_id.is_some()
-            || dto.sequence_number.is_some()
-        {
-            query.push(" WHERE ");
-
-            if let Some(id) = dto.id {
-                query.push("id = ").push_bind(id);
-                first_condition = false;
-            }
-
-            if let Some(ref name) = dto.name {
-                if !first_condition {
-                    query.push(" AND ");
-                }
-                query.push("name LIKE ").push_bind(format!("%{}%", name));
-                first_condition = false;
-            }
+        let mut query = QueryBuilder::new("SELECT * FROM units WHERE deleted_at IS NULL");
 
-            if let Some(textbook_id) = dto.textbook_id {
-                if !first_condition {
-                    query.push(" AND ");
-                }
-                query.push("textbook_id = ").push_bind(textbook_id);
-                first_condition = false;
-            }
-
-            if let Some(sequence_number) = dto.sequence_number {
-                if !first_condition {
-                    query.push(" AND ");
-                }
-                query.push("sequence_number = ").push_bind(sequence_number);
-            }
+        if let Some(id) = dto.id {
+            query.push(" AND id = ").push_bind(id);
+        }
+        if let Some(ref name) = dto.name {
+            query
+                .push(" AND name LIKE ")
+                .push_bind(format!("%{}%", name));
+        }
+        if let Some(textbook_id) = dto.textbook_id {
+            query.push(" AND textbook_id = ").push_bind(textbook_id);
+        }
+        if let Some(sequence_number) = dto.sequence_number {
+            query
+                .push(" AND sequence_number = ")
+                .push_bind(sequence_number);
         }
 
         query.push(" ORDER BY sequence_number");
@@ -73,69 +59,169 @@ impl UnitRepositoryImpl {
 #[async_trait]
 impl Repository<Unit, i32> for UnitRepositoryImpl {
     async fn find_by_id(&self, id: i32) -> Result<Option<Unit>> {
-        let unit = sqlx::query_as!(Unit, "SELECT * FROM units WHERE id = $1", id)
-            .fetch_optional(&*self.pool)
-            .await?;
+        let unit = sqlx::query_as!(
+            Unit,
+            "SELECT * FROM units WHERE id = $1 AND deleted_at IS NULL",
+            id
+        )
+        .fetch_optional(&*self.pool)
+        .await?;
 
         Ok(unit)
     }
 
     async fn find_all(&self) -> Result<Vec<Unit>> {
-        let units = sqlx::query_as!(Unit, "SELECT * FROM units ORDER BY id")
-            .fetch_all(&*self.pool)
-            .await?;
+        let units = sqlx::query_as!(
+            Unit,
+            "SELECT * FROM units WHERE deleted_at IS NULL ORDER BY id"
+        )
+        .fetch_all(&*self.pool)
+        .await?;
 
         Ok(units)
     }
 
     async fn save(&self, unit: &Unit) -> Result<Unit> {
+        let mut tx = self.pool.begin().await?;
         let result = if let Some(id) = unit.id {
-            // Update
-            sqlx::query_as!(
+            // Update，word_count 由单词映射的增删维护，这里不覆盖
+            let previous: Option<(Option<i32>, Option<i32>)> = sqlx::query_as(
+                "SELECT textbook_id, sequence_number FROM units WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+            )
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            let sequence_number = if previous == Some((unit.textbook_id, unit.sequence_number)) {
+                unit.sequence_number
+            } else {
+                claim_sequence(&mut tx, unit.textbook_id, unit.sequence_number, Some(id)).await?
+            };
+            let result = sqlx::query_as!(
                 Unit,
                 r#"
                 UPDATE units 
-                SET name = $1, textbook_id = $2, sequence_number = $3, word_count = $4
-                WHERE id = $5
+                SET name = $1, textbook_id = $2, sequence_number = $3
+                WHERE id = $4 AND deleted_at IS NULL
                 RETURNING *
                 "#,
                 unit.name,
                 unit.textbook_id,
-                unit.sequence_number,
-                unit.word_count,
+                sequence_number,
                 id
             )
-            .fetch_one(&*self.pool)
-            .await?
+            .fetch_one(&mut *tx)
+            .await?;
+            if let Some((previous_textbook_id, _)) = previous {
+                if previous_textbook_id != result.textbook_id {
+                    let words = result.word_count.unwrap_or_default();
+                    adjust_textbook_counts(&mut tx, previous_textbook_id, -1, -words).await?;
+                    adjust_textbook_counts(&mut tx, result.textbook_id, 1, words).await?;
+                }
+            }
+            result
         } else {
-            // Insert
-            sqlx::query_as!(
+            // Insert，新单元还没有单词
+            let sequence_number =
+                claim_sequence(&mut tx, unit.textbook_id, unit.sequence_number, None).await?;
+            let result = sqlx::query_as!(
                 Unit,
                 r#"
                 INSERT INTO units (name, textbook_id, sequence_number, word_count)
-                VALUES ($1, $2, $3, $4)
+                VALUES ($1, $2, $3, 0)
                 RETURNING *
                 "#,
                 unit.name,
                 unit.textbook_id,
-                unit.sequence_number,
-                unit.word_count
+                sequence_number
             )
-            .fetch_one(&*self.pool)
-            .await?
+            .fetch_one(&mut *tx)
+            .await?;
+            adjust_textbook_counts(&mut tx, result.textbook_id, 1, 0).await?;
+            result
         };
+        tx.commit().await?;
 
         Ok(result)
     }
 
     async fn delete(&self, id: i32) -> Result<()> {
-        sqlx::query!("DELETE FROM units WHERE id = $1", id)
-            .execute(&*self.pool)
-            .await?;
+        let mut tx = self.pool.begin().await?;
+        soft_delete(&mut tx, TrashEntity::Unit, id).await?;
+        tx.commit().await?;
         Ok(())
     }
 }
 
+/// 在同一事务中原子地调整课本的单元数和单词数
+pub(super) async fn adjust_textbook_counts(
+    tx: &mut Transaction<'_, Postgres>,
+    textbook_id: Option<i32>,
+    units: i32,
+    words: i32,
+) -> Result<()> {
+    let Some(textbook_id) = textbook_id else {
+        return Ok(());
+    };
+    sqlx::query(
+        r#"
+        UPDATE textbooks
+        SET unit_count = COALESCE(unit_count, 0) + $2, word_count = COALESCE(word_count, 0) + $3
+        WHERE id = $1
+        "#,
+    )
+    .bind(textbook_id)
+    .bind(units)
+    .bind(words)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// 为新增或调整了位置的单元确定序号：未指定时排在课本最后；
+/// 指定的序号已被其他单元占用时，把该序号及之后的单元依次后移一位
+pub(super) async fn claim_sequence(
+    tx: &mut Transaction<'_, Postgres>,
+    textbook_id: Option<i32>,
+    sequence_number: Option<i32>,
+    exclude_id: Option<i32>,
+) -> Result<Option<i32>> {
+    let Some(textbook_id) = textbook_id else {
+        return Ok(sequence_number);
+    };
+    let siblings: Vec<Option<i32>> = sqlx::query_scalar(
+        r#"
+        SELECT sequence_number FROM units
+        WHERE textbook_id = $1 AND deleted_at IS NULL AND id IS DISTINCT FROM $2
+        ORDER BY id
+        FOR UPDATE
+        "#,
+    )
+    .bind(textbook_id)
+    .bind(exclude_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let Some(sequence_number) = sequence_number else {
+        let last = siblings.iter().flatten().max().copied().unwrap_or_default();
+        return Ok(Some(last + 1));
+    };
+    if siblings.contains(&Some(sequence_number)) {
+        sqlx::query(
+            r#"
+            UPDATE units SET sequence_number = sequence_number + 1
+            WHERE textbook_id = $1 AND deleted_at IS NULL AND id IS DISTINCT FROM $2
+              AND sequence_number >= $3
+            "#,
+        )
+        .bind(textbook_id)
+        .bind(exclude_id)
+        .bind(sequence_number)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(Some(sequence_number))
+}
+
 #[async_trait]
 impl UnitRepository for UnitRepositoryImpl {
     async fn find_by_dto(&self, dto: &UnitDTO) -> Result<Vec<Unit>> {
@@ -151,7 +237,7 @@ impl UnitRepository for UnitRepositoryImpl {
     async fn find_by_textbook_id(&self, textbook_id: Option<i32>) -> Result<Vec<Unit>> {
         let units = sqlx::query_as!(
             Unit,
-            "SELECT * FROM units WHERE textbook_id = $1 ORDER BY sequence_number",
+            "SELECT * FROM units WHERE textbook_id = $1 AND deleted_at IS NULL ORDER BY sequence_number",
             textbook_id
         )
         .fetch_all(&*self.pool)
@@ -159,4 +245,129 @@ impl UnitRepository for UnitRepositoryImpl {
 
         Ok(units)
     }
+
+    async fn reorder(&self, textbook_id: i32, op: Reorder) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let mut ids: Vec<i32> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM units
+            WHERE textbook_id = $1 AND deleted_at IS NULL
+            ORDER BY sequence_number NULLS LAST, id
+            FOR UPDATE
+            "#,
+        )
+        .bind(textbook_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        op.apply(&mut ids)?;
+
+        sqlx::query(
+            r#"
+            UPDATE units u SET sequence_number = o.position::int
+            FROM unnest($1::int[]) WITH ORDINALITY AS o(id, position)
+            WHERE u.id = o.id AND u.sequence_number IS DISTINCT FROM o.position::int
+            "#,
+        )
+        .bind(&ids)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::repositories::{TrashRepository, TrashRepositoryImpl};
+    use time::OffsetDateTime;
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_claim_sequence_and_reorder() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let units = UnitRepositoryImpl::new(pool.clone());
+        let (textbook_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO textbooks (name, unit_count, word_count) VALUES ('sequence', 0, 0) RETURNING id",
+        )
+        .fetch_one(&*pool)
+        .await?;
+        let save = |sequence_number: Option<i32>| {
+            let mut unit = Unit::new();
+            unit.textbook_id = Some(textbook_id);
+            unit.sequence_number = sequence_number;
+            unit
+        };
+        let order = || async {
+            let units = units.find_by_textbook_id(Some(textbook_id)).await?;
+            anyhow::Ok(
+                units
+                    .iter()
+                    .map(|unit| (unit.id.unwrap(), unit.sequence_number.unwrap()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        // 未指定序号时排在最后，占用已有序号时后面的单元依次后移
+        let a = units.save(&save(None)).await?.id.unwrap();
+        let b = units.save(&save(None)).await?.id.unwrap();
+        let c = units.save(&save(Some(1))).await?.id.unwrap();
+        assert_eq!(order().await?, vec![(c, 1), (a, 2), (b, 3)]);
+
+        units
+            .reorder(textbook_id, Reorder::Move { id: b, position: 1 })
+            .await?;
+        assert_eq!(order().await?, vec![(b, 1), (c, 2), (a, 3)]);
+        units
+            .reorder(
+                textbook_id,
+                Reorder::Swap {
+                    first: b,
+                    second: a,
+                },
+            )
+            .await?;
+        assert_eq!(order().await?, vec![(a, 1), (c, 2), (b, 3)]);
+        assert!(units
+            .reorder(
+                textbook_id,
+                Reorder::Move {
+                    id: -1,
+                    position: 1
+                }
+            )
+            .await
+            .is_err());
+
+        // 数据库约束拒绝重复的序号
+        assert!(sqlx::query(
+            "INSERT INTO units (name, textbook_id, sequence_number, word_count) VALUES ('Unit', $1, 2, 0)",
+        )
+        .bind(textbook_id)
+        .execute(&*pool)
+        .await
+        .is_err());
+
+        // 删除后原位置被新单元占用，恢复时回到原位置，新单元后移
+        let since = OffsetDateTime::now_utc() - time::Duration::minutes(1);
+        units.delete(c).await?;
+        let d = units.save(&save(Some(2))).await?.id.unwrap();
+        assert_eq!(order().await?, vec![(a, 1), (d, 2), (b, 3)]);
+        TrashRepositoryImpl::new(pool.clone())
+            .restore(TrashEntity::Unit, c, since)
+            .await?
+            .unwrap();
+        assert_eq!(order().await?, vec![(a, 1), (c, 2), (d, 3), (b, 4)]);
+
+        sqlx::query("DELETE FROM units WHERE textbook_id = $1")
+            .bind(textbook_id)
+            .execute(&*pool)
+            .await?;
+        sqlx::query("DELETE FROM textbooks WHERE id = $1")
+            .bind(textbook_id)
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,185 @@
+use super::Repository;
+use crate::domain::models::user::User;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+#[async_trait]
+pub trait UserRepository: Repository<User, i32> + Send + Sync {
+    /// 根据用户名查询
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>>;
+
+    /// 查询用户数量
+    async fn count(&self) -> Result<i64>;
+
+    /// 保存登录会话，token_hash 为令牌的摘要
+    async fn create_session(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: OffsetDateTime,
+    ) -> Result<()>;
+
+    /// 根据令牌摘要查询未过期会话的用户
+    async fn find_by_session(&self, token_hash: &str) -> Result<Option<User>>;
+
+    /// 删除一个会话
+    async fn delete_session(&self, token_hash: &str) -> Result<()>;
+
+    /// 删除用户的全部会话，用于禁用账号和修改密码
+    async fn delete_sessions(&self, user_id: i32) -> Result<()>;
+}
+
+pub struct UserRepositoryImpl {
+    pool: Arc<PgPool>,
+}
+
+impl UserRepositoryImpl {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Repository<User, i32> for UserRepositoryImpl {
+    async fn find_by_id(&self, id: i32) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&*self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    async fn find_all(&self) -> Result<Vec<User>> {
+        let users = sqlx::query_as::<_, User>("SELECT * FROM users ORDER BY id")
+            .fetch_all(&*self.pool)
+            .await?;
+
+        Ok(users)
+    }
+
+    async fn save(&self, user: &User) -> Result<User> {
+        let saved = match user.id {
+            Some(id) => {
+                sqlx::query_as::<_, User>(
+                    r#"
+                    UPDATE users
+                    SET password_hash = $2, display_name = $3, role = $4, disabled = $5,
+                        updated_at = CURRENT_TIMESTAMP
+                    WHERE id = $1
+                    RETURNING *
+                    "#,
+                )
+                .bind(id)
+                .bind(&user.password_hash)
+                .bind(&user.display_name)
+                .bind(&user.role)
+                .bind(user.disabled)
+                .fetch_one(&*self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_as::<_, User>(
+                    r#"
+                    INSERT INTO users (username, password_hash, display_name, role, disabled)
+                    VALUES ($1, $2, $3, $4, $5)
+                    RETURNING *
+                    "#,
+                )
+                .bind(&user.username)
+                .bind(&user.password_hash)
+                .bind(&user.display_name)
+                .bind(&user.role)
+                .bind(user.disabled)
+                .fetch_one(&*self.pool)
+                .await?
+            }
+        };
+
+        Ok(saved)
+    }
+
+    async fn delete(&self, id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(id)
+            .execute(&*self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UserRepository for UserRepositoryImpl {
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_optional(&*self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    async fn count(&self) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+            .fetch_one(&*self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    async fn create_session(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: OffsetDateTime,
+    ) -> Result<()> {
+        // 顺便清理过期的会话
+        sqlx::query("DELETE FROM user_sessions WHERE user_id = $1 AND expires_at <= now()")
+            .bind(user_id)
+            .execute(&*self.pool)
+            .await?;
+        sqlx::query(
+            "INSERT INTO user_sessions (user_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+        )
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .execute(&*self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_by_session(&self, token_hash: &str) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.*
+            FROM user_sessions s
+            JOIN users u ON u.id = s.user_id
+            WHERE s.token_hash = $1 AND s.expires_at > now()
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn delete_session(&self, token_hash: &str) -> Result<()> {
+        sqlx::query("DELETE FROM user_sessions WHERE token_hash = $1")
+            .bind(token_hash)
+            .execute(&*self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_sessions(&self, user_id: i32) -> Result<()> {
+        sqlx::query("DELETE FROM user_sessions WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&*self.pool)
+            .await?;
+        Ok(())
+    }
+}
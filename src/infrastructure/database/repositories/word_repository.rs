@@ -1,10 +1,16 @@
+use super::trash_repository::soft_delete;
 use super::Repository;
 use crate::api::dto::unit_word_dto::WordPageRequestDTO;
-use crate::domain::models::word::Word;
+use crate::domain::models::trash::TrashEntity;
+use crate::domain::models::word::{MeaningParseFailure, Word};
+use crate::domain::models::word_edit::{WordEdit, WordField, WordPatch};
+use crate::domain::models::word_revision::{RevisionSource, WordContent, WordRevision};
 use crate::infrastructure::database::repositories::base::Paginated;
+use crate::infrastructure::dto::WordMeaning;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use sqlx::{PgPool, Pool, Postgres};
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, Transaction};
 use std::sync::Arc;
 use tracing::debug;
 
@@ -21,6 +27,27 @@ pub trait WordRepository: Repository<Word, i32> + Send + Sync {
 
     /// 查询单词数量
     async fn count(&self) -> Result<u32>;
+
+    /// 查询被编辑锁定的字段
+    async fn find_locked_fields(&self, word_id: i32) -> Result<Vec<WordField>>;
+
+    /// 在同一事务中写入人工修改、记录修改历史并更新锁定的字段
+    async fn patch(&self, word_id: i32, patch: &WordPatch) -> Result<Word>;
+
+    /// 查询单词的人工修改记录，最新的在前
+    async fn find_edits(&self, word_id: i32) -> Result<Vec<WordEdit>>;
+
+    /// 保存单词，内容有变化时记录一个修订版本
+    async fn save_as(&self, word: &Word, source: RevisionSource) -> Result<Word>;
+
+    /// 查询单词的修订版本，最新的在前
+    async fn find_revisions(&self, word_id: i32) -> Result<Vec<WordRevision>>;
+
+    /// 把单词内容回滚到指定版本，并记录为一个新的修订版本
+    async fn rollback(&self, word_id: i32, revision_id: i32, editor: Option<&str>) -> Result<Word>;
+
+    /// 查询迁移到 word_meanings 时无法解析的释义
+    async fn find_meaning_parse_failures(&self) -> Result<Vec<MeaningParseFailure>>;
 }
 pub struct WordRepositoryImpl {
     pool: Arc<PgPool>,
@@ -32,18 +59,30 @@ impl WordRepositoryImpl {
     }
 }
 
+/// word_details 视图中按顺序聚合为 JSON 数组的释义，查询时转换为 Word 的 meanings
+#[derive(sqlx::Type)]
+#[sqlx(transparent)]
+pub(super) struct WordMeanings(Json<Vec<WordMeaning>>);
+
+impl From<WordMeanings> for Vec<WordMeaning> {
+    fn from(meanings: WordMeanings) -> Self {
+        meanings.0 .0
+    }
+}
+
 #[async_trait]
 impl Repository<Word, i32> for WordRepositoryImpl {
     async fn find_by_id(&self, id: i32) -> Result<Option<Word>> {
         let word = sqlx::query_as!(
             Word,
             r#"
-            SELECT 
-                word_id, word, phonetic_us, pronunciation_us, 
-                phonetic_uk, pronunciation_uk, meaning, example,
-                created_at, updated_at
-            FROM words 
-            WHERE word_id = $1
+            SELECT
+                word_id, word as "word!", phonetic_us, pronunciation_us,
+                phonetic_uk, pronunciation_uk, example,
+                created_at, updated_at, meaning_prompt_version, example_prompt_version,
+                meanings as "meanings!: WordMeanings"
+            FROM word_details
+            WHERE word_id = $1 AND deleted_at IS NULL
             "#,
             id
         )
@@ -57,11 +96,13 @@ impl Repository<Word, i32> for WordRepositoryImpl {
         let words = sqlx::query_as!(
             Word,
             r#"
-            SELECT 
-                word_id, word, phonetic_us, pronunciation_us, 
-                phonetic_uk, pronunciation_uk, meaning, example,
-                created_at, updated_at
-            FROM words 
+            SELECT
+                word_id, word as "word!", phonetic_us, pronunciation_us,
+                phonetic_uk, pronunciation_uk, example,
+                created_at, updated_at, meaning_prompt_version, example_prompt_version,
+                meanings as "meanings!: WordMeanings"
+            FROM word_details
+            WHERE deleted_at IS NULL
             ORDER BY word_id
             "#
         )
@@ -72,123 +113,123 @@ impl Repository<Word, i32> for WordRepositoryImpl {
     }
 
     async fn save(&self, entity: &Word) -> Result<Word> {
-        // Ensure word_id is None for new entries
-        if entity.word_id.is_some() {
-            debug!(
-                "Attempting to save an entity with an existing word_id: {:?}",
-                entity.word_id
-            );
-        }
+        self.save_as(entity, RevisionSource::System).await
+    }
 
-        let should_insert = entity.word_id.is_none() || entity.word_id.unwrap() <= 0;
+    async fn delete(&self, id: i32) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        soft_delete(&mut tx, TrashEntity::Word, id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
 
-        let result = if !should_insert {
-            let id = entity.word_id.unwrap();
-            sqlx::query_as!(
-                Word,
-                r#"
-                UPDATE words 
-                SET word = $1, 
-                    phonetic_us = $2, 
-                    pronunciation_us = $3,
-                    phonetic_uk = $4,
-                    pronunciation_uk = $5,
-                    meaning = $6,
-                    example = $7
-                WHERE word_id = $8
-                RETURNING word_id, word, phonetic_us, pronunciation_us, 
-                          phonetic_uk, pronunciation_uk, meaning, example,
-                          created_at, updated_at
-                "#,
-                entity.word,
-                entity.phonetic_us,
-                entity.pronunciation_us,
-                entity.phonetic_uk,
-                entity.pronunciation_uk,
-                entity.meaning,
-                entity.example,
-                id
-            )
-            .fetch_one(&*self.pool)
-            .await?
-        } else {
-            // Check if the word already exists
-            let existing_word =
-                sqlx::query!("SELECT word_id FROM words WHERE word = $1", entity.word)
-                    .fetch_optional(&*self.pool)
-                    .await?;
-
-            match existing_word {
-                Some(existing) => {
-                    sqlx::query_as!(
-                        Word,
-                        r#"
-                        UPDATE words 
-                        SET phonetic_us = $2,
-                            pronunciation_us = $3,
-                            phonetic_uk = $4,
-                            pronunciation_uk = $5,
-                            meaning = $6,
-                            example = $7
-                        WHERE word_id = $1
-                        RETURNING word_id, word, phonetic_us, pronunciation_us, 
-                                  phonetic_uk, pronunciation_uk, meaning, example,
-                                  created_at, updated_at
-                        "#,
-                        existing.word_id,
-                        entity.phonetic_us,
-                        entity.pronunciation_us,
-                        entity.phonetic_uk,
-                        entity.pronunciation_uk,
-                        entity.meaning,
-                        entity.example,
-                    )
-                    .fetch_one(&*self.pool)
-                    .await?
-                }
-                None => {
-                    // Log the values being inserted
-                    sqlx::query_as!(
-                        Word,
-                        r#"
-                        INSERT INTO words (
-                            word, phonetic_us, pronunciation_us, 
-                            phonetic_uk, pronunciation_uk, meaning, example
-                        )
-                        VALUES ($1, $2, $3, $4, $5, $6, $7)
-                        RETURNING word_id, word, phonetic_us, pronunciation_us, 
-                                  phonetic_uk, pronunciation_uk, meaning, example,
-                                  created_at, updated_at
-                        "#,
-                        entity.word,
-                        entity.phonetic_us,
-                        entity.pronunciation_us,
-                        entity.phonetic_uk,
-                        entity.pronunciation_uk,
-                        entity.meaning,
-                        entity.example
-                    )
-                    .fetch_one(&*self.pool)
-                    .await?
-                }
-            }
-        };
-        Ok(result)
+/// 在事务中查询单词，包括已删除的单词
+async fn fetch_word(tx: &mut Transaction<'_, Postgres>, word_id: i32) -> Result<Option<Word>> {
+    let word = sqlx::query_as!(
+        Word,
+        r#"
+        SELECT
+            word_id, word as "word!", phonetic_us, pronunciation_us,
+            phonetic_uk, pronunciation_uk, example,
+            created_at, updated_at, meaning_prompt_version, example_prompt_version,
+            meanings as "meanings!: WordMeanings"
+        FROM word_details
+        WHERE word_id = $1
+        "#,
+        word_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+    Ok(word)
+}
+
+/// 锁定单词行并返回当前内容，用于计算修订差异；单词不存在或已删除时返回 None
+async fn lock_content(
+    tx: &mut Transaction<'_, Postgres>,
+    word_id: i32,
+) -> Result<Option<WordContent>> {
+    let locked = sqlx::query_scalar::<_, i32>(
+        "SELECT word_id FROM words WHERE word_id = $1 AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(word_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+    if locked.is_none() {
+        return Ok(None);
     }
+    let word = fetch_word(tx, word_id).await?;
+    Ok(word.as_ref().map(WordContent::from))
+}
 
-    async fn delete(&self, id: i32) -> Result<()> {
-        sqlx::query!(
-            r#"
-            DELETE FROM words 
-            WHERE word_id = $1
-            "#,
-            id
-        )
-        .execute(&*self.pool)
+/// 用给定的释义替换单词的全部释义，未指明来源的释义记为 source
+async fn replace_meanings(
+    tx: &mut Transaction<'_, Postgres>,
+    word_id: i32,
+    meanings: &[WordMeaning],
+    source: RevisionSource,
+) -> Result<()> {
+    sqlx::query("DELETE FROM word_meanings WHERE word_id = $1")
+        .bind(word_id)
+        .execute(&mut **tx)
         .await?;
+    if meanings.is_empty() {
+        return Ok(());
+    }
 
-        Ok(())
+    let pos: Vec<&str> = meanings.iter().map(|m| m.pos.trim()).collect();
+    let definitions: Vec<&str> = meanings.iter().map(|m| m.definition.trim()).collect();
+    let glosses: Vec<Option<&str>> = meanings.iter().map(|m| m.gloss.as_deref()).collect();
+    let sources: Vec<&str> = meanings
+        .iter()
+        .map(|m| m.source.as_deref().unwrap_or(source.as_str()))
+        .collect();
+    sqlx::query(
+        r#"
+        INSERT INTO word_meanings (word_id, sequence, pos, definition, gloss, source)
+        SELECT $1, m.sequence, m.pos, m.definition, m.gloss, m.source
+        FROM unnest($2::text[], $3::text[], $4::text[], $5::text[])
+             WITH ORDINALITY AS m(pos, definition, gloss, source, sequence)
+        "#,
+    )
+    .bind(word_id)
+    .bind(&pos)
+    .bind(&definitions)
+    .bind(&glosses)
+    .bind(&sources)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// 记录修改后的内容；新单词总是记录，已有单词只在内容有变化时记录
+async fn record_revision(
+    tx: &mut Transaction<'_, Postgres>,
+    word: &Word,
+    previous: Option<WordContent>,
+    source: RevisionSource,
+    editor: Option<&str>,
+) -> Result<()> {
+    let content = WordContent::from(word);
+    let is_new = previous.is_none();
+    let changes = previous.unwrap_or_default().diff(&content);
+    if changes.is_empty() && !is_new {
+        return Ok(());
     }
+    sqlx::query(
+        r#"
+        INSERT INTO word_revisions (word_id, source, editor, content, changes)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(word.word_id)
+    .bind(source.as_str())
+    .bind(editor)
+    .bind(serde_json::to_value(&content)?)
+    .bind(serde_json::Value::Object(changes))
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
 }
 
 #[async_trait]
@@ -197,12 +238,13 @@ impl WordRepository for WordRepositoryImpl {
         let word = sqlx::query_as!(
             Word,
             r#"
-            SELECT 
-                word_id, word, phonetic_us, pronunciation_us, 
-                phonetic_uk, pronunciation_uk, meaning, example,
-                created_at, updated_at
-            FROM words 
-            WHERE word = $1
+            SELECT
+                word_id, word as "word!", phonetic_us, pronunciation_us,
+                phonetic_uk, pronunciation_uk, example,
+                created_at, updated_at, meaning_prompt_version, example_prompt_version,
+                meanings as "meanings!: WordMeanings"
+            FROM word_details
+            WHERE word = $1 AND deleted_at IS NULL
             "#,
             word
         )
@@ -216,14 +258,15 @@ impl WordRepository for WordRepositoryImpl {
         let words = sqlx::query_as!(
             Word,
             r#"
-            SELECT 
-                w.word_id, w.word, w.phonetic_us, w.pronunciation_us, 
-                w.phonetic_uk, w.pronunciation_uk, w.meaning, w.example,
-                w.created_at, w.updated_at
-            FROM words w
+            SELECT
+                w.word_id, w.word as "word!", w.phonetic_us, w.pronunciation_us,
+                w.phonetic_uk, w.pronunciation_uk, w.example,
+                w.created_at, w.updated_at, w.meaning_prompt_version, w.example_prompt_version,
+                w.meanings as "meanings!: WordMeanings"
+            FROM word_details w
             JOIN word_unit_mappings wum ON w.word_id = wum.word_id
-            WHERE wum.unit_id = $1
-            ORDER BY wum.id
+            WHERE wum.unit_id = $1 AND wum.deleted_at IS NULL AND w.deleted_at IS NULL
+            ORDER BY wum.sequence NULLS LAST, wum.id
             "#,
             unit_id
         )
@@ -238,13 +281,20 @@ impl WordRepository for WordRepositoryImpl {
         let words = sqlx::query_as!(
             Word,
             r#"
-            SELECT 
-                word_id, word, phonetic_us, pronunciation_us, 
-                phonetic_uk, pronunciation_uk, meaning, example,
-                created_at, updated_at
-            FROM words 
-            WHERE word ILIKE $1 OR meaning ILIKE $1
-            ORDER BY word
+            SELECT
+                w.word_id, w.word as "word!", w.phonetic_us, w.pronunciation_us,
+                w.phonetic_uk, w.pronunciation_uk, w.example,
+                w.created_at, w.updated_at, w.meaning_prompt_version, w.example_prompt_version,
+                w.meanings as "meanings!: WordMeanings"
+            FROM word_details w
+            WHERE (
+                w.word ILIKE $1
+                OR EXISTS (
+                    SELECT 1 FROM word_meanings m
+                    WHERE m.word_id = w.word_id AND (m.definition ILIKE $1 OR m.gloss ILIKE $1)
+                )
+            ) AND w.deleted_at IS NULL
+            ORDER BY w.word
             "#,
             search_pattern
         )
@@ -257,7 +307,7 @@ impl WordRepository for WordRepositoryImpl {
     async fn count(&self) -> Result<u32> {
         let count = sqlx::query_scalar!(
             r#"
-            SELECT COUNT(*) FROM words
+            SELECT COUNT(*) FROM words WHERE deleted_at IS NULL
             "#
         )
         .fetch_one(&*self.pool)
@@ -265,4 +315,471 @@ impl WordRepository for WordRepositoryImpl {
         .map_err(|e| anyhow!(e))?;
         Ok(count.unwrap() as u32)
     }
+
+    async fn find_locked_fields(&self, word_id: i32) -> Result<Vec<WordField>> {
+        let locked: Option<Vec<String>> = sqlx::query_scalar(
+            "SELECT locked_fields FROM words WHERE word_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(word_id)
+        .fetch_optional(&*self.pool)
+        .await?;
+
+        locked
+            .unwrap_or_default()
+            .iter()
+            .map(|field| field.parse())
+            .collect()
+    }
+
+    async fn patch(&self, word_id: i32, patch: &WordPatch) -> Result<Word> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query_scalar::<_, i32>(
+            "SELECT word_id FROM words WHERE word_id = $1 AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(word_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        let current = fetch_word(&mut tx, word_id)
+            .await?
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+
+        let mut updated = current.clone();
+        for (field, value) in &patch.changes {
+            let old_value = current.field(*field);
+            if old_value == *value {
+                continue;
+            }
+            updated.set_field(*field, value.clone())?;
+            sqlx::query(
+                r#"
+                INSERT INTO word_edits (word_id, field, old_value, new_value, editor)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(word_id)
+            .bind(field.as_str())
+            .bind(old_value)
+            .bind(value)
+            .bind(&patch.editor)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let locked: Vec<&str> = if patch.lock {
+            patch
+                .changes
+                .iter()
+                .map(|(field, _)| field.as_str())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let unlocked: Vec<&str> = patch.unlock.iter().map(|field| field.as_str()).collect();
+        sqlx::query(
+            r#"
+            UPDATE words
+            SET phonetic_us = $2, phonetic_uk = $3, example = $4,
+                meaning_prompt_version = $5, example_prompt_version = $6,
+                locked_fields = ARRAY(
+                    SELECT DISTINCT field FROM unnest(locked_fields || $7::text[]) AS field
+                    WHERE field <> ALL($8::text[])
+                    ORDER BY field
+                )
+            WHERE word_id = $1
+            "#,
+        )
+        .bind(word_id)
+        .bind(&updated.phonetic_us)
+        .bind(&updated.phonetic_uk)
+        .bind(&updated.example)
+        .bind(updated.meaning_prompt_version)
+        .bind(updated.example_prompt_version)
+        .bind(&locked)
+        .bind(&unlocked)
+        .execute(&mut *tx)
+        .await?;
+        if updated.meanings != current.meanings {
+            replace_meanings(&mut tx, word_id, &updated.meanings, RevisionSource::Editor).await?;
+        }
+        let word = fetch_word(&mut tx, word_id)
+            .await?
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        record_revision(
+            &mut tx,
+            &word,
+            Some(WordContent::from(&current)),
+            RevisionSource::Editor,
+            patch.editor.as_deref(),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(word)
+    }
+
+    async fn find_edits(&self, word_id: i32) -> Result<Vec<WordEdit>> {
+        let edits = sqlx::query_as::<_, WordEdit>(
+            r#"
+            SELECT id, word_id, field, old_value, new_value, editor, created_at
+            FROM word_edits
+            WHERE word_id = $1
+            ORDER BY id DESC
+            "#,
+        )
+        .bind(word_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(edits)
+    }
+
+    async fn save_as(&self, entity: &Word, source: RevisionSource) -> Result<Word> {
+        // Ensure word_id is None for new entries
+        if entity.word_id.is_some() {
+            debug!(
+                "Attempting to save an entity with an existing word_id: {:?}",
+                entity.word_id
+            );
+        }
+
+        let should_insert = entity.word_id.is_none() || entity.word_id.unwrap() <= 0;
+
+        let mut tx = self.pool.begin().await?;
+        let existing_id = if should_insert {
+            // Check if the word already exists，已删除的单词重新创建时恢复原记录
+            sqlx::query_scalar::<_, i32>("SELECT word_id FROM words WHERE word = $1 FOR UPDATE")
+                .bind(&entity.word)
+                .fetch_optional(&mut *tx)
+                .await?
+        } else {
+            entity.word_id
+        };
+
+        let (word_id, previous, meaning_locked) = match existing_id {
+            Some(id) => {
+                let previous = lock_content(&mut tx, id).await?;
+                // 按 ID 更新时单词必须存在且未删除，按拼写重新创建时恢复已删除的单词
+                if !should_insert && previous.is_none() {
+                    return Err(anyhow!("Word {} not found", id));
+                }
+                // 被编辑锁定的字段保持原值，补全任务不会覆盖人工修正
+                let meaning_locked: bool = sqlx::query_scalar(
+                    r#"
+                    UPDATE words
+                    SET word = $2,
+                        phonetic_us = CASE WHEN 'phonetic_us' = ANY(locked_fields) THEN phonetic_us ELSE $3 END,
+                        pronunciation_us = $4,
+                        phonetic_uk = CASE WHEN 'phonetic_uk' = ANY(locked_fields) THEN phonetic_uk ELSE $5 END,
+                        pronunciation_uk = $6,
+                        example = CASE WHEN 'example' = ANY(locked_fields) THEN example ELSE $7 END,
+                        meaning_prompt_version = CASE WHEN 'meaning' = ANY(locked_fields) THEN meaning_prompt_version ELSE $8 END,
+                        example_prompt_version = CASE WHEN 'example' = ANY(locked_fields) THEN example_prompt_version ELSE $9 END,
+                        deleted_at = CASE WHEN $10 THEN NULL ELSE deleted_at END
+                    WHERE word_id = $1 AND (deleted_at IS NULL OR $10)
+                    RETURNING 'meaning' = ANY(locked_fields)
+                    "#,
+                )
+                .bind(id)
+                .bind(&entity.word)
+                .bind(&entity.phonetic_us)
+                .bind(&entity.pronunciation_us)
+                .bind(&entity.phonetic_uk)
+                .bind(&entity.pronunciation_uk)
+                .bind(&entity.example)
+                .bind(entity.meaning_prompt_version)
+                .bind(entity.example_prompt_version)
+                .bind(should_insert)
+                .fetch_one(&mut *tx)
+                .await?;
+                (id, previous, meaning_locked)
+            }
+            None => {
+                let id: i32 = sqlx::query_scalar(
+                    r#"
+                    INSERT INTO words (
+                        word, phonetic_us, pronunciation_us,
+                        phonetic_uk, pronunciation_uk, example,
+                        meaning_prompt_version, example_prompt_version
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                    RETURNING word_id
+                    "#,
+                )
+                .bind(&entity.word)
+                .bind(&entity.phonetic_us)
+                .bind(&entity.pronunciation_us)
+                .bind(&entity.phonetic_uk)
+                .bind(&entity.pronunciation_uk)
+                .bind(&entity.example)
+                .bind(entity.meaning_prompt_version)
+                .bind(entity.example_prompt_version)
+                .fetch_one(&mut *tx)
+                .await?;
+                (id, None, false)
+            }
+        };
+        let meanings_changed = previous
+            .as_ref()
+            .is_none_or(|content| content.meanings != entity.meanings);
+        if !meaning_locked && meanings_changed {
+            replace_meanings(&mut tx, word_id, &entity.meanings, source).await?;
+        }
+
+        let result = fetch_word(&mut tx, word_id)
+            .await?
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        record_revision(&mut tx, &result, previous, source, None).await?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    async fn find_revisions(&self, word_id: i32) -> Result<Vec<WordRevision>> {
+        let revisions = sqlx::query_as::<_, WordRevision>(
+            r#"
+            SELECT id, word_id, source, editor, content, changes, created_at
+            FROM word_revisions
+            WHERE word_id = $1
+            ORDER BY id DESC
+            "#,
+        )
+        .bind(word_id)
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(revisions)
+    }
+
+    async fn rollback(&self, word_id: i32, revision_id: i32, editor: Option<&str>) -> Result<Word> {
+        let mut tx = self.pool.begin().await?;
+        let previous = lock_content(&mut tx, word_id)
+            .await?
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        let revision = sqlx::query_as::<_, WordRevision>(
+            r#"
+            SELECT id, word_id, source, editor, content, changes, created_at
+            FROM word_revisions
+            WHERE id = $1 AND word_id = $2
+            "#,
+        )
+        .bind(revision_id)
+        .bind(word_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| anyhow!("Revision {} of word {} not found", revision_id, word_id))?;
+        let content = revision.content()?;
+        if content.meaning_raw.is_some() {
+            return Err(anyhow!(
+                "Revision {} of word {} has meanings that could not be migrated and cannot be rolled back",
+                revision_id,
+                word_id
+            ));
+        }
+
+        // 回滚是编辑的操作，直接写回历史内容，不受字段锁定的限制
+        sqlx::query(
+            r#"
+            UPDATE words
+            SET phonetic_us = $2, phonetic_uk = $3, pronunciation_us = $4, pronunciation_uk = $5,
+                example = $6, meaning_prompt_version = $7, example_prompt_version = $8
+            WHERE word_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(word_id)
+        .bind(&content.phonetic_us)
+        .bind(&content.phonetic_uk)
+        .bind(&content.pronunciation_us)
+        .bind(&content.pronunciation_uk)
+        .bind(&content.example)
+        .bind(content.meaning_prompt_version)
+        .bind(content.example_prompt_version)
+        .execute(&mut *tx)
+        .await?;
+        if content.meanings != previous.meanings {
+            replace_meanings(
+                &mut tx,
+                word_id,
+                &content.meanings,
+                RevisionSource::Rollback,
+            )
+            .await?;
+        }
+        let word = fetch_word(&mut tx, word_id)
+            .await?
+            .ok_or_else(|| anyhow!("Word {} not found", word_id))?;
+        record_revision(
+            &mut tx,
+            &word,
+            Some(previous),
+            RevisionSource::Rollback,
+            editor,
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(word)
+    }
+
+    async fn find_meaning_parse_failures(&self) -> Result<Vec<MeaningParseFailure>> {
+        let failures = sqlx::query_as::<_, MeaningParseFailure>(
+            r#"
+            SELECT f.word_id, w.word, f.meaning, f.error,
+                   EXISTS (SELECT 1 FROM word_meanings m WHERE m.word_id = f.word_id) AS resolved,
+                   f.created_at
+            FROM word_meaning_parse_failures f
+            JOIN words w ON w.word_id = f.word_id
+            WHERE w.deleted_at IS NULL
+            ORDER BY f.word_id
+            "#,
+        )
+        .fetch_all(&*self.pool)
+        .await?;
+
+        Ok(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meaning(pos: &str, definition: &str) -> WordMeaning {
+        WordMeaning {
+            pos: pos.to_string(),
+            definition: definition.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_patch_locks_fields() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let words = WordRepositoryImpl::new(pool.clone());
+        let mut word = Word::new("patch-lock");
+        word.meanings = vec![meaning("n.", "generated")];
+        word.meaning_prompt_version = Some(1);
+        let word = words.save(&word).await?;
+        let word_id = word.word_id.unwrap();
+
+        let patch = WordPatch {
+            changes: vec![
+                (
+                    WordField::Meaning,
+                    Some(r#"[{"pos":"n.","definition":"manual"}]"#.to_string()),
+                ),
+                (WordField::PhoneticUs, None),
+            ],
+            lock: true,
+            unlock: Vec::new(),
+            editor: Some("editor-1".to_string()),
+        };
+        let patched = words.patch(word_id, &patch).await?;
+        assert_eq!(patched.meanings[0].definition, "manual");
+        assert_eq!(patched.meanings[0].source.as_deref(), Some("editor"));
+        assert_eq!(patched.meaning_prompt_version, None);
+        assert_eq!(
+            words.find_locked_fields(word_id).await?,
+            vec![WordField::Meaning, WordField::PhoneticUs]
+        );
+        // 值没有变化的字段只锁定，不记录修改
+        let edits = words.find_edits(word_id).await?;
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].old_value.as_deref(),
+            Some(r#"[{"pos":"n.","definition":"generated","source":"system"}]"#)
+        );
+        assert_eq!(words.search_words("manu").await?.len(), 1);
+
+        let mut overwrite = patched.clone();
+        overwrite.meanings = vec![meaning("n.", "generated")];
+        overwrite.example = Some("generated".to_string());
+        let saved = words.save(&overwrite).await?;
+        assert_eq!(saved.meanings, patched.meanings);
+        assert_eq!(saved.example.as_deref(), Some("generated"));
+
+        let unlock = WordPatch {
+            unlock: vec![WordField::Meaning],
+            ..Default::default()
+        };
+        words.patch(word_id, &unlock).await?;
+        assert_eq!(
+            words.find_locked_fields(word_id).await?,
+            vec![WordField::PhoneticUs]
+        );
+
+        sqlx::query("DELETE FROM words WHERE word_id = $1")
+            .bind(word_id)
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database at DATABASE_URL"]
+    async fn test_revisions_and_rollback() -> Result<()> {
+        let pool = Arc::new(PgPool::connect(&std::env::var("DATABASE_URL")?).await?);
+        let words = WordRepositoryImpl::new(pool.clone());
+        let mut word = Word::new("revision-word");
+        word.meanings = vec![meaning("n.", "dictionary")];
+        let created = words.save_as(&word, RevisionSource::Dictionary).await?;
+        let word_id = created.word_id.unwrap();
+
+        let mut generated = created.clone();
+        generated.meanings = vec![meaning("n.", "generated"), meaning("v.", "generate")];
+        generated.meaning_prompt_version = Some(3);
+        words.save_as(&generated, RevisionSource::Llm).await?;
+        // 内容没有变化时不记录新版本
+        words.save_as(&generated, RevisionSource::Llm).await?;
+
+        let revisions = words.find_revisions(word_id).await?;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].source, "llm");
+        assert_eq!(
+            revisions[0].changes["meanings"]["old"][0]["definition"],
+            "dictionary"
+        );
+        assert_eq!(revisions[0].content()?.meanings.len(), 2);
+        assert_eq!(revisions[1].source, "dictionary");
+
+        let restored = words
+            .rollback(word_id, revisions[1].id.unwrap(), Some("editor-1"))
+            .await?;
+        assert_eq!(restored.meanings.len(), 1);
+        assert_eq!(restored.meanings[0].definition, "dictionary");
+        assert_eq!(restored.meanings[0].source.as_deref(), Some("dictionary"));
+        assert_eq!(restored.meaning_prompt_version, None);
+        let revisions = words.find_revisions(word_id).await?;
+        assert_eq!(revisions[0].source, "rollback");
+        assert_eq!(revisions[0].editor.as_deref(), Some("editor-1"));
+        assert!(words.rollback(word_id, -1, None).await.is_err());
+
+        // 迁移时释义无法解析的版本不能回滚
+        let (raw_id,): (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO word_revisions (word_id, source, content, changes)
+            VALUES ($1, 'system', '{"meanings": [], "meaning_raw": "apple; 苹果"}', '{}')
+            RETURNING id
+            "#,
+        )
+        .bind(word_id)
+        .fetch_one(&*pool)
+        .await?;
+        let err = words.rollback(word_id, raw_id, None).await.unwrap_err();
+        assert!(err.to_string().contains("cannot be rolled back"), "{}", err);
+
+        // 已删除的单词不能回滚
+        words.delete(word_id).await?;
+        let err = words
+            .rollback(word_id, revisions[1].id.unwrap(), None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+
+        sqlx::query("DELETE FROM words WHERE word_id = $1")
+            .bind(word_id)
+            .execute(&*pool)
+            .await?;
+        Ok(())
+    }
 }
@@ -1,10 +1,14 @@
 use super::base::Repository;
+use super::trash_repository::soft_delete;
+use super::word_repository::WordMeanings;
 use crate::api::dto::unit_word_dto::WordDTO;
+use crate::common::utils::sequence::Reorder;
+use crate::domain::models::trash::TrashEntity;
 use crate::domain::models::word::Word;
 use crate::domain::models::word_unit_mapping::WordUnitMapping;
 use anyhow::Result;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use std::sync::Arc;
 
 #[async_trait]
@@ -18,8 +22,41 @@ pub trait WordUnitMappingRepository: Repository<WordUnitMapping, i32> + Send + S
 
     /// 批量保存映射关系
     async fn batch_save(&self, mappings: &[WordUnitMapping]) -> Result<Vec<WordUnitMapping>>;
+
+    /// 在同一事务中调整单元中单词的顺序，并把序号整理为连续的 1..n
+    async fn reorder(&self, unit_id: i32, op: Reorder) -> Result<()>;
 }
 
+/// 单元单词列表的查询列，对应 `WordDTO`；设置了单元含义时只返回单元含义，否则返回单词的全部释义
+pub(super) const UNIT_WORD_COLUMNS: &str = r#"
+    wum.id,
+    wum.word_id,
+    w.word,
+    CASE WHEN wum.meaning IS NOT NULL THEN
+        jsonb_build_array(jsonb_build_object(
+            'pos', COALESCE(wum.pos, ''),
+            'definition', wum.meaning
+        ))
+    ELSE COALESCE((
+        SELECT jsonb_agg(jsonb_strip_nulls(jsonb_build_object(
+            'pos', m.pos, 'definition', m.definition, 'gloss', m.gloss, 'source', m.source
+        )) ORDER BY m.sequence)
+        FROM word_meanings m
+        WHERE m.word_id = w.word_id
+    ), '[]')
+    END as meanings,
+    wum.pos,
+    wum.sequence,
+    COALESCE(wum.example, w.example) as example,
+    TO_CHAR(wum.created_at, 'YYYY-MM-DD HH:MI:SS') as created_at,
+    TO_CHAR(wum.updated_at, 'YYYY-MM-DD HH:MI:SS') as updated_at,
+    wum.unit_id,
+    w.phonetic_us,
+    w.phonetic_uk,
+    w.pronunciation_us,
+    w.pronunciation_uk
+"#;
+
 pub struct WordUnitMappingRepositoryImpl {
     pool: Arc<PgPool>,
 }
@@ -35,7 +72,7 @@ impl Repository<WordUnitMapping, i32> for WordUnitMappingRepositoryImpl {
     async fn find_by_id(&self, id: i32) -> Result<Option<WordUnitMapping>> {
         let mapping = sqlx::query_as!(
             WordUnitMapping,
-            "SELECT * FROM word_unit_mappings WHERE id = $1",
+            "SELECT * FROM word_unit_mappings WHERE id = $1 AND deleted_at IS NULL",
             id
         )
         .fetch_optional(&*self.pool)
@@ -47,7 +84,7 @@ impl Repository<WordUnitMapping, i32> for WordUnitMappingRepositoryImpl {
     async fn find_all(&self) -> Result<Vec<WordUnitMapping>> {
         let mappings = sqlx::query_as!(
             WordUnitMapping,
-            "SELECT * FROM word_unit_mappings ORDER BY id"
+            "SELECT * FROM word_unit_mappings WHERE deleted_at IS NULL ORDER BY id"
         )
         .fetch_all(&*self.pool)
         .await?;
@@ -56,60 +93,128 @@ impl Repository<WordUnitMapping, i32> for WordUnitMappingRepositoryImpl {
     }
 
     async fn save(&self, mapping: &WordUnitMapping) -> Result<WordUnitMapping> {
-        let result = if let Some(id) = mapping.id {
-            // Update
-            sqlx::query_as!(
-                WordUnitMapping,
-                r#"
-                UPDATE word_unit_mappings
-                SET word_id = $1, unit_id = $2
-                WHERE id = $3
-                RETURNING *
-                "#,
-                mapping.word_id,
-                mapping.unit_id,
-                id
-            )
-            .fetch_one(&*self.pool)
-            .await?
-        } else {
-            // Insert
-            sqlx::query_as!(
-                WordUnitMapping,
-                r#"
-                INSERT INTO word_unit_mappings (word_id, unit_id)
-                VALUES ($1, $2)
-                RETURNING *
-                "#,
-                mapping.word_id,
-                mapping.unit_id,
-            )
-            .fetch_one(&*self.pool)
-            .await?
-        };
-
+        let mut tx = self.pool.begin().await?;
+        let result = save_in_tx(&mut tx, mapping).await?;
+        tx.commit().await?;
         Ok(result)
     }
 
     async fn delete(&self, id: i32) -> Result<()> {
-        sqlx::query!("DELETE FROM word_unit_mappings WHERE id = $1", id)
-            .execute(&*self.pool)
-            .await?;
+        let mut tx = self.pool.begin().await?;
+        soft_delete(&mut tx, TrashEntity::UnitWord, id).await?;
+        tx.commit().await?;
         Ok(())
     }
 }
 
+/// 在事务中保存映射关系；新增或移动到其他单元时同步调整单元和课本的单词数
+async fn save_in_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    mapping: &WordUnitMapping,
+) -> Result<WordUnitMapping> {
+    let result = if let Some(id) = mapping.id {
+        // Update
+        let previous: Option<(Option<i32>,)> =
+            sqlx::query_as("SELECT unit_id FROM word_unit_mappings WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut **tx)
+                .await?;
+        let result = sqlx::query_as!(
+            WordUnitMapping,
+            r#"
+            UPDATE word_unit_mappings
+            SET word_id = $1, unit_id = $2, example = $3, example_prompt_version = $4,
+                meaning = $5, pos = $6, sequence = $7
+            WHERE id = $8 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+            mapping.word_id,
+            mapping.unit_id,
+            mapping.example,
+            mapping.example_prompt_version,
+            mapping.meaning,
+            mapping.pos,
+            mapping.sequence,
+            id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        if let Some((previous_unit_id,)) = previous {
+            if previous_unit_id != result.unit_id {
+                adjust_word_count(tx, previous_unit_id, -1).await?;
+                adjust_word_count(tx, result.unit_id, 1).await?;
+            }
+        }
+        result
+    } else {
+        // Insert
+        let result = sqlx::query_as!(
+            WordUnitMapping,
+            r#"
+            INSERT INTO word_unit_mappings (
+                word_id, unit_id, example, example_prompt_version, meaning, pos, sequence
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+            mapping.word_id,
+            mapping.unit_id,
+            mapping.example,
+            mapping.example_prompt_version,
+            mapping.meaning,
+            mapping.pos,
+            mapping.sequence,
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        adjust_word_count(tx, result.unit_id, 1).await?;
+        result
+    };
+
+    Ok(result)
+}
+
+/// 在同一事务中原子地调整单元及其所属课本的单词数
+pub(super) async fn adjust_word_count(
+    tx: &mut Transaction<'_, Postgres>,
+    unit_id: Option<i32>,
+    delta: i32,
+) -> Result<()> {
+    let Some(unit_id) = unit_id else {
+        return Ok(());
+    };
+    sqlx::query(
+        r#"
+        WITH unit AS (
+            UPDATE units SET word_count = COALESCE(word_count, 0) + $2
+            WHERE id = $1
+            RETURNING textbook_id
+        )
+        UPDATE textbooks SET word_count = COALESCE(word_count, 0) + $2
+        WHERE id = (SELECT textbook_id FROM unit)
+        "#,
+    )
+    .bind(unit_id)
+    .bind(delta)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
 #[async_trait]
 impl WordUnitMappingRepository for WordUnitMappingRepositoryImpl {
     async fn find_word_by_unit_id(&self, unit_id: i32) -> Result<Vec<Word>> {
         let words = sqlx::query_as!(
             Word,
             r#"
-            select w.*
+            select
+                w.word_id, w.word as "word!", w.phonetic_us, w.pronunciation_us,
+                w.phonetic_uk, w.pronunciation_uk, w.example,
+                w.created_at, w.updated_at, w.meaning_prompt_version, w.example_prompt_version,
+                w.meanings as "meanings!: WordMeanings"
             from word_unit_mappings wum
-            right join words w
+            right join word_details w
             on wum.word_id = w.word_id
-            where wum.unit_id = $1
+            where wum.unit_id = $1 and wum.deleted_at is null
             order by w.word
             "#,
             unit_id
@@ -121,30 +226,18 @@ impl WordUnitMappingRepository for WordUnitMappingRepositoryImpl {
     }
 
     async fn find_word_dto_by_unit_id(&self, unit_id: i32) -> Result<Vec<WordDTO>> {
-        let word_dto = sqlx::query_as!(
-            WordDTO,
+        let word_dto = sqlx::query_as::<_, WordDTO>(&format!(
             r#"
-            select
-            wum.id,
-            wum.word_id,
-            w.word,
-            w.meaning,
-            w.example,
-            TO_CHAR(wum.created_at, 'YYYY-MM-DD HH:MI:SS') as created_at,
-            TO_CHAR(wum.updated_at, 'YYYY-MM-DD HH:MI:SS') as updated_at,
-            wum.unit_id,
-            w.phonetic_us,
-            w.phonetic_uk,
-            w.pronunciation_us,
-            w.pronunciation_uk
+            select {}
             from word_unit_mappings wum
             right join words w
             on wum.word_id = w.word_id
-            where wum.unit_id = $1
-            order by wum.id
+            where wum.unit_id = $1 and wum.deleted_at is null
+            order by wum.sequence nulls last, wum.id
             "#,
-            unit_id
-        )
+            UNIT_WORD_COLUMNS
+        ))
+        .bind(unit_id)
         .fetch_all(&*self.pool)
         .await?;
 
@@ -156,7 +249,7 @@ impl WordUnitMappingRepository for WordUnitMappingRepositoryImpl {
             WordUnitMapping,
             r#"
             SELECT * FROM word_unit_mappings
-            WHERE word_id = $1 
+            WHERE word_id = $1 AND deleted_at IS NULL
             ORDER BY id
             "#,
             word_id
@@ -172,39 +265,40 @@ impl WordUnitMappingRepository for WordUnitMappingRepositoryImpl {
         let mut results = Vec::with_capacity(mappings.len());
 
         for mapping in mappings {
-            let result = if let Some(id) = mapping.id {
-                sqlx::query_as!(
-                    WordUnitMapping,
-                    r#"
-                    UPDATE word_unit_mappings
-                    SET word_id = $1, unit_id = $2
-                    WHERE id = $3
-                    RETURNING *
-                    "#,
-                    mapping.word_id,
-                    mapping.unit_id,
-                    id
-                )
-                .fetch_one(&mut *tx)
-                .await?
-            } else {
-                sqlx::query_as!(
-                    WordUnitMapping,
-                    r#"
-                    INSERT INTO word_unit_mappings (word_id, unit_id)
-                    VALUES ($1, $2)
-                    RETURNING *
-                    "#,
-                    mapping.word_id,
-                    mapping.unit_id
-                )
-                .fetch_one(&mut *tx)
-                .await?
-            };
-            results.push(result);
+            results.push(save_in_tx(&mut tx, mapping).await?);
         }
 
         tx.commit().await?;
         Ok(results)
     }
+
+    async fn reorder(&self, unit_id: i32, op: Reorder) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let mut ids: Vec<i32> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM word_unit_mappings
+            WHERE unit_id = $1 AND deleted_at IS NULL
+            ORDER BY sequence NULLS LAST, id
+            FOR UPDATE
+            "#,
+        )
+        .bind(unit_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        op.apply(&mut ids)?;
+
+        sqlx::query(
+            r#"
+            UPDATE word_unit_mappings m SET sequence = o.position::int
+            FROM unnest($1::int[]) WITH ORDINALITY AS o(id, position)
+            WHERE m.id = o.id AND m.sequence IS DISTINCT FROM o.position::int
+            "#,
+        )
+        .bind(&ids)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
 }
@@ -7,8 +7,15 @@ pub struct WordInfo {
     pub meanings: Vec<WordMeaning>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 一条释义，保存在 word_meanings 表中，按顺序排列
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct WordMeaning {
     pub pos: String,
     pub definition: String,
+    /// 可选的英文释义
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gloss: Option<String>,
+    /// 释义来源，保存时未指定则记为本次保存的来源
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
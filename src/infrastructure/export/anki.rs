@@ -0,0 +1,344 @@
+//! 生成 Anki `.apkg` 牌组：zip 包中包含 SQLite 格式的 `collection.anki2` 和空的 `media` 清单
+
+use super::html::escape;
+use super::WordSheet;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde_json::json;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// 笔记类型 id 固定不变，重复导入时 Anki 会识别为同一笔记类型
+const MODEL_ID: i64 = 1_607_392_319;
+const MODEL_NAME: &str = "English Assistant Word";
+const FIELDS: [&str; 4] = ["Word", "Phonetic", "Meaning", "Example"];
+const FRONT_TEMPLATE: &str =
+    "<div class=\"word\">{{Word}}</div><div class=\"phonetic\">{{Phonetic}}</div>";
+const BACK_TEMPLATE: &str =
+    "{{FrontSide}}<hr id=\"answer\"><div class=\"meaning\">{{Meaning}}</div><div class=\"example\">{{Example}}</div>";
+const CARD_CSS: &str = ".card { font-family: arial; font-size: 20px; text-align: center; color: black; background-color: white; }\n.word { font-size: 32px; font-weight: bold; }\n.phonetic { color: #666; }\n.example { margin-top: 12px; font-size: 16px; color: #444; }";
+
+/// Anki 2.1 旧版集合结构（schema 11），新版 Anki 导入时会自动升级
+const SCHEMA: &str = r#"
+CREATE TABLE col (id integer primary key, crt integer not null, mod integer not null, scm integer not null, ver integer not null, dty integer not null, usn integer not null, ls integer not null, conf text not null, models text not null, decks text not null, dconf text not null, tags text not null);
+CREATE TABLE notes (id integer primary key, guid text not null, mid integer not null, mod integer not null, usn integer not null, tags text not null, flds text not null, sfld integer not null, csum integer not null, flags integer not null, data text not null);
+CREATE TABLE cards (id integer primary key, nid integer not null, did integer not null, ord integer not null, mod integer not null, usn integer not null, type integer not null, queue integer not null, due integer not null, ivl integer not null, factor integer not null, reps integer not null, lapses integer not null, left integer not null, odue integer not null, odid integer not null, flags integer not null, data text not null);
+CREATE TABLE revlog (id integer primary key, cid integer not null, usn integer not null, ivl integer not null, lastIvl integer not null, factor integer not null, time integer not null, type integer not null);
+CREATE TABLE graves (usn integer not null, oid integer not null, type integer not null);
+CREATE INDEX ix_notes_usn on notes (usn);
+CREATE INDEX ix_cards_usn on cards (usn);
+CREATE INDEX ix_revlog_usn on revlog (usn);
+CREATE INDEX ix_cards_nid on cards (nid);
+CREATE INDEX ix_cards_sched on cards (did, queue, due);
+CREATE INDEX ix_revlog_cid on revlog (cid);
+CREATE INDEX ix_notes_csum on notes (csum);
+"#;
+
+/// 生成 `.apkg` 文件内容；牌组以 `sheet.title` 命名，每个单词一条笔记，并以单元名作为标签
+pub fn build_apkg(sheet: &WordSheet) -> Result<Vec<u8>> {
+    // SQLite 需要落盘后才能打包，使用临时文件并在结束后删除
+    let path = temp_path();
+    let result = write_collection(&path, sheet).and_then(|_| {
+        let collection = std::fs::read(&path).context("Failed to read Anki collection")?;
+        package(&collection)
+    });
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+fn temp_path() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("anki-{}-{}.anki2", std::process::id(), nanos))
+}
+
+fn package(collection: &[u8]) -> Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    zip.start_file("collection.anki2", options)?;
+    zip.write_all(collection)?;
+    zip.start_file("media", options)?;
+    zip.write_all(b"{}")?;
+    Ok(zip.finish()?.into_inner())
+}
+
+fn write_collection(path: &PathBuf, sheet: &WordSheet) -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let now_secs = now.as_secs() as i64;
+    let now_ms = now.as_millis() as i64;
+    let deck_id = stable_id(&format!("deck:{}", sheet.title));
+
+    let mut conn = Connection::open(path).context("Failed to create Anki collection")?;
+    conn.execute_batch(SCHEMA)?;
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO col VALUES (1, ?1, ?2, ?2, 11, 0, 0, 0, ?3, ?4, ?5, ?6, '{}')",
+        params![
+            now_secs - now_secs % 86_400,
+            now_ms,
+            collection_config(deck_id).to_string(),
+            models(deck_id, now_secs).to_string(),
+            decks(deck_id, &sheet.title, now_secs).to_string(),
+            deck_configs().to_string(),
+        ],
+    )?;
+
+    let mut position = 0i64;
+    for unit in &sheet.units {
+        let tag = tag_name(&unit.name);
+        for word in &unit.words {
+            let fields = [
+                escape(&word.word),
+                escape(word.phonetic().unwrap_or_default()),
+                html_lines(word.meaning.as_deref()),
+                html_lines(word.example.as_deref()),
+            ];
+            // 笔记和卡片 id 是毫秒时间戳，按顺序递增保证唯一
+            let note_id = now_ms + position;
+            tx.execute(
+                "INSERT INTO notes VALUES (?1, ?2, ?3, ?4, -1, ?5, ?6, ?7, ?8, 0, '')",
+                params![
+                    note_id,
+                    guid(&sheet.title, &word.word),
+                    MODEL_ID,
+                    now_secs,
+                    format!(" {} ", tag),
+                    fields.join("\u{1f}"),
+                    fields[0],
+                    checksum(&fields[0]),
+                ],
+            )?;
+            tx.execute(
+                "INSERT INTO cards VALUES (?1, ?2, ?3, 0, ?4, -1, 0, 0, ?5, 0, 0, 0, 0, 0, 0, 0, 0, '')",
+                params![note_id, note_id, deck_id, now_secs, position + 1],
+            )?;
+            position += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn html_lines(text: Option<&str>) -> String {
+    text.unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(escape)
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// Anki 标签不能包含空格
+fn tag_name(name: &str) -> String {
+    let tag = name.split_whitespace().collect::<Vec<_>>().join("_");
+    if tag.is_empty() {
+        "unit".to_string()
+    } else {
+        tag
+    }
+}
+
+/// 由名称生成稳定的 id，重复导出同一课本时 Anki 会合并到同一牌组
+fn stable_id(key: &str) -> i64 {
+    let digest = Sha256::digest(key.as_bytes());
+    let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) as i64;
+    (1 << 30) + value % (1 << 30)
+}
+
+/// 笔记的 guid，同一牌组中的同一单词保持不变，重复导入时更新而不是新增
+fn guid(deck: &str, word: &str) -> String {
+    let digest = Sha256::digest(format!("{}\u{1f}{}", deck, word).as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 排序字段 SHA1 的前 8 位十六进制，Anki 用于查重
+fn checksum(field: &str) -> i64 {
+    let digest = Sha1::digest(field.as_bytes());
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) as i64
+}
+
+fn collection_config(deck_id: i64) -> serde_json::Value {
+    json!({
+        "activeDecks": [deck_id],
+        "curDeck": deck_id,
+        "curModel": MODEL_ID.to_string(),
+        "addToCur": true,
+        "collapseTime": 1200,
+        "dueCounts": true,
+        "estTimes": true,
+        "newBury": true,
+        "newSpread": 0,
+        "nextPos": 1,
+        "sortBackwards": false,
+        "sortType": "noteFld",
+        "timeLim": 0
+    })
+}
+
+fn models(deck_id: i64, now: i64) -> serde_json::Value {
+    let fields: Vec<_> = FIELDS
+        .iter()
+        .enumerate()
+        .map(|(ord, name)| {
+            json!({"name": name, "ord": ord, "font": "Arial", "size": 20, "media": [], "rtl": false, "sticky": false})
+        })
+        .collect();
+    json!({
+        MODEL_ID.to_string(): {
+            "id": MODEL_ID,
+            "name": MODEL_NAME,
+            "type": 0,
+            "mod": now,
+            "usn": -1,
+            "did": deck_id,
+            "sortf": 0,
+            "flds": fields,
+            "tmpls": [{
+                "name": "Card 1",
+                "ord": 0,
+                "qfmt": FRONT_TEMPLATE,
+                "afmt": BACK_TEMPLATE,
+                "bqfmt": "",
+                "bafmt": "",
+                "did": null
+            }],
+            "css": CARD_CSS,
+            "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\setlength{\\parindent}{0in}\n\\begin{document}\n",
+            "latexPost": "\\end{document}",
+            "req": [[0, "any", [0]]],
+            "tags": [],
+            "vers": []
+        }
+    })
+}
+
+fn deck(id: i64, name: &str, now: i64) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "desc": "",
+        "mod": now,
+        "usn": -1,
+        "conf": 1,
+        "dyn": 0,
+        "collapsed": false,
+        "browserCollapsed": false,
+        "extendNew": 10,
+        "extendRev": 50,
+        "newToday": [0, 0],
+        "revToday": [0, 0],
+        "lrnToday": [0, 0],
+        "timeToday": [0, 0]
+    })
+}
+
+fn decks(deck_id: i64, name: &str, now: i64) -> serde_json::Value {
+    json!({
+        "1": deck(1, "Default", now),
+        deck_id.to_string(): deck(deck_id, name, now)
+    })
+}
+
+fn deck_configs() -> serde_json::Value {
+    json!({
+        "1": {
+            "id": 1,
+            "name": "Default",
+            "mod": 0,
+            "usn": 0,
+            "autoplay": true,
+            "replayq": true,
+            "timer": 0,
+            "maxTaken": 60,
+            "new": {"bury": true, "delays": [1, 10], "initialFactor": 2500, "ints": [1, 4, 7], "order": 1, "perDay": 20, "separate": true},
+            "rev": {"bury": true, "ease4": 1.3, "fuzz": 0.05, "ivlFct": 1, "maxIvl": 36500, "minSpace": 1, "perDay": 100},
+            "lapse": {"delays": [10], "leechAction": 0, "leechFails": 8, "minInt": 1, "mult": 0}
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::export::{ExportUnit, ExportWord};
+    use std::io::Read;
+
+    #[test]
+    fn test_build_apkg() {
+        let word = |word: &str| ExportWord {
+            word: word.to_string(),
+            phonetic_us: Some("/test/".to_string()),
+            meaning: Some("n. 测试\nv. 试验".to_string()),
+            ..Default::default()
+        };
+        let sheet = WordSheet {
+            title: "人教版三年级上册".to_string(),
+            units: vec![
+                ExportUnit {
+                    name: "Unit 1 Hello".to_string(),
+                    words: vec![word("hello"), word("goodbye")],
+                },
+                ExportUnit {
+                    name: "Unit 2".to_string(),
+                    words: vec![word("apple")],
+                },
+            ],
+        };
+
+        let apkg = build_apkg(&sheet).unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(apkg)).unwrap();
+        let mut media = String::new();
+        archive
+            .by_name("media")
+            .unwrap()
+            .read_to_string(&mut media)
+            .unwrap();
+        assert_eq!(media, "{}");
+
+        let mut collection = Vec::new();
+        archive
+            .by_name("collection.anki2")
+            .unwrap()
+            .read_to_end(&mut collection)
+            .unwrap();
+        let path = temp_path();
+        std::fs::write(&path, collection).unwrap();
+        let conn = Connection::open(&path).unwrap();
+
+        let notes: Vec<(String, String, String)> = conn
+            .prepare("SELECT sfld, tags, flds FROM notes ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(notes.len(), 3);
+        assert_eq!(notes[0].0, "hello");
+        assert_eq!(notes[0].1, " Unit_1_Hello ");
+        assert_eq!(
+            notes[0].2,
+            "hello\u{1f}/test/\u{1f}n. 测试<br>v. 试验\u{1f}"
+        );
+        assert_eq!(notes[2].1, " Unit_2 ");
+
+        let (cards, decks): (i64, String) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM cards), decks FROM col",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(cards, 3);
+        assert!(decks.contains("人教版三年级上册"));
+        drop(conn);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use super::WordSheet;
+
+const HEADER: [&str; 6] = [
+    "unit",
+    "word",
+    "phonetic_us",
+    "phonetic_uk",
+    "meaning",
+    "example",
+];
+
+/// 渲染 CSV，带 BOM 以便 Excel 正确识别中文；多行的释义和例句保留换行
+pub fn render_csv(sheet: &WordSheet) -> String {
+    let mut csv = String::from("\u{feff}");
+    push_record(&mut csv, HEADER.iter().copied());
+    for unit in &sheet.units {
+        for word in &unit.words {
+            push_record(
+                &mut csv,
+                [
+                    unit.name.as_str(),
+                    word.word.as_str(),
+                    word.phonetic_us.as_deref().unwrap_or_default(),
+                    word.phonetic_uk.as_deref().unwrap_or_default(),
+                    word.meaning.as_deref().unwrap_or_default(),
+                    word.example.as_deref().unwrap_or_default(),
+                ],
+            );
+        }
+    }
+    csv
+}
+
+fn push_record<'a>(csv: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    let fields: Vec<String> = fields.into_iter().map(escape_field).collect();
+    csv.push_str(&fields.join(","));
+    csv.push_str("\r\n");
+}
+
+/// 按 RFC 4180 转义：包含逗号、双引号或换行的字段用双引号包裹
+fn escape_field(field: &str) -> String {
+    let field = field.trim();
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::utils::word_list::{parse_word_list, ImportFormat};
+    use crate::infrastructure::export::{ExportUnit, ExportWord};
+
+    #[test]
+    fn test_render_csv_round_trips_through_import() {
+        let sheet = WordSheet {
+            title: "Book".to_string(),
+            units: vec![ExportUnit {
+                name: "Unit 1".to_string(),
+                words: vec![ExportWord {
+                    word: "ice cream".to_string(),
+                    meaning: Some("n. 冰淇淋, 雪糕\nn. \"甜筒\"".to_string()),
+                    ..Default::default()
+                }],
+            }],
+        };
+        let csv = render_csv(&sheet);
+        assert!(csv.starts_with("\u{feff}unit,word,phonetic_us,phonetic_uk,meaning,example\r\n"));
+        assert!(csv.contains("Unit 1,ice cream,,,\"n. 冰淇淋, 雪糕\nn. \"\"甜筒\"\"\",\r\n"));
+
+        // 导出的 CSV 可以直接用于单元单词导入
+        let lines = parse_word_list(&csv, ImportFormat::Csv);
+        let row = lines[0].row.as_ref().unwrap();
+        assert_eq!(row.word, "ice cream");
+        assert_eq!(row.meaning.as_deref(), Some("n. 冰淇淋, 雪糕\nn. \"甜筒\""));
+    }
+}
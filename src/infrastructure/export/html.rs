@@ -0,0 +1,137 @@
+use super::{ExportWord, WordSheet};
+
+/// 打印样式：A4 纸，每个单元从新的一页开始，浏览器中可直接“打印 → 另存为 PDF”
+const STYLE: &str = r#"
+@page { size: A4; margin: 15mm; }
+body { font-family: "Helvetica Neue", Arial, "PingFang SC", "Microsoft YaHei", sans-serif; font-size: 12pt; color: #222; }
+h1 { font-size: 18pt; text-align: center; margin: 0 0 12pt; }
+h2 { font-size: 14pt; margin: 18pt 0 6pt; }
+section + section { page-break-before: always; }
+table { width: 100%; border-collapse: collapse; }
+th, td { border: 1px solid #999; padding: 4pt 6pt; vertical-align: top; text-align: left; }
+th { background: #f0f0f0; }
+tr { page-break-inside: avoid; }
+.no { width: 2.5em; text-align: center; }
+.word { font-weight: bold; }
+.phonetic { color: #555; font-family: "Lucida Sans Unicode", "Arial Unicode MS", sans-serif; }
+.example { font-size: 10pt; color: #444; }
+.blank { height: 1.6em; }
+.names { text-align: right; margin-bottom: 8pt; }
+"#;
+
+/// 渲染可打印的单词表；`dictation` 为 true 时隐藏释义和例句，留出空白供学生默写
+pub fn render_html(sheet: &WordSheet, dictation: bool) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(&sheet.title)));
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", escape(&sheet.title)));
+    if dictation {
+        html.push_str("<p class=\"names\">姓名：__________ 班级：__________ 得分：______</p>\n");
+    }
+
+    for unit in &sheet.units {
+        html.push_str("<section>\n");
+        html.push_str(&format!("<h2>{}</h2>\n", escape(&unit.name)));
+        html.push_str("<table>\n<thead><tr><th class=\"no\">#</th><th>单词</th><th>音标</th>");
+        if dictation {
+            html.push_str("<th>释义</th>");
+        } else {
+            html.push_str("<th>释义</th><th>例句</th>");
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for (index, word) in unit.words.iter().enumerate() {
+            html.push_str(&render_row(index + 1, word, dictation));
+        }
+        html.push_str("</tbody>\n</table>\n</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_row(no: usize, word: &ExportWord, dictation: bool) -> String {
+    let mut row = format!(
+        "<tr><td class=\"no\">{}</td><td class=\"word\">{}</td><td class=\"phonetic\">{}</td>",
+        no,
+        escape(&word.word),
+        escape(word.phonetic().unwrap_or_default())
+    );
+    if dictation {
+        row.push_str("<td class=\"blank\"></td>");
+    } else {
+        row.push_str(&format!(
+            "<td>{}</td><td class=\"example\">{}</td>",
+            multiline(word.meaning.as_deref()),
+            multiline(word.example.as_deref())
+        ));
+    }
+    row.push_str("</tr>\n");
+    row
+}
+
+fn multiline(text: Option<&str>) -> String {
+    text.unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(escape)
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// 转义 HTML 特殊字符
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::export::ExportUnit;
+
+    fn sheet() -> WordSheet {
+        WordSheet {
+            title: "Book <1>".to_string(),
+            units: vec![ExportUnit {
+                name: "Unit 1".to_string(),
+                words: vec![ExportWord {
+                    word: "apple".to_string(),
+                    phonetic_us: Some("/ˈæpl/".to_string()),
+                    phonetic_uk: None,
+                    meaning: Some("n. 苹果\nn. 苹果树".to_string()),
+                    example: Some("I eat an apple.\n我吃了一个苹果。".to_string()),
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_handout() {
+        let html = render_html(&sheet(), false);
+        assert!(html.contains("<title>Book &lt;1&gt;</title>"));
+        assert!(html.contains("<td class=\"phonetic\">/ˈæpl/</td>"));
+        assert!(html.contains("<td>n. 苹果<br>n. 苹果树</td>"));
+        assert!(html.contains("I eat an apple.<br>我吃了一个苹果。"));
+    }
+
+    #[test]
+    fn test_render_dictation_hides_meaning() {
+        let html = render_html(&sheet(), true);
+        assert!(html.contains("apple"));
+        assert!(html.contains("姓名"));
+        assert!(!html.contains("苹果"));
+        assert!(!html.contains("I eat an apple."));
+    }
+}
@@ -0,0 +1,98 @@
+//! 单词表导出：可打印的 HTML 讲义、CSV 和 Anki 牌组
+
+pub mod anki;
+pub mod csv;
+pub mod html;
+
+use crate::infrastructure::dto::WordMeaning;
+
+/// 导出的一个单词，释义已格式化为每行一个义项
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportWord {
+    pub word: String,
+    pub phonetic_us: Option<String>,
+    pub phonetic_uk: Option<String>,
+    pub meaning: Option<String>,
+    pub example: Option<String>,
+}
+
+impl ExportWord {
+    /// 美式音标优先
+    pub fn phonetic(&self) -> Option<&str> {
+        self.phonetic_us
+            .as_deref()
+            .or(self.phonetic_uk.as_deref())
+            .filter(|phonetic| !phonetic.trim().is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportUnit {
+    pub name: String,
+    pub words: Vec<ExportWord>,
+}
+
+/// 一次导出的内容：整本课本或单个单元
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WordSheet {
+    pub title: String,
+    pub units: Vec<ExportUnit>,
+}
+
+/// 导出生成的文件
+#[derive(Debug, Clone)]
+pub struct ExportFile {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub content: Vec<u8>,
+}
+
+/// 把释义转为 “n. 苹果” 形式，每个义项一行；没有释义时为空
+pub fn format_meaning(meanings: &[WordMeaning]) -> Option<String> {
+    if meanings.is_empty() {
+        return None;
+    }
+    let lines = meanings
+        .iter()
+        .map(|m| format!("{} {}", m.pos.trim(), m.definition.trim()))
+        .map(|line| line.trim().to_string())
+        .collect::<Vec<_>>();
+    Some(lines.join("\n"))
+}
+
+/// 文件名中去掉路径分隔符等不安全字符
+pub fn file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    match stem.trim() {
+        "" => "words".to_string(),
+        stem => stem.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_meaning() {
+        let meanings: Vec<WordMeaning> = serde_json::from_str(
+            r#"[{"pos":"n.","definition":"苹果"},{"pos":"","definition":" 苹果树 "}]"#,
+        )
+        .unwrap();
+        assert_eq!(format_meaning(&meanings).unwrap(), "n. 苹果\n苹果树");
+        assert_eq!(format_meaning(&[]), None);
+    }
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("人教版 3/上"), "人教版 3_上");
+        assert_eq!(file_stem("  "), "words");
+    }
+}
@@ -0,0 +1,245 @@
+use serde::Serialize;
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// 熔断器状态
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 正常放行请求
+    Closed,
+    /// 连续失败过多，拒绝请求直到冷却结束
+    Open,
+    /// 冷却结束，放行一个试探请求
+    HalfOpen,
+}
+
+#[derive(Debug, Clone)]
+pub struct BreakerConfig {
+    /// 连续失败多少次后熔断
+    pub failure_threshold: u32,
+    /// 熔断后多久进入半开状态
+    pub cooldown: Duration,
+    /// 单次调用的超时时间，超时按失败计
+    pub call_timeout: Duration,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(60),
+            call_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl BreakerConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let read = |key: &str| env::var(key).ok().and_then(|v| v.parse::<u64>().ok());
+
+        Self {
+            failure_threshold: read("LLM_BREAKER_FAILURE_THRESHOLD")
+                .map(|v| v as u32)
+                .unwrap_or(default.failure_threshold),
+            cooldown: read("LLM_BREAKER_COOLDOWN_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.cooldown),
+            call_timeout: read("LLM_CALL_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.call_timeout),
+        }
+    }
+}
+
+/// 熔断器对外展示的状态快照
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakerSnapshot {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub total_successes: u64,
+    pub total_failures: u64,
+    pub last_error: Option<String>,
+    pub opened_at: Option<String>,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    total_successes: u64,
+    total_failures: u64,
+    last_error: Option<String>,
+    opened_at: Option<Instant>,
+    opened_at_wall: Option<OffsetDateTime>,
+    /// 试探请求的开始时间；调用方的请求被取消时不会记录结果，超过调用超时后允许再次试探
+    probe_started_at: Option<Instant>,
+}
+
+/// 单个模型服务的熔断器
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: BreakerConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: BreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                total_successes: 0,
+                total_failures: 0,
+                last_error: None,
+                opened_at: None,
+                opened_at_wall: None,
+                probe_started_at: None,
+            }),
+        }
+    }
+
+    /// 判断当前是否允许发起请求，冷却结束后只放行一个试探请求，试探超过调用超时仍无结果时再放行一个
+    pub fn allow_request(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                let cooled_down = inner
+                    .opened_at
+                    .is_none_or(|opened_at| opened_at.elapsed() >= self.config.cooldown);
+                if cooled_down {
+                    inner.state = CircuitState::HalfOpen;
+                    inner.probe_started_at = Some(Instant::now());
+                }
+                cooled_down
+            }
+            CircuitState::HalfOpen => {
+                let probe_done = inner
+                    .probe_started_at
+                    .is_none_or(|started_at| started_at.elapsed() >= self.config.call_timeout);
+                if probe_done {
+                    inner.probe_started_at = Some(Instant::now());
+                }
+                probe_done
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.total_successes += 1;
+        inner.opened_at = None;
+        inner.opened_at_wall = None;
+        inner.probe_started_at = None;
+    }
+
+    pub fn record_failure(&self, error: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.total_failures += 1;
+        inner.last_error = Some(error.to_string());
+        inner.probe_started_at = None;
+
+        let should_open = inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.config.failure_threshold;
+        if should_open {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            inner.opened_at_wall = Some(OffsetDateTime::now_utc());
+        }
+    }
+
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        let inner = self.inner.lock().unwrap();
+        BreakerSnapshot {
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            total_successes: inner.total_successes,
+            total_failures: inner.total_failures,
+            last_error: inner.last_error.clone(),
+            opened_at: inner
+                .opened_at_wall
+                .and_then(|dt| dt.format(&Rfc3339).ok()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(cooldown: Duration) -> BreakerConfig {
+        BreakerConfig {
+            failure_threshold: 2,
+            cooldown,
+            call_timeout: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(config(Duration::from_secs(60)));
+        assert!(breaker.allow_request());
+
+        breaker.record_failure("timeout");
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+        breaker.record_success();
+        breaker.record_failure("timeout");
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+        breaker.record_failure("timeout");
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+        assert!(!breaker.allow_request());
+
+        let snapshot = breaker.snapshot();
+        assert_eq!(snapshot.total_failures, 3);
+        assert_eq!(snapshot.consecutive_failures, 2);
+        assert!(snapshot.opened_at.is_some());
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe() {
+        let breaker = CircuitBreaker::new(config(Duration::ZERO));
+        breaker.record_failure("error");
+        breaker.record_failure("error");
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.snapshot().state, CircuitState::HalfOpen);
+        assert!(!breaker.allow_request());
+
+        // 试探失败重新熔断
+        breaker.record_failure("still failing");
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+
+        // 试探成功恢复
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+        assert_eq!(breaker.snapshot().consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_abandoned_probe_expires() {
+        let breaker = CircuitBreaker::new(BreakerConfig {
+            call_timeout: Duration::from_millis(20),
+            ..config(Duration::ZERO)
+        });
+        breaker.record_failure("error");
+        breaker.record_failure("error");
+
+        // 试探请求被取消，没有记录结果
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.snapshot().state, CircuitState::HalfOpen);
+    }
+}
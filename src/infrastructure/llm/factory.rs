@@ -1,4 +1,6 @@
 use crate::infrastructure::llm::impl_deepseek::DeepSeekServiceImpl;
+use crate::infrastructure::llm::impl_mock::MockServiceImpl;
+use crate::infrastructure::llm::impl_openai_compatible::OpenAICompatibleServiceImpl;
 use crate::infrastructure::llm::provider::{LLMConfig, LLMProvider};
 use crate::infrastructure::llm::{LLMService, YiServiceImpl};
 use anyhow::Result;
@@ -9,6 +11,8 @@ use std::sync::Arc;
 
 lazy_static! {
     static ref PROVIDER_CACHE: DashMap<String, Arc<dyn LLMService + Send + Sync>> = DashMap::new();
+    /// 从数据库加载的模型服务配置，按名称索引
+    static ref PROVIDER_CONFIGS: DashMap<String, LLMConfig> = DashMap::new();
 }
 
 pub trait LLMServiceFactoryTrait: Send + Sync {
@@ -19,8 +23,35 @@ pub struct LLMServiceFactory;
 
 impl LLMServiceFactory {
     pub fn create(config: &LLMConfig) -> Result<Arc<dyn LLMService + Send + Sync>> {
-        let cache_key = Self::generate_cache_key(&config);
+        Self::create_cached(Self::generate_cache_key(config), config)
+    }
+
+    /// 注册（或替换）指定名称的模型服务配置，并使旧的服务实例失效
+    pub fn register_config(name: &str, config: LLMConfig) {
+        Self::invalidate(name);
+        PROVIDER_CONFIGS.insert(name.to_lowercase(), config);
+    }
+
+    /// 移除指定名称的配置及其缓存的服务实例
+    pub fn unregister_config(name: &str) {
+        PROVIDER_CONFIGS.remove(&name.to_lowercase());
+        Self::invalidate(name);
+    }
+
+    /// 清除指定名称的缓存服务实例，下次调用时按最新配置重建
+    pub fn invalidate(name: &str) {
+        let prefix = Self::named_cache_prefix(name);
+        PROVIDER_CACHE.retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    fn named_cache_prefix(name: &str) -> String {
+        format!("{}#", name.to_lowercase())
+    }
 
+    fn create_cached(
+        cache_key: String,
+        config: &LLMConfig,
+    ) -> Result<Arc<dyn LLMService + Send + Sync>> {
         if let Some(cached) = PROVIDER_CACHE.get(&cache_key) {
             return Ok(cached.clone());
         }
@@ -36,6 +67,16 @@ impl LLMServiceFactory {
                 service.configure(&config)?;
                 Arc::new(service)
             }
+            LLMProvider::OpenAICompatible => {
+                let mut service = OpenAICompatibleServiceImpl::new();
+                service.configure(config)?;
+                Arc::new(service)
+            }
+            LLMProvider::Mock => {
+                let mut service = MockServiceImpl::new();
+                service.configure(config)?;
+                Arc::new(service)
+            }
         };
 
         PROVIDER_CACHE.insert(cache_key, service.clone());
@@ -44,31 +85,74 @@ impl LLMServiceFactory {
 
     fn generate_cache_key(config: &LLMConfig) -> String {
         format!(
-            "{}:{}:{}:{}",
+            "{}:{}:{}:{}:{:?}:{:?}",
             config.provider.to_string(),
             config.api_key.as_deref().unwrap_or("default"),
             config.base_url.as_deref().unwrap_or("default"),
-            config.model_name.as_deref().unwrap_or("default")
+            config.model_name.as_deref().unwrap_or("default"),
+            config.timeout,
+            config.params
         )
     }
 }
 
 impl LLMServiceFactoryTrait for LLMServiceFactory {
     fn create_from_name(&self, name: &str) -> Result<Arc<dyn LLMService + Send + Sync>> {
-        let provider = name.parse::<LLMProvider>()?;
-        let mut config = LLMConfig::new(provider);
+        // 优先使用数据库中的配置
+        if let Some(config) = PROVIDER_CONFIGS.get(&name.to_lowercase()) {
+            let cache_key = format!(
+                "{}{}",
+                Self::named_cache_prefix(name),
+                Self::generate_cache_key(&config)
+            );
+            return Self::create_cached(cache_key, &config);
+        }
+
+        // 兼容旧的环境变量配置
+        let prefix = format!("LLM_{}", name.to_uppercase());
+        // 名称与厂商可以不同，例如 LLM_QWEN_PROVIDER=openai-compatible
+        let provider = env::var(format!("{}_PROVIDER", prefix))
+            .unwrap_or_else(|_| name.to_string())
+            .parse::<LLMProvider>()?;
+        let mut config = LLMConfig::new(provider.clone());
 
-        let api_key = env::var(format!("LLM_{}_API_KEY", name.to_uppercase()))?;
-        let base_url = env::var(format!("LLM_{}_BASE_URL", name.to_uppercase()))?;
-        let model = env::var(format!("LLM_{}_MODEL", name.to_uppercase()))?;
-        let timeout = env::var(format!("LLM_{}_TIMEOUT", name.to_uppercase()))?;
+        let base_url = env::var(format!("{}_BASE_URL", prefix))?;
+        let model = env::var(format!("{}_MODEL", prefix))?;
+        let timeout = env::var(format!("{}_TIMEOUT", prefix))?;
 
         config
-            .with_api_key(api_key)
             .with_base_url(base_url)
             .with_model(model)
             .with_timeout(timeout.parse()?);
 
-        Self::create(&config)
+        // OpenAI 兼容服务（如本地 Ollama）允许不配置 API key
+        match env::var(format!("{}_API_KEY", prefix)) {
+            Ok(api_key) => {
+                config.with_api_key(api_key);
+            }
+            Err(e)
+                if provider != LLMProvider::OpenAICompatible && provider != LLMProvider::Mock =>
+            {
+                return Err(e.into())
+            }
+            Err(_) => {}
+        }
+
+        if let Ok(temperature) = env::var(format!("{}_TEMPERATURE", prefix)) {
+            config.with_temperature(temperature.parse()?);
+        }
+        if let Ok(top_p) = env::var(format!("{}_TOP_P", prefix)) {
+            config.with_top_p(top_p.parse()?);
+        }
+        if let Ok(max_tokens) = env::var(format!("{}_MAX_TOKENS", prefix)) {
+            config.with_max_tokens(max_tokens.parse()?);
+        }
+
+        let cache_key = format!(
+            "{}{}",
+            Self::named_cache_prefix(name),
+            Self::generate_cache_key(&config)
+        );
+        Self::create_cached(cache_key, &config)
     }
 }
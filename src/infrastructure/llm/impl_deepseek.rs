@@ -1,324 +1,262 @@
-use crate::infrastructure::dto::WordInfo;
-use crate::infrastructure::llm::interface::LLMService;
-use crate::infrastructure::llm::prompts::LanguagePrompts;
+use crate::infrastructure::llm::impl_openai_compatible::OpenAICompatibleServiceImpl;
+use crate::infrastructure::llm::interface::{ChatMessage, ChatStream, LLMService};
 use crate::infrastructure::llm::provider::LLMConfig;
-use crate::infrastructure::llm::utils;
-use anyhow::{anyhow, Result};
+use crate::infrastructure::llm::stream;
+use crate::infrastructure::llm::usage::{self, TokenUsage};
+use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
-use deepseek_api_client::Message;
-use dotenv::dotenv;
-use std::env;
-use tokio::runtime::Handle;
-use tracing::debug;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
 
 pub struct DeepSeekServiceImpl {
+    client: Client,
     api_key: Option<String>,
+    api_url: String,
+    model_name: String,
     timeout: u64,
 }
 
 impl DeepSeekServiceImpl {
     pub fn new() -> Result<Self> {
         Ok(Self {
+            client: Client::new(),
             api_key: None,
+            api_url: "https://api.deepseek.com/chat/completions".to_string(),
+            model_name: "deepseek-chat".to_string(),
             timeout: 30,
         })
     }
-
-    /// Helper function to execute a chat completion request
-    async fn execute_chat(
-        &self,
-        system_prompt: &str,
-        user_prompt: &str,
-        temperature: f32,
-    ) -> Result<String> {
-        let messages = vec![
-            Message {
-                role: "system".to_string(),
-                content: system_prompt.to_string(),
-            },
-            Message {
-                role: "user".to_string(),
-                content: user_prompt.to_string(),
-            },
-        ];
-        if self.api_key.is_none() {
-            return Err(anyhow!("No API key provided"));
-        }
-        let api_key = self.api_key.clone().unwrap();
-
-        // Run the blocking operation in a separate thread
-        let handle = Handle::current();
-        let response = handle
-            .spawn_blocking(move || {
-                let mut chat_fn = if temperature > 0.5 {
-                    deepseek_api_client::chat_completion_sync(&api_key)
-                } else {
-                    deepseek_api_client::code_completion_sync(&api_key)
-                };
-                chat_fn(messages)
-            })
-            .await??;
-
-        deepseek_api_client::get_response_text(&response, 0)
-            .ok_or_else(|| anyhow!("No response received from DeepSeek API"))
-    }
 }
 
 #[async_trait]
 impl LLMService for DeepSeekServiceImpl {
     fn configure(&mut self, config: &LLMConfig) -> Result<()> {
         self.api_key = config.api_key.clone();
-        self.timeout = config.timeout.unwrap();
+        if let Some(timeout) = config.timeout {
+            self.timeout = timeout;
+        }
+        if let Some(base_url) = &config.base_url {
+            self.api_url = OpenAICompatibleServiceImpl::normalize_api_url(base_url);
+        }
+        if let Some(model_name) = &config.model_name {
+            self.model_name = model_name.clone();
+        }
         Ok(())
     }
 
-    async fn get_phonetics(&self, word: &str) -> Result<(String, String)> {
-        let content = self
-            .execute_chat(
-                LanguagePrompts::PHONETICS_SYSTEM,
-                &LanguagePrompts::phonetics_user(word),
-                0.3,
-            )
-            .await?;
-        debug!(" get phonetics from AI response: {:?}", content);
-        let cleaned_content = utils::clean_json_response(&content);
-        utils::extract_phonetics(cleaned_content.as_str())
-    }
+    async fn chat(&self, messages: &[ChatMessage], temperature: f32) -> Result<String> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or_else(|| anyhow!("No API key provided"))?;
+        let body = json!({
+            "model": self.model_name,
+            "messages": messages,
+            "temperature": temperature
+        });
 
-    async fn get_example_sentences(&self, word: &str) -> Result<String> {
-        let content = self
-            .execute_chat(
-                LanguagePrompts::SENTENCES_SYSTEM,
-                &LanguagePrompts::example_sentences_user(word),
-                0.7,
-            )
-            .await?;
-        debug!("example sentences from AI response : {}", content);
-        let cleaned_content = utils::clean_json_response(&content);
-        utils::extract_example_sentences(cleaned_content.as_str())
-    }
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(&self.api_url)
+            .timeout(Duration::from_secs(self.timeout))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to {}", self.api_url))?;
 
-    async fn get_word_info(&self, word: &str) -> Result<WordInfo> {
-        let content = self
-            .execute_chat(
-                LanguagePrompts::WORD_INFO_SYSTEM,
-                &LanguagePrompts::word_info_user(word),
-                0.3,
-            )
-            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Non-success status code: {}, body: {}",
+                status,
+                text
+            ));
+        }
 
-        // Clean the response before parsing
-        let cleaned_content = utils::clean_json_response(&content);
-        debug!("get word info from AI response : {}", content);
+        let response_body: Value = serde_json::from_str(&response.text().await?)?;
+        let content = response_body["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|content| content.to_string())
+            .ok_or_else(|| anyhow!("No response received from DeepSeek API"))?;
+        usage::record(
+            "deepseek",
+            &self.model_name,
+            TokenUsage::from_response(&response_body),
+            started.elapsed(),
+        );
+        Ok(content)
+    }
 
-        serde_json::from_str::<WordInfo>(&cleaned_content).map_err(|e| {
-            anyhow!(
-                "Failed to parse word info response for '{}': {}.\nResponse: {}",
-                word,
-                e,
-                cleaned_content
-            )
-        })
+    async fn chat_stream(&self, system_prompt: &str, user_prompt: &str) -> Result<ChatStream> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or_else(|| anyhow!("No API key provided"))?;
+        let body = json!({
+            "model": self.model_name,
+            "messages": [
+                {"role": "system", "content": system_prompt},
+                {"role": "user", "content": user_prompt}
+            ],
+            "temperature": 0.7
+        });
+        stream::post_chat_stream(&self.client, &self.api_url, Some(api_key), "deepseek", body).await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    //! 这些测试会调用真实的 DeepSeek 接口，默认忽略；离线测试见 `impl_mock`
+    //! 运行方式：`cargo test impl_deepseek -- --ignored`（需要 LLM_DEEPSEEK_API_KEY）
     use super::*;
+    use crate::infrastructure::llm::provider::LLMProvider;
+    use dotenv::dotenv;
+    use std::env;
 
-    fn setup() {
+    fn live_service() -> DeepSeekServiceImpl {
         // Ensure .env is loaded before each test
         dotenv().ok();
-        // Print current environment variable to help debug
-        if let Ok(api_key) = env::var("LLM_DEEPSEEK_API_KEY") {
-            println!(
-                "Found API key: {}...{}",
-                &api_key[..8],
-                &api_key[api_key.len() - 4..]
-            );
-        } else {
-            println!("Warning: LLM_DEEP_SEEK_API_KEY not found in environment");
-        }
+        let api_key = env::var("LLM_DEEPSEEK_API_KEY").unwrap_or_default();
+        let mut service = DeepSeekServiceImpl::new().unwrap();
+        service
+            .configure(LLMConfig::new(LLMProvider::DeepSeek).with_api_key(api_key))
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn test_configure_uses_model_and_base_url() {
+        let mut service = DeepSeekServiceImpl::new().unwrap();
+        service
+            .configure(
+                LLMConfig::new(LLMProvider::DeepSeek)
+                    .with_api_key("sk-test")
+                    .with_base_url("https://proxy.example.com/v1/")
+                    .with_model("deepseek-reasoner")
+                    .with_timeout(45),
+            )
+            .unwrap();
+        assert_eq!(
+            service.api_url,
+            "https://proxy.example.com/v1/chat/completions"
+        );
+        assert_eq!(service.model_name, "deepseek-reasoner");
+        assert_eq!(service.timeout, 45);
+    }
+
+    fn is_chinese(text: &str) -> bool {
+        text.chars()
+            .any(|c| (c as u32) > 0x4E00 && (c as u32) < 0x9FFF)
     }
 
     #[tokio::test]
+    #[ignore = "requires network access and LLM_DEEPSEEK_API_KEY"]
     async fn test_get_phonetics() {
-        println!("\n=== Testing get_phonetics ===");
-        setup(); // Ensure environment is properly set up
-        match DeepSeekServiceImpl::new() {
-            Ok(service) => {
-                println!("Service initialized successfully");
-                let word = "hello";
-                println!("Testing word: {}", word);
+        let service = live_service();
+        let word = "hello";
 
-                match service.get_phonetics(word).await {
-                    Ok((us, uk)) => {
-                        println!("✅ Test successful!");
-                        println!("Results for word '{}':", word);
-                        println!("US phonetic: {}", us);
-                        println!("UK phonetic: {}", uk);
+        match service.get_phonetics(word).await {
+            Ok((us, uk)) => {
+                println!("Results for word '{}':", word);
+                println!("US phonetic: {}", us);
+                println!("UK phonetic: {}", uk);
 
-                        assert!(!us.is_empty(), "US phonetic is empty");
-                        assert!(!uk.is_empty(), "UK phonetic is empty");
-                        assert!(
-                            us.contains('/') || us.contains('['),
-                            "US phonetic lacks proper formatting: {}",
-                            us
-                        );
-                        assert!(
-                            uk.contains('/') || uk.contains('['),
-                            "UK phonetic lacks proper formatting: {}",
-                            uk
-                        );
-                    }
-                    Err(e) => {
-                        println!("❌ Test failed!");
-                        println!("Error: {}", e);
-                        panic!("Test failed: {}", e);
-                    }
-                }
-            }
-            Err(e) => {
-                println!("❌ Failed to initialize service");
-                println!("Error: {}", e);
-                panic!("Failed to initialize service: {}", e);
+                assert!(!us.is_empty(), "US phonetic is empty");
+                assert!(!uk.is_empty(), "UK phonetic is empty");
+                // 音标会去掉 /.../ 或 [...] 包裹，由调用方统一添加
+                assert!(
+                    !us.contains('/') && !us.contains('['),
+                    "US phonetic should not be wrapped: {}",
+                    us
+                );
+                assert!(
+                    !uk.contains('/') && !uk.contains('['),
+                    "UK phonetic should not be wrapped: {}",
+                    uk
+                );
             }
+            Err(e) => panic!("Test failed: {}", e),
         }
-        println!("=== Test completed ===\n");
     }
 
     #[tokio::test]
+    #[ignore = "requires network access and LLM_DEEPSEEK_API_KEY"]
     async fn test_get_example_sentences() {
-        println!("\n=== Testing get_example_sentences ===");
-        setup(); // Ensure environment is properly set up
-        match DeepSeekServiceImpl::new() {
-            Ok(service) => {
-                println!("Service initialized successfully");
-                let word = "hello";
-                println!("Testing word: {}", word);
-
-                match service.get_example_sentences(word).await {
-                    Ok(sentences) => {
-                        println!("✅ Test successful!");
-                        println!("Results for word '{}':", word);
+        let service = live_service();
+        let word = "hello";
 
-                        assert_eq!(
-                            sentences.len(),
-                            2,
-                            "Expected 2 sentences, got {}",
-                            sentences.len()
-                        );
+        match service.get_example_sentences(word).await {
+            Ok((examples, _)) => {
+                println!("Results for word '{}':\n{}", word, examples);
 
-                        println!("\nSimple sentence:");
-                        println!("English: {}", sentences[0].0);
-                        println!("Chinese: {}", sentences[0].1);
-                        println!("\nComplex sentence:");
-                        println!("English: {}", sentences[1].0);
-                        println!("Chinese: {}", sentences[1].1);
+                // 例句格式为 “英文\n中文\n” 交替排列
+                let lines: Vec<&str> = examples.lines().collect();
+                assert_eq!(lines.len(), 4, "Expected 2 sentence pairs, got {:?}", lines);
 
-                        for (i, (en, zh)) in sentences.iter().enumerate() {
-                            // Validate English sentence
-                            assert!(!en.is_empty(), "English sentence {} is empty", i + 1);
-                            assert!(
-                                en.to_lowercase().contains(word),
-                                "Sentence {} doesn't contain the word '{}': {}",
-                                i + 1,
-                                word,
-                                en
-                            );
-                            println!("\nValidation for sentence {}:", i + 1);
-                            println!("✓ English sentence is not empty");
-                            println!("✓ Contains target word '{}'", word);
-
-                            // Validate Chinese translation
-                            assert!(!zh.is_empty(), "Chinese translation {} is empty", i + 1);
-                            assert!(
-                                zh.chars()
-                                    .any(|c| (c as u32) > 0x4E00 && (c as u32) < 0x9FFF),
-                                "Translation {} should contain Chinese characters: {}",
-                                i + 1,
-                                zh
-                            );
-                            println!("✓ Chinese translation is not empty");
-                            println!("✓ Contains Chinese characters");
-                        }
-                    }
-                    Err(e) => {
-                        println!("❌ Test failed!");
-                        println!("Error: {}", e);
-                        panic!("Test failed: {}", e);
-                    }
+                for (i, pair) in lines.chunks(2).enumerate() {
+                    assert!(
+                        pair[0].to_lowercase().contains(word),
+                        "Sentence {} doesn't contain the word '{}': {}",
+                        i + 1,
+                        word,
+                        pair[0]
+                    );
+                    assert!(
+                        is_chinese(pair[1]),
+                        "Translation {} should contain Chinese characters: {}",
+                        i + 1,
+                        pair[1]
+                    );
                 }
             }
-            Err(e) => {
-                println!("❌ Failed to initialize service");
-                println!("Error: {}", e);
-                panic!("Failed to initialize service: {}", e);
-            }
+            Err(e) => panic!("Test failed: {}", e),
         }
-        println!("=== Test completed ===\n");
     }
 
     #[tokio::test]
+    #[ignore = "requires network access and LLM_DEEPSEEK_API_KEY"]
     async fn test_get_word_info() {
-        println!("\n=== Testing get_word_info ===");
-        setup(); // Ensure environment is properly set up
+        let service = live_service();
+        let word = "name";
 
-        match DeepSeekServiceImpl::new() {
-            Ok(service) => {
-                println!("Service initialized successfully");
-                let word = "name";
-                println!("Testing word: {}", word);
-
-                match service.get_word_info(word).await {
-                    Ok(word_info) => {
-                        println!("✅ Test successful!");
-                        println!("Results for word '{}':", word);
-                        println!("US phonetic: {}", word_info.us_phonetic);
-                        println!("UK phonetic: {}", word_info.uk_phonetic);
-                        println!("\nMeanings:");
-                        for (i, meaning) in word_info.meanings.iter().enumerate() {
-                            println!("{}. {} - {}", i + 1, meaning.pos, meaning.definition);
-                        }
+        match service.get_word_info(word).await {
+            Ok((word_info, _)) => {
+                println!("US phonetic: {}", word_info.us_phonetic);
+                println!("UK phonetic: {}", word_info.uk_phonetic);
+                for (i, meaning) in word_info.meanings.iter().enumerate() {
+                    println!("{}. {} - {}", i + 1, meaning.pos, meaning.definition);
+                }
 
-                        // Validate response
-                        assert!(
-                            !word_info.us_phonetic.is_empty(),
-                            "US phonetic should not be empty"
-                        );
-                        assert!(
-                            !word_info.uk_phonetic.is_empty(),
-                            "UK phonetic should not be empty"
-                        );
-                        assert!(
-                            !word_info.meanings.is_empty(),
-                            "Meanings should not be empty"
-                        );
+                assert!(
+                    !word_info.us_phonetic.is_empty(),
+                    "US phonetic should not be empty"
+                );
+                assert!(
+                    !word_info.uk_phonetic.is_empty(),
+                    "UK phonetic should not be empty"
+                );
+                assert!(
+                    !word_info.meanings.is_empty(),
+                    "Meanings should not be empty"
+                );
 
-                        for meaning in &word_info.meanings {
-                            assert!(
-                                !meaning.pos.is_empty(),
-                                "Part of speech should not be empty"
-                            );
-                            assert!(
-                                !meaning.definition.is_empty(),
-                                "Definition should not be empty"
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        println!("❌ Test failed!");
-                        println!("Error: {}", e);
-                        panic!("Test failed: {}", e);
-                    }
+                for meaning in &word_info.meanings {
+                    assert!(
+                        !meaning.pos.is_empty(),
+                        "Part of speech should not be empty"
+                    );
+                    assert!(
+                        !meaning.definition.is_empty(),
+                        "Definition should not be empty"
+                    );
                 }
             }
-            Err(e) => {
-                println!("❌ Failed to initialize service");
-                println!("Error: {}", e);
-                panic!("Failed to initialize service: {}", e);
-            }
+            Err(e) => panic!("Test failed: {}", e),
         }
-        println!("=== Test completed ===\n");
     }
 }
@@ -0,0 +1,351 @@
+use crate::infrastructure::llm::interface::{ChatMessage, ChatStream, LLMService};
+use crate::infrastructure::llm::prompts::{
+    prompt_registry, registry, EXAMPLE_SENTENCES_PROMPT, GRADE_EXAMPLE_SENTENCES_PROMPT,
+    PHONETICS_PROMPT, UNIT_MEANING_PROMPT, WORD_EXPLAIN_PROMPT, WORD_INFO_PROMPT,
+};
+use crate::infrastructure::llm::provider::LLMConfig;
+use crate::infrastructure::llm::usage::{self, TokenUsage};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use futures::stream;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// 默认的 fixture 目录
+const DEFAULT_FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/llm");
+/// 找不到单词对应的 fixture 时使用的文件名
+const DEFAULT_FIXTURE: &str = "default";
+/// 回复中会被替换为单词的占位符
+const WORD_PLACEHOLDER: &str = "{word}";
+/// 估算 token 数时每个 token 对应的字符数
+const CHARS_PER_TOKEN: usize = 4;
+
+/// 提示词类型，对应 fixture 目录下的子目录
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PromptKind {
+    Phonetics,
+    ExampleSentences,
+    GradeExampleSentences,
+    WordInfo,
+    UnitMeaning,
+    Explain,
+}
+
+impl PromptKind {
+    const ALL: [PromptKind; 6] = [
+        PromptKind::Phonetics,
+        PromptKind::ExampleSentences,
+        PromptKind::GradeExampleSentences,
+        PromptKind::WordInfo,
+        PromptKind::UnitMeaning,
+        PromptKind::Explain,
+    ];
+
+    fn dir_name(&self) -> &'static str {
+        match self {
+            PromptKind::Phonetics => "phonetics",
+            // 年级例句与普通例句共用 fixture
+            PromptKind::ExampleSentences | PromptKind::GradeExampleSentences => "example_sentences",
+            PromptKind::WordInfo => "word_info",
+            PromptKind::UnitMeaning => "unit_meaning",
+            PromptKind::Explain => "explain",
+        }
+    }
+
+    fn prompt_name(&self) -> &'static str {
+        match self {
+            PromptKind::Phonetics => PHONETICS_PROMPT,
+            PromptKind::ExampleSentences => EXAMPLE_SENTENCES_PROMPT,
+            PromptKind::GradeExampleSentences => GRADE_EXAMPLE_SENTENCES_PROMPT,
+            PromptKind::WordInfo => WORD_INFO_PROMPT,
+            PromptKind::UnitMeaning => UNIT_MEANING_PROMPT,
+            PromptKind::Explain => WORD_EXPLAIN_PROMPT,
+        }
+    }
+
+    /// 用当前启用的提示词模板反推调用类型和单词
+    fn detect(user_prompt: &str) -> Option<(PromptKind, String)> {
+        Self::ALL.into_iter().find_map(|kind| {
+            let template = prompt_registry().get(kind.prompt_name(), None).ok()?;
+            let mut vars = registry::match_template(&template.user_prompt, user_prompt)?;
+            Some((kind, vars.remove("word")?))
+        })
+    }
+}
+
+/// 一个 fixture 文件的内容
+///
+/// `replies` 按尝试次数依次返回（修复重试时使用下一条），超出后重复最后一条；
+/// 字符串原样返回，可用于模拟格式错误的 JSON
+#[derive(Debug, Deserialize)]
+struct Fixture {
+    #[serde(default)]
+    latency_ms: u64,
+    #[serde(default)]
+    status: Option<u16>,
+    #[serde(default)]
+    replies: Vec<Value>,
+}
+
+impl Fixture {
+    fn reply(&self, attempt: usize, word: &str) -> String {
+        let reply = match self.replies.get(attempt).or(self.replies.last()) {
+            Some(Value::String(text)) => text.clone(),
+            Some(value) => value.to_string(),
+            None => String::new(),
+        };
+        reply.replace(WORD_PLACEHOLDER, word)
+    }
+}
+
+/// 离线模拟服务：按 “提示词类型 + 单词” 读取 fixture 目录中的预设回复，
+/// 支持模拟延迟、格式错误的输出和错误状态码
+pub struct MockServiceImpl {
+    fixture_dir: PathBuf,
+    model_name: String,
+}
+
+impl MockServiceImpl {
+    pub fn new() -> Self {
+        Self {
+            fixture_dir: PathBuf::from(DEFAULT_FIXTURE_DIR),
+            model_name: "mock".to_string(),
+        }
+    }
+
+    /// 单词转为文件名，非字母数字字符统一替换为下划线
+    fn fixture_key(word: &str) -> String {
+        word.trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    fn load_fixture(&self, kind: PromptKind, word: &str) -> Result<Fixture> {
+        let dir = self.fixture_dir.join(kind.dir_name());
+        let path = [Self::fixture_key(word), DEFAULT_FIXTURE.to_string()]
+            .iter()
+            .map(|name| dir.join(format!("{}.json", name)))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                anyhow!(
+                    "No mock fixture for {} '{}' in {}",
+                    kind.dir_name(),
+                    word,
+                    dir.display()
+                )
+            })?;
+        Self::read_fixture(&path)
+    }
+
+    fn read_fixture(path: &Path) -> Result<Fixture> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mock fixture {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid mock fixture {}", path.display()))
+    }
+
+    /// 模拟一次请求：等待设定的延迟，非 2xx 状态码按请求失败处理
+    async fn respond(&self, user_prompt: &str, attempt: usize) -> Result<String> {
+        let (kind, word) = PromptKind::detect(user_prompt)
+            .ok_or_else(|| anyhow!("Mock service does not recognize prompt: {}", user_prompt))?;
+        let fixture = self.load_fixture(kind, &word)?;
+        debug!(
+            "mock {} response for '{}' (attempt {})",
+            kind.dir_name(),
+            word,
+            attempt + 1
+        );
+
+        if fixture.latency_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(fixture.latency_ms)).await;
+        }
+
+        let reply = fixture.reply(attempt, &word);
+        match fixture.status {
+            Some(status) if !(200..300).contains(&status) => Err(anyhow!(
+                "Non-success status code: {}, body: {}",
+                status,
+                reply
+            )),
+            _ => Ok(reply),
+        }
+    }
+}
+
+#[async_trait]
+impl LLMService for MockServiceImpl {
+    fn configure(&mut self, config: &LLMConfig) -> Result<()> {
+        if let Some(dir) = &config.base_url {
+            self.fixture_dir = PathBuf::from(dir);
+        }
+        if let Some(model_name) = &config.model_name {
+            self.model_name = model_name.clone();
+        }
+        if !self.fixture_dir.is_dir() {
+            return Err(anyhow!(
+                "Mock fixture directory not found: {}",
+                self.fixture_dir.display()
+            ));
+        }
+        Ok(())
+    }
+
+    async fn chat(&self, messages: &[ChatMessage], _temperature: f32) -> Result<String> {
+        let user_prompt = messages
+            .iter()
+            .find(|message| message.role == "user")
+            .ok_or_else(|| anyhow!("No user message in mock chat request"))?;
+        // 每次修复重试都会追加一条 assistant 消息
+        let attempt = messages
+            .iter()
+            .filter(|message| message.role == "assistant")
+            .count();
+        let started = Instant::now();
+        let reply = self.respond(&user_prompt.content, attempt).await?;
+
+        // 没有真实的 usage，按字符数估算以便离线验证用量统计
+        let prompt_chars: usize = messages.iter().map(|m| m.content.chars().count()).sum();
+        let estimate = |chars: usize| chars.div_ceil(CHARS_PER_TOKEN) as i32;
+        usage::record(
+            "mock",
+            &self.model_name,
+            Some(TokenUsage {
+                prompt_tokens: estimate(prompt_chars),
+                completion_tokens: estimate(reply.chars().count()),
+            }),
+            started.elapsed(),
+        );
+        Ok(reply)
+    }
+
+    async fn chat_stream(&self, system_prompt: &str, user_prompt: &str) -> Result<ChatStream> {
+        let started = Instant::now();
+        let reply = self.respond(user_prompt, 0).await?;
+        let prompt_chars = system_prompt.chars().count() + user_prompt.chars().count();
+        let estimate = |chars: usize| chars.div_ceil(CHARS_PER_TOKEN) as i32;
+        usage::record(
+            "mock",
+            &self.model_name,
+            Some(TokenUsage {
+                prompt_tokens: estimate(prompt_chars),
+                completion_tokens: estimate(reply.chars().count()),
+            }),
+            started.elapsed(),
+        );
+        let deltas: Vec<Result<String>> = reply
+            .split_inclusive(char::is_whitespace)
+            .map(|delta| Ok(delta.to_string()))
+            .collect();
+        Ok(Box::pin(stream::iter(deltas)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::llm::prompts::{GradeProfile, BUILTIN_VERSION};
+    use crate::infrastructure::llm::provider::LLMProvider;
+    use futures::StreamExt;
+    use std::time::Instant;
+
+    fn service() -> MockServiceImpl {
+        let mut service = MockServiceImpl::new();
+        service
+            .configure(&LLMConfig::new(LLMProvider::Mock))
+            .unwrap();
+        service
+    }
+
+    fn render(name: &str, word: &str) -> String {
+        prompt_registry()
+            .render(name, &[("word", word), ("count", "2")])
+            .unwrap()
+            .user_prompt
+    }
+
+    #[test]
+    fn test_detect_prompt_kind() {
+        let (kind, word) =
+            PromptKind::detect(&render(EXAMPLE_SENTENCES_PROMPT, "ice cream")).unwrap();
+        assert_eq!(kind, PromptKind::ExampleSentences);
+        assert_eq!(word, "ice cream");
+
+        let (kind, word) = PromptKind::detect(&render(WORD_INFO_PROMPT, "don't")).unwrap();
+        assert_eq!(kind, PromptKind::WordInfo);
+        assert_eq!(word, "don't");
+
+        assert!(PromptKind::detect("hello").is_none());
+        assert_eq!(MockServiceImpl::fixture_key(" Ice Cream "), "ice_cream");
+        assert_eq!(MockServiceImpl::fixture_key("../etc"), "___etc");
+    }
+
+    #[test]
+    fn test_configure_requires_fixture_dir() {
+        let mut config = LLMConfig::new(LLMProvider::Mock);
+        config.with_base_url("/path/does/not/exist");
+        assert!(MockServiceImpl::new().configure(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_word_fixture_and_default_fallback() {
+        let service = service();
+
+        let (us, uk) = service.get_phonetics("hello").await.unwrap();
+        assert_eq!(us, "həˈloʊ");
+        assert_eq!(uk, "həˈləʊ");
+
+        let (info, version) = service.get_word_info("banana").await.unwrap();
+        assert_eq!(version, BUILTIN_VERSION);
+        assert_eq!(info.meanings[0].definition, "banana 的释义");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_reply_is_repaired() {
+        let (examples, _) = service().get_example_sentences("flaky").await.unwrap();
+        assert_eq!(examples.lines().count(), 4);
+        assert!(examples.starts_with("The flaky"));
+
+        let profile = GradeProfile::for_grade(3, None).unwrap();
+        let (examples, _) = service()
+            .get_grade_example_sentences("ice cream", &profile)
+            .await
+            .unwrap();
+        assert!(examples.contains("ice cream"), "{}", examples);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_and_latency() {
+        let service = service();
+
+        let error = service.get_word_info("unavailable").await.unwrap_err();
+        assert!(error.to_string().contains("503"), "{}", error);
+
+        let start = Instant::now();
+        service.get_word_info("slow").await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_explain_stream() {
+        let deltas: Vec<String> = service()
+            .explain_word_stream("hello")
+            .await
+            .unwrap()
+            .map(|delta| delta.unwrap())
+            .collect()
+            .await;
+        assert!(deltas.len() > 1);
+        assert!(deltas.concat().contains("**hello**"));
+    }
+}
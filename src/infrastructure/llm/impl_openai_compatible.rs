@@ -0,0 +1,213 @@
+use crate::infrastructure::llm::interface::{ChatMessage, ChatStream, LLMService};
+use crate::infrastructure::llm::provider::{LLMConfig, ModelParams};
+use crate::infrastructure::llm::stream;
+use crate::infrastructure::llm::usage::{self, TokenUsage};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+const CHAT_COMPLETIONS_PATH: &str = "/chat/completions";
+
+/// 通用的 OpenAI 兼容服务，适用于任何提供 `/v1/chat/completions` 接口的厂商
+pub struct OpenAICompatibleServiceImpl {
+    client: Client,
+    api_key: Option<String>,
+    api_url: String,
+    model_name: String,
+    timeout: u64,
+    params: ModelParams,
+}
+
+impl OpenAICompatibleServiceImpl {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            api_key: None,
+            api_url: String::new(),
+            model_name: String::new(),
+            timeout: 30,
+            params: ModelParams::default(),
+        }
+    }
+
+    /// 补全接口地址，允许配置为 `https://host/v1` 或完整的 `https://host/v1/chat/completions`
+    pub(super) fn normalize_api_url(base_url: &str) -> String {
+        let base_url = base_url.trim().trim_end_matches('/');
+        if base_url.ends_with(CHAT_COMPLETIONS_PATH) {
+            base_url.to_string()
+        } else {
+            format!("{}{}", base_url, CHAT_COMPLETIONS_PATH)
+        }
+    }
+
+    /// 构造请求体，配置中的采样参数优先于调用方给出的默认温度
+    fn build_body(&self, messages: &[ChatMessage], temperature: f32) -> Value {
+        let mut body = json!({
+            "model": self.model_name,
+            "messages": messages,
+            "temperature": self.params.temperature.unwrap_or(temperature)
+        });
+        if let Some(top_p) = self.params.top_p {
+            body["top_p"] = json!(top_p);
+        }
+        if let Some(max_tokens) = self.params.max_tokens {
+            body["max_tokens"] = json!(max_tokens);
+        }
+        body
+    }
+}
+
+#[async_trait]
+impl LLMService for OpenAICompatibleServiceImpl {
+    fn configure(&mut self, config: &LLMConfig) -> Result<()> {
+        let base_url = config
+            .base_url
+            .as_deref()
+            .ok_or_else(|| anyhow!("Base URL is required for OpenAI compatible service"))?;
+        self.api_url = Self::normalize_api_url(base_url);
+
+        self.model_name = config
+            .model_name
+            .clone()
+            .ok_or_else(|| anyhow!("Model name is required for OpenAI compatible service"))?;
+
+        // 本地部署的模型（如 Ollama）可以不需要 API key
+        self.api_key = config.api_key.clone().filter(|key| !key.is_empty());
+
+        if let Some(timeout) = config.timeout {
+            self.timeout = timeout;
+        }
+        self.client = Client::builder()
+            .connect_timeout(Duration::from_secs(self.timeout))
+            .build()?;
+
+        self.params = config.params.clone();
+        Ok(())
+    }
+
+    async fn chat(&self, messages: &[ChatMessage], temperature: f32) -> Result<String> {
+        let body = self.build_body(messages, temperature);
+
+        let started = Instant::now();
+        let mut request = self
+            .client
+            .post(&self.api_url)
+            .timeout(Duration::from_secs(self.timeout));
+        if let Some(api_key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let response = request
+            .header("Content-Type", "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to {}", self.api_url))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Non-success status code: {}, body: {}", status, text));
+        }
+
+        let response_body: Value = serde_json::from_str(&response.text().await?)?;
+        let content = response_body["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|content| content.to_string())
+            .ok_or_else(|| anyhow!("Failed to extract content from AI response"))?;
+        usage::record(
+            "openai-compatible",
+            &self.model_name,
+            TokenUsage::from_response(&response_body),
+            started.elapsed(),
+        );
+        Ok(content)
+    }
+
+    async fn chat_stream(&self, system_prompt: &str, user_prompt: &str) -> Result<ChatStream> {
+        let messages = [
+            ChatMessage::system(system_prompt),
+            ChatMessage::user(user_prompt),
+        ];
+        let body = self.build_body(&messages, 0.7);
+        stream::post_chat_stream(
+            &self.client,
+            &self.api_url,
+            self.api_key.as_deref(),
+            "openai-compatible",
+            body,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::llm::provider::LLMProvider;
+
+    fn messages() -> [ChatMessage; 2] {
+        [ChatMessage::system("system"), ChatMessage::user("user")]
+    }
+
+    #[test]
+    fn test_normalize_api_url() {
+        assert_eq!(
+            OpenAICompatibleServiceImpl::normalize_api_url("http://localhost:11434/v1/"),
+            "http://localhost:11434/v1/chat/completions"
+        );
+        assert_eq!(
+            OpenAICompatibleServiceImpl::normalize_api_url(
+                "https://api.moonshot.cn/v1/chat/completions"
+            ),
+            "https://api.moonshot.cn/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_configure_honours_config() {
+        let mut config = LLMConfig::new(LLMProvider::OpenAICompatible);
+        config
+            .with_base_url("https://dashscope.aliyuncs.com/compatible-mode/v1")
+            .with_model("qwen-plus")
+            .with_timeout(60)
+            .with_top_p(0.9)
+            .with_max_tokens(512);
+
+        let mut service = OpenAICompatibleServiceImpl::new();
+        service.configure(&config).unwrap();
+
+        assert_eq!(
+            service.api_url,
+            "https://dashscope.aliyuncs.com/compatible-mode/v1/chat/completions"
+        );
+        assert_eq!(service.model_name, "qwen-plus");
+        assert_eq!(service.timeout, 60);
+        assert!(service.api_key.is_none());
+
+        let body = service.build_body(&messages(), 0.3);
+        assert_eq!(body["model"], "qwen-plus");
+        assert_eq!(body["messages"][1]["role"], "user");
+        assert_eq!(body["temperature"].as_f64().unwrap() as f32, 0.3);
+        assert_eq!(body["top_p"].as_f64().unwrap() as f32, 0.9);
+        assert_eq!(body["max_tokens"], 512);
+    }
+
+    #[test]
+    fn test_configure_requires_base_url_and_model() {
+        let mut service = OpenAICompatibleServiceImpl::new();
+        let mut config = LLMConfig::new(LLMProvider::OpenAICompatible);
+        assert!(service.configure(&config).is_err());
+
+        config.with_base_url("http://localhost:8000/v1");
+        assert!(service.configure(&config).is_err());
+
+        config.with_model("Qwen2.5-7B-Instruct").with_temperature(0.1);
+        service.configure(&config).unwrap();
+        let body = service.build_body(&messages(), 0.7);
+        assert_eq!(body["temperature"].as_f64().unwrap() as f32, 0.1);
+        assert!(body.get("top_p").is_none());
+    }
+}
@@ -1,18 +1,18 @@
-use crate::infrastructure::dto::WordInfo;
-use crate::infrastructure::llm::interface::LLMService;
-use crate::infrastructure::llm::prompts::LanguagePrompts;
+use crate::infrastructure::llm::interface::{ChatMessage, ChatStream, LLMService};
 use crate::infrastructure::llm::provider::LLMConfig;
-use crate::infrastructure::llm::utils;
+use crate::infrastructure::llm::stream;
+use crate::infrastructure::llm::usage::{self, TokenUsage};
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::{json, Value};
-use tracing::debug;
+use std::time::Instant;
 
 pub struct YiServiceImpl {
     client: Client,
     api_key: String,
     api_url: String,
+    model_name: String,
     timeout: u64,
 }
 
@@ -22,9 +22,18 @@ impl YiServiceImpl {
             client: Client::new(),
             api_key: String::new(),
             api_url: "https://api.lingyiwanwu.com/v1/chat/completions".to_string(),
+            model_name: "yi-lightning".to_string(),
             timeout: 30,
         }
     }
+
+    fn build_body(&self, messages: &[ChatMessage], temperature: f32) -> Value {
+        json!({
+            "model": self.model_name,
+            "messages": messages,
+            "temperature": temperature
+        })
+    }
 }
 
 #[async_trait]
@@ -40,6 +49,10 @@ impl LLMService for YiServiceImpl {
             self.api_url = base_url.clone();
         }
 
+        if let Some(model_name) = &config.model_name {
+            self.model_name = model_name.clone();
+        }
+
         if let Some(timeout) = config.timeout {
             self.timeout = timeout;
         }
@@ -47,145 +60,96 @@ impl LLMService for YiServiceImpl {
         Ok(())
     }
 
-    async fn get_phonetics(&self, word: &str) -> Result<(String, String)> {
+    async fn chat(&self, messages: &[ChatMessage], temperature: f32) -> Result<String> {
+        let started = Instant::now();
         let response = self
             .client
             .post(&self.api_url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
-            .body(
-                json!({
-                    "model": "yi-lightning",
-                    "messages": [
-                        {"role": "system", "content": LanguagePrompts::PHONETICS_SYSTEM},
-                        {"role": "user", "content": LanguagePrompts::phonetics_user(word)}
-                    ],
-                    "temperature": 0.3
-                })
-                .to_string(),
-            )
+            .body(self.build_body(messages, temperature).to_string())
             .send()
             .await?;
 
-        let response_body: Value = serde_json::from_str(&response.text().await?)?;
-        let content = response_body["choices"][0]["message"]["content"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Failed to extract content from AI response"))?;
-
-        utils::extract_phonetics(content)
-    }
-
-    async fn get_example_sentences(&self, word: &str) -> Result<String> {
-        let response = self
-            .client
-            .post(&self.api_url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .body(
-                json!({
-                    "model": "yi-lightning",
-                    "messages": [
-                        {"role": "system", "content": LanguagePrompts::SENTENCES_SYSTEM},
-                        {"role": "user", "content": LanguagePrompts::example_sentences_user(word)}
-                    ],
-                    "temperature": 0.7
-                })
-                .to_string(),
-            )
-            .send()
-            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Non-success status code: {}, body: {}", status, text));
+        }
 
         let response_body: Value = serde_json::from_str(&response.text().await?)?;
         let content = response_body["choices"][0]["message"]["content"]
             .as_str()
+            .map(|content| content.to_string())
             .ok_or_else(|| anyhow!("Failed to extract content from AI response"))?;
-        let cleaned_content = utils::clean_json_response(content);
-        debug!("example sentences from AI response : {}", cleaned_content);
-        utils::extract_example_sentences(cleaned_content.as_str())
+        usage::record(
+            "yi",
+            &self.model_name,
+            TokenUsage::from_response(&response_body),
+            started.elapsed(),
+        );
+        Ok(content)
     }
 
-    async fn get_word_info(&self, word: &str) -> Result<WordInfo> {
-        let response = self
-            .client
-            .post(&self.api_url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .body(
-                json!({
-                    "model": "yi-lightning",
-                    "messages": [
-                        {"role": "system", "content": LanguagePrompts::WORD_INFO_SYSTEM},
-                        {"role": "user", "content": LanguagePrompts::word_info_user(word)}
-                    ],
-                    "temperature": 0.3
-                })
-                .to_string(),
-            )
-            .send()
-            .await?;
-
-        let response_body: Value = serde_json::from_str(&response.text().await?)?;
-        let content = response_body["choices"][0]["message"]["content"]
-            .as_str()
-            .ok_or_else(|| anyhow!("Failed to extract content from AI response"))?;
-
-        // Clean the response before parsing
-        let cleaned_content = utils::clean_json_response(content);
-        debug!("word info from AI response : {}", cleaned_content);
-        serde_json::from_str::<WordInfo>(&cleaned_content).map_err(|e| {
-            anyhow!(
-                "Failed to parse word info response for '{}': {}.\nResponse: {}",
-                word,
-                e,
-                cleaned_content
-            )
-        })
+    async fn chat_stream(&self, system_prompt: &str, user_prompt: &str) -> Result<ChatStream> {
+        let messages = [
+            ChatMessage::system(system_prompt),
+            ChatMessage::user(user_prompt),
+        ];
+        let body = self.build_body(&messages, 0.7);
+        stream::post_chat_stream(&self.client, &self.api_url, Some(&self.api_key), "yi", body).await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    //! 这些测试会调用真实的零一万物接口，默认忽略；离线测试见 `impl_mock`
+    //! 运行方式：`cargo test impl_yi -- --ignored`（需要 LLM_YI_API_KEY）
     use super::*;
-    use tokio;
+    use crate::infrastructure::llm::provider::LLMProvider;
+    use dotenv::dotenv;
 
-    #[tokio::test]
-    async fn test_get_example_sentences() {
+    fn live_service() -> YiServiceImpl {
+        dotenv().ok();
         let mut service = YiServiceImpl::new();
-        service.configure(
-            &LLMConfig::new(crate::infrastructure::llm::provider::LLMProvider::Yi)
-                .with_api_key(std::env::var("YI_API_KEY").unwrap_or_default()),
-        )?;
+        service
+            .configure(
+                LLMConfig::new(LLMProvider::Yi)
+                    .with_api_key(std::env::var("LLM_YI_API_KEY").unwrap_or_default()),
+            )
+            .unwrap();
+        service
+    }
 
+    fn is_chinese(text: &str) -> bool {
+        text.chars()
+            .any(|c| (c as u32) > 0x4E00 && (c as u32) < 0x9FFF)
+    }
+
+    #[tokio::test]
+    #[ignore = "requires network access and LLM_YI_API_KEY"]
+    async fn test_get_example_sentences() {
+        let service = live_service();
         let word = "happy";
 
         match service.get_example_sentences(word).await {
-            Ok(sentences) => {
-                assert_eq!(sentences.len(), 2, "Should return exactly 2 sentences");
-
-                println!("\nTest results for word '{}':", word);
-                println!("Simple sentence:");
-                println!("English: {}", sentences[0].0);
-                println!("Chinese: {}", sentences[0].1);
-                println!("\nComplex sentence:");
-                println!("English: {}", sentences[1].0);
-                println!("Chinese: {}", sentences[1].1);
+            Ok((examples, _)) => {
+                // 例句格式为 “英文\n中文\n” 交替排列
+                let lines: Vec<&str> = examples.lines().collect();
+                assert_eq!(lines.len(), 4, "Should return exactly 2 sentence pairs");
+                println!("\nTest results for word '{}':\n{}", word, examples);
 
-                for (eng, _) in &sentences {
+                for pair in lines.chunks(2) {
                     assert!(
-                        eng.to_lowercase().contains(&word.to_lowercase()),
+                        pair[0].to_lowercase().contains(&word.to_lowercase()),
                         "Sentence should contain the word '{}': {}",
                         word,
-                        eng
+                        pair[0]
                     );
-                }
-
-                for (_, chn) in &sentences {
-                    assert!(!chn.is_empty(), "Chinese translation should not be empty");
                     assert!(
-                        chn.chars()
-                            .any(|c| (c as u32) > 0x4E00 && (c as u32) < 0x9FFF),
+                        is_chinese(pair[1]),
                         "Translation should contain Chinese characters: {}",
-                        chn
+                        pair[1]
                     );
                 }
             }
@@ -194,13 +158,9 @@ mod tests {
     }
 
     #[tokio::test]
+    #[ignore = "requires network access and LLM_YI_API_KEY"]
     async fn test_get_phonetics() {
-        let mut service = YiServiceImpl::new();
-        service.configure(
-            &LLMConfig::new(crate::infrastructure::llm::provider::LLMProvider::Yi)
-                .with_api_key(std::env::var("YI_API_KEY").unwrap_or_default()),
-        )?;
-
+        let service = live_service();
         let word = "hello";
 
         match service.get_phonetics(word).await {
@@ -212,14 +172,15 @@ mod tests {
                 assert!(!us.is_empty(), "US phonetic should not be empty");
                 assert!(!uk.is_empty(), "UK phonetic should not be empty");
 
+                // 音标会去掉 /.../ 或 [...] 包裹，由调用方统一添加
                 assert!(
-                    us.contains('/') || us.contains('['),
-                    "US phonetic should contain phonetic symbols: {}",
+                    !us.contains('/') && !us.contains('['),
+                    "US phonetic should not be wrapped: {}",
                     us
                 );
                 assert!(
-                    uk.contains('/') || uk.contains('['),
-                    "UK phonetic should contain phonetic symbols: {}",
+                    !uk.contains('/') && !uk.contains('['),
+                    "UK phonetic should not be wrapped: {}",
                     uk
                 );
             }
@@ -228,30 +189,20 @@ mod tests {
     }
 
     #[tokio::test]
+    #[ignore = "requires network access and LLM_YI_API_KEY"]
     async fn test_get_word_info() {
-        println!("\n=== Testing get_word_info ===");
-
-        let mut service = YiServiceImpl::new();
-        service.configure(
-            &LLMConfig::new(crate::infrastructure::llm::provider::LLMProvider::Yi)
-                .with_api_key(std::env::var("YI_API_KEY").unwrap_or_default()),
-        )?;
-
+        let service = live_service();
         let word = "name"; // 测试一个既可以作为名词也可以作为动词的词
 
-        println!("Testing word: {}", word);
         match service.get_word_info(word).await {
-            Ok(word_info) => {
+            Ok((word_info, _)) => {
                 println!("\n=== Word Info Results ===");
-                println!("Phonetics:");
                 println!("  US: {}", word_info.us_phonetic);
                 println!("  UK: {}", word_info.uk_phonetic);
-                println!("\nMeanings:");
                 for (i, meaning) in word_info.meanings.iter().enumerate() {
                     println!("{}. {} - {}", i + 1, meaning.pos, meaning.definition);
                 }
 
-                // Basic validations
                 assert!(
                     !word_info.us_phonetic.is_empty(),
                     "US phonetic should not be empty"
@@ -265,32 +216,19 @@ mod tests {
                     "Should have at least one meaning"
                 );
 
-                // Validate meanings
                 for meaning in &word_info.meanings {
                     assert!(
                         !meaning.pos.is_empty(),
                         "Part of speech should not be empty"
                     );
                     assert!(
-                        !meaning.definition.is_empty(),
-                        "Definition should not be empty"
-                    );
-                    assert!(
-                        meaning
-                            .definition
-                            .chars()
-                            .any(|c| (c as u32) > 0x4E00 && (c as u32) < 0x9FFF),
+                        is_chinese(&meaning.definition),
                         "Definition should contain Chinese characters: {}",
                         meaning.definition
                     );
                 }
-
-                println!("\n✅ Test passed successfully!");
-            }
-            Err(e) => {
-                println!("❌ Test failed!");
-                panic!("Test failed: {}", e);
             }
+            Err(e) => panic!("Test failed: {}", e),
         }
     }
 }
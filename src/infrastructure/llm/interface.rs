@@ -1,13 +1,179 @@
-use crate::infrastructure::dto::WordInfo;
+use crate::infrastructure::dto::{WordInfo, WordMeaning};
+use crate::infrastructure::llm::prompts::{
+    prompt_registry, GradeProfile, LanguagePrompts, UnitContext, EXAMPLE_SENTENCES_PROMPT,
+    GRADE_EXAMPLE_SENTENCES_PROMPT, PHONETICS_PROMPT, UNIT_MEANING_PROMPT, WORD_EXPLAIN_PROMPT,
+    WORD_INFO_PROMPT,
+};
 use crate::infrastructure::llm::provider::LLMConfig;
+use crate::infrastructure::llm::structured::{self, JsonTask};
+use crate::infrastructure::llm::utils;
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::Stream;
+use serde::Serialize;
+use std::pin::Pin;
+
+/// 流式对话输出，每一项为模型返回的增量文本
+pub type ChatStream = Pin<Box<dyn Stream<Item = Result<String>> + Send>>;
+
+/// 对话中的一条消息，role 为 system / user / assistant
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: "system".to_string(),
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: "assistant".to_string(),
+            content: content.into(),
+        }
+    }
+}
 
 #[async_trait]
 pub trait LLMService: Send + Sync {
     fn configure(&mut self, config: &LLMConfig) -> Result<()>;
 
-    async fn get_phonetics(&self, word: &str) -> Result<(String, String)>;
-    async fn get_example_sentences(&self, word: &str) -> Result<String>;
-    async fn get_word_info(&self, word: &str) -> Result<WordInfo>;
+    /// 多轮对话，返回模型输出的完整文本
+    async fn chat(&self, messages: &[ChatMessage], temperature: f32) -> Result<String>;
+
+    /// 流式对话，返回模型逐步生成的增量文本
+    async fn chat_stream(&self, system_prompt: &str, user_prompt: &str) -> Result<ChatStream>;
+
+    /// 单独查询美式和英式音标，用于词典或单词信息缺少音标时补全
+    async fn get_phonetics(&self, word: &str) -> Result<(String, String)> {
+        let prompt = prompt_registry().render(PHONETICS_PROMPT, &[("word", word)])?;
+        let value = structured::complete_json(
+            self,
+            JsonTask {
+                name: "phonetics",
+                subject: word,
+                system_prompt: &prompt.system_prompt,
+                user_prompt: prompt.user_prompt,
+                schema: LanguagePrompts::phonetics_schema(),
+                temperature: 0.3,
+            },
+        )
+        .await?;
+        utils::extract_phonetics(value)
+    }
+
+    /// 生成例句，同时返回渲染时使用的提示词版本
+    async fn get_example_sentences(&self, word: &str) -> Result<(String, i32)> {
+        let count = utils::MAX_EXAMPLE_SENTENCES.to_string();
+        let prompt = prompt_registry().render(
+            EXAMPLE_SENTENCES_PROMPT,
+            &[("word", word), ("count", count.as_str())],
+        )?;
+        let value = structured::complete_json(
+            self,
+            JsonTask {
+                name: "example_sentences",
+                subject: word,
+                system_prompt: &prompt.system_prompt,
+                user_prompt: prompt.user_prompt,
+                schema: LanguagePrompts::example_sentences_schema(),
+                temperature: 0.7,
+            },
+        )
+        .await?;
+        Ok((utils::extract_example_sentences(value)?, prompt.version))
+    }
+
+    /// 按年级控制词汇、句长和话题生成例句，同时返回渲染时使用的提示词版本
+    async fn get_grade_example_sentences(
+        &self,
+        word: &str,
+        profile: &GradeProfile,
+    ) -> Result<(String, i32)> {
+        let count = utils::MAX_EXAMPLE_SENTENCES.to_string();
+        let grade_vars = profile.variables();
+        let mut vars = vec![("word", word), ("count", count.as_str())];
+        vars.extend(grade_vars.iter().map(|(key, value)| (*key, value.as_str())));
+        let prompt = prompt_registry().render(GRADE_EXAMPLE_SENTENCES_PROMPT, &vars)?;
+        let value = structured::complete_json(
+            self,
+            JsonTask {
+                name: "grade_example_sentences",
+                subject: word,
+                system_prompt: &prompt.system_prompt,
+                user_prompt: prompt.user_prompt,
+                schema: LanguagePrompts::example_sentences_schema(),
+                temperature: 0.7,
+            },
+        )
+        .await?;
+        Ok((utils::extract_example_sentences(value)?, prompt.version))
+    }
+
+    /// 查询音标与释义，同时返回渲染时使用的提示词版本
+    async fn get_word_info(&self, word: &str) -> Result<(WordInfo, i32)> {
+        let prompt = prompt_registry().render(WORD_INFO_PROMPT, &[("word", word)])?;
+        let value = structured::complete_json(
+            self,
+            JsonTask {
+                name: "word_info",
+                subject: word,
+                system_prompt: &prompt.system_prompt,
+                user_prompt: prompt.user_prompt,
+                schema: LanguagePrompts::word_info_schema(),
+                temperature: 0.3,
+            },
+        )
+        .await?;
+        Ok((utils::extract_word_info(value)?, prompt.version))
+    }
+
+    /// 根据单元上下文从候选义项中选出最合适的一项，返回其下标
+    async fn pick_unit_meaning(
+        &self,
+        word: &str,
+        context: &UnitContext,
+        senses: &[WordMeaning],
+    ) -> Result<usize> {
+        let context_vars = context.variables(senses);
+        let mut vars = vec![("word", word)];
+        vars.extend(
+            context_vars
+                .iter()
+                .map(|(key, value)| (*key, value.as_str())),
+        );
+        let prompt = prompt_registry().render(UNIT_MEANING_PROMPT, &vars)?;
+        let value = structured::complete_json(
+            self,
+            JsonTask {
+                name: "unit_meaning",
+                subject: word,
+                system_prompt: &prompt.system_prompt,
+                user_prompt: prompt.user_prompt,
+                schema: LanguagePrompts::unit_meaning_schema(),
+                temperature: 0.2,
+            },
+        )
+        .await?;
+        utils::extract_sense_index(value, senses.len())
+    }
+
+    /// 流式生成单词讲解
+    async fn explain_word_stream(&self, word: &str) -> Result<ChatStream> {
+        let prompt = prompt_registry().render(WORD_EXPLAIN_PROMPT, &[("word", word)])?;
+        self.chat_stream(&prompt.system_prompt, &prompt.user_prompt)
+            .await
+    }
 }
@@ -1,21 +1,91 @@
+use crate::infrastructure::llm::circuit_breaker::{BreakerConfig, BreakerSnapshot, CircuitBreaker};
 use crate::infrastructure::llm::factory::LLMServiceFactoryTrait;
+use crate::infrastructure::llm::structured::StructuredAttempt;
+use crate::infrastructure::llm::usage;
 use crate::infrastructure::llm::LLMService;
-use anyhow::Result;
-use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::env;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+
+/// 保留的最近故障转移事件数量
+const MAX_FAILOVER_EVENTS: usize = 100;
+
+/// 一次故障转移事件
+#[derive(Debug, Clone, Serialize)]
+pub struct FailoverEvent {
+    pub provider: String,
+    pub next_provider: Option<String>,
+    pub operation: String,
+    pub reason: String,
+    pub occurred_at: String,
+}
+
+/// 单个模型服务的健康状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderHealth {
+    pub provider: String,
+    #[serde(flatten)]
+    pub breaker: BreakerSnapshot,
+}
+
+/// 模型服务整体健康报告
+#[derive(Debug, Clone, Serialize)]
+pub struct LLMHealthReport {
+    pub fallback_chain: Vec<String>,
+    pub providers: Vec<ProviderHealth>,
+    pub recent_failovers: Vec<FailoverEvent>,
+    /// 最近的结构化输出校验与修复记录
+    pub structured_attempts: Vec<StructuredAttempt>,
+}
 
 pub struct LLMManager<F>
 where
     F: LLMServiceFactoryTrait,
 {
     factory: F,
+    fallback_chain: Vec<String>,
+    breaker_config: BreakerConfig,
+    breakers: DashMap<String, Arc<CircuitBreaker>>,
+    failover_events: Mutex<VecDeque<FailoverEvent>>,
 }
 
 impl<F> LLMManager<F>
 where
     F: LLMServiceFactoryTrait,
 {
+    /// 从环境变量读取降级链（LLM_FALLBACK_CHAIN=deepseek,yi,ollama）和熔断配置
     pub fn new(factory: F) -> Self {
-        LLMManager { factory }
+        let fallback_chain = env::var("LLM_FALLBACK_CHAIN")
+            .map(|chain| {
+                chain
+                    .split(',')
+                    .map(|name| name.trim().to_lowercase())
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self::with_config(factory, fallback_chain, BreakerConfig::from_env())
+    }
+
+    pub fn with_config(
+        factory: F,
+        fallback_chain: Vec<String>,
+        breaker_config: BreakerConfig,
+    ) -> Self {
+        LLMManager {
+            factory,
+            fallback_chain,
+            breaker_config,
+            breakers: DashMap::new(),
+            failover_events: Mutex::new(VecDeque::new()),
+        }
     }
 
     pub fn get_llm_service(
@@ -24,4 +94,260 @@ where
     ) -> Result<Arc<dyn LLMService + Send + Sync>> {
         self.factory.create_from_name(provider_name)
     }
+
+    /// 按 “首选模型 + 降级链” 的顺序依次尝试调用，跳过已熔断的模型
+    pub async fn execute<T, Op, Fut>(&self, primary: &str, operation: &str, op: Op) -> Result<T>
+    where
+        Op: Fn(Arc<dyn LLMService + Send + Sync>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let chain = self.resolve_chain(primary);
+        let mut errors = Vec::new();
+
+        for (index, provider) in chain.iter().enumerate() {
+            let next_provider = chain.get(index + 1).cloned();
+            let breaker = self.breaker(provider);
+
+            if !breaker.allow_request() {
+                errors.push(format!("{}: circuit open", provider));
+                self.record_failover(provider, &next_provider, operation, "circuit open");
+                continue;
+            }
+
+            let result = match self.get_llm_service(provider) {
+                Ok(service) => {
+                    let call = usage::scope(provider, operation, op(service));
+                    match tokio::time::timeout(self.breaker_config.call_timeout, call).await {
+                        Ok(result) => result,
+                        Err(_) => Err(anyhow!(
+                            "timed out after {}s",
+                            self.breaker_config.call_timeout.as_secs()
+                        )),
+                    }
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(value) => {
+                    breaker.record_success();
+                    if index > 0 {
+                        info!("LLM operation '{}' served by fallback '{}'", operation, provider);
+                    }
+                    return Ok(value);
+                }
+                Err(e) => {
+                    let reason = e.to_string();
+                    warn!(
+                        "LLM operation '{}' failed on '{}': {}",
+                        operation, provider, reason
+                    );
+                    breaker.record_failure(&reason);
+                    self.record_failover(provider, &next_provider, operation, &reason);
+                    errors.push(format!("{}: {}", provider, reason));
+                }
+            }
+        }
+
+        Err(anyhow!(
+            "All LLM providers failed for '{}': {}",
+            operation,
+            errors.join("; ")
+        ))
+    }
+
+    /// 汇总熔断器状态和最近的故障转移事件，结构化输出记录由调用方从数据库读取后传入
+    pub fn health_report(&self, structured_attempts: Vec<StructuredAttempt>) -> LLMHealthReport {
+        let mut names: Vec<String> = self.fallback_chain.clone();
+        for entry in self.breakers.iter() {
+            if !names.contains(entry.key()) {
+                names.push(entry.key().clone());
+            }
+        }
+
+        let providers = names
+            .into_iter()
+            .map(|provider| {
+                let breaker = self.breaker(&provider).snapshot();
+                ProviderHealth { provider, breaker }
+            })
+            .collect();
+
+        let recent_failovers = self
+            .failover_events
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .cloned()
+            .collect();
+
+        LLMHealthReport {
+            fallback_chain: self.fallback_chain.clone(),
+            providers,
+            recent_failovers,
+            structured_attempts,
+        }
+    }
+
+    fn resolve_chain(&self, primary: &str) -> Vec<String> {
+        let mut chain = vec![primary.trim().to_lowercase()];
+        for provider in &self.fallback_chain {
+            if !chain.contains(provider) {
+                chain.push(provider.clone());
+            }
+        }
+        chain
+    }
+
+    fn breaker(&self, provider: &str) -> Arc<CircuitBreaker> {
+        self.breakers
+            .entry(provider.to_string())
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(self.breaker_config.clone())))
+            .clone()
+    }
+
+    fn record_failover(
+        &self,
+        provider: &str,
+        next_provider: &Option<String>,
+        operation: &str,
+        reason: &str,
+    ) {
+        let event = FailoverEvent {
+            provider: provider.to_string(),
+            next_provider: next_provider.clone(),
+            operation: operation.to_string(),
+            reason: reason.to_string(),
+            occurred_at: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+        };
+
+        let mut events = self.failover_events.lock().unwrap();
+        if events.len() >= MAX_FAILOVER_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::dto::WordInfo;
+    use crate::infrastructure::llm::circuit_breaker::CircuitState;
+    use crate::infrastructure::llm::interface::{ChatMessage, ChatStream};
+    use crate::infrastructure::llm::provider::LLMConfig;
+    use async_trait::async_trait;
+    use std::time::Duration;
+
+    struct StubService {
+        name: String,
+    }
+
+    #[async_trait]
+    impl LLMService for StubService {
+        fn configure(&mut self, _config: &LLMConfig) -> Result<()> {
+            Ok(())
+        }
+
+        async fn chat(&self, _messages: &[ChatMessage], _temperature: f32) -> Result<String> {
+            Err(anyhow!("not supported"))
+        }
+
+        async fn get_example_sentences(&self, _word: &str) -> Result<(String, i32)> {
+            if self.name == "broken" {
+                Err(anyhow!("service unavailable"))
+            } else {
+                Ok((format!("example from {}", self.name), 1))
+            }
+        }
+
+        async fn get_word_info(&self, _word: &str) -> Result<(WordInfo, i32)> {
+            Err(anyhow!("not supported"))
+        }
+
+        async fn chat_stream(&self, _system: &str, _user: &str) -> Result<ChatStream> {
+            Err(anyhow!("not supported"))
+        }
+    }
+
+    struct StubFactory;
+
+    impl LLMServiceFactoryTrait for StubFactory {
+        fn create_from_name(&self, name: &str) -> Result<Arc<dyn LLMService + Send + Sync>> {
+            if name == "missing" {
+                return Err(anyhow!("provider not configured"));
+            }
+            Ok(Arc::new(StubService {
+                name: name.to_string(),
+            }))
+        }
+    }
+
+    fn manager(chain: &[&str]) -> LLMManager<StubFactory> {
+        LLMManager::with_config(
+            StubFactory,
+            chain.iter().map(|name| name.to_string()).collect(),
+            BreakerConfig {
+                failure_threshold: 2,
+                cooldown: Duration::from_secs(60),
+                call_timeout: Duration::from_secs(1),
+            },
+        )
+    }
+
+    async fn example(manager: &LLMManager<StubFactory>, primary: &str) -> Result<String> {
+        manager
+            .execute(primary, "get_example_sentences", |service| async move {
+                service.get_example_sentences("hello").await
+            })
+            .await
+            .map(|(example, _)| example)
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_in_order() {
+        let manager = manager(&["missing", "yi", "local"]);
+
+        let result = example(&manager, "broken").await.unwrap();
+        assert_eq!(result, "example from yi");
+
+        let report = manager.health_report(Vec::new());
+        assert_eq!(report.recent_failovers.len(), 2);
+        assert_eq!(report.recent_failovers[0].provider, "missing");
+        assert_eq!(
+            report.recent_failovers[0].next_provider.as_deref(),
+            Some("yi")
+        );
+        assert_eq!(report.recent_failovers[1].provider, "broken");
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_and_skips_provider() {
+        let manager = manager(&["yi"]);
+
+        example(&manager, "broken").await.unwrap();
+        example(&manager, "broken").await.unwrap();
+        assert_eq!(manager.breaker("broken").snapshot().state, CircuitState::Open);
+
+        example(&manager, "broken").await.unwrap();
+        let report = manager.health_report(Vec::new());
+        let broken = report
+            .providers
+            .iter()
+            .find(|p| p.provider == "broken")
+            .unwrap();
+        assert_eq!(broken.breaker.total_failures, 2);
+        assert_eq!(report.recent_failovers[0].reason, "circuit open");
+    }
+
+    #[tokio::test]
+    async fn test_all_providers_failed() {
+        let manager = manager(&["missing"]);
+        let error = example(&manager, "broken").await.unwrap_err().to_string();
+        assert!(error.contains("broken: service unavailable"));
+        assert!(error.contains("missing: provider not configured"));
+    }
 }
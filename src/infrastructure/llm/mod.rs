@@ -1,14 +1,20 @@
+pub mod circuit_breaker;
 pub mod factory;
 pub mod impl_deepseek;
+pub mod impl_mock;
+pub mod impl_openai_compatible;
 pub mod impl_yi;
 pub mod interface;
 pub mod manager;
 pub mod prompts;
 pub mod provider;
+pub mod stream;
+pub mod structured;
+pub mod usage;
 pub mod utils;
 
 pub use impl_yi::YiServiceImpl;
-pub use interface::LLMService;
+pub use interface::{ChatStream, LLMService};
 
 use crate::infrastructure::llm::factory::LLMServiceFactory;
 use crate::infrastructure::llm::manager::LLMManager;
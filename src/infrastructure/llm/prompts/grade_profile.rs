@@ -0,0 +1,107 @@
+/// 按年级控制例句难度：词汇范围、句子长度和话题
+///
+/// grade_id 对应 grades 表的种子数据：1-6 为小学，7-9 为初中，10-12 为高中
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeProfile {
+    pub grade_id: i32,
+    /// 年级名称，如 “三年级”、“高一”
+    pub grade: String,
+    pub vocabulary: &'static str,
+    pub max_words: u32,
+    pub topics: &'static str,
+}
+
+const GRADE_NAMES: [&str; 12] = [
+    "一年级",
+    "二年级",
+    "三年级",
+    "四年级",
+    "五年级",
+    "六年级",
+    "七年级",
+    "八年级",
+    "九年级",
+    "高一",
+    "高二",
+    "高三",
+];
+
+impl GradeProfile {
+    /// 根据教材的 grade_id 生成难度配置，未知年级返回 None；
+    /// `name` 为教材上冗余保存的年级名称，为空时使用默认名称
+    pub fn for_grade(grade_id: i32, name: Option<&str>) -> Option<Self> {
+        let default_name = GRADE_NAMES.get(usize::try_from(grade_id - 1).ok()?)?;
+        let (vocabulary, max_words, topics) = match grade_id {
+            1..=2 => (
+                "very basic words a young child already knows",
+                6,
+                "family, animals, colours, toys and food",
+            ),
+            3..=4 => (
+                "simple primary school words",
+                8,
+                "school, friends, hobbies and the weather",
+            ),
+            5..=6 => (
+                "common primary school vocabulary",
+                12,
+                "school life, sports, festivals and travel",
+            ),
+            7..=9 => (
+                "junior high school vocabulary",
+                16,
+                "daily life, nature, technology and culture",
+            ),
+            _ => (
+                "senior high school vocabulary",
+                22,
+                "society, science, the environment and history",
+            ),
+        };
+        let grade = name
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(default_name);
+
+        Some(Self {
+            grade_id,
+            grade: grade.to_string(),
+            vocabulary,
+            max_words,
+            topics,
+        })
+    }
+
+    /// 渲染 `grade_example_sentences` 模板时使用的变量，`word` 和 `count` 由调用方补充
+    pub fn variables(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("grade", self.grade.clone()),
+            ("vocabulary", self.vocabulary.to_string()),
+            ("max_words", self.max_words.to_string()),
+            ("topics", self.topics.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_grade() {
+        let first = GradeProfile::for_grade(1, None).unwrap();
+        assert_eq!(first.grade, "一年级");
+        assert_eq!(first.max_words, 6);
+
+        let senior = GradeProfile::for_grade(12, Some("高三")).unwrap();
+        assert_eq!(senior.grade, "高三");
+        assert!(senior.max_words > first.max_words);
+
+        assert_eq!(
+            GradeProfile::for_grade(3, Some(" ")).unwrap().grade,
+            "三年级"
+        );
+        assert!(GradeProfile::for_grade(0, None).is_none());
+        assert!(GradeProfile::for_grade(13, None).is_none());
+    }
+}
@@ -1,3 +1,17 @@
+use crate::domain::models::prompt_template::PromptTemplate;
+use serde_json::{json, Value};
+
+/// 模板名称
+pub const PHONETICS_PROMPT: &str = "phonetics";
+pub const EXAMPLE_SENTENCES_PROMPT: &str = "example_sentences";
+pub const GRADE_EXAMPLE_SENTENCES_PROMPT: &str = "grade_example_sentences";
+pub const WORD_INFO_PROMPT: &str = "word_info";
+pub const WORD_EXPLAIN_PROMPT: &str = "word_explain";
+pub const UNIT_MEANING_PROMPT: &str = "unit_meaning";
+
+/// 内置模板的版本号
+pub const BUILTIN_VERSION: i32 = 0;
+
 pub struct LanguagePrompts;
 
 impl LanguagePrompts {
@@ -41,45 +55,186 @@ impl LanguagePrompts {
         5. 只返回 JSON 数据，不要返回任何额外说明文字。\n\
         6. 确保 JSON 格式正确，所有字段名使用双引号。";
 
-    pub fn phonetics_user(word: &str) -> String {
-        format!(
-            r#"Please provide the International Phonetic Alphabet (IPA) pronunciations for the English word "{}".
+    pub const WORD_EXPLAIN_SYSTEM: &'static str = "\
+        你是一名经验丰富的中小学英语老师，擅长用通俗易懂的中文为学生讲解英语单词。\
+        讲解内容包括：常见词义、常用搭配、易混淆点以及一到两个简单例句（附中文翻译）。\
+        请直接输出讲解内容，使用简洁的 Markdown 格式，不要输出 JSON。";
+
+    pub const UNIT_MEANING_SYSTEM: &'static str = "\
+        你是一名经验丰富的中小学英语老师，需要根据教材和单元的上下文，\
+        从单词的候选释义中选出它在该单元中最可能的含义。只返回 JSON，不要输出任何说明文字。";
+
+    pub const PHONETICS_USER: &'static str = r#"Please provide the International Phonetic Alphabet (IPA) pronunciations for the English word "{word}".
         Return a JSON object with the following structure:
-        {{
+        {
             "us_ipa": "american ipa",
             "uk_ipa": "british ipa"
-        }}
+        }
         Do not include any additional text or explanation.
-        "#,
-            word
-        )
-    }
+        "#;
+
+    pub const SENTENCES_USER: &'static str = r#"Please provide {count} example sentences using the word "{word}".
+        Start with a simple sentence and make the following ones more complex.
+        Return a JSON array with the following structure:
+        [
+            {
+                "english": "English sentence 1",
+                "chinese": "Chinese translation 1"
+            },
+            {
+                "english": "English sentence 2",
+                "chinese": "Chinese translation 2"
+            }
+        ]
+        Do not include any additional text or explanation.
+        "#;
 
-    pub fn example_sentences_user(word: &str) -> String {
-        format!(
-            r#"Please provide two example sentences using the word "{}".
-        One sentence should be simple, and the other should be more complex.
+    pub const GRADE_SENTENCES_USER: &'static str = r#"Please provide {count} example sentences using the word "{word}" for a Chinese student in {grade}.
+        Apart from "{word}", use only {vocabulary}.
+        Keep every sentence under {max_words} words and prefer topics such as {topics}.
         Return a JSON array with the following structure:
         [
-            {{
+            {
                 "english": "English sentence 1",
                 "chinese": "Chinese translation 1"
-            }},
-            {{
+            },
+            {
                 "english": "English sentence 2",
                 "chinese": "Chinese translation 2"
-            }}
+            }
         ]
         Do not include any additional text or explanation.
-        "#,
-            word
-        )
+        "#;
+
+    pub const WORD_INFO_USER: &'static str = "请提供英文单词 '{word}' 的详细信息，按照指定的 JSON 格式返回。记住相同词性的解释要合并在一起，用分号分隔。";
+
+    pub const UNIT_MEANING_USER: &'static str =
+        "教材《{textbook}》的 {unit} 中出现了单词 '{word}'，同单元的其他单词有：{unit_words}。\n\
+        该单词的候选释义如下：\n\
+        {senses}\n\
+        请选出最符合本单元语境的一项，返回 JSON：{\"index\": 候选编号}";
+
+    pub const WORD_EXPLAIN_USER: &'static str = "请为学生讲解英文单词或短语 '{word}'。";
+
+    /// 各模板渲染时可用的变量
+    pub fn variables(name: &str) -> &'static [&'static str] {
+        match name {
+            EXAMPLE_SENTENCES_PROMPT => &["word", "count"],
+            GRADE_EXAMPLE_SENTENCES_PROMPT => &[
+                "word",
+                "count",
+                "grade",
+                "vocabulary",
+                "max_words",
+                "topics",
+            ],
+            UNIT_MEANING_PROMPT => &["word", "textbook", "unit", "unit_words", "senses"],
+            _ => &["word"],
+        }
+    }
+
+    /// 代码内置的模板，版本号为 0，数据库中没有启用的版本时使用
+    pub fn builtin_templates() -> Vec<PromptTemplate> {
+        [
+            (
+                PHONETICS_PROMPT,
+                Self::PHONETICS_SYSTEM,
+                Self::PHONETICS_USER,
+            ),
+            (
+                EXAMPLE_SENTENCES_PROMPT,
+                Self::SENTENCES_SYSTEM,
+                Self::SENTENCES_USER,
+            ),
+            (
+                GRADE_EXAMPLE_SENTENCES_PROMPT,
+                Self::SENTENCES_SYSTEM,
+                Self::GRADE_SENTENCES_USER,
+            ),
+            (
+                WORD_INFO_PROMPT,
+                Self::WORD_INFO_SYSTEM,
+                Self::WORD_INFO_USER,
+            ),
+            (
+                UNIT_MEANING_PROMPT,
+                Self::UNIT_MEANING_SYSTEM,
+                Self::UNIT_MEANING_USER,
+            ),
+            (
+                WORD_EXPLAIN_PROMPT,
+                Self::WORD_EXPLAIN_SYSTEM,
+                Self::WORD_EXPLAIN_USER,
+            ),
+        ]
+        .into_iter()
+        .map(|(name, system, user)| {
+            let mut template = PromptTemplate::new(name, BUILTIN_VERSION, system, user);
+            template.description = Some("built-in".to_string());
+            template
+        })
+        .collect()
+    }
+
+    /// `PHONETICS_USER` 输出需要满足的 JSON Schema
+    pub fn phonetics_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["us_ipa", "uk_ipa"],
+            "properties": {
+                "us_ipa": {"type": "string", "minLength": 1},
+                "uk_ipa": {"type": "string", "minLength": 1}
+            }
+        })
+    }
+
+    /// `SENTENCES_USER` 和 `GRADE_SENTENCES_USER` 输出需要满足的 JSON Schema
+    pub fn example_sentences_schema() -> Value {
+        json!({
+            "type": "array",
+            "minItems": 1,
+            "items": {
+                "type": "object",
+                "required": ["english", "chinese"],
+                "properties": {
+                    "english": {"type": "string", "minLength": 1},
+                    "chinese": {"type": "string", "minLength": 1}
+                }
+            }
+        })
+    }
+
+    /// `WORD_INFO_USER` 输出需要满足的 JSON Schema，找不到音标时允许为空字符串
+    pub fn word_info_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["us_phonetic", "uk_phonetic", "meanings"],
+            "properties": {
+                "us_phonetic": {"type": "string"},
+                "uk_phonetic": {"type": "string"},
+                "meanings": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["pos", "definition"],
+                        "properties": {
+                            "pos": {"type": "string"},
+                            "definition": {"type": "string"}
+                        }
+                    }
+                }
+            }
+        })
     }
 
-    pub fn word_info_user(word: &str) -> String {
-        format!(
-            "请提供英文单词 '{}' 的详细信息，按照指定的 JSON 格式返回。记住相同词性的解释要合并在一起，用分号分隔。",
-            word
-        )
+    /// `UNIT_MEANING_USER` 输出需要满足的 JSON Schema，index 为候选释义的编号（从 1 开始）
+    pub fn unit_meaning_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["index"],
+            "properties": {
+                "index": {"type": "integer", "minimum": 1}
+            }
+        })
     }
 }
@@ -1,2 +1,11 @@
+mod grade_profile;
 mod language_prompts;
-pub use language_prompts::LanguagePrompts;
+pub mod registry;
+mod unit_context;
+pub use grade_profile::GradeProfile;
+pub use language_prompts::{
+    LanguagePrompts, BUILTIN_VERSION, EXAMPLE_SENTENCES_PROMPT, GRADE_EXAMPLE_SENTENCES_PROMPT,
+    PHONETICS_PROMPT, UNIT_MEANING_PROMPT, WORD_EXPLAIN_PROMPT, WORD_INFO_PROMPT,
+};
+pub use registry::{prompt_registry, PromptRegistry, RenderedPrompt};
+pub use unit_context::UnitContext;
@@ -0,0 +1,382 @@
+use crate::domain::models::prompt_template::PromptTemplate;
+use crate::infrastructure::llm::prompts::language_prompts::{LanguagePrompts, BUILTIN_VERSION};
+use anyhow::{anyhow, Context, Result};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+lazy_static! {
+    static ref PROMPT_REGISTRY: PromptRegistry = PromptRegistry::new();
+}
+
+/// 全局提示词模板注册表，启动时只包含内置模板
+pub fn prompt_registry() -> &'static PromptRegistry {
+    &PROMPT_REGISTRY
+}
+
+/// 渲染后的提示词
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenderedPrompt {
+    pub name: String,
+    pub version: i32,
+    pub system_prompt: String,
+    pub user_prompt: String,
+}
+
+/// 同一名称下的所有版本及当前启用的版本
+struct PromptVersions {
+    versions: BTreeMap<i32, Arc<PromptTemplate>>,
+    active: i32,
+}
+
+/// 按名称和版本管理提示词模板，每个名称同时只有一个启用的版本
+pub struct PromptRegistry {
+    templates: RwLock<HashMap<String, PromptVersions>>,
+}
+
+impl PromptRegistry {
+    pub fn new() -> Self {
+        let registry = Self {
+            templates: RwLock::new(HashMap::new()),
+        };
+        registry.load(Vec::new());
+        registry
+    }
+
+    /// 用给定的模板替换内置模板以外的所有版本，`is_active` 的版本会被启用
+    pub fn load(&self, templates: Vec<PromptTemplate>) {
+        let mut map = HashMap::new();
+        for template in LanguagePrompts::builtin_templates() {
+            let mut versions = BTreeMap::new();
+            let name = template.name.clone();
+            versions.insert(BUILTIN_VERSION, Arc::new(template));
+            map.insert(
+                name,
+                PromptVersions {
+                    versions,
+                    active: BUILTIN_VERSION,
+                },
+            );
+        }
+        *self.templates.write().unwrap() = map;
+
+        for template in templates {
+            self.upsert(template);
+        }
+    }
+
+    /// 新增或替换一个版本，`is_active` 为 true 时同时启用
+    pub fn upsert(&self, template: PromptTemplate) {
+        let mut map = self.templates.write().unwrap();
+        let entry = map
+            .entry(template.name.clone())
+            .or_insert_with(|| PromptVersions {
+                versions: BTreeMap::new(),
+                active: template.version,
+            });
+        if template.is_active {
+            entry.active = template.version;
+        }
+        entry.versions.insert(template.version, Arc::new(template));
+    }
+
+    /// 启用指定版本，版本 0 表示恢复为内置模板
+    pub fn activate(&self, name: &str, version: i32) -> Result<()> {
+        let mut map = self.templates.write().unwrap();
+        let entry = map
+            .get_mut(name)
+            .ok_or_else(|| anyhow!("Unknown prompt template: {}", name))?;
+        if !entry.versions.contains_key(&version) {
+            return Err(anyhow!(
+                "Prompt template {} has no version {}",
+                name,
+                version
+            ));
+        }
+        entry.active = version;
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.read().unwrap().contains_key(name)
+    }
+
+    /// 当前启用的版本号
+    #[cfg(test)]
+    pub fn active_version(&self, name: &str) -> Option<i32> {
+        self.templates
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|entry| entry.active)
+    }
+
+    /// 获取指定版本，未指定时返回启用的版本
+    pub fn get(&self, name: &str, version: Option<i32>) -> Result<Arc<PromptTemplate>> {
+        let map = self.templates.read().unwrap();
+        let entry = map
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown prompt template: {}", name))?;
+        let version = version.unwrap_or(entry.active);
+        entry
+            .versions
+            .get(&version)
+            .cloned()
+            .ok_or_else(|| anyhow!("Prompt template {} has no version {}", name, version))
+    }
+
+    /// 所有模板的所有版本，按名称和版本排序，`is_active` 反映当前启用状态
+    pub fn list(&self) -> Vec<PromptTemplate> {
+        let map = self.templates.read().unwrap();
+        let mut names: Vec<&String> = map.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .flat_map(|name| {
+                let entry = &map[name];
+                entry.versions.values().map(move |template| {
+                    let mut template = template.as_ref().clone();
+                    template.is_active = template.version == entry.active;
+                    template
+                })
+            })
+            .collect()
+    }
+
+    /// 使用启用的版本渲染提示词
+    pub fn render(&self, name: &str, vars: &[(&str, &str)]) -> Result<RenderedPrompt> {
+        self.render_version(name, None, vars)
+    }
+
+    pub fn render_version(
+        &self,
+        name: &str,
+        version: Option<i32>,
+        vars: &[(&str, &str)],
+    ) -> Result<RenderedPrompt> {
+        let template = self.get(name, version)?;
+        let render = |text: &str| {
+            render_template(text, vars).with_context(|| {
+                format!(
+                    "Failed to render prompt template {} v{}",
+                    template.name, template.version
+                )
+            })
+        };
+        Ok(RenderedPrompt {
+            name: template.name.clone(),
+            version: template.version,
+            system_prompt: render(&template.system_prompt)?,
+            user_prompt: render(&template.user_prompt)?,
+        })
+    }
+}
+
+impl Default for PromptRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 模板中的一段：普通文本或 `{name}` 形式的变量
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Var(&'a str),
+}
+
+/// 拆分模板；只有由字母、数字和下划线组成的 `{...}` 才视为变量，JSON 示例中的花括号保持原样
+fn segments(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let name_len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        let name = &after[..name_len];
+        let is_var = !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && after[name_len..].starts_with('}');
+        if is_var {
+            if start > 0 {
+                segments.push(Segment::Text(&rest[..start]));
+            }
+            segments.push(Segment::Var(name));
+            rest = &after[name_len + 1..];
+        } else {
+            segments.push(Segment::Text(&rest[..=start]));
+            rest = after;
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
+
+/// 模板中用到的变量名，按首次出现的顺序
+pub fn template_variables(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for segment in segments(template) {
+        if let Segment::Var(name) = segment {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// 替换模板中的变量，缺少的变量会报错
+pub fn render_template(template: &str, vars: &[(&str, &str)]) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut missing = Vec::new();
+    for segment in segments(template) {
+        match segment {
+            Segment::Text(text) => rendered.push_str(text),
+            Segment::Var(name) => match vars.iter().find(|(key, _)| *key == name) {
+                Some((_, value)) => rendered.push_str(value),
+                None => missing.push(name),
+            },
+        }
+    }
+    if !missing.is_empty() {
+        return Err(anyhow!("missing variables: {}", missing.join(", ")));
+    }
+    Ok(rendered)
+}
+
+/// 根据模板从渲染结果中反推变量的值，不匹配时返回 None
+pub fn match_template(template: &str, rendered: &str) -> Option<HashMap<String, String>> {
+    let segments = segments(template);
+    let mut vars = HashMap::new();
+    let mut rest = rendered;
+    for (index, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Text(text) => rest = rest.strip_prefix(text)?,
+            Segment::Var(name) => {
+                let value = match segments.get(index + 1) {
+                    Some(Segment::Text(next)) => &rest[..rest.find(next)?],
+                    Some(Segment::Var(_)) => return None,
+                    None => rest,
+                };
+                vars.insert(name.to_string(), value.to_string());
+                rest = &rest[value.len()..];
+            }
+        }
+    }
+    rest.is_empty().then_some(vars)
+}
+
+/// 读取目录中的 `*.json` 模板文件，每个文件为一个模板版本
+pub fn load_dir(dir: &Path) -> Result<Vec<PromptTemplate>> {
+    let mut templates = Vec::new();
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read prompt template dir {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read prompt template {}", path.display()))?;
+        let template: PromptTemplate = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid prompt template {}", path.display()))?;
+        templates.push(template);
+    }
+    templates.sort_by(|a, b| (&a.name, a.version).cmp(&(&b.name, b.version)));
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::llm::prompts::{EXAMPLE_SENTENCES_PROMPT, WORD_INFO_PROMPT};
+
+    #[test]
+    fn test_render_template() {
+        let template = r#"Give {count} sentences for "{word}" as {"english": "..."}"#;
+        assert_eq!(template_variables(template), vec!["count", "word"]);
+
+        let rendered = render_template(template, &[("word", "happy"), ("count", "2")]).unwrap();
+        assert_eq!(
+            rendered,
+            r#"Give 2 sentences for "happy" as {"english": "..."}"#
+        );
+
+        let error = render_template("{word} for {grade}", &[("word", "happy")]).unwrap_err();
+        assert_eq!(error.to_string(), "missing variables: grade");
+    }
+
+    #[test]
+    fn test_match_template() {
+        let vars = match_template(
+            "Explain '{word}' to {grade} students.",
+            "Explain 'ice cream' to 三年级 students.",
+        )
+        .unwrap();
+        assert_eq!(vars["word"], "ice cream");
+        assert_eq!(vars["grade"], "三年级");
+
+        assert!(match_template("Explain '{word}'.", "Describe 'happy'.").is_none());
+    }
+
+    #[test]
+    fn test_builtin_templates_render() {
+        let registry = PromptRegistry::new();
+        let prompt = registry
+            .render(
+                EXAMPLE_SENTENCES_PROMPT,
+                &[("word", "happy"), ("count", "2")],
+            )
+            .unwrap();
+        assert_eq!(prompt.version, BUILTIN_VERSION);
+        assert!(prompt
+            .user_prompt
+            .contains("provide 2 example sentences using the word \"happy\""));
+        assert!(prompt
+            .user_prompt
+            .contains("\"english\": \"English sentence 1\""));
+
+        let prompt = registry
+            .render(WORD_INFO_PROMPT, &[("word", "name")])
+            .unwrap();
+        assert!(prompt.system_prompt.contains("\"meanings\": ["));
+    }
+
+    #[test]
+    fn test_activate_versions() {
+        let registry = PromptRegistry::new();
+        let mut v1 = PromptTemplate::new(WORD_INFO_PROMPT, 1, "system v1", "word {word} v1");
+        v1.is_active = true;
+        let v2 = PromptTemplate::new(WORD_INFO_PROMPT, 2, "system v2", "word {word} v2");
+        registry.load(vec![v1, v2]);
+
+        assert_eq!(registry.active_version(WORD_INFO_PROMPT), Some(1));
+        let prompt = registry.render(WORD_INFO_PROMPT, &[("word", "x")]).unwrap();
+        assert_eq!(prompt.user_prompt, "word x v1");
+
+        registry.activate(WORD_INFO_PROMPT, 2).unwrap();
+        assert_eq!(registry.get(WORD_INFO_PROMPT, None).unwrap().version, 2);
+        assert!(registry.activate(WORD_INFO_PROMPT, 3).is_err());
+
+        let active: Vec<_> = registry
+            .list()
+            .into_iter()
+            .filter(|t| t.name == WORD_INFO_PROMPT && t.is_active)
+            .map(|t| t.version)
+            .collect();
+        assert_eq!(active, vec![2]);
+
+        registry
+            .activate(WORD_INFO_PROMPT, BUILTIN_VERSION)
+            .unwrap();
+        assert_eq!(
+            registry.active_version(WORD_INFO_PROMPT),
+            Some(BUILTIN_VERSION)
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use crate::infrastructure::dto::WordMeaning;
+
+/// 单词在教材中出现的上下文，用于判断单词在该单元的具体含义
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnitContext {
+    /// 课本名称，如 “人教版三年级上册”
+    pub textbook: String,
+    /// 单元名称
+    pub unit: String,
+    /// 同一单元的其他单词
+    pub words: Vec<String>,
+}
+
+/// 同一单元最多带上的单词数，避免提示词过长
+const MAX_CONTEXT_WORDS: usize = 30;
+
+impl UnitContext {
+    /// 渲染 `unit_meaning` 模板时使用的变量，`word` 由调用方补充
+    pub fn variables(&self, senses: &[WordMeaning]) -> Vec<(&'static str, String)> {
+        let words = if self.words.is_empty() {
+            "（无）".to_string()
+        } else {
+            self.words
+                .iter()
+                .take(MAX_CONTEXT_WORDS)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let senses = senses
+            .iter()
+            .enumerate()
+            .map(|(index, sense)| format!("{}. {} {}", index + 1, sense.pos, sense.definition))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        vec![
+            ("textbook", self.textbook.clone()),
+            ("unit", self.unit.clone()),
+            ("unit_words", words),
+            ("senses", senses),
+        ]
+    }
+}
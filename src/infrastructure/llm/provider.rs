@@ -6,6 +6,18 @@ use std::str::FromStr;
 pub enum LLMProvider {
     Yi,
     DeepSeek,
+    /// 任意兼容 OpenAI `/v1/chat/completions` 接口的服务，如 Qwen、Moonshot、Ollama、vLLM
+    OpenAICompatible,
+    /// 离线测试用的模拟服务，从固定的 fixture 目录读取预设回复
+    Mock,
+}
+
+/// 模型级别的采样参数，未设置时使用各调用的默认值
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelParams {
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -15,6 +27,7 @@ pub struct LLMConfig {
     pub model_name: Option<String>,
     pub base_url: Option<String>,
     pub timeout: Option<u64>,
+    pub params: ModelParams,
 }
 
 impl LLMConfig {
@@ -25,6 +38,7 @@ impl LLMConfig {
             model_name: None,
             base_url: None,
             timeout: Some(30),
+            params: ModelParams::default(),
         }
     }
 
@@ -47,6 +61,21 @@ impl LLMConfig {
         self.timeout = Some(timeout);
         self
     }
+
+    pub fn with_temperature(&mut self, temperature: f32) -> &mut Self {
+        self.params.temperature = Some(temperature);
+        self
+    }
+
+    pub fn with_top_p(&mut self, top_p: f32) -> &mut Self {
+        self.params.top_p = Some(top_p);
+        self
+    }
+
+    pub fn with_max_tokens(&mut self, max_tokens: u32) -> &mut Self {
+        self.params.max_tokens = Some(max_tokens);
+        self
+    }
 }
 
 impl FromStr for LLMProvider {
@@ -56,6 +85,10 @@ impl FromStr for LLMProvider {
         match s.to_lowercase().as_str() {
             "yi" => Ok(LLMProvider::Yi),
             "deepseek" => Ok(LLMProvider::DeepSeek),
+            "openai" | "openai-compatible" | "openai_compatible" => {
+                Ok(LLMProvider::OpenAICompatible)
+            }
+            "mock" => Ok(LLMProvider::Mock),
             _ => Err(anyhow!("Unsupported LLM provider: {}", s)),
         }
     }
@@ -66,6 +99,8 @@ impl ToString for LLMProvider {
         match self {
             LLMProvider::Yi => "yi".to_string(),
             LLMProvider::DeepSeek => "deepseek".to_string(),
+            LLMProvider::OpenAICompatible => "openai-compatible".to_string(),
+            LLMProvider::Mock => "mock".to_string(),
         }
     }
 }
@@ -0,0 +1,182 @@
+use crate::infrastructure::llm::interface::ChatStream;
+use crate::infrastructure::llm::usage::{StreamUsage, TokenUsage};
+use anyhow::{anyhow, Context, Result};
+use futures::{future, stream, StreamExt};
+use reqwest::{Client, Response};
+use serde_json::{json, Value};
+use tracing::debug;
+
+/// SSE 中单行数据的解析结果
+#[derive(Debug, PartialEq)]
+enum SseLine {
+    Delta(String),
+    Usage(TokenUsage),
+    Done,
+    Skip,
+}
+
+/// 发送 OpenAI 兼容的流式对话请求，`body` 中会自动加入 `"stream": true`，并要求在最后返回用量
+///
+/// 未提供 `api_key` 时不携带 Authorization 头（如本地 Ollama）；`provider` 为记录用量时的默认服务名
+pub async fn post_chat_stream(
+    client: &Client,
+    api_url: &str,
+    api_key: Option<&str>,
+    provider: &str,
+    mut body: Value,
+) -> Result<ChatStream> {
+    body["stream"] = Value::Bool(true);
+    body["stream_options"] = json!({"include_usage": true});
+    let usage = StreamUsage::start(provider, body["model"].as_str().unwrap_or_default());
+
+    let mut request = client.post(api_url);
+    if let Some(api_key) = api_key {
+        request = request.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let response = request
+        .header("Content-Type", "application/json")
+        .header("Accept", "text/event-stream")
+        .body(body.to_string())
+        .send()
+        .await
+        .context("Failed to send stream request")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Non-success status code: {}, body: {}", status, text));
+    }
+
+    Ok(sse_to_chat_stream(response, usage))
+}
+
+/// 将 OpenAI 风格的 SSE 响应转换为增量文本流，流结束或被丢弃时记录用量
+pub fn sse_to_chat_stream(response: Response, mut usage: StreamUsage) -> ChatStream {
+    // 按字节缓冲，避免多字节字符被拆分到两个 chunk 中
+    let mut buffer: Vec<u8> = Vec::new();
+
+    let deltas = response
+        .bytes_stream()
+        .flat_map(move |chunk| {
+            let lines: Vec<Result<SseLine>> = match chunk {
+                Ok(bytes) => {
+                    buffer.extend_from_slice(&bytes);
+                    drain_lines(&mut buffer)
+                        .iter()
+                        .map(|line| parse_sse_line(line))
+                        .filter(|line| match line {
+                            Ok(SseLine::Usage(tokens)) => {
+                                usage.set(*tokens);
+                                false
+                            }
+                            _ => true,
+                        })
+                        .collect()
+                }
+                Err(e) => vec![Err(anyhow!("Failed to read stream chunk: {}", e))],
+            };
+            stream::iter(lines)
+        })
+        .take_while(|line| future::ready(!matches!(line, Ok(SseLine::Done))))
+        .filter_map(|line| {
+            future::ready(match line {
+                Ok(SseLine::Delta(delta)) => Some(Ok(delta)),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+        });
+
+    Box::pin(deltas)
+}
+
+/// 取出缓冲区中所有完整的行，不完整的部分保留在缓冲区
+fn drain_lines(buffer: &mut Vec<u8>) -> Vec<String> {
+    let mut lines = Vec::new();
+    while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+        let line: Vec<u8> = buffer.drain(..=pos).collect();
+        lines.push(String::from_utf8_lossy(&line).trim().to_string());
+    }
+    lines
+}
+
+/// 解析单行 SSE 数据，只关心 `data:` 行
+fn parse_sse_line(line: &str) -> Result<SseLine> {
+    let data = match line.strip_prefix("data:") {
+        Some(data) => data.trim(),
+        None => return Ok(SseLine::Skip),
+    };
+
+    if data == "[DONE]" {
+        return Ok(SseLine::Done);
+    }
+
+    let value: Value = serde_json::from_str(data)
+        .map_err(|e| anyhow!("Failed to parse stream chunk: {}, data: {}", e, data))?;
+
+    if let Some(error) = value.get("error") {
+        return Err(anyhow!("Stream returned an error: {}", error));
+    }
+
+    // 用量在最后一个 chunk 中返回，此时没有增量文本
+    match value["choices"][0]["delta"]["content"].as_str() {
+        Some(content) if !content.is_empty() => Ok(SseLine::Delta(content.to_string())),
+        _ if value["usage"].is_object() => {
+            Ok(TokenUsage::from_response(&value).map_or(SseLine::Skip, SseLine::Usage))
+        }
+        _ => {
+            debug!("stream chunk without content: {}", data);
+            Ok(SseLine::Skip)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sse_line() {
+        let line = r#"data: {"choices":[{"index":0,"delta":{"content":"你好"}}]}"#;
+        assert_eq!(
+            parse_sse_line(line).unwrap(),
+            SseLine::Delta("你好".to_string())
+        );
+
+        let role_only = r#"data: {"choices":[{"index":0,"delta":{"role":"assistant"}}]}"#;
+        assert_eq!(parse_sse_line(role_only).unwrap(), SseLine::Skip);
+
+        let usage = r#"data: {"choices":[],"usage":{"prompt_tokens":20,"completion_tokens":8}}"#;
+        assert_eq!(
+            parse_sse_line(usage).unwrap(),
+            SseLine::Usage(TokenUsage {
+                prompt_tokens: 20,
+                completion_tokens: 8
+            })
+        );
+
+        assert_eq!(parse_sse_line("data: [DONE]").unwrap(), SseLine::Done);
+        assert_eq!(parse_sse_line(": keep-alive").unwrap(), SseLine::Skip);
+        assert_eq!(parse_sse_line("").unwrap(), SseLine::Skip);
+
+        assert!(parse_sse_line(r#"data: {"error":{"message":"rate limit"}}"#).is_err());
+        assert!(parse_sse_line("data: {not json").is_err());
+    }
+
+    #[test]
+    fn test_drain_lines_keeps_partial_line() {
+        let text = "data: 第一行\ndata: 第二";
+        let mut buffer = text.as_bytes().to_vec();
+        // 在多字节字符中间截断，剩余部分应保留在缓冲区
+        buffer.truncate(buffer.len() - 1);
+
+        let lines = drain_lines(&mut buffer);
+        assert_eq!(lines, vec!["data: 第一行".to_string()]);
+        assert!(!buffer.is_empty());
+
+        buffer.extend_from_slice(&"二\n".as_bytes()[2..]);
+        let lines = drain_lines(&mut buffer);
+        assert_eq!(lines, vec!["data: 第二".to_string()]);
+        assert!(buffer.is_empty());
+    }
+}
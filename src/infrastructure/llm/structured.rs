@@ -0,0 +1,336 @@
+use crate::infrastructure::llm::interface::{ChatMessage, LLMService};
+use crate::infrastructure::llm::utils;
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use jsonschema::JSONSchema;
+use lazy_static::lazy_static;
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use serde_json::Value;
+use std::env;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+use tracing::{debug, warn};
+
+/// 默认最多尝试次数（含首次请求）
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+/// 等待持久化的尝试记录上限，数据库写入跟不上时丢弃新的记录
+const ATTEMPT_QUEUE_CAPACITY: usize = 1024;
+
+static ATTEMPT_SINK: OnceCell<Sender<StructuredAttempt>> = OnceCell::new();
+
+lazy_static! {
+    static ref MAX_ATTEMPTS: u32 = env::var("LLM_STRUCTURED_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_ATTEMPTS);
+    /// 按 schema 文本缓存编译好的校验器，每种任务的 schema 只编译一次
+    static ref VALIDATORS: DashMap<String, Arc<JSONSchema>> = DashMap::new();
+}
+
+/// 一次需要返回 JSON 的模型调用
+pub struct JsonTask<'a> {
+    /// 任务名称，如 phonetics / word_info
+    pub name: &'a str,
+    /// 任务对象，通常是单词
+    pub subject: &'a str,
+    pub system_prompt: &'a str,
+    pub user_prompt: String,
+    /// 模型输出需要满足的 JSON Schema
+    pub schema: Value,
+    pub temperature: f32,
+}
+
+/// 一次结构化输出尝试及其结果
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct StructuredAttempt {
+    pub task: String,
+    pub subject: String,
+    pub attempt: i32,
+    pub success: bool,
+    pub error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub occurred_at: OffsetDateTime,
+}
+
+/// 设置尝试记录的接收端，返回的 receiver 由调用方负责持久化
+///
+/// 未初始化时记录会被丢弃（例如测试环境）
+pub fn init_attempt_sink() -> Result<Receiver<StructuredAttempt>> {
+    let (sender, receiver) = mpsc::channel(ATTEMPT_QUEUE_CAPACITY);
+    ATTEMPT_SINK
+        .set(sender)
+        .map_err(|_| anyhow!("Structured attempt sink already initialized"))?;
+    Ok(receiver)
+}
+
+/// 请求模型返回 JSON 并按 schema 校验，校验失败时把错误发回给模型要求修正
+///
+/// 网络等调用错误直接返回，交由上层的降级链处理
+pub async fn complete_json<S>(service: &S, task: JsonTask<'_>) -> Result<Value>
+where
+    S: LLMService + ?Sized,
+{
+    complete_json_with_attempts(service, task, *MAX_ATTEMPTS).await
+}
+
+async fn complete_json_with_attempts<S>(
+    service: &S,
+    task: JsonTask<'_>,
+    max_attempts: u32,
+) -> Result<Value>
+where
+    S: LLMService + ?Sized,
+{
+    let mut messages = vec![
+        ChatMessage::system(task.system_prompt),
+        ChatMessage::user(task.user_prompt.as_str()),
+    ];
+    let mut last_error = String::new();
+
+    for attempt in 1..=max_attempts {
+        let content = service.chat(&messages, task.temperature).await?;
+        debug!("{} response for '{}' (attempt {}): {}", task.name, task.subject, attempt, content);
+
+        match parse_and_validate(&content, &task.schema) {
+            Ok(value) => {
+                record_attempt(&task, attempt, None);
+                return Ok(value);
+            }
+            Err(error) => {
+                warn!(
+                    "{} response for '{}' failed validation (attempt {}/{}): {}",
+                    task.name, task.subject, attempt, max_attempts, error
+                );
+                record_attempt(&task, attempt, Some(&error));
+                messages.push(ChatMessage::assistant(content));
+                messages.push(ChatMessage::user(repair_instruction(&error, &task.schema)));
+                last_error = error;
+            }
+        }
+    }
+
+    Err(anyhow!(
+        "Invalid {} response for '{}' after {} attempts: {}",
+        task.name,
+        task.subject,
+        max_attempts,
+        last_error
+    ))
+}
+
+/// 解析模型输出并按 schema 校验，返回可直接发回给模型的错误描述
+pub fn parse_and_validate(content: &str, schema: &Value) -> Result<Value, String> {
+    let value = parse_json(content)?;
+    let compiled = validator(schema)?;
+
+    let result = compiled.validate(&value).map_err(|errors| {
+        errors
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                if path.is_empty() {
+                    e.to_string()
+                } else {
+                    format!("{}: {}", path, e)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    });
+    result.map(|_| value)
+}
+
+fn validator(schema: &Value) -> Result<Arc<JSONSchema>, String> {
+    let key = schema.to_string();
+    if let Some(compiled) = VALIDATORS.get(&key) {
+        return Ok(compiled.clone());
+    }
+    let compiled =
+        Arc::new(JSONSchema::compile(schema).map_err(|e| format!("invalid schema: {}", e))?);
+    VALIDATORS.insert(key, compiled.clone());
+    Ok(compiled)
+}
+
+/// 宽松解析：去掉代码块标记，必要时截取正文中的 JSON 片段
+fn parse_json(content: &str) -> Result<Value, String> {
+    let cleaned = utils::clean_json_response(content);
+    let error = match serde_json::from_str::<Value>(&cleaned) {
+        Ok(value) => return Ok(value),
+        Err(e) => e,
+    };
+
+    let start = cleaned.find(['{', '[']);
+    let end = cleaned.rfind(['}', ']']);
+    if let (Some(start), Some(end)) = (start, end) {
+        if start < end {
+            if let Ok(value) = serde_json::from_str::<Value>(&cleaned[start..=end]) {
+                return Ok(value);
+            }
+        }
+    }
+
+    Err(format!("response is not valid JSON: {}", error))
+}
+
+fn repair_instruction(error: &str, schema: &Value) -> String {
+    format!(
+        "Your previous response did not match the required format: {}\n\
+        Return only the corrected JSON that conforms to this JSON Schema, \
+        without code fences or any additional text:\n{}",
+        error, schema
+    )
+}
+
+fn record_attempt(task: &JsonTask<'_>, attempt: u32, error: Option<&str>) {
+    let record = StructuredAttempt {
+        task: task.name.to_string(),
+        subject: task.subject.to_string(),
+        attempt: attempt as i32,
+        success: error.is_none(),
+        error: error.map(str::to_string),
+        occurred_at: OffsetDateTime::now_utc(),
+    };
+
+    if let Some(sink) = ATTEMPT_SINK.get() {
+        if let Err(TrySendError::Full(record)) = sink.try_send(record) {
+            warn!(
+                "Structured attempt queue is full, dropped record for '{}'",
+                record.subject
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::llm::interface::ChatStream;
+    use crate::infrastructure::llm::prompts::registry::render_template;
+    use crate::infrastructure::llm::prompts::LanguagePrompts;
+    use crate::infrastructure::llm::provider::LLMConfig;
+    use async_trait::async_trait;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// 按顺序返回预设回复，并记录收到的对话
+    struct ScriptedService {
+        replies: Mutex<VecDeque<String>>,
+        received: Mutex<Vec<Vec<ChatMessage>>>,
+    }
+
+    impl ScriptedService {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().map(|r| r.to_string()).collect()),
+                received: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LLMService for ScriptedService {
+        fn configure(&mut self, _config: &LLMConfig) -> Result<()> {
+            Ok(())
+        }
+
+        async fn chat(&self, messages: &[ChatMessage], _temperature: f32) -> Result<String> {
+            self.received.lock().unwrap().push(messages.to_vec());
+            self.replies
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| anyhow!("no more replies"))
+        }
+
+        async fn chat_stream(&self, _system: &str, _user: &str) -> Result<ChatStream> {
+            Err(anyhow!("not supported"))
+        }
+    }
+
+    fn phonetics_task(subject: &str) -> JsonTask<'_> {
+        JsonTask {
+            name: "phonetics",
+            subject,
+            system_prompt: LanguagePrompts::PHONETICS_SYSTEM,
+            user_prompt: render_template(LanguagePrompts::PHONETICS_USER, &[("word", subject)])
+                .unwrap(),
+            schema: LanguagePrompts::phonetics_schema(),
+            temperature: 0.3,
+        }
+    }
+
+    #[test]
+    fn test_parse_json_with_surrounding_text() {
+        let content = "Sure! Here it is:\n{\"us_ipa\": \"həˈloʊ\", \"uk_ipa\": \"həˈləʊ\"}\nHope it helps.";
+        let value = parse_and_validate(content, &LanguagePrompts::phonetics_schema()).unwrap();
+        assert_eq!(value["us_ipa"], "həˈloʊ");
+
+        let error = parse_and_validate("not json", &LanguagePrompts::phonetics_schema())
+            .unwrap_err();
+        assert!(error.starts_with("response is not valid JSON"));
+    }
+
+    #[test]
+    fn test_schema_errors_are_reported() {
+        let error = parse_and_validate(
+            r#"[{"english": "I am happy.", "chinese": ""}]"#,
+            &LanguagePrompts::example_sentences_schema(),
+        )
+        .unwrap_err();
+        assert!(error.contains("/0/chinese"), "{}", error);
+
+        let error =
+            parse_and_validate(r#"{"us_ipa": "ˈhæpi"}"#, &LanguagePrompts::phonetics_schema())
+                .unwrap_err();
+        assert!(error.contains("uk_ipa"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_repairs_invalid_output() {
+        let mut records = init_attempt_sink().unwrap();
+        let service = ScriptedService::new(&[
+            r#"{"us_ipa": "ˈhæpi"}"#,
+            r#"```json
+            {"us_ipa": "ˈhæpi", "uk_ipa": "ˈhæpi"}
+            ```"#,
+        ]);
+
+        let value = complete_json_with_attempts(&service, phonetics_task("repair-ok"), 3)
+            .await
+            .unwrap();
+        assert_eq!(value["uk_ipa"], "ˈhæpi");
+
+        let received = service.received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        let repair = &received[1];
+        assert_eq!(repair.len(), 4);
+        assert_eq!(repair[2], ChatMessage::assistant(r#"{"us_ipa": "ˈhæpi"}"#));
+        assert_eq!(repair[3].role, "user");
+        assert!(repair[3].content.contains("uk_ipa"));
+
+        let mut attempts = Vec::new();
+        while let Ok(attempt) = records.try_recv() {
+            if attempt.subject == "repair-ok" {
+                attempts.push(attempt);
+            }
+        }
+        assert_eq!(attempts.len(), 2);
+        assert!(!attempts[0].success);
+        assert!(attempts[1].success);
+        assert_eq!(attempts[1].attempt, 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let service = ScriptedService::new(&["oops", "still oops", "never used"]);
+
+        let error = complete_json_with_attempts(&service, phonetics_task("repair-fail"), 2)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("after 2 attempts"), "{}", error);
+        assert_eq!(service.received.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,315 @@
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+use tracing::{debug, warn};
+
+/// 不经过 `LLMManager` 直接调用模型时使用的操作名
+const UNKNOWN_OPERATION: &str = "direct";
+/// 等待持久化的用量记录上限，数据库写入跟不上时丢弃新的记录
+const USAGE_QUEUE_CAPACITY: usize = 1024;
+
+static USAGE_SINK: OnceCell<Sender<UsageRecord>> = OnceCell::new();
+/// 队列已满被丢弃的用量记录数
+static DROPPED_RECORDS: AtomicU64 = AtomicU64::new(0);
+
+tokio::task_local! {
+    static CALL_CONTEXT: CallContext;
+}
+
+/// 当前调用的模型服务名称和业务操作，由 `LLMManager::execute` 设置
+#[derive(Debug, Clone)]
+struct CallContext {
+    provider: String,
+    operation: String,
+}
+
+/// 响应中 `usage` 字段给出的 token 数
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TokenUsage {
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+}
+
+impl TokenUsage {
+    /// 读取 OpenAI 风格响应中的 `usage.prompt_tokens` / `usage.completion_tokens`
+    pub fn from_response(response: &Value) -> Option<Self> {
+        let usage = response.get("usage")?;
+        let tokens = |key: &str| usage.get(key).and_then(Value::as_i64).map(|v| v as i32);
+        Some(Self {
+            prompt_tokens: tokens("prompt_tokens")?,
+            completion_tokens: tokens("completion_tokens").unwrap_or_default(),
+        })
+    }
+}
+
+/// 一次模型调用的用量记录
+#[derive(Debug, Clone)]
+pub struct UsageRecord {
+    pub provider: String,
+    pub model: String,
+    pub operation: String,
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+    pub latency_ms: i64,
+    pub created_at: OffsetDateTime,
+}
+
+/// 每 1K token 的价格，分别计算输入和输出
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    #[serde(default)]
+    pub prompt_per_1k: f64,
+    #[serde(default)]
+    pub completion_per_1k: f64,
+}
+
+impl ModelPricing {
+    /// 从 LLM_{NAME}_PROMPT_PRICE_PER_1K / LLM_{NAME}_COMPLETION_PRICE_PER_1K 读取价格
+    pub fn from_env(provider: &str) -> Option<Self> {
+        Self::from_env_prefix(&format!("LLM_{}", provider.to_uppercase()))
+    }
+
+    /// 从 LLM_{NAME}_{MODEL}_PROMPT_PRICE_PER_1K 读取单个模型的价格，模型名中的非字母数字字符替换为下划线，
+    /// 如 deepseek-reasoner 对应 LLM_DEEPSEEK_DEEPSEEK_REASONER_PROMPT_PRICE_PER_1K
+    pub fn from_env_for_model(provider: &str, model: &str) -> Option<Self> {
+        let model: String = model
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        Self::from_env_prefix(&format!(
+            "LLM_{}_{}",
+            provider.to_uppercase(),
+            model.to_uppercase()
+        ))
+    }
+
+    fn from_env_prefix(prefix: &str) -> Option<Self> {
+        let price = |kind: &str| {
+            env::var(format!("{}_{}_PRICE_PER_1K", prefix, kind))
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+        };
+        match (price("PROMPT"), price("COMPLETION")) {
+            (None, None) => None,
+            (prompt, completion) => Some(Self {
+                prompt_per_1k: prompt.unwrap_or_default(),
+                completion_per_1k: completion.unwrap_or_default(),
+            }),
+        }
+    }
+
+    pub fn estimate(&self, prompt_tokens: i64, completion_tokens: i64) -> f64 {
+        (prompt_tokens as f64 * self.prompt_per_1k
+            + completion_tokens as f64 * self.completion_per_1k)
+            / 1000.0
+    }
+}
+
+/// 设置用量记录的接收端，返回的 receiver 由调用方负责持久化
+///
+/// 未初始化时记录会被丢弃（例如测试环境）
+pub fn init_usage_sink() -> anyhow::Result<Receiver<UsageRecord>> {
+    let (sender, receiver) = mpsc::channel(USAGE_QUEUE_CAPACITY);
+    USAGE_SINK
+        .set(sender)
+        .map_err(|_| anyhow::anyhow!("LLM usage sink already initialized"))?;
+    Ok(receiver)
+}
+
+/// 在给定的模型服务和操作上下文中执行调用，期间的用量记录会带上这些信息
+pub async fn scope<F: Future>(provider: &str, operation: &str, future: F) -> F::Output {
+    let context = CallContext {
+        provider: provider.to_string(),
+        operation: operation.to_string(),
+    };
+    CALL_CONTEXT.scope(context, future).await
+}
+
+/// 记录一次成功的模型调用，由各模型服务实现在收到响应后调用
+///
+/// `fallback_provider` 用于不经过 `LLMManager` 的调用，此时没有配置名称
+pub fn record(fallback_provider: &str, model: &str, usage: Option<TokenUsage>, latency: Duration) {
+    send(build_record(fallback_provider, model, usage, latency));
+}
+
+fn send(record: UsageRecord) {
+    debug!(
+        "LLM usage: provider={}, model={}, operation={}, prompt={}, completion={}, latency={}ms",
+        record.provider,
+        record.model,
+        record.operation,
+        record.prompt_tokens,
+        record.completion_tokens,
+        record.latency_ms
+    );
+    if let Some(sink) = USAGE_SINK.get() {
+        if let Err(TrySendError::Full(record)) = sink.try_send(record) {
+            let dropped = DROPPED_RECORDS.fetch_add(1, Ordering::Relaxed) + 1;
+            warn!(
+                "LLM usage queue is full, dropped record for {} ({} dropped so far)",
+                record.provider, dropped
+            );
+        }
+    }
+}
+
+/// 流式调用的用量，流读完或被丢弃时记录一次
+///
+/// 创建时保存调用上下文，因为流通常在 `scope` 之外才被读取
+pub struct StreamUsage {
+    fallback_provider: String,
+    model: String,
+    context: Option<CallContext>,
+    started: Instant,
+    usage: Option<TokenUsage>,
+}
+
+impl StreamUsage {
+    pub fn start(fallback_provider: &str, model: &str) -> Self {
+        Self {
+            fallback_provider: fallback_provider.to_string(),
+            model: model.to_string(),
+            context: current_context(),
+            started: Instant::now(),
+            usage: None,
+        }
+    }
+
+    /// 保存流中返回的 token 数
+    pub fn set(&mut self, usage: TokenUsage) {
+        self.usage = Some(usage);
+    }
+}
+
+impl Drop for StreamUsage {
+    fn drop(&mut self) {
+        send(record_in_context(
+            self.context.take(),
+            &self.fallback_provider,
+            &self.model,
+            self.usage,
+            self.started.elapsed(),
+        ));
+    }
+}
+
+fn current_context() -> Option<CallContext> {
+    CALL_CONTEXT.try_with(|context| context.clone()).ok()
+}
+
+fn build_record(
+    fallback_provider: &str,
+    model: &str,
+    usage: Option<TokenUsage>,
+    latency: Duration,
+) -> UsageRecord {
+    record_in_context(current_context(), fallback_provider, model, usage, latency)
+}
+
+fn record_in_context(
+    context: Option<CallContext>,
+    fallback_provider: &str,
+    model: &str,
+    usage: Option<TokenUsage>,
+    latency: Duration,
+) -> UsageRecord {
+    let usage = usage.unwrap_or_default();
+    UsageRecord {
+        provider: context
+            .as_ref()
+            .map(|c| c.provider.clone())
+            .unwrap_or_else(|| fallback_provider.to_string()),
+        model: model.to_string(),
+        operation: context
+            .map(|c| c.operation)
+            .unwrap_or_else(|| UNKNOWN_OPERATION.to_string()),
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        latency_ms: latency.as_millis() as i64,
+        created_at: OffsetDateTime::now_utc(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_token_usage_from_response() {
+        let response = json!({
+            "choices": [],
+            "usage": {"prompt_tokens": 120, "completion_tokens": 45, "total_tokens": 165}
+        });
+        assert_eq!(
+            TokenUsage::from_response(&response),
+            Some(TokenUsage {
+                prompt_tokens: 120,
+                completion_tokens: 45
+            })
+        );
+        assert_eq!(TokenUsage::from_response(&json!({"choices": []})), None);
+    }
+
+    #[test]
+    fn test_pricing_estimate() {
+        let pricing = ModelPricing {
+            prompt_per_1k: 0.002,
+            completion_per_1k: 0.008,
+        };
+        let cost = pricing.estimate(1500, 500);
+        assert!((cost - 0.007).abs() < 1e-9, "{}", cost);
+    }
+
+    #[tokio::test]
+    async fn test_record_uses_call_context() {
+        let usage = Some(TokenUsage {
+            prompt_tokens: 10,
+            completion_tokens: 5,
+        });
+        let latency = Duration::from_millis(250);
+
+        let record = scope("qwen", "get_word_info", async {
+            build_record("openai-compatible", "qwen-plus", usage, latency)
+        })
+        .await;
+        assert_eq!(record.provider, "qwen");
+        assert_eq!(record.operation, "get_word_info");
+        assert_eq!(record.model, "qwen-plus");
+        assert_eq!(record.latency_ms, 250);
+
+        let record = build_record("yi", "yi-lightning", None, latency);
+        assert_eq!(record.provider, "yi");
+        assert_eq!(record.operation, UNKNOWN_OPERATION);
+        assert_eq!(record.prompt_tokens, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_usage_keeps_call_context() {
+        let mut tracker = scope("qwen", "explain_word_stream", async {
+            StreamUsage::start("openai-compatible", "qwen-plus")
+        })
+        .await;
+        tracker.set(TokenUsage {
+            prompt_tokens: 30,
+            completion_tokens: 12,
+        });
+
+        let record = record_in_context(
+            tracker.context.take(),
+            &tracker.fallback_provider,
+            &tracker.model,
+            tracker.usage,
+            Duration::ZERO,
+        );
+        assert_eq!(record.provider, "qwen");
+        assert_eq!(record.operation, "explain_word_stream");
+        assert_eq!(record.completion_tokens, 12);
+    }
+}
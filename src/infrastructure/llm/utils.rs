@@ -1,6 +1,10 @@
-use crate::infrastructure::dto::PhoneticsResponse;
+use crate::infrastructure::dto::{PhoneticsResponse, WordInfo, WordMeaning};
 use anyhow::{anyhow, Result};
 use serde::Deserialize;
+use serde_json::Value;
+
+/// 例句最多保留的数量
+pub const MAX_EXAMPLE_SENTENCES: usize = 2;
 
 #[derive(Deserialize)]
 struct SentencePair {
@@ -24,44 +28,154 @@ pub fn clean_json_response(content: &str) -> String {
     }
 }
 
-/// Extract and validate phonetics from LLM response
-pub fn extract_phonetics(content: &str) -> Result<(String, String)> {
-    let response: PhoneticsResponse = serde_json::from_str(content)
-        .map_err(|err| anyhow!("Failed to parse JSON response: {}", err))?;
+/// Strip the `/.../` or `[...]` wrapper some models add around IPA
+pub fn normalize_phonetic(phonetic: &str) -> String {
+    phonetic
+        .trim()
+        .trim_matches(|c| matches!(c, '/' | '[' | ']' | '\\'))
+        .trim()
+        .to_string()
+}
 
-    let us_phonetic = response.us_ipa.trim().to_string();
-    let uk_phonetic = response.uk_ipa.trim().to_string();
+/// Extract phonetics from a schema-validated LLM response
+pub fn extract_phonetics(value: Value) -> Result<(String, String)> {
+    let response: PhoneticsResponse = serde_json::from_value(value)
+        .map_err(|err| anyhow!("Failed to parse JSON response: {}", err))?;
 
-    // Validate phonetic format
-    if us_phonetic.contains('/') || us_phonetic.contains('[') {
-        return Err(anyhow!("Invalid US phonetic format: {}", us_phonetic));
-    }
-    if uk_phonetic.contains('/') || uk_phonetic.contains('[') {
-        return Err(anyhow!("Invalid UK phonetic format: {}", uk_phonetic));
+    let us_phonetic = normalize_phonetic(&response.us_ipa);
+    let uk_phonetic = normalize_phonetic(&response.uk_ipa);
+    if us_phonetic.is_empty() || uk_phonetic.is_empty() {
+        return Err(anyhow!("Empty phonetic in response"));
     }
 
     Ok((us_phonetic, uk_phonetic))
 }
-/// Extract and validate example sentences from LLM response
-pub fn extract_example_sentences(content: &str) -> Result<String> {
-    let sentences: Vec<SentencePair> = serde_json::from_str(content)
+
+/// Extract example sentences from a schema-validated LLM response, keeping at most two pairs
+pub fn extract_example_sentences(value: Value) -> Result<String> {
+    let sentences: Vec<SentencePair> = serde_json::from_value(value)
         .map_err(|err| anyhow!("Failed to parse JSON response: {}", err))?;
 
     if sentences.is_empty() {
         return Err(anyhow!("No valid sentence pairs found in the response"));
     }
-    if sentences.len() != 2 {
-        return Err(anyhow!(
-            "Expected 2 sentence pairs, got {}",
-            sentences.len()
-        ));
-    }
 
     let mut examples = String::new();
-    for pair in sentences {
+    for pair in sentences.into_iter().take(MAX_EXAMPLE_SENTENCES) {
         let english = pair.english.trim();
         let chinese = pair.chinese.trim();
         examples.push_str(&format!("{}\n{}\n", english, chinese));
     }
     Ok(examples)
 }
+
+/// Extract word info from a schema-validated LLM response
+pub fn extract_word_info(value: Value) -> Result<WordInfo> {
+    let mut word_info: WordInfo = serde_json::from_value(value)
+        .map_err(|err| anyhow!("Failed to parse word info response: {}", err))?;
+    word_info.us_phonetic = normalize_phonetic(&word_info.us_phonetic);
+    word_info.uk_phonetic = normalize_phonetic(&word_info.uk_phonetic);
+    Ok(word_info)
+}
+
+/// 把按词性合并的释义拆成单个义项，同一词性的多个含义以中英文分号分隔
+pub fn split_senses(meanings: &[WordMeaning]) -> Vec<WordMeaning> {
+    meanings
+        .iter()
+        .flat_map(|meaning| {
+            meaning
+                .definition
+                .split(['；', ';'])
+                .map(str::trim)
+                .filter(|definition| !definition.is_empty())
+                .map(|definition| WordMeaning {
+                    pos: meaning.pos.trim().to_string(),
+                    definition: definition.to_string(),
+                    ..Default::default()
+                })
+        })
+        .collect()
+}
+
+/// Extract the chosen sense index (1-based) from a schema-validated LLM response
+pub fn extract_sense_index(value: Value, sense_count: usize) -> Result<usize> {
+    let index = value["index"]
+        .as_u64()
+        .ok_or_else(|| anyhow!("Missing sense index in response"))? as usize;
+    if index == 0 || index > sense_count {
+        return Err(anyhow!(
+            "Sense index {} out of range 1..={}",
+            index,
+            sense_count
+        ));
+    }
+    Ok(index - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_phonetic() {
+        assert_eq!(normalize_phonetic(" /həˈloʊ/ "), "həˈloʊ");
+        assert_eq!(normalize_phonetic("[həˈləʊ]"), "həˈləʊ");
+        assert_eq!(normalize_phonetic("ˈhæpi"), "ˈhæpi");
+    }
+
+    #[test]
+    fn test_extract_phonetics_accepts_wrapped_ipa() {
+        let (us, uk) =
+            extract_phonetics(json!({"us_ipa": "/həˈloʊ/", "uk_ipa": "[həˈləʊ]"})).unwrap();
+        assert_eq!(us, "həˈloʊ");
+        assert_eq!(uk, "həˈləʊ");
+
+        assert!(extract_phonetics(json!({"us_ipa": "//", "uk_ipa": "x"})).is_err());
+    }
+
+    #[test]
+    fn test_extract_example_sentences_keeps_two_pairs() {
+        let value = json!([
+            {"english": " I am happy. ", "chinese": "我很开心。"},
+            {"english": "She was happy to help.", "chinese": "她很乐意帮忙。"},
+            {"english": "Happy birthday!", "chinese": "生日快乐！"}
+        ]);
+        let examples = extract_example_sentences(value).unwrap();
+        assert_eq!(
+            examples,
+            "I am happy.\n我很开心。\nShe was happy to help.\n她很乐意帮忙。\n"
+        );
+
+        let single = extract_example_sentences(json!([
+            {"english": "I am happy.", "chinese": "我很开心。"}
+        ]))
+        .unwrap();
+        assert_eq!(single, "I am happy.\n我很开心。\n");
+    }
+
+    #[test]
+    fn test_split_senses() {
+        let meanings = vec![
+            WordMeaning {
+                pos: "n.".to_string(),
+                definition: "苹果；苹果树; ".to_string(),
+                ..Default::default()
+            },
+            WordMeaning {
+                pos: "adj.".to_string(),
+                definition: "苹果色的".to_string(),
+                ..Default::default()
+            },
+        ];
+        let senses = split_senses(&meanings);
+        assert_eq!(senses.len(), 3);
+        assert_eq!(senses[1].pos, "n.");
+        assert_eq!(senses[1].definition, "苹果树");
+        assert_eq!(senses[2].definition, "苹果色的");
+
+        assert_eq!(extract_sense_index(json!({"index": 2}), 3).unwrap(), 1);
+        assert!(extract_sense_index(json!({"index": 4}), 3).is_err());
+        assert!(extract_sense_index(json!({"index": 0}), 3).is_err());
+    }
+}
@@ -6,6 +6,7 @@
 //! - Caching (`cache/`): Redis and other caching mechanisms
 //! - External Services (`services/`): Implementation of domain service interfaces
 //! - Third-party Integrations (`external/`): External API clients and adapters
+//! - Exports (`export/`): Word list rendering to printable HTML, CSV and Anki decks
 //!
 //! The infrastructure layer provides concrete implementations of interfaces defined
 //! in the domain layer and handles all external resource interactions.
@@ -13,5 +14,6 @@
 pub mod cache;
 pub mod database;
 pub mod dto;
+pub mod export;
 pub mod llm;
 pub mod third_party;
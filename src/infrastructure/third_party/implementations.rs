@@ -89,6 +89,7 @@ impl ThirdPartyService for HongliangServiceImpl {
                         Some(WordMeaning {
                             pos: arr[0].as_str()?.to_string(),
                             definition: arr[1].as_str()?.to_string(),
+                            ..Default::default()
                         })
                     } else {
                         None
@@ -116,6 +117,7 @@ mod tests {
     use super::*;
 
     #[tokio::test]
+    #[ignore = "requires network access"]
     async fn test_fetch_word_info() {
         let service = HongliangServiceImpl::new();
         let result = service.fetch_word_info("name").await;
@@ -1,10 +1,15 @@
 use crate::api::configure_routes;
-use crate::app::{HandlerFactory, RequestLogger, ServiceContainer};
+use crate::app::{Authentication, HandlerFactory, RequestLogger, ServiceContainer};
 use crate::common::utils;
 use crate::config::Settings;
+use crate::domain::services::{
+    purge_interval_from_env, spawn_job_workers, spawn_trash_purger, WorkerConfig,
+};
 use crate::infrastructure::cache::redis;
 use crate::infrastructure::database::db;
 use crate::infrastructure::llm::init_llm_manager;
+use crate::infrastructure::llm::structured::init_attempt_sink;
+use crate::infrastructure::llm::usage::init_usage_sink;
 use actix_cors::Cors;
 use actix_web::http::header;
 use actix_web::{middleware, web, App, HttpServer};
@@ -12,9 +17,10 @@ use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
 use chrono_tz::Asia::Shanghai;
 use dotenv::dotenv;
 use sqlx::PgPool;
+use std::env;
 use std::fmt::Write;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 use tracing_appender::rolling;
 use tracing_subscriber::fmt::format::Writer;
 use tracing_subscriber::fmt::time::FormatTime;
@@ -75,9 +81,11 @@ async fn initialize_infrastructure() -> std::io::Result<Arc<PgPool>> {
     Ok(pool)
 }
 
-fn create_cors() -> Cors {
-    Cors::default()
-        .allow_any_origin()
+/// 只允许配置的来源跨域访问，未配置时拒绝全部跨域请求
+fn create_cors(allowed_origins: &[String]) -> Cors {
+    allowed_origins
+        .iter()
+        .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
         .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
         .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
         .allowed_header(header::CONTENT_TYPE)
@@ -94,6 +102,77 @@ async fn main() -> std::io::Result<()> {
 
     // init llm
     init_llm_manager();
+    // Load model provider configs stored in database, env vars remain as fallback
+    if let Err(e) = service_container
+        .get_model_provider_service()
+        .sync_llm_configs()
+        .await
+    {
+        warn!("Failed to load model provider configs from database: {}", e);
+    }
+    // Load prompt templates stored in database, built-in prompts remain as fallback
+    if let Err(e) = service_container
+        .get_prompt_template_service()
+        .sync_templates()
+        .await
+    {
+        warn!("Failed to load prompt templates from database: {}", e);
+    }
+    // Persist token usage of LLM calls in the background
+    match init_usage_sink() {
+        Ok(mut records) => {
+            let model_provider_service = service_container.get_model_provider_service();
+            tokio::spawn(async move {
+                while let Some(record) = records.recv().await {
+                    if let Err(e) = model_provider_service.record_usage(record).await {
+                        warn!("Failed to save LLM usage record: {}", e);
+                    }
+                }
+            });
+        }
+        Err(e) => warn!("Failed to initialize LLM usage recording: {}", e),
+    }
+    // Persist structured output attempts in the background
+    match init_attempt_sink() {
+        Ok(mut attempts) => {
+            let model_provider_service = service_container.get_model_provider_service();
+            tokio::spawn(async move {
+                while let Some(attempt) = attempts.recv().await {
+                    if let Err(e) = model_provider_service
+                        .record_structured_attempt(attempt)
+                        .await
+                    {
+                        warn!("Failed to save structured output attempt: {}", e);
+                    }
+                }
+            });
+        }
+        Err(e) => warn!("Failed to initialize structured output attempt recording: {}", e),
+    }
+    // Requeue jobs whose leases expired (left by a crashed or stopped instance), then start background job workers
+    let job_service = service_container.get_job_service();
+    match job_service.recover().await {
+        Ok(0) => {}
+        Ok(count) => info!("Requeued {} jobs with expired leases", count),
+        Err(e) => warn!("Failed to requeue jobs with expired leases: {}", e),
+    }
+    spawn_job_workers(job_service, WorkerConfig::from_env());
+    // Permanently remove soft-deleted data past the trash retention period, now and then periodically
+    spawn_trash_purger(
+        service_container.get_trash_service(),
+        purge_interval_from_env(),
+    );
+    // Create the first admin from ADMIN_USERNAME / ADMIN_PASSWORD when there are no users yet
+    let user_service = service_container.get_user_service();
+    match (env::var("ADMIN_USERNAME"), env::var("ADMIN_PASSWORD")) {
+        (Ok(username), Ok(password)) => match user_service.ensure_admin(&username, &password).await
+        {
+            Ok(true) => info!("Created initial admin user {}", username),
+            Ok(false) => {}
+            Err(e) => warn!("Failed to create initial admin user: {}", e),
+        },
+        _ => warn!("ADMIN_USERNAME or ADMIN_PASSWORD not set, no initial admin will be created"),
+    }
     // Initialize handler factory
     let handler_factory = HandlerFactory::new(
         service_container.get_grade_service(),
@@ -105,15 +184,34 @@ async fn main() -> std::io::Result<()> {
         service_container.get_word_service(),
         service_container.get_word_unit_service(),
         service_container.get_model_provider_service(),
+        service_container.get_prompt_template_service(),
+        service_container.get_job_service(),
+        service_container.get_export_service(),
+        service_container.get_trash_service(),
+        user_service.clone(),
+        service_container.get_study_service(),
+        service_container.get_quiz_service(),
+        service_container.get_dictation_service(),
+        service_container.get_mistake_service(),
     );
 
     let settings = Settings::global();
+    let allowed_origins: Vec<String> = env::var("CORS_ALLOWED_ORIGINS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|origin| origin.trim().to_string())
+        .filter(|origin| !origin.is_empty())
+        .collect();
+    if allowed_origins.is_empty() {
+        warn!("CORS_ALLOWED_ORIGINS not set, cross-origin requests are rejected");
+    }
 
     // Start HTTP server
     HttpServer::new(move || {
         App::new()
-            .wrap(create_cors())
+            .wrap(create_cors(&allowed_origins))
             .wrap(middleware::Logger::default())
+            .wrap(Authentication::new(user_service.clone()))
             .wrap(RequestLogger)
             .configure(|cfg| configure_routes(cfg, handler_factory.clone()))
     })